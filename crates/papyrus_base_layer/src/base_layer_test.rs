@@ -1,10 +1,16 @@
 use pretty_assertions::assert_eq;
 use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::core::Nonce;
 use starknet_api::felt;
 
+use crate::constants::{
+    LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER,
+    MESSAGE_TO_L2_CANCELED_EVENT_IDENTIFIER,
+};
 use crate::ethereum_base_layer_contract::{EthereumBaseLayerConfig, EthereumBaseLayerContract};
+use crate::fake_base_layer_contract::{fake_block_hash, FakeBaseLayerContract};
 use crate::test_utils::get_test_ethereum_node;
-use crate::BaseLayerContract;
+use crate::{BaseLayerContract, EventData, L1BlockHeader, L1Event};
 
 // TODO: move to global test_utils crate and use everywhere instead of relying on the
 // confusing `#[ignore]` api to mark slow tests.
@@ -43,3 +49,71 @@ async fn latest_proved_block_ethereum() {
         assert_eq!(latest_block, expected);
     }
 }
+
+#[tokio::test]
+// Note: the test requires ganache-cli installed, otherwise it is ignored.
+async fn get_block_header_ethereum() {
+    if !in_ci() {
+        return;
+    }
+
+    let (node_handle, starknet_contract_address) = get_test_ethereum_node();
+    let config = EthereumBaseLayerConfig {
+        node_url: node_handle.0.endpoint().parse().unwrap(),
+        starknet_contract_address,
+    };
+    let contract = EthereumBaseLayerContract::new(config).unwrap();
+
+    let header = contract.get_block_header(30).await.unwrap().unwrap();
+    assert_eq!(header.number, 30);
+    assert_eq!(contract.get_block_header(1000).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn fake_base_layer_blocks() {
+    let mut base_layer = FakeBaseLayerContract::new();
+    assert_eq!(base_layer.latest_l1_block_number(0).await.unwrap(), None);
+
+    base_layer.add_block(12, 100, 1, vec![]);
+    base_layer.prove_block(BlockNumber(7), BlockHash(felt!("0x7")));
+    let latest_block_number = base_layer.add_block(24, 200, 2, vec![]);
+
+    assert_eq!(latest_block_number, 1);
+    assert_eq!(base_layer.latest_l1_block_number(0).await.unwrap(), Some(1));
+    assert_eq!(base_layer.latest_l1_block_number(2).await.unwrap(), None);
+    assert_eq!(
+        base_layer.get_block_header(1).await.unwrap(),
+        Some(L1BlockHeader {
+            number: 1,
+            hash: fake_block_hash(1),
+            timestamp: 24,
+            base_fee_per_gas: 200,
+            blob_fee: 2
+        })
+    );
+    assert_eq!(base_layer.get_block_header(2).await.unwrap(), None);
+    assert_eq!(
+        base_layer.latest_proved_block(1).await.unwrap(),
+        Some((BlockNumber(7), BlockHash(felt!("0x7"))))
+    );
+}
+
+#[tokio::test]
+async fn fake_base_layer_events() {
+    let msg = |nonce: u8| EventData { nonce: Nonce(felt!(nonce)), ..Default::default() };
+    let mut base_layer = FakeBaseLayerContract::new();
+    base_layer.add_block(12, 100, 1, vec![L1Event::MessageToL2Canceled(msg(0))]);
+    base_layer.add_block(24, 100, 1, vec![L1Event::ConsumedMessageToL2(msg(1))]);
+    base_layer.add_block(36, 100, 1, vec![L1Event::MessageToL2Canceled(msg(2))]);
+
+    let canceled = [MESSAGE_TO_L2_CANCELED_EVENT_IDENTIFIER];
+    assert_eq!(
+        base_layer.events(0, 1, &canceled).await.unwrap(),
+        [L1Event::MessageToL2Canceled(msg(0))]
+    );
+    assert_eq!(
+        base_layer.events(1, 10, &canceled).await.unwrap(),
+        [L1Event::MessageToL2Canceled(msg(2))]
+    );
+    assert_eq!(base_layer.events(0, 2, &[LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER]).await.unwrap(), []);
+}
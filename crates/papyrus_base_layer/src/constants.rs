@@ -0,0 +1,17 @@
+use alloy_sol_types::SolEvent;
+
+use crate::ethereum_base_layer_contract::{
+    ConsumedMessageToL2,
+    LogMessageToL2,
+    MessageToL2Canceled,
+    MessageToL2CancellationStarted,
+};
+
+/// The signature of a Starknet contract event, used to filter events on the base layer.
+pub type EventIdentifier = &'static str;
+
+pub const LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER: EventIdentifier = LogMessageToL2::SIGNATURE;
+pub const CONSUMED_MESSAGE_TO_L2_EVENT_IDENTIFIER: EventIdentifier = ConsumedMessageToL2::SIGNATURE;
+pub const MESSAGE_TO_L2_CANCELLATION_STARTED_EVENT_IDENTIFIER: EventIdentifier =
+    MessageToL2CancellationStarted::SIGNATURE;
+pub const MESSAGE_TO_L2_CANCELED_EVENT_IDENTIFIER: EventIdentifier = MessageToL2Canceled::SIGNATURE;
@@ -7,7 +7,8 @@ use alloy_json_rpc::RpcError;
 pub(crate) use alloy_primitives::Address as EthereumContractAddress;
 use alloy_provider::network::Ethereum;
 use alloy_provider::{Provider, ProviderBuilder, RootProvider};
-use alloy_sol_types::sol_data;
+use alloy_rpc_types_eth::{BlockNumberOrTag, Filter, Log};
+use alloy_sol_types::{sol, sol_data, SolEvent};
 use alloy_transport::TransportErrorKind;
 use alloy_transport_http::{Client, Http};
 use async_trait::async_trait;
@@ -15,11 +16,69 @@ use papyrus_config::dumping::{ser_param, ser_required_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializationType, SerializedParam};
 use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::core::{ContractAddress, EntryPointSelector, EthAddress, Nonce};
 use starknet_api::hash::StarkHash;
-use starknet_types_core::felt;
+use starknet_api::transaction::fields::{Calldata, Fee};
+use starknet_api::StarknetApiError;
+use starknet_types_core::felt::{self, Felt};
 use url::Url;
 
-use crate::BaseLayerContract;
+use crate::constants::EventIdentifier;
+use crate::{BaseLayerContract, EventData, L1BlockHeader, L1BlockNumber, L1Event};
+
+// The Starknet core contract events that are relevant for L1->L2 messaging.
+sol! {
+    #[derive(Debug)]
+    event LogMessageToL2(
+        address indexed fromAddress,
+        uint256 indexed toAddress,
+        uint256 indexed selector,
+        uint256[] payload,
+        uint256 nonce,
+        uint256 fee
+    );
+
+    #[derive(Debug)]
+    event ConsumedMessageToL2(
+        address indexed fromAddress,
+        uint256 indexed toAddress,
+        uint256 indexed selector,
+        uint256[] payload,
+        uint256 nonce
+    );
+
+    #[derive(Debug)]
+    event MessageToL2CancellationStarted(
+        address indexed fromAddress,
+        uint256 indexed toAddress,
+        uint256 indexed selector,
+        uint256[] payload,
+        uint256 nonce
+    );
+
+    #[derive(Debug)]
+    event MessageToL2Canceled(
+        address indexed fromAddress,
+        uint256 indexed toAddress,
+        uint256 indexed selector,
+        uint256[] payload,
+        uint256 nonce
+    );
+}
+
+// Decodes the payload shared by the L1->L2 message events that carry no fee.
+macro_rules! decode_event_data {
+    ($log:expr, $event_type:ty) => {{
+        let event = $log.log_decode::<$event_type>()?.inner.data;
+        EventData::try_from(RawEventData {
+            from_address: event.fromAddress,
+            to_address: event.toAddress,
+            selector: event.selector,
+            payload: event.payload,
+            nonce: event.nonce,
+        })?
+    }};
+}
 
 #[derive(thiserror::Error, Debug)]
 pub enum EthereumBaseLayerError {
@@ -27,12 +86,18 @@ pub enum EthereumBaseLayerError {
     Contract(#[from] alloy_contract::Error),
     #[error(transparent)]
     FeltParseError(#[from] felt::FromStrError),
+    #[error("Fee is out of range: {0}.")]
+    FeeOutOfRange(alloy_primitives::ruint::FromUintError<u128>),
     #[error(transparent)]
     RpcError(#[from] RpcError<TransportErrorKind>),
     #[error(transparent)]
     Serde(#[from] serde_json::Error),
     #[error(transparent)]
+    StarknetApi(#[from] StarknetApiError),
+    #[error(transparent)]
     TypeError(#[from] alloy_sol_types::Error),
+    #[error("Unsupported event with topics: {0:?}.")]
+    UnsupportedEvent(Vec<alloy_primitives::B256>),
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -103,9 +168,7 @@ impl BaseLayerContract for EthereumBaseLayerContract {
         &self,
         finality: u64,
     ) -> Result<Option<(BlockNumber, BlockHash)>, Self::Error> {
-        let ethereum_block_number =
-            self.contract.provider().get_block_number().await?.checked_sub(finality);
-        let Some(ethereum_block_number) = ethereum_block_number else {
+        let Some(ethereum_block_number) = self.latest_l1_block_number(finality).await? else {
             return Ok(None);
         };
 
@@ -124,4 +187,113 @@ impl BaseLayerContract for EthereumBaseLayerContract {
             BlockHash(StarkHash::from_hex(&state_block_hash.to_string())?),
         )))
     }
+
+    async fn latest_l1_block_number(
+        &self,
+        finality: u64,
+    ) -> Result<Option<L1BlockNumber>, Self::Error> {
+        Ok(self.contract.provider().get_block_number().await?.checked_sub(finality))
+    }
+
+    async fn events(
+        &self,
+        from_block: L1BlockNumber,
+        until_block: L1BlockNumber,
+        event_identifiers: &[EventIdentifier],
+    ) -> Result<Vec<L1Event>, Self::Error> {
+        let filter = Filter::new()
+            .address(*self.contract.address())
+            .from_block(from_block)
+            .to_block(until_block)
+            .events(event_identifiers);
+
+        let logs = self.contract.provider().get_logs(&filter).await?;
+        logs.into_iter().map(parse_event).collect()
+    }
+
+    async fn get_block_header(
+        &self,
+        block_number: L1BlockNumber,
+    ) -> Result<Option<L1BlockHeader>, Self::Error> {
+        let Some(block) = self
+            .contract
+            .provider()
+            .get_block_by_number(BlockNumberOrTag::Number(block_number), false)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        // Blocks from before London (EIP-1559) and Cancun (EIP-4844) have no base fee and no blob
+        // fee, respectively.
+        Ok(Some(L1BlockHeader {
+            number: block.header.number,
+            hash: block.header.hash.0,
+            timestamp: block.header.timestamp,
+            base_fee_per_gas: block.header.base_fee_per_gas.unwrap_or_default(),
+            blob_fee: block.header.blob_fee().unwrap_or_default(),
+        }))
+    }
+}
+
+fn parse_event(log: Log) -> Result<L1Event, EthereumBaseLayerError> {
+    match log.topic0() {
+        Some(&LogMessageToL2::SIGNATURE_HASH) => {
+            let event = log.log_decode::<LogMessageToL2>()?.inner.data;
+            let fee = Fee(event.fee.try_into().map_err(EthereumBaseLayerError::FeeOutOfRange)?);
+            let event_data = EventData::try_from(RawEventData {
+                from_address: event.fromAddress,
+                to_address: event.toAddress,
+                selector: event.selector,
+                payload: event.payload,
+                nonce: event.nonce,
+            })?;
+            Ok(L1Event::LogMessageToL2 { tx: event_data.into(), fee })
+        }
+        Some(&ConsumedMessageToL2::SIGNATURE_HASH) => {
+            Ok(L1Event::ConsumedMessageToL2(decode_event_data!(log, ConsumedMessageToL2)))
+        }
+        Some(&MessageToL2CancellationStarted::SIGNATURE_HASH) => {
+            Ok(L1Event::MessageToL2CancellationStarted(decode_event_data!(
+                log,
+                MessageToL2CancellationStarted
+            )))
+        }
+        Some(&MessageToL2Canceled::SIGNATURE_HASH) => {
+            Ok(L1Event::MessageToL2Canceled(decode_event_data!(log, MessageToL2Canceled)))
+        }
+        _ => Err(EthereumBaseLayerError::UnsupportedEvent(log.topics().to_vec())),
+    }
+}
+
+// The fields shared by all messaging events, as they appear on L1.
+struct RawEventData {
+    from_address: EthereumContractAddress,
+    to_address: alloy_primitives::U256,
+    selector: alloy_primitives::U256,
+    payload: Vec<alloy_primitives::U256>,
+    nonce: alloy_primitives::U256,
+}
+
+impl TryFrom<RawEventData> for EventData {
+    type Error = EthereumBaseLayerError;
+
+    fn try_from(raw: RawEventData) -> Result<Self, Self::Error> {
+        let from_address =
+            EthAddress::try_from(Felt::from_bytes_be_slice(raw.from_address.as_slice()))?;
+        let to_address = ContractAddress::try_from(u256_to_felt(&raw.to_address))?;
+        let payload = Calldata(raw.payload.iter().map(u256_to_felt).collect::<Vec<_>>().into());
+
+        Ok(EventData {
+            from_address,
+            to_address,
+            entry_point_selector: EntryPointSelector(u256_to_felt(&raw.selector)),
+            payload,
+            nonce: Nonce(u256_to_felt(&raw.nonce)),
+        })
+    }
+}
+
+fn u256_to_felt(value: &alloy_primitives::U256) -> Felt {
+    Felt::from_bytes_be(&value.to_be_bytes())
 }
@@ -0,0 +1,135 @@
+use async_trait::async_trait;
+use starknet_api::block::{BlockHash, BlockNumber};
+
+use crate::constants::{
+    EventIdentifier,
+    CONSUMED_MESSAGE_TO_L2_EVENT_IDENTIFIER,
+    LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER,
+    MESSAGE_TO_L2_CANCELED_EVENT_IDENTIFIER,
+    MESSAGE_TO_L2_CANCELLATION_STARTED_EVENT_IDENTIFIER,
+};
+use crate::ethereum_base_layer_contract::EthereumBaseLayerError;
+use crate::{BaseLayerContract, L1BlockHash, L1BlockHeader, L1BlockNumber, L1Event};
+
+/// An in-memory base layer, for tests and setups without an L1 node.
+/// The chain starts empty, and blocks are appended with `add_block`.
+#[derive(Clone, Debug, Default)]
+pub struct FakeBaseLayerContract {
+    blocks: Vec<FakeL1Block>,
+}
+
+#[derive(Clone, Debug)]
+struct FakeL1Block {
+    header: L1BlockHeader,
+    events: Vec<L1Event>,
+    proved_block: Option<(BlockNumber, BlockHash)>,
+}
+
+impl FakeBaseLayerContract {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a block with the given gas prices and Starknet contract events, and returns its
+    /// number.
+    pub fn add_block(
+        &mut self,
+        timestamp: u64,
+        base_fee_per_gas: u128,
+        blob_fee: u128,
+        events: Vec<L1Event>,
+    ) -> L1BlockNumber {
+        let number = self.blocks.len().try_into().expect("Block number should fit in u64.");
+        let header = L1BlockHeader {
+            number,
+            hash: fake_block_hash(number),
+            timestamp,
+            base_fee_per_gas,
+            blob_fee,
+        };
+        self.blocks.push(FakeL1Block { header, events, proved_block: None });
+        number
+    }
+
+    /// Records a Starknet state update in the latest L1 block.
+    pub fn prove_block(&mut self, block_number: BlockNumber, block_hash: BlockHash) {
+        let latest_block =
+            self.blocks.last_mut().expect("No L1 block to prove the state update in.");
+        latest_block.proved_block = Some((block_number, block_hash));
+    }
+
+    fn block_range(&self, from_block: L1BlockNumber, until_block: L1BlockNumber) -> &[FakeL1Block] {
+        let n_blocks = self.blocks.len();
+        let from = usize::try_from(from_block).unwrap_or(usize::MAX).min(n_blocks);
+        let until =
+            usize::try_from(until_block).unwrap_or(usize::MAX).saturating_add(1).min(n_blocks);
+        self.blocks.get(from..until).unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl BaseLayerContract for FakeBaseLayerContract {
+    type Error = EthereumBaseLayerError;
+
+    async fn latest_proved_block(
+        &self,
+        finality: u64,
+    ) -> Result<Option<(BlockNumber, BlockHash)>, Self::Error> {
+        let Some(latest_l1_block_number) = self.latest_l1_block_number(finality).await? else {
+            return Ok(None);
+        };
+
+        Ok(self
+            .block_range(0, latest_l1_block_number)
+            .iter()
+            .rev()
+            .find_map(|block| block.proved_block))
+    }
+
+    async fn latest_l1_block_number(
+        &self,
+        finality: u64,
+    ) -> Result<Option<L1BlockNumber>, Self::Error> {
+        Ok(self.blocks.last().and_then(|block| block.header.number.checked_sub(finality)))
+    }
+
+    async fn events(
+        &self,
+        from_block: L1BlockNumber,
+        until_block: L1BlockNumber,
+        event_identifiers: &[EventIdentifier],
+    ) -> Result<Vec<L1Event>, Self::Error> {
+        Ok(self
+            .block_range(from_block, until_block)
+            .iter()
+            .flat_map(|block| &block.events)
+            .filter(|event| event_identifiers.contains(&event_identifier(event)))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_block_header(
+        &self,
+        block_number: L1BlockNumber,
+    ) -> Result<Option<L1BlockHeader>, Self::Error> {
+        Ok(self.block_range(block_number, block_number).first().map(|block| block.header))
+    }
+}
+
+// Fake blocks are never reverted, so their hashes only need to differ from each other.
+pub(crate) fn fake_block_hash(block_number: L1BlockNumber) -> L1BlockHash {
+    let mut hash = [0; 32];
+    hash[24..].copy_from_slice(&block_number.to_be_bytes());
+    hash
+}
+
+fn event_identifier(event: &L1Event) -> EventIdentifier {
+    match event {
+        L1Event::ConsumedMessageToL2(_) => CONSUMED_MESSAGE_TO_L2_EVENT_IDENTIFIER,
+        L1Event::LogMessageToL2 { .. } => LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER,
+        L1Event::MessageToL2CancellationStarted(_) => {
+            MESSAGE_TO_L2_CANCELLATION_STARTED_EVENT_IDENTIFIER
+        }
+        L1Event::MessageToL2Canceled(_) => MESSAGE_TO_L2_CANCELED_EVENT_IDENTIFIER,
+    }
+}
@@ -1,7 +1,17 @@
 use async_trait::async_trait;
+#[cfg(any(feature = "testing", test))]
+use mockall::automock;
 use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::core::{ContractAddress, EntryPointSelector, EthAddress, Nonce};
+use starknet_api::transaction::fields::{Calldata, Fee};
+use starknet_api::transaction::{L1HandlerTransaction, TransactionVersion};
+
+use crate::constants::EventIdentifier;
 
+pub mod constants;
 pub mod ethereum_base_layer_contract;
+#[cfg(any(feature = "testing", test))]
+pub mod fake_base_layer_contract;
 
 #[cfg(any(feature = "testing", test))]
 pub mod test_utils;
@@ -9,7 +19,14 @@ pub mod test_utils;
 #[cfg(test)]
 mod base_layer_test;
 
+pub type L1BlockNumber = u64;
+pub type L1BlockHash = [u8; 32];
+
 /// Interface for getting data from the Starknet base contract.
+#[cfg_attr(
+    any(feature = "testing", test),
+    automock(type Error = ethereum_base_layer_contract::EthereumBaseLayerError;)
+)]
 #[async_trait]
 pub trait BaseLayerContract {
     type Error;
@@ -20,4 +37,77 @@ pub trait BaseLayerContract {
         &self,
         finality: u64,
     ) -> Result<Option<(BlockNumber, BlockHash)>, Self::Error>;
+
+    /// Get the latest L1 block number, where finality determines how many blocks back
+    /// (0 = latest).
+    async fn latest_l1_block_number(
+        &self,
+        finality: u64,
+    ) -> Result<Option<L1BlockNumber>, Self::Error>;
+
+    /// Get the Starknet contract events with the given identifiers (event signatures), emitted in
+    /// the given L1 block range (inclusive).
+    async fn events(
+        &self,
+        from_block: L1BlockNumber,
+        until_block: L1BlockNumber,
+        event_identifiers: &[EventIdentifier],
+    ) -> Result<Vec<L1Event>, Self::Error>;
+
+    /// Get the header of the given L1 block, or None if the block doesn't exist yet.
+    async fn get_block_header(
+        &self,
+        block_number: L1BlockNumber,
+    ) -> Result<Option<L1BlockHeader>, Self::Error>;
+}
+
+/// The parts of an L1 block header that are relevant to Starknet, mainly for pricing L1 gas and
+/// detecting L1 reorgs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct L1BlockHeader {
+    pub number: L1BlockNumber,
+    /// Changes if the block is reverted by an L1 reorg.
+    pub hash: L1BlockHash,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// In wei.
+    pub base_fee_per_gas: u128,
+    /// In wei.
+    pub blob_fee: u128,
+}
+
+/// A Starknet contract event, parsed into Starknet types.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum L1Event {
+    ConsumedMessageToL2(EventData),
+    LogMessageToL2 { tx: L1HandlerTransaction, fee: Fee },
+    MessageToL2CancellationStarted(EventData),
+    MessageToL2Canceled(EventData),
+}
+
+/// The common payload of the L1->L2 messaging events.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EventData {
+    pub from_address: EthAddress,
+    pub to_address: ContractAddress,
+    pub entry_point_selector: EntryPointSelector,
+    pub payload: Calldata,
+    pub nonce: Nonce,
+}
+
+impl From<EventData> for L1HandlerTransaction {
+    fn from(event_data: EventData) -> Self {
+        // The L1 sender address is passed to the L1 handler as the first calldata argument.
+        let calldata = std::iter::once(event_data.from_address.into())
+            .chain(event_data.payload.0.iter().copied())
+            .collect();
+
+        L1HandlerTransaction {
+            version: TransactionVersion::ZERO,
+            nonce: event_data.nonce,
+            contract_address: event_data.to_address,
+            entry_point_selector: event_data.entry_point_selector,
+            calldata: Calldata(std::sync::Arc::new(calldata)),
+        }
+    }
 }
@@ -43,3 +43,6 @@ pub const PAPYRUS_CONSENSUS_HEIGHT: &str = "papyrus_consensus_height";
 
 /// The number of times consensus has progressed due to the sync protocol.
 pub const PAPYRUS_CONSENSUS_SYNC_COUNT: &str = "papyrus_consensus_sync_count";
+
+/// The number of equivocations consensus has seen, labeled by the equivocating validator.
+pub const PAPYRUS_CONSENSUS_EQUIVOCATION_COUNT: &str = "papyrus_consensus_equivocation_count";
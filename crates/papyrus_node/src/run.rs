@@ -13,7 +13,10 @@ use papyrus_common::pending_classes::PendingClasses;
 use papyrus_config::presentation::get_config_presentation;
 use papyrus_config::validators::config_validate;
 use papyrus_consensus::config::ConsensusConfig;
+use papyrus_consensus::signing::create_signer_and_verifier;
 use papyrus_consensus::stream_handler::StreamHandler;
+use papyrus_consensus::validator_set::EpochValidatorSets;
+use papyrus_consensus::wal::open_wal;
 use papyrus_consensus_orchestrator::papyrus_consensus_context::PapyrusConsensusContext;
 use papyrus_monitoring_gateway::MonitoringServer;
 use papyrus_network::gossipsub_impl::Topic;
@@ -201,12 +204,15 @@ fn spawn_consensus(
     let (outbound_internal_sender, inbound_internal_receiver, _) =
         StreamHandler::get_channels(inbound_network_receiver, outbound_network_sender);
 
+    let (signer, signature_verifier) = create_signer_and_verifier(&config)?;
+    let wal = open_wal(config.wal_dir.as_deref())?;
     let context = PapyrusConsensusContext::new(
         storage_reader.clone(),
         network_channels.broadcast_topic_client.clone(),
         outbound_internal_sender,
-        config.num_validators,
+        EpochValidatorSets::from_config(&config),
         None,
+        signer.clone(),
     );
 
     Ok(tokio::spawn(async move {
@@ -218,6 +224,10 @@ fn spawn_consensus(
             config.validator_id,
             config.consensus_delay,
             config.timeouts.clone(),
+            config.future_messages.clone(),
+            signer,
+            signature_verifier,
+            wal,
             network_channels.into(),
             inbound_internal_receiver,
             futures::stream::pending(),
@@ -1,6 +1,7 @@
 use futures::channel::{mpsc, oneshot};
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::core::ContractAddress;
+use starknet_api::crypto::utils::Signature;
 use starknet_api::executable_transaction::Transaction as ExecutableTransaction;
 use starknet_api::transaction::{Transaction, TransactionHash};
 
@@ -31,6 +32,8 @@ pub struct Vote {
     pub round: u32,
     pub block_hash: Option<BlockHash>,
     pub voter: ContractAddress,
+    /// The voter's signature. Only `None` before the vote is signed.
+    pub signature: Option<Signature>,
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -74,6 +77,22 @@ pub struct ProposalInit {
     pub proposer: ContractAddress,
 }
 
+/// The block info of a proposed block, sent right after `ProposalInit`. Validators check the L1 gas
+/// prices against their own view of L1 before validating the proposal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsensusBlockInfo {
+    /// The height of the consensus (block number).
+    pub height: BlockNumber,
+    /// The block timestamp, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// Address of the one who built the block.
+    pub builder: ContractAddress,
+    /// The L1 gas price, in wei.
+    pub l1_gas_price_wei: u128,
+    /// The L1 data (blob) gas price, in wei.
+    pub l1_data_gas_price_wei: u128,
+}
+
 /// There is one or more batches of transactions in a proposed block.
 #[derive(Debug, Clone, PartialEq)]
 pub struct TransactionBatch {
@@ -90,6 +109,8 @@ pub struct ProposalFin {
     /// The block hash of the proposed block.
     /// TODO(guyn): Consider changing the content ID
     pub proposal_content_id: BlockHash,
+    /// The proposer's signature. Only `None` before the fin is signed.
+    pub signature: Option<Signature>,
 }
 
 /// A part of the proposal.
@@ -97,6 +118,8 @@ pub struct ProposalFin {
 pub enum ProposalPart {
     /// The initialization part of the proposal.
     Init(ProposalInit),
+    /// The block info of the proposed block.
+    BlockInfo(ConsensusBlockInfo),
     /// A part of the proposal that contains one or more transactions.
     Transactions(TransactionBatch),
     /// The final part of the proposal, including the block hash.
@@ -5,11 +5,13 @@ use std::convert::{TryFrom, TryInto};
 
 use prost::Message;
 use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::crypto::utils::Signature;
 use starknet_api::hash::StarkHash;
 use starknet_api::transaction::{Transaction, TransactionHash};
 use starknet_types_core::felt::Felt;
 
 use crate::consensus::{
+    ConsensusBlockInfo,
     ConsensusMessage,
     Proposal,
     ProposalFin,
@@ -103,7 +105,9 @@ impl TryFrom<protobuf::Vote> for Vote {
             .ok_or(ProtobufConversionError::MissingField { field_description: "voter" })?
             .try_into()?;
 
-        Ok(Vote { vote_type, height, round, block_hash, voter })
+        let signature = value.signature.map(Signature::try_from).transpose()?;
+
+        Ok(Vote { vote_type, height, round, block_hash, voter, signature })
     }
 }
 
@@ -120,12 +124,35 @@ impl From<Vote> for protobuf::Vote {
             round: value.round,
             block_hash: value.block_hash.map(|hash| hash.0.into()),
             voter: Some(value.voter.into()),
+            signature: value.signature.map(Into::into),
         }
     }
 }
 
 auto_impl_into_and_try_from_vec_u8!(Vote, protobuf::Vote);
 
+impl TryFrom<protobuf::ConsensusSignature> for Signature {
+    type Error = ProtobufConversionError;
+
+    fn try_from(value: protobuf::ConsensusSignature) -> Result<Self, Self::Error> {
+        let r = value
+            .r
+            .ok_or(ProtobufConversionError::MissingField { field_description: "signature::r" })?
+            .try_into()?;
+        let s = value
+            .s
+            .ok_or(ProtobufConversionError::MissingField { field_description: "signature::s" })?
+            .try_into()?;
+        Ok(Signature { r, s })
+    }
+}
+
+impl From<Signature> for protobuf::ConsensusSignature {
+    fn from(value: Signature) -> Self {
+        protobuf::ConsensusSignature { r: Some(value.r.into()), s: Some(value.s.into()) }
+    }
+}
+
 impl<T: Into<Vec<u8>> + TryFrom<Vec<u8>, Error = ProtobufConversionError>>
     TryFrom<protobuf::StreamMessage> for StreamMessage<T>
 {
@@ -227,6 +254,49 @@ impl From<ProposalInit> for protobuf::ProposalInit {
 
 auto_impl_into_and_try_from_vec_u8!(ProposalInit, protobuf::ProposalInit);
 
+impl TryFrom<protobuf::BlockInfo> for ConsensusBlockInfo {
+    type Error = ProtobufConversionError;
+    fn try_from(value: protobuf::BlockInfo) -> Result<Self, Self::Error> {
+        let height = value.height;
+        let timestamp = value.timestamp;
+        let builder = value
+            .builder
+            .ok_or(ProtobufConversionError::MissingField { field_description: "builder" })?
+            .try_into()?;
+        let l1_gas_price_wei = value
+            .l1_gas_price_wei
+            .ok_or(ProtobufConversionError::MissingField { field_description: "l1_gas_price_wei" })?
+            .into();
+        let l1_data_gas_price_wei = value
+            .l1_data_gas_price_wei
+            .ok_or(ProtobufConversionError::MissingField {
+                field_description: "l1_data_gas_price_wei",
+            })?
+            .into();
+        Ok(ConsensusBlockInfo {
+            height: BlockNumber(height),
+            timestamp,
+            builder,
+            l1_gas_price_wei,
+            l1_data_gas_price_wei,
+        })
+    }
+}
+
+impl From<ConsensusBlockInfo> for protobuf::BlockInfo {
+    fn from(value: ConsensusBlockInfo) -> Self {
+        protobuf::BlockInfo {
+            height: value.height.0,
+            timestamp: value.timestamp,
+            builder: Some(value.builder.into()),
+            l1_gas_price_wei: Some(value.l1_gas_price_wei.into()),
+            l1_data_gas_price_wei: Some(value.l1_data_gas_price_wei.into()),
+        }
+    }
+}
+
+auto_impl_into_and_try_from_vec_u8!(ConsensusBlockInfo, protobuf::BlockInfo);
+
 // TODO(guyn): remove tx_hashes once we know how to compile the hashes
 // when making the executable transactions.
 impl TryFrom<protobuf::TransactionBatch> for TransactionBatch {
@@ -266,13 +336,17 @@ impl TryFrom<protobuf::ProposalFin> for ProposalFin {
             })?
             .try_into()?;
         let proposal_content_id = BlockHash(proposal_content_id);
-        Ok(ProposalFin { proposal_content_id })
+        let signature = value.signature.map(Signature::try_from).transpose()?;
+        Ok(ProposalFin { proposal_content_id, signature })
     }
 }
 
 impl From<ProposalFin> for protobuf::ProposalFin {
     fn from(value: ProposalFin) -> Self {
-        protobuf::ProposalFin { proposal_content_id: Some(value.proposal_content_id.0.into()) }
+        protobuf::ProposalFin {
+            proposal_content_id: Some(value.proposal_content_id.0.into()),
+            signature: value.signature.map(Into::into),
+        }
     }
 }
 
@@ -289,6 +363,7 @@ impl TryFrom<protobuf::ProposalPart> for ProposalPart {
 
         match part {
             Message::Init(init) => Ok(ProposalPart::Init(init.try_into()?)),
+            Message::BlockInfo(block_info) => Ok(ProposalPart::BlockInfo(block_info.try_into()?)),
             Message::Transactions(content) => Ok(ProposalPart::Transactions(content.try_into()?)),
             Message::Fin(fin) => Ok(ProposalPart::Fin(fin.try_into()?)),
         }
@@ -301,6 +376,9 @@ impl From<ProposalPart> for protobuf::ProposalPart {
             ProposalPart::Init(init) => protobuf::ProposalPart {
                 message: Some(protobuf::proposal_part::Message::Init(init.into())),
             },
+            ProposalPart::BlockInfo(block_info) => protobuf::ProposalPart {
+                message: Some(protobuf::proposal_part::Message::BlockInfo(block_info.into())),
+            },
             ProposalPart::Transactions(content) => protobuf::ProposalPart {
                 message: Some(protobuf::proposal_part::Message::Transactions(content.into())),
             },
@@ -12,6 +12,7 @@ use starknet_api::transaction::{
 };
 
 use crate::consensus::{
+    ConsensusBlockInfo,
     ConsensusMessage,
     Proposal,
     ProposalFin,
@@ -117,6 +118,17 @@ fn convert_proposal_init_to_vec_u8_and_back() {
     assert_eq!(proposal_init, res_data);
 }
 
+#[test]
+fn convert_block_info_to_vec_u8_and_back() {
+    let mut rng = get_rng();
+
+    let block_info = ConsensusBlockInfo::get_test_instance(&mut rng);
+
+    let bytes_data: Vec<u8> = block_info.clone().into();
+    let res_data = ConsensusBlockInfo::try_from(bytes_data).unwrap();
+    assert_eq!(block_info, res_data);
+}
+
 #[test]
 fn convert_transaction_batch_to_vec_u8_and_back() {
     let mut rng = get_rng();
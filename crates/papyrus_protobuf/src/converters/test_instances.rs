@@ -2,9 +2,11 @@ use papyrus_test_utils::{auto_impl_get_test_instance, get_number_of_variants, Ge
 use rand::Rng;
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::core::ContractAddress;
+use starknet_api::crypto::utils::Signature;
 use starknet_api::transaction::{Transaction, TransactionHash};
 
 use crate::consensus::{
+    ConsensusBlockInfo,
     ConsensusMessage,
     Proposal,
     ProposalFin,
@@ -36,6 +38,7 @@ auto_impl_get_test_instance! {
         pub round: u32,
         pub block_hash: Option<BlockHash>,
         pub voter: ContractAddress,
+        pub signature: Option<Signature>,
     }
     pub enum VoteType {
         Prevote = 0,
@@ -47,8 +50,16 @@ auto_impl_get_test_instance! {
         pub valid_round: Option<u32>,
         pub proposer: ContractAddress,
     }
+    pub struct ConsensusBlockInfo {
+        pub height: BlockNumber,
+        pub timestamp: u64,
+        pub builder: ContractAddress,
+        pub l1_gas_price_wei: u128,
+        pub l1_data_gas_price_wei: u128,
+    }
     pub struct ProposalFin {
         pub proposal_content_id: BlockHash,
+        pub signature: Option<Signature>,
     }
     pub struct TransactionBatch {
         pub transactions: Vec<Transaction>,
@@ -58,6 +69,7 @@ auto_impl_get_test_instance! {
         Init(ProposalInit) = 0,
         Fin(ProposalFin) = 1,
         Transactions(TransactionBatch) = 2,
+        BlockInfo(ConsensusBlockInfo) = 3,
     }
 
 }
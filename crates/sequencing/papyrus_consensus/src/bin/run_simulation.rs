@@ -270,12 +270,14 @@ async fn build_node(data_dir: &str, logs_dir: &str, i: usize, papyrus_args: &Pap
         "RUST_LOG=papyrus_consensus=debug,papyrus=info target/release/run_consensus \
          --network.#is_none false --base_layer.node_url {} --storage.db_config.path_prefix {} \
          --consensus.#is_none false --consensus.validator_id 0x{} --consensus.num_validators {} \
+         --consensus.insecure_test_signatures true --consensus.wal_dir {}/consensus_wal \
          --network.tcp_port {} --rpc.server_address 127.0.0.1:{} \
          --monitoring_gateway.server_address 127.0.0.1:{} --collect_metrics true ",
         papyrus_args.base_layer_node_url,
         data_dir,
         i,
         papyrus_args.num_validators,
+        data_dir,
         tcp_port,
         find_free_port(),
         monitoring_gateway_server_port
@@ -3,25 +3,36 @@
 //! such as the validator ID, the network topic of the consensus, and the starting block height.
 
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use papyrus_config::converters::{
     deserialize_float_seconds_to_duration,
     deserialize_seconds_to_duration,
 };
-use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
+use papyrus_config::dumping::{
+    append_sub_config_name,
+    ser_optional_param,
+    ser_param,
+    SerializeConfig,
+};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use papyrus_network::NetworkConfig;
-use serde::{Deserialize, Serialize};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize};
 use starknet_api::block::BlockNumber;
+use starknet_api::crypto::utils::PublicKey;
+use starknet_types_core::felt::Felt;
+use validator::Validate;
 
 use super::types::ValidatorId;
+use crate::validator_set::VotingPower;
 
 const CONSENSUS_TCP_PORT: u16 = 10100;
 const CONSENSUS_QUIC_PORT: u16 = 10101;
 
 /// Configuration for consensus.
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Validate)]
 pub struct ConsensusConfig {
     /// The validator ID of the node.
     pub validator_id: ValidatorId,
@@ -32,14 +43,35 @@ pub struct ConsensusConfig {
     /// The number of validators in the consensus.
     // Used for testing in an early milestones.
     pub num_validators: u64,
+    /// The number of heights in an epoch. The validator set can only change between epochs.
+    #[validate(range(min = 1))]
+    pub epoch_length: u64,
     /// The delay (seconds) before starting consensus to give time for network peering.
     #[serde(deserialize_with = "deserialize_seconds_to_duration")]
     pub consensus_delay: Duration,
     /// Timeouts configuration for consensus.
     pub timeouts: TimeoutsConfig,
-    // TODO(Dan/Matan): validate configs (#[validate]).
+    /// Limits on caching messages and proposals for future heights.
+    pub future_messages: FutureMessagesConfig,
     /// The network configuration for the consensus.
+    #[validate]
     pub network_config: NetworkConfig,
+    /// The directory of the write-ahead log of this node's votes and proposals. If not set, the
+    /// log is kept in memory and doesn't survive restarts.
+    pub wal_dir: Option<PathBuf>,
+    /// The Stark private key with which this node signs its votes and proposals. Required unless
+    /// `insecure_test_signatures` is set.
+    pub private_key: Option<Felt>,
+    /// The Stark public keys of the validators, with which their signatures are verified.
+    #[serde(deserialize_with = "deserialize_validator_public_keys")]
+    pub validator_public_keys: BTreeMap<ValidatorId, PublicKey>,
+    /// The voting power of each validator. If not set, the validators are the first
+    /// `num_validators` IDs, with equal voting power.
+    #[serde(deserialize_with = "deserialize_validator_weights")]
+    pub validator_weights: BTreeMap<ValidatorId, VotingPower>,
+    /// Sign and verify messages with test signatures, which don't authenticate their validator,
+    /// instead of the configured keys. Only for tests.
+    pub insecure_test_signatures: bool,
 }
 
 impl SerializeConfig for ConsensusConfig {
@@ -69,19 +101,128 @@ impl SerializeConfig for ConsensusConfig {
                 "The number of validators in the consensus.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "epoch_length",
+                &self.epoch_length,
+                "The number of heights in an epoch. The validator set can only change between \
+                 epochs.",
+                ParamPrivacyInput::Public,
+            ),
             ser_param(
                 "consensus_delay",
                 &self.consensus_delay.as_secs(),
                 "Delay (seconds) before starting consensus to give time for network peering.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "validator_weights",
+                &serialize_validator_map(&self.validator_weights, VotingPower::to_string),
+                "The voting power of each validator, in the format \"validator_id:voting_power \
+                 validator_id:voting_power\". If not set, the validators are the first \
+                 num_validators IDs, with equal voting power.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "validator_public_keys",
+                &serialize_validator_map(&self.validator_public_keys, |public_key| {
+                    format!("{:#x}", public_key.0)
+                }),
+                "The Stark public keys of the validators, with which their signatures are \
+                 verified, in the format \"validator_id:public_key validator_id:public_key\".",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "insecure_test_signatures",
+                &self.insecure_test_signatures,
+                "If true, messages are signed and verified with test signatures, which don't \
+                 authenticate their validator, instead of the configured keys. Only for tests.",
+                ParamPrivacyInput::Public,
+            ),
         ]);
         config.extend(append_sub_config_name(self.timeouts.dump(), "timeouts"));
+        config.extend(append_sub_config_name(self.future_messages.dump(), "future_messages"));
         config.extend(append_sub_config_name(self.network_config.dump(), "network_config"));
+        config.extend(ser_optional_param(
+            &self.wal_dir,
+            PathBuf::from("./data/consensus_wal"),
+            "wal_dir",
+            "The directory of the write-ahead log of this node's votes and proposals, which is \
+             replayed after a restart so that the node doesn't send conflicting messages. If not \
+             set, the log is kept in memory.",
+            ParamPrivacyInput::Public,
+        ));
+        config.extend(ser_optional_param(
+            &self.private_key,
+            Felt::ZERO,
+            "private_key",
+            "The Stark private key with which this node signs its votes and proposals. Required \
+             unless insecure_test_signatures is set.",
+            ParamPrivacyInput::Private,
+        ));
         config
     }
 }
 
+/// Serializes a map of the validators to a "validator_id:value validator_id:value" string.
+fn serialize_validator_map<T>(
+    map: &BTreeMap<ValidatorId, T>,
+    format_value: impl Fn(&T) -> String,
+) -> String {
+    map.iter()
+        .map(|(validator_id, value)| format!("{:#x}:{}", validator_id.0.key(), format_value(value)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Deserializes a map of the validators from a "validator_id:value validator_id:value" string.
+fn deserialize_validator_map<'de, D, T>(
+    de: D,
+    parse_value: impl Fn(&str) -> Result<T, String>,
+) -> Result<BTreeMap<ValidatorId, T>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw_str: String = Deserialize::deserialize(de)?;
+    raw_str
+        .split_whitespace()
+        .map(|raw_pair| {
+            let (raw_validator_id, raw_value) = raw_pair.split_once(':').ok_or_else(|| {
+                D::Error::custom(format!(
+                    "Pair \"{raw_pair}\" is not valid. The expected format is validator_id:value."
+                ))
+            })?;
+            let validator_id = parse_felt(raw_validator_id)
+                .and_then(|felt| ValidatorId::try_from(felt).map_err(|err| err.to_string()))
+                .map_err(D::Error::custom)?;
+            Ok((validator_id, parse_value(raw_value).map_err(D::Error::custom)?))
+        })
+        .collect()
+}
+
+fn parse_felt(raw: &str) -> Result<Felt, String> {
+    Felt::from_hex(raw).map_err(|err| format!("Invalid felt \"{raw}\": {err}"))
+}
+
+fn deserialize_validator_public_keys<'de, D>(
+    de: D,
+) -> Result<BTreeMap<ValidatorId, PublicKey>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_validator_map(de, |raw| parse_felt(raw).map(PublicKey))
+}
+
+fn deserialize_validator_weights<'de, D>(
+    de: D,
+) -> Result<BTreeMap<ValidatorId, VotingPower>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_validator_map(de, |raw| {
+        raw.parse().map_err(|err| format!("Invalid voting power \"{raw}\": {err}"))
+    })
+}
+
 impl Default for ConsensusConfig {
     fn default() -> Self {
         let network_config = NetworkConfig {
@@ -94,9 +235,16 @@ impl Default for ConsensusConfig {
             network_topic: "consensus".to_string(),
             start_height: BlockNumber::default(),
             num_validators: 1,
+            epoch_length: 100,
             consensus_delay: Duration::from_secs(5),
             timeouts: TimeoutsConfig::default(),
+            future_messages: FutureMessagesConfig::default(),
             network_config,
+            wal_dir: Some(PathBuf::from("./data/consensus_wal")),
+            private_key: None,
+            validator_public_keys: BTreeMap::new(),
+            validator_weights: BTreeMap::new(),
+            insecure_test_signatures: false,
         }
     }
 }
@@ -149,3 +297,40 @@ impl Default for TimeoutsConfig {
         }
     }
 }
+
+/// Configuration for caching messages and proposals which arrive before their height is reached.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct FutureMessagesConfig {
+    /// How many heights ahead of the current height messages and proposals are cached for.
+    pub height_limit: u64,
+    /// The maximal number of messages cached from each peer, and of proposals cached from each
+    /// proposer.
+    pub per_peer_limit: usize,
+}
+
+impl SerializeConfig for FutureMessagesConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "height_limit",
+                &self.height_limit,
+                "How many heights ahead of the current height messages and proposals are cached \
+                 for.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "per_peer_limit",
+                &self.per_peer_limit,
+                "The maximal number of messages cached from each peer, and of proposals cached \
+                 from each proposer.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+impl Default for FutureMessagesConfig {
+    fn default() -> Self {
+        Self { height_limit: 1, per_peer_limit: 100 }
+    }
+}
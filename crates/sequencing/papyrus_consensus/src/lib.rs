@@ -5,6 +5,7 @@
 
 pub mod config;
 pub mod manager;
+pub mod signing;
 #[allow(missing_docs)]
 pub mod simulation_network_receiver;
 #[allow(missing_docs)]
@@ -16,5 +17,7 @@ pub mod stream_handler;
 pub(crate) mod test_utils;
 #[allow(missing_docs)]
 pub mod types;
+pub mod validator_set;
+pub mod wal;
 
 pub use manager::run_consensus;
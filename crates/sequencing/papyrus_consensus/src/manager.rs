@@ -4,19 +4,23 @@
 #[path = "manager_test.rs"]
 mod manager_test;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::Arc;
 use std::time::Duration;
 
 use futures::channel::mpsc;
 use futures::stream::FuturesUnordered;
 use futures::{Stream, StreamExt};
 use papyrus_common::metrics::{PAPYRUS_CONSENSUS_HEIGHT, PAPYRUS_CONSENSUS_SYNC_COUNT};
-use papyrus_network::network_manager::BroadcastTopicClientTrait;
+use papyrus_network::network_manager::{BroadcastTopicClient, BroadcastTopicClientTrait};
+use papyrus_network_types::network_types::{BroadcastedMessageMetadata, OpaquePeerId};
 use papyrus_protobuf::consensus::{ConsensusMessage, ProposalInit};
 use starknet_api::block::BlockNumber;
 use tracing::{debug, info, instrument, warn};
 
-use crate::config::TimeoutsConfig;
+use crate::config::{FutureMessagesConfig, TimeoutsConfig};
+use crate::signing::{ConsensusSigner, SignatureVerifier};
 use crate::single_height_consensus::{ShcReturn, SingleHeightConsensus};
 use crate::types::{
     BroadcastConsensusMessageChannel,
@@ -25,6 +29,7 @@ use crate::types::{
     Decision,
     ValidatorId,
 };
+use crate::wal::ConsensusWal;
 
 // TODO(dvir): add test for this.
 #[instrument(skip_all, level = "info")]
@@ -37,8 +42,15 @@ pub async fn run_consensus<ContextT, SyncReceiverT>(
     validator_id: ValidatorId,
     consensus_delay: Duration,
     timeouts: TimeoutsConfig,
+    future_messages: FutureMessagesConfig,
+    signer: Arc<dyn ConsensusSigner>,
+    signature_verifier: Arc<dyn SignatureVerifier>,
+    wal: Arc<dyn ConsensusWal>,
     mut broadcast_channels: BroadcastConsensusMessageChannel,
-    mut inbound_proposal_receiver: mpsc::Receiver<mpsc::Receiver<ContextT::ProposalPart>>,
+    mut inbound_proposal_receiver: mpsc::Receiver<(
+        OpaquePeerId,
+        mpsc::Receiver<ContextT::ProposalPart>,
+    )>,
     mut sync_receiver: SyncReceiverT,
 ) -> Result<(), ConsensusError>
 where
@@ -59,7 +71,14 @@ where
     tokio::time::sleep(consensus_delay).await;
     assert!(start_observe_height <= start_active_height);
     let mut current_height = start_observe_height;
-    let mut manager = MultiHeightManager::new(validator_id, timeouts);
+    let mut manager = MultiHeightManager::new(
+        validator_id,
+        timeouts,
+        future_messages,
+        signer,
+        signature_verifier,
+        Arc::clone(&wal),
+    );
     #[allow(clippy::as_conversions)] // FIXME: use int metrics so `as f64` may be removed.
     loop {
         metrics::gauge!(PAPYRUS_CONSENSUS_HEIGHT, current_height.0 as f64);
@@ -88,40 +107,64 @@ where
                 current_height = sync_height?.unchecked_next();
             }
         }
+        // The heights below the current height are over, so their state won't be replayed.
+        wal.prune(current_height).await?;
     }
 }
 
 /// Runs Tendermint repeatedly across different heights. Handles issues which are not explicitly
 /// part of the single height consensus algorithm (e.g. messages from future heights).
-#[derive(Debug, Default)]
-struct MultiHeightManager {
+struct MultiHeightManager<ContextT: ConsensusContext> {
     validator_id: ValidatorId,
-    cached_messages: BTreeMap<u64, Vec<ConsensusMessage>>,
+    // Messages by the peer which sent them.
+    cached_messages:
+        FutureHeightCache<OpaquePeerId, (ConsensusMessage, BroadcastedMessageMetadata)>,
+    // Proposals by the peer which sent them.
+    cached_proposals:
+        FutureHeightCache<OpaquePeerId, (ProposalInit, mpsc::Receiver<ContextT::ProposalPart>)>,
     timeouts: TimeoutsConfig,
+    signer: Arc<dyn ConsensusSigner>,
+    signature_verifier: Arc<dyn SignatureVerifier>,
+    wal: Arc<dyn ConsensusWal>,
 }
 
-impl MultiHeightManager {
+impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
     /// Create a new consensus manager.
-    pub fn new(validator_id: ValidatorId, timeouts: TimeoutsConfig) -> Self {
-        Self { validator_id, cached_messages: BTreeMap::new(), timeouts }
+    pub fn new(
+        validator_id: ValidatorId,
+        timeouts: TimeoutsConfig,
+        future_messages: FutureMessagesConfig,
+        signer: Arc<dyn ConsensusSigner>,
+        signature_verifier: Arc<dyn SignatureVerifier>,
+        wal: Arc<dyn ConsensusWal>,
+    ) -> Self {
+        Self {
+            validator_id,
+            cached_messages: FutureHeightCache::new(future_messages.clone()),
+            cached_proposals: FutureHeightCache::new(future_messages),
+            timeouts,
+            signer,
+            signature_verifier,
+            wal,
+        }
     }
 
     /// Run the consensus algorithm for a single height.
     ///
     /// Assumes that `height` is monotonically increasing across calls for the sake of filtering
-    /// `cached_messaged`.
+    /// the cached messages and proposals.
     #[instrument(skip(self, context, broadcast_channels), level = "info")]
-    pub async fn run_height<ContextT>(
+    pub async fn run_height(
         &mut self,
         context: &mut ContextT,
         height: BlockNumber,
         is_observer: bool,
         broadcast_channels: &mut BroadcastConsensusMessageChannel,
-        proposal_receiver: &mut mpsc::Receiver<mpsc::Receiver<ContextT::ProposalPart>>,
-    ) -> Result<Decision, ConsensusError>
-    where
-        ContextT: ConsensusContext,
-    {
+        proposal_receiver: &mut mpsc::Receiver<(
+            OpaquePeerId,
+            mpsc::Receiver<ContextT::ProposalPart>,
+        )>,
+    ) -> Result<Decision, ConsensusError> {
         let validators = context.validators(height).await;
         info!("running consensus for height {height:?} with validator set {validators:?}");
         let mut shc = SingleHeightConsensus::new(
@@ -130,6 +173,9 @@ impl MultiHeightManager {
             self.validator_id,
             validators,
             self.timeouts.clone(),
+            Arc::clone(&self.signer),
+            Arc::clone(&self.signature_verifier),
+            Arc::clone(&self.wal),
         );
         let mut shc_events = FuturesUnordered::new();
 
@@ -142,13 +188,33 @@ impl MultiHeightManager {
             }
         }
 
-        let mut current_height_messages = self.get_current_height_messages(height);
+        for (proposal_init, content_receiver) in self.cached_proposals.take(height) {
+            match shc.handle_proposal(context, proposal_init, content_receiver).await? {
+                ShcReturn::Decision(decision) => return Ok(decision),
+                ShcReturn::Tasks(tasks) => {
+                    for task in tasks {
+                        shc_events.push(task.run());
+                    }
+                }
+            }
+        }
+
+        let mut current_height_messages = self.cached_messages.take(height);
         loop {
             let shc_return = tokio::select! {
                 message = next_message(&mut current_height_messages, broadcast_channels) => {
-                    self.handle_message(context, height, &mut shc, message?).await?
+                    let (message, metadata) = message?;
+                    self.handle_message(
+                        context,
+                        height,
+                        &mut shc,
+                        message,
+                        metadata,
+                        &mut broadcast_channels.broadcast_topic_client,
+                    )
+                    .await?
                 },
-                Some(mut content_receiver) = proposal_receiver.next() => {
+                Some((peer, mut content_receiver)) = proposal_receiver.next() => {
                     // Get the first message to verify the init was sent.
                      // TODO(guyn): add a timeout and panic, since StreamHandler should only send once
                     // the first message (message_id=0) has arrived.
@@ -158,7 +224,15 @@ impl MultiHeightManager {
                         ));
                     };
                     let proposal_init: ProposalInit = first_part.try_into()?;
-                    self.handle_proposal(context, height, &mut shc, proposal_init, content_receiver).await?
+                    self.handle_proposal(
+                        context,
+                        height,
+                        &mut shc,
+                        peer,
+                        proposal_init,
+                        content_receiver,
+                    )
+                    .await?
                 },
                 Some(shc_event) = shc_events.next() => {
                     shc.handle_event(context, shc_event).await?
@@ -176,45 +250,58 @@ impl MultiHeightManager {
         }
     }
 
-    // Handle a new proposal receiver from the network.
-    async fn handle_proposal<ContextT>(
+    // Handle a new proposal receiver from the network. Proposals for future heights are cached
+    // under the peer which sent them, since the proposer in the init is only claimed by the peer
+    // and caching by it would let a peer evict the real proposer's proposals.
+    async fn handle_proposal(
         &mut self,
         context: &mut ContextT,
         height: BlockNumber,
         shc: &mut SingleHeightConsensus,
+        peer: OpaquePeerId,
         proposal_init: ProposalInit,
         content_receiver: mpsc::Receiver<ContextT::ProposalPart>,
-    ) -> Result<ShcReturn, ConsensusError>
-    where
-        ContextT: ConsensusContext,
-    {
-        // TODO(guyn): what is the right thing to do if proposal's height doesn't match?
-        if proposal_init.height != height {
-            // TODO(guyn): add caching of heights for future use.
-            warn!("Received a proposal for a different height. {:?}", proposal_init);
+    ) -> Result<ShcReturn, ConsensusError> {
+        if proposal_init.height == height {
+            return shc.handle_proposal(context, proposal_init, content_receiver).await;
+        }
+        debug!("Received a proposal for a different height. {:?}", proposal_init);
+        if proposal_init.height < height {
+            return Ok(ShcReturn::Tasks(Vec::new()));
+        }
+        if proposal_init.proposer != context.proposer(proposal_init.height, proposal_init.round) {
+            warn!("Dropping a proposal from an unexpected proposer. {:?}", proposal_init);
+            return Ok(ShcReturn::Tasks(Vec::new()));
+        }
+        let proposal_height = proposal_init.height;
+        if !self.cached_proposals.insert(
+            height,
+            proposal_height,
+            peer,
+            (proposal_init, content_receiver),
+        ) {
+            debug!("Dropping a proposal for height {proposal_height}.");
         }
-        shc.handle_proposal(context, proposal_init, content_receiver).await
+        Ok(ShcReturn::Tasks(Vec::new()))
     }
 
-    // Handle a single consensus message.
-    async fn handle_message<ContextT>(
+    // Handle a single consensus message. Peers which send messages with invalid signatures are
+    // reported to the network.
+    async fn handle_message(
         &mut self,
         context: &mut ContextT,
         height: BlockNumber,
         shc: &mut SingleHeightConsensus,
         message: ConsensusMessage,
-    ) -> Result<ShcReturn, ConsensusError>
-    where
-        ContextT: ConsensusContext,
-    {
-        // TODO(matan): We need to figure out an actual cacheing strategy under 2 constraints:
-        // 1. Malicious - must be capped so a malicious peer can't DoS us.
-        // 2. Parallel proposals - we may send/receive a proposal for (H+1, 0).
-        // In general I think we will want to only cache (H+1, 0) messages.
+        metadata: BroadcastedMessageMetadata,
+        broadcast_topic_client: &mut BroadcastTopicClient<ConsensusMessage>,
+    ) -> Result<ShcReturn, ConsensusError> {
         if message.height() != height.0 {
             debug!("Received a message for a different height. {:?}", message);
-            if message.height() > height.0 {
-                self.cached_messages.entry(message.height()).or_default().push(message);
+            let message_height = BlockNumber(message.height());
+            let peer = metadata.originator_id.clone();
+            if !self.cached_messages.insert(height, message_height, peer, (message, metadata)) {
+                debug!("Dropping a message for height {message_height}.");
             }
             return Ok(ShcReturn::Tasks(Vec::new()));
         }
@@ -222,38 +309,86 @@ impl MultiHeightManager {
             ConsensusMessage::Proposal(_) => Err(ConsensusError::InternalNetworkError(
                 "Proposal variant of ConsensusMessage no longer supported".to_string(),
             )),
-            _ => {
-                let res = shc.handle_message(context, message).await?;
-                Ok(res)
-            }
+            _ => match shc.handle_message(context, message).await {
+                Err(ConsensusError::InvalidSignature(err)) => {
+                    warn!("Reporting peer {:?}: {err}", metadata.originator_id);
+                    let _ = broadcast_topic_client.report_peer(metadata).await;
+                    Ok(ShcReturn::Tasks(Vec::new()))
+                }
+                res => res,
+            },
         }
     }
+}
 
-    // Filters the cached messages:
-    // - returns all of the current height messages.
-    // - drops messages from earlier heights.
-    // - retains future messages in the cache.
-    fn get_current_height_messages(&mut self, height: BlockNumber) -> Vec<ConsensusMessage> {
-        // Depends on `cached_messages` being sorted by height.
-        loop {
-            let Some(entry) = self.cached_messages.first_entry() else {
-                return Vec::new();
-            };
-            match entry.key().cmp(&height.0) {
-                std::cmp::Ordering::Greater => return Vec::new(),
-                std::cmp::Ordering::Equal => return entry.remove(),
-                std::cmp::Ordering::Less => {
-                    entry.remove();
+/// Holds messages for future heights until their height is reached. To bound the memory a malicious
+/// peer can take up, only heights up to `height_limit` ahead of the current height are cached, and
+/// each source (e.g., a peer) can have up to `per_peer_limit` entries cached.
+struct FutureHeightCache<SourceT, T> {
+    config: FutureMessagesConfig,
+    // {height: [(source, entry)]}
+    entries: BTreeMap<BlockNumber, Vec<(SourceT, T)>>,
+    // {source: number of cached entries}
+    entries_per_source: HashMap<SourceT, usize>,
+}
+
+impl<SourceT: Clone + Eq + Hash, T> FutureHeightCache<SourceT, T> {
+    fn new(config: FutureMessagesConfig) -> Self {
+        Self { config, entries: BTreeMap::new(), entries_per_source: HashMap::new() }
+    }
+
+    // Caches `entry` for `height`, while running `current_height`. Returns false if the entry was
+    // dropped, since its height is out of the window or its source reached the limit.
+    fn insert(
+        &mut self,
+        current_height: BlockNumber,
+        height: BlockNumber,
+        source: SourceT,
+        entry: T,
+    ) -> bool {
+        if height <= current_height || height.0 - current_height.0 > self.config.height_limit {
+            return false;
+        }
+        let num_entries = self.entries_per_source.entry(source.clone()).or_default();
+        if *num_entries >= self.config.per_peer_limit {
+            return false;
+        }
+        *num_entries += 1;
+        self.entries.entry(height).or_default().push((source, entry));
+        true
+    }
+
+    // Returns the entries of `height`, and drops those of earlier heights.
+    fn take(&mut self, height: BlockNumber) -> Vec<T> {
+        let mut current_height_entries = Vec::new();
+        // Depends on `entries` being sorted by height.
+        while let Some(height_entries) = self.entries.first_entry() {
+            if *height_entries.key() > height {
+                break;
+            }
+            let is_current_height = *height_entries.key() == height;
+            for (source, entry) in height_entries.remove() {
+                let num_entries = self
+                    .entries_per_source
+                    .get_mut(&source)
+                    .expect("Cached entries are counted per source.");
+                *num_entries -= 1;
+                if *num_entries == 0 {
+                    self.entries_per_source.remove(&source);
+                }
+                if is_current_height {
+                    current_height_entries.push(entry);
                 }
             }
         }
+        current_height_entries
     }
 }
 
 async fn next_message(
-    cached_messages: &mut Vec<ConsensusMessage>,
+    cached_messages: &mut Vec<(ConsensusMessage, BroadcastedMessageMetadata)>,
     broadcast_channels: &mut BroadcastConsensusMessageChannel,
-) -> Result<ConsensusMessage, ConsensusError> {
+) -> Result<(ConsensusMessage, BroadcastedMessageMetadata), ConsensusError> {
     let BroadcastConsensusMessageChannel { broadcasted_messages_receiver, broadcast_topic_client } =
         broadcast_channels;
     if let Some(msg) = cached_messages.pop() {
@@ -267,11 +402,10 @@ async fn next_message(
             )
         })?;
     match msg {
-        // TODO(matan): Return report_sender for use in later errors by SHC.
         Ok(msg) => {
             let _ =
                 broadcast_topic_client.continue_propagation(&broadcasted_message_metadata).await;
-            Ok(msg)
+            Ok((msg, broadcasted_message_metadata))
         }
         Err(e) => {
             // Failed to parse consensus message
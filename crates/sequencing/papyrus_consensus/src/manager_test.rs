@@ -1,9 +1,10 @@
+use std::sync::Arc;
 use std::time::Duration;
 use std::vec;
 
 use async_trait::async_trait;
 use futures::channel::{mpsc, oneshot};
-use futures::SinkExt;
+use futures::{SinkExt, StreamExt};
 use lazy_static::lazy_static;
 use mockall::mock;
 use mockall::predicate::eq;
@@ -12,7 +13,7 @@ use papyrus_network::network_manager::test_utils::{
     MockBroadcastedMessagesSender,
     TestSubscriberChannels,
 };
-use papyrus_network_types::network_types::BroadcastedMessageMetadata;
+use papyrus_network_types::network_types::{BroadcastedMessageMetadata, OpaquePeerId};
 use papyrus_protobuf::consensus::{
     ConsensusMessage,
     ProposalFin,
@@ -25,10 +26,20 @@ use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::transaction::Transaction;
 use starknet_types_core::felt::Felt;
 
-use super::{run_consensus, MultiHeightManager};
-use crate::config::TimeoutsConfig;
-use crate::test_utils::{precommit, prevote, proposal, proposal_init};
-use crate::types::{ConsensusContext, ConsensusError, ProposalContentId, Round, ValidatorId};
+use super::{run_consensus, FutureHeightCache, MultiHeightManager};
+use crate::config::{FutureMessagesConfig, TimeoutsConfig};
+use crate::signing::{TestSignatureVerifier, TestSigner};
+use crate::test_utils::{precommit, prevote, proposal_fin, proposal_init};
+use crate::types::{
+    ConsensusContext,
+    ConsensusError,
+    Evidence,
+    ProposalContentId,
+    Round,
+    ValidatorId,
+};
+use crate::validator_set::ValidatorSet;
+use crate::wal::InMemoryWal;
 
 lazy_static! {
     static ref PROPOSER_ID: ValidatorId = 0_u32.into();
@@ -69,7 +80,7 @@ mock! {
             init: ProposalInit,
         );
 
-        async fn validators(&self, height: BlockNumber) -> Vec<ValidatorId>;
+        async fn validators(&self, height: BlockNumber) -> ValidatorSet;
 
         fn proposer(&self, height: BlockNumber, round: Round) -> ValidatorId;
 
@@ -82,6 +93,8 @@ mock! {
         ) -> Result<(), ConsensusError>;
 
         async fn set_height_and_round(&mut self, height: BlockNumber, round: Round);
+
+        async fn report_evidence(&mut self, evidence: Evidence) -> Result<(), ConsensusError>;
     }
 }
 
@@ -92,30 +105,46 @@ async fn send(sender: &mut MockBroadcastedMessagesSender<ConsensusMessage>, msg:
 }
 
 async fn send_proposal(
-    proposal_receiver_sender: &mut mpsc::Sender<mpsc::Receiver<ProposalPart>>,
+    proposal_receiver_sender: &mut mpsc::Sender<(OpaquePeerId, mpsc::Receiver<ProposalPart>)>,
+    content: ProposalPart,
+) {
+    let peer = OpaquePeerId::get_test_instance(&mut get_rng());
+    send_proposal_from(proposal_receiver_sender, peer, content).await;
+}
+
+async fn send_proposal_from(
+    proposal_receiver_sender: &mut mpsc::Sender<(OpaquePeerId, mpsc::Receiver<ProposalPart>)>,
+    peer: OpaquePeerId,
     content: ProposalPart,
 ) {
     let (mut proposal_sender, proposal_receiver) = mpsc::channel(CHANNEL_SIZE);
-    proposal_receiver_sender.send(proposal_receiver).await.unwrap();
+    proposal_receiver_sender.send((peer, proposal_receiver)).await.unwrap();
     proposal_sender.send(content).await.unwrap();
 }
 
-#[ignore] // TODO(guyn): return this once caching proposals is implemented.
 #[tokio::test]
 async fn manager_multiple_heights_unordered() {
     let TestSubscriberChannels { mock_network, subscriber_channels } =
         mock_register_broadcast_topic().unwrap();
     let mut sender = mock_network.broadcasted_messages_sender;
 
-    // TODO(guyn): refactor this test to pass proposals through the correct channels.
-    let (_proposal_receiver_sender, mut proposal_receiver_receiver) = mpsc::channel(CHANNEL_SIZE);
+    let (mut proposal_receiver_sender, mut proposal_receiver_receiver) =
+        mpsc::channel(CHANNEL_SIZE);
 
     // Send messages for height 2 followed by those for height 1.
-    send(&mut sender, proposal(Felt::TWO, 2, 0, *PROPOSER_ID)).await;
+    send_proposal(
+        &mut proposal_receiver_sender,
+        ProposalPart::Init(proposal_init(2, 0, *PROPOSER_ID)),
+    )
+    .await;
     send(&mut sender, prevote(Some(Felt::TWO), 2, 0, *PROPOSER_ID)).await;
     send(&mut sender, precommit(Some(Felt::TWO), 2, 0, *PROPOSER_ID)).await;
 
-    send(&mut sender, proposal(Felt::ONE, 1, 0, *PROPOSER_ID)).await;
+    send_proposal(
+        &mut proposal_receiver_sender,
+        ProposalPart::Init(proposal_init(1, 0, *PROPOSER_ID)),
+    )
+    .await;
     send(&mut sender, prevote(Some(Felt::ONE), 1, 0, *PROPOSER_ID)).await;
     send(&mut sender, precommit(Some(Felt::ONE), 1, 0, *PROPOSER_ID)).await;
 
@@ -126,20 +155,26 @@ async fn manager_multiple_heights_unordered() {
         .return_once(move |_, _, _, _, _| {
             let (block_sender, block_receiver) = oneshot::channel();
             block_sender
-                .send((
-                    BlockHash(Felt::ONE),
-                    ProposalFin { proposal_content_id: BlockHash(Felt::ONE) },
-                ))
+                .send((BlockHash(Felt::ONE), proposal_fin(Felt::ONE, 1, 0, *PROPOSER_ID)))
                 .unwrap();
             block_receiver
         })
         .times(1);
-    context.expect_validators().returning(move |_| vec![*PROPOSER_ID, *VALIDATOR_ID]);
+    context
+        .expect_validators()
+        .returning(move |_| ValidatorSet::uniform([*PROPOSER_ID, *VALIDATOR_ID]));
     context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
     context.expect_set_height_and_round().returning(move |_, _| ());
     context.expect_broadcast().returning(move |_| Ok(()));
 
-    let mut manager = MultiHeightManager::new(*VALIDATOR_ID, TIMEOUTS.clone());
+    let mut manager = MultiHeightManager::new(
+        *VALIDATOR_ID,
+        TIMEOUTS.clone(),
+        FutureMessagesConfig::default(),
+        Arc::new(TestSigner::new(*VALIDATOR_ID)),
+        Arc::new(TestSignatureVerifier),
+        Arc::new(InMemoryWal::default()),
+    );
     let mut subscriber_channels = subscriber_channels.into();
     let decision = manager
         .run_height(
@@ -159,10 +194,7 @@ async fn manager_multiple_heights_unordered() {
         .return_once(move |_, _, _, _, _| {
             let (block_sender, block_receiver) = oneshot::channel();
             block_sender
-                .send((
-                    BlockHash(Felt::TWO),
-                    ProposalFin { proposal_content_id: BlockHash(Felt::TWO) },
-                ))
+                .send((BlockHash(Felt::TWO), proposal_fin(Felt::TWO, 2, 0, *PROPOSER_ID)))
                 .unwrap();
             block_receiver
         })
@@ -180,7 +212,93 @@ async fn manager_multiple_heights_unordered() {
     assert_eq!(decision.block, BlockHash(Felt::TWO));
 }
 
-#[ignore] // TODO(guyn): return this once caching proposals is implemented.
+#[tokio::test]
+async fn spoofed_proposer_cannot_evict_future_proposal() {
+    let TestSubscriberChannels { mock_network, subscriber_channels } =
+        mock_register_broadcast_topic().unwrap();
+    let mut sender = mock_network.broadcasted_messages_sender;
+
+    let (mut proposal_receiver_sender, mut proposal_receiver_receiver) =
+        mpsc::channel(CHANNEL_SIZE);
+
+    // A peer claiming to be the proposer fills its quota for height 2 before the real proposal
+    // arrives from the proposer's peer.
+    let spoofer = OpaquePeerId::get_test_instance(&mut get_rng());
+    for _ in 0..2 {
+        send_proposal_from(
+            &mut proposal_receiver_sender,
+            spoofer.clone(),
+            ProposalPart::Init(proposal_init(2, 1, *PROPOSER_ID)),
+        )
+        .await;
+    }
+    send_proposal(
+        &mut proposal_receiver_sender,
+        ProposalPart::Init(proposal_init(2, 0, *PROPOSER_ID)),
+    )
+    .await;
+    send(&mut sender, prevote(Some(Felt::TWO), 2, 0, *PROPOSER_ID)).await;
+    send(&mut sender, precommit(Some(Felt::TWO), 2, 0, *PROPOSER_ID)).await;
+
+    send_proposal(
+        &mut proposal_receiver_sender,
+        ProposalPart::Init(proposal_init(1, 0, *PROPOSER_ID)),
+    )
+    .await;
+    send(&mut sender, prevote(Some(Felt::ONE), 1, 0, *PROPOSER_ID)).await;
+    send(&mut sender, precommit(Some(Felt::ONE), 1, 0, *PROPOSER_ID)).await;
+
+    let mut context = MockTestContext::new();
+    context.expect_validate_proposal().returning(move |height, round, _, _, _| {
+        let (block_sender, block_receiver) = oneshot::channel();
+        let id = Felt::from(height.0) + Felt::from(round);
+        block_sender
+            .send((BlockHash(id), proposal_fin(id, height.0, round, *PROPOSER_ID)))
+            .unwrap();
+        block_receiver
+    });
+    context
+        .expect_validators()
+        .returning(move |_| ValidatorSet::uniform([*PROPOSER_ID, *VALIDATOR_ID]));
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    context.expect_broadcast().returning(move |_| Ok(()));
+
+    let mut manager = MultiHeightManager::new(
+        *VALIDATOR_ID,
+        TIMEOUTS.clone(),
+        FutureMessagesConfig { height_limit: 1, per_peer_limit: 1 },
+        Arc::new(TestSigner::new(*VALIDATOR_ID)),
+        Arc::new(TestSignatureVerifier),
+        Arc::new(InMemoryWal::default()),
+    );
+    let mut subscriber_channels = subscriber_channels.into();
+    let decision = manager
+        .run_height(
+            &mut context,
+            BlockNumber(1),
+            false,
+            &mut subscriber_channels,
+            &mut proposal_receiver_receiver,
+        )
+        .await
+        .unwrap();
+    assert_eq!(decision.block, BlockHash(Felt::ONE));
+
+    // The real proposal for round 0 was cached despite the spoofer, so height 2 is decided.
+    let decision = manager
+        .run_height(
+            &mut context,
+            BlockNumber(2),
+            false,
+            &mut subscriber_channels,
+            &mut proposal_receiver_receiver,
+        )
+        .await
+        .unwrap();
+    assert_eq!(decision.block, BlockHash(Felt::TWO));
+}
+
 #[tokio::test]
 async fn run_consensus_sync() {
     // Set expectations.
@@ -193,11 +311,13 @@ async fn run_consensus_sync() {
     context.expect_validate_proposal().return_once(move |_, _, _, _, _| {
         let (block_sender, block_receiver) = oneshot::channel();
         block_sender
-            .send((BlockHash(Felt::TWO), ProposalFin { proposal_content_id: BlockHash(Felt::TWO) }))
+            .send((BlockHash(Felt::TWO), proposal_fin(Felt::TWO, 2, 0, *PROPOSER_ID)))
             .unwrap();
         block_receiver
     });
-    context.expect_validators().returning(move |_| vec![*PROPOSER_ID, *VALIDATOR_ID]);
+    context
+        .expect_validators()
+        .returning(move |_| ValidatorSet::uniform([*PROPOSER_ID, *VALIDATOR_ID]));
     context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
     context.expect_set_height_and_round().returning(move |_, _| ());
     context.expect_broadcast().returning(move |_| Ok(()));
@@ -230,6 +350,10 @@ async fn run_consensus_sync() {
             *VALIDATOR_ID,
             Duration::ZERO,
             TIMEOUTS.clone(),
+            FutureMessagesConfig::default(),
+            Arc::new(TestSigner::new(*VALIDATOR_ID)),
+            Arc::new(TestSignatureVerifier),
+            Arc::new(InMemoryWal::default()),
             subscriber_channels.into(),
             proposal_receiver_receiver,
             &mut sync_receiver,
@@ -264,11 +388,13 @@ async fn run_consensus_sync_cancellation_safety() {
     context.expect_validate_proposal().return_once(move |_, _, _, _, _| {
         let (block_sender, block_receiver) = oneshot::channel();
         block_sender
-            .send((BlockHash(Felt::ONE), ProposalFin { proposal_content_id: BlockHash(Felt::ONE) }))
+            .send((BlockHash(Felt::ONE), proposal_fin(Felt::ONE, 1, 0, *PROPOSER_ID)))
             .unwrap();
         block_receiver
     });
-    context.expect_validators().returning(move |_| vec![*PROPOSER_ID, *VALIDATOR_ID]);
+    context
+        .expect_validators()
+        .returning(move |_| ValidatorSet::uniform([*PROPOSER_ID, *VALIDATOR_ID]));
     context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
     context.expect_set_height_and_round().returning(move |_, _| ());
     context.expect_broadcast().with(eq(prevote(Some(Felt::ONE), 1, 0, *VALIDATOR_ID))).return_once(
@@ -297,6 +423,10 @@ async fn run_consensus_sync_cancellation_safety() {
             *VALIDATOR_ID,
             Duration::ZERO,
             TIMEOUTS.clone(),
+            FutureMessagesConfig::default(),
+            Arc::new(TestSigner::new(*VALIDATOR_ID)),
+            Arc::new(TestSignatureVerifier),
+            Arc::new(InMemoryWal::default()),
             subscriber_channels.into(),
             proposal_receiver_receiver,
             &mut sync_receiver,
@@ -350,16 +480,16 @@ async fn test_timeouts() {
 
     let mut context = MockTestContext::new();
     context.expect_set_height_and_round().returning(move |_, _| ());
-    context.expect_validate_proposal().returning(move |_, _, _, _, _| {
+    context.expect_validate_proposal().returning(move |height, round, _, _, _| {
         let (block_sender, block_receiver) = oneshot::channel();
         block_sender
-            .send((BlockHash(Felt::ONE), ProposalFin { proposal_content_id: BlockHash(Felt::ONE) }))
+            .send((BlockHash(Felt::ONE), proposal_fin(Felt::ONE, height.0, round, *PROPOSER_ID)))
             .unwrap();
         block_receiver
     });
-    context
-        .expect_validators()
-        .returning(move |_| vec![*PROPOSER_ID, *VALIDATOR_ID, *VALIDATOR_ID_2, *VALIDATOR_ID_3]);
+    context.expect_validators().returning(move |_| {
+        ValidatorSet::uniform([*PROPOSER_ID, *VALIDATOR_ID, *VALIDATOR_ID_2, *VALIDATOR_ID_3])
+    });
     context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
 
     let (timeout_send, timeout_receive) = oneshot::channel();
@@ -374,7 +504,14 @@ async fn test_timeouts() {
         });
     context.expect_broadcast().returning(move |_| Ok(()));
 
-    let mut manager = MultiHeightManager::new(*VALIDATOR_ID, TIMEOUTS.clone());
+    let mut manager = MultiHeightManager::new(
+        *VALIDATOR_ID,
+        TIMEOUTS.clone(),
+        FutureMessagesConfig::default(),
+        Arc::new(TestSigner::new(*VALIDATOR_ID)),
+        Arc::new(TestSignatureVerifier),
+        Arc::new(InMemoryWal::default()),
+    );
     let manager_handle = tokio::spawn(async move {
         let decision = manager
             .run_height(
@@ -406,3 +543,86 @@ async fn test_timeouts() {
 
     manager_handle.await.unwrap();
 }
+
+#[tokio::test]
+async fn report_peer_on_invalid_signature() {
+    let TestSubscriberChannels { mut mock_network, subscriber_channels } =
+        mock_register_broadcast_topic().unwrap();
+    let (_proposal_receiver_sender, mut proposal_receiver_receiver) = mpsc::channel(CHANNEL_SIZE);
+
+    let mut context = MockTestContext::new();
+    context
+        .expect_validators()
+        .returning(move |_| ValidatorSet::uniform([*PROPOSER_ID, *VALIDATOR_ID]));
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    context.expect_broadcast().returning(move |_| Ok(()));
+
+    let mut manager = MultiHeightManager::new(
+        *VALIDATOR_ID,
+        TIMEOUTS.clone(),
+        FutureMessagesConfig::default(),
+        Arc::new(TestSigner::new(*VALIDATOR_ID)),
+        Arc::new(TestSignatureVerifier),
+        Arc::new(InMemoryWal::default()),
+    );
+    let _manager_handle = tokio::spawn(async move {
+        manager
+            .run_height(
+                &mut context,
+                BlockNumber(1),
+                false,
+                &mut subscriber_channels.into(),
+                &mut proposal_receiver_receiver,
+            )
+            .await
+    });
+
+    let ConsensusMessage::Vote(mut vote) = prevote(Some(Felt::ONE), 1, 0, *PROPOSER_ID) else {
+        unreachable!("prevote returns a vote");
+    };
+    vote.signature = None;
+    let metadata = BroadcastedMessageMetadata::get_test_instance(&mut get_rng());
+    mock_network
+        .broadcasted_messages_sender
+        .send((ConsensusMessage::Vote(vote), metadata.clone()))
+        .await
+        .unwrap();
+
+    let reported_peer = mock_network.reported_messages_receiver.next().await.unwrap();
+    assert_eq!(reported_peer, metadata.originator_id.private_get_peer_id());
+}
+
+#[test]
+fn future_height_cache_only_accepts_heights_in_the_window() {
+    let mut cache =
+        FutureHeightCache::new(FutureMessagesConfig { height_limit: 2, per_peer_limit: 10 });
+    let current_height = BlockNumber(5);
+
+    assert!(!cache.insert(current_height, BlockNumber(4), *PROPOSER_ID, "past"));
+    assert!(!cache.insert(current_height, BlockNumber(5), *PROPOSER_ID, "current"));
+    assert!(cache.insert(current_height, BlockNumber(6), *PROPOSER_ID, "next"));
+    assert!(cache.insert(current_height, BlockNumber(7), *PROPOSER_ID, "in_window"));
+    assert!(!cache.insert(current_height, BlockNumber(8), *PROPOSER_ID, "beyond_window"));
+
+    // Entries of earlier heights are dropped.
+    assert_eq!(cache.take(BlockNumber(7)), vec!["in_window"]);
+    assert_eq!(cache.take(BlockNumber(6)), Vec::<&str>::new());
+}
+
+#[test]
+fn future_height_cache_is_bounded_per_peer() {
+    let mut cache =
+        FutureHeightCache::new(FutureMessagesConfig { height_limit: 1, per_peer_limit: 2 });
+    let current_height = BlockNumber(1);
+    let next_height = current_height.unchecked_next();
+
+    assert!(cache.insert(current_height, next_height, *PROPOSER_ID, 1));
+    assert!(cache.insert(current_height, next_height, *PROPOSER_ID, 2));
+    assert!(!cache.insert(current_height, next_height, *PROPOSER_ID, 3));
+    assert!(cache.insert(current_height, next_height, *VALIDATOR_ID, 4));
+    assert_eq!(cache.take(next_height), vec![1, 2, 4]);
+
+    // Taking the entries out of the cache frees up room for the peer.
+    assert!(cache.insert(next_height, next_height.unchecked_next(), *PROPOSER_ID, 5));
+}
@@ -0,0 +1,267 @@
+//! Signing and verification of consensus messages, see [`ConsensusSigner`] and
+//! [`SignatureVerifier`].
+//!
+//! Votes are signed by their voter, and the fin of a proposal by its proposer. What gets signed is
+//! a Poseidon hash of the message, prefixed by the message type so that a signature over one type
+//! of message can't be passed off as another.
+
+#[cfg(test)]
+#[path = "signing_test.rs"]
+mod signing_test;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use papyrus_protobuf::consensus::{ProposalFin, Vote, VoteType};
+use starknet_api::block::BlockNumber;
+use starknet_api::crypto::utils::{verify_message_hash_signature, PublicKey, Signature};
+use starknet_types_core::felt::Felt;
+use tracing::warn;
+
+use crate::config::ConsensusConfig;
+use crate::types::{Round, ValidatorId};
+
+// Prefixed to the signed messages, to separate the signatures of different message types.
+const VOTE_PREFIX: &[u8] = b"CONSENSUS_VOTE";
+const PROPOSAL_FIN_PREFIX: &[u8] = b"CONSENSUS_PROPOSAL_FIN";
+
+/// Errors from signing a consensus message or verifying its signature.
+#[derive(thiserror::Error, Clone, Debug, PartialEq)]
+pub enum SigningError {
+    /// The message is not signed.
+    #[error("Message from {0} is not signed.")]
+    MissingSignature(ValidatorId),
+    /// There is no key to verify the signatures of this validator.
+    #[error("Unknown signer {0}.")]
+    UnknownSigner(ValidatorId),
+    /// The signature doesn't match the message and the signer.
+    #[error("Invalid signature from {signer}: {reason}")]
+    InvalidSignature {
+        /// The validator that supposedly signed the message.
+        signer: ValidatorId,
+        /// Why the signature was rejected.
+        reason: String,
+    },
+    /// This node failed to sign a message.
+    #[error("Failed to sign message: {0}")]
+    SignFailed(String),
+    /// The keys to sign and verify messages with are not configured.
+    #[error("Missing consensus keys: {0}")]
+    MissingKeys(String),
+}
+
+/// Signs the consensus messages sent by this node.
+pub trait ConsensusSigner: Send + Sync {
+    /// Signs `message_hash` on behalf of this node's validator.
+    fn sign(&self, message_hash: Felt) -> Result<Signature, SigningError>;
+
+    /// Returns `vote` with this node's signature.
+    fn sign_vote(&self, mut vote: Vote) -> Result<Vote, SigningError> {
+        vote.signature = Some(self.sign(vote_hash(&vote))?);
+        Ok(vote)
+    }
+
+    /// Returns `fin` with this node's signature, as the proposer of `height` and `round`.
+    fn sign_proposal_fin(
+        &self,
+        height: BlockNumber,
+        round: Round,
+        mut fin: ProposalFin,
+    ) -> Result<ProposalFin, SigningError> {
+        fin.signature = Some(self.sign(proposal_fin_hash(height, round, &fin))?);
+        Ok(fin)
+    }
+}
+
+/// Verifies the signatures of the consensus messages received from other validators.
+pub trait SignatureVerifier: Send + Sync {
+    /// Verifies that `signature` was made by `signer` over `message_hash`.
+    fn verify(
+        &self,
+        signer: ValidatorId,
+        message_hash: Felt,
+        signature: &Signature,
+    ) -> Result<(), SigningError>;
+
+    /// Verifies that `vote` was signed by its voter.
+    fn verify_vote(&self, vote: &Vote) -> Result<(), SigningError> {
+        let signature =
+            vote.signature.as_ref().ok_or(SigningError::MissingSignature(vote.voter))?;
+        self.verify(vote.voter, vote_hash(vote), signature)
+    }
+
+    /// Verifies that `fin` was signed by `proposer` for the proposal of `height` and `round`.
+    fn verify_proposal_fin(
+        &self,
+        proposer: ValidatorId,
+        height: BlockNumber,
+        round: Round,
+        fin: &ProposalFin,
+    ) -> Result<(), SigningError> {
+        let signature = fin.signature.as_ref().ok_or(SigningError::MissingSignature(proposer))?;
+        self.verify(proposer, proposal_fin_hash(height, round, fin), signature)
+    }
+}
+
+/// The hash signed by the voter, covering all the fields of the vote except for the signature.
+pub fn vote_hash(vote: &Vote) -> Felt {
+    let vote_type = match vote.vote_type {
+        VoteType::Prevote => Felt::ZERO,
+        VoteType::Precommit => Felt::ONE,
+    };
+    // Flag nil votes, so that they can't be confused with a vote for any block hash.
+    let (is_nil, block_hash) = match vote.block_hash {
+        Some(block_hash) => (Felt::ZERO, block_hash.0),
+        None => (Felt::ONE, Felt::ZERO),
+    };
+    starknet_crypto::poseidon_hash_many(&[
+        Felt::from_bytes_be_slice(VOTE_PREFIX),
+        vote_type,
+        Felt::from(vote.height),
+        Felt::from(vote.round),
+        is_nil,
+        block_hash,
+        *vote.voter.0.key(),
+    ])
+}
+
+/// The hash signed by the proposer. The fin doesn't contain the height and round of the proposal,
+/// so they are added to prevent replaying the fin in another proposal.
+pub fn proposal_fin_hash(height: BlockNumber, round: Round, fin: &ProposalFin) -> Felt {
+    starknet_crypto::poseidon_hash_many(&[
+        Felt::from_bytes_be_slice(PROPOSAL_FIN_PREFIX),
+        Felt::from(height.0),
+        Felt::from(round),
+        fin.proposal_content_id.0,
+    ])
+}
+
+/// Creates the signer of this node and the verifier of the validators' signatures, from the keys
+/// in `config`. Fails if the private key or the public keys of the validators are missing.
+///
+/// Only if `insecure_test_signatures` is set, messages are signed and verified with [`TestSigner`]
+/// signatures instead, which don't authenticate their validator, so this is only fit for tests.
+pub fn create_signer_and_verifier(
+    config: &ConsensusConfig,
+) -> Result<(Arc<dyn ConsensusSigner>, Arc<dyn SignatureVerifier>), SigningError> {
+    if config.insecure_test_signatures {
+        warn!("Signing consensus messages with insecure test signatures.");
+        return Ok((
+            Arc::new(TestSigner::new(config.validator_id)),
+            Arc::new(TestSignatureVerifier),
+        ));
+    }
+    let private_key = config
+        .private_key
+        .ok_or_else(|| SigningError::MissingKeys("the private key is not set".to_string()))?;
+    if config.validator_public_keys.is_empty() {
+        return Err(SigningError::MissingKeys(
+            "the public keys of the validators are not set".to_string(),
+        ));
+    }
+    let public_keys = config.validator_public_keys.clone().into_iter().collect();
+    Ok((Arc::new(LocalKeySigner::new(private_key)), Arc::new(PublicKeyVerifier::new(public_keys))))
+}
+
+/// Signs with a Stark private key held by this node.
+pub struct LocalKeySigner {
+    private_key: Felt,
+}
+
+impl LocalKeySigner {
+    /// Creates a signer for the validator owning `private_key`.
+    pub fn new(private_key: Felt) -> Self {
+        Self { private_key }
+    }
+
+    /// The public key with which other validators verify this signer's signatures.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey(starknet_crypto::get_public_key(&self.private_key))
+    }
+}
+
+impl ConsensusSigner for LocalKeySigner {
+    fn sign(&self, message_hash: Felt) -> Result<Signature, SigningError> {
+        let k = starknet_crypto::rfc6979_generate_k(&message_hash, &self.private_key, None);
+        let signature = starknet_crypto::sign(&self.private_key, &message_hash, &k)
+            .map_err(|err| SigningError::SignFailed(format!("{err:?}")))?;
+        Ok(Signature { r: signature.r, s: signature.s })
+    }
+}
+
+/// Verifies signatures against the Stark public keys of the validators.
+#[derive(Clone, Debug, Default)]
+pub struct PublicKeyVerifier {
+    public_keys: HashMap<ValidatorId, PublicKey>,
+}
+
+impl PublicKeyVerifier {
+    /// Creates a verifier of the signatures of the given validators.
+    pub fn new(public_keys: HashMap<ValidatorId, PublicKey>) -> Self {
+        Self { public_keys }
+    }
+}
+
+impl SignatureVerifier for PublicKeyVerifier {
+    fn verify(
+        &self,
+        signer: ValidatorId,
+        message_hash: Felt,
+        signature: &Signature,
+    ) -> Result<(), SigningError> {
+        let public_key =
+            self.public_keys.get(&signer).ok_or(SigningError::UnknownSigner(signer))?;
+        match verify_message_hash_signature(&message_hash, signature, public_key) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(SigningError::InvalidSignature {
+                signer,
+                reason: "The signature doesn't match the public key.".to_string(),
+            }),
+            Err(err) => Err(SigningError::InvalidSignature { signer, reason: err.to_string() }),
+        }
+    }
+}
+
+/// Signs without a key, for tests. Nodes only use it if `insecure_test_signatures` is set.
+///
+/// The signature is made of the message hash and the validator ID, so it only protects against
+/// corrupted messages, not against impersonation.
+#[derive(Clone, Copy, Debug)]
+pub struct TestSigner {
+    validator_id: ValidatorId,
+}
+
+impl TestSigner {
+    /// Creates a signer on behalf of `validator_id`.
+    pub fn new(validator_id: ValidatorId) -> Self {
+        Self { validator_id }
+    }
+}
+
+impl ConsensusSigner for TestSigner {
+    fn sign(&self, message_hash: Felt) -> Result<Signature, SigningError> {
+        Ok(Signature { r: message_hash, s: *self.validator_id.0.key() })
+    }
+}
+
+/// Verifies the signatures of [`TestSigner`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TestSignatureVerifier;
+
+impl SignatureVerifier for TestSignatureVerifier {
+    fn verify(
+        &self,
+        signer: ValidatorId,
+        message_hash: Felt,
+        signature: &Signature,
+    ) -> Result<(), SigningError> {
+        let expected = TestSigner::new(signer).sign(message_hash)?;
+        if signature != &expected {
+            return Err(SigningError::InvalidSignature {
+                signer,
+                reason: format!("Expected {expected:?}, got {signature:?}."),
+            });
+        }
+        Ok(())
+    }
+}
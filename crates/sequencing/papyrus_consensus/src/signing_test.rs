@@ -0,0 +1,192 @@
+use std::collections::{BTreeMap, HashMap};
+
+use assert_matches::assert_matches;
+use papyrus_protobuf::consensus::{ProposalFin, Vote, VoteType};
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_types_core::felt::Felt;
+use test_case::test_case;
+
+use crate::config::ConsensusConfig;
+use crate::signing::{
+    create_signer_and_verifier,
+    ConsensusSigner,
+    LocalKeySigner,
+    PublicKeyVerifier,
+    SignatureVerifier,
+    SigningError,
+    TestSignatureVerifier,
+    TestSigner,
+};
+use crate::types::ValidatorId;
+
+const VOTER_PRIVATE_KEY: Felt = Felt::from_hex_unchecked("0x1234");
+const OTHER_PRIVATE_KEY: Felt = Felt::from_hex_unchecked("0x5678");
+
+fn voter() -> ValidatorId {
+    1_u32.into()
+}
+
+fn other_validator() -> ValidatorId {
+    2_u32.into()
+}
+
+fn vote(vote_type: VoteType, block_felt: Option<Felt>) -> Vote {
+    Vote {
+        vote_type,
+        height: 1,
+        round: 0,
+        block_hash: block_felt.map(BlockHash),
+        voter: voter(),
+        signature: None,
+    }
+}
+
+fn local_key_verifier() -> PublicKeyVerifier {
+    PublicKeyVerifier::new(HashMap::from([
+        (voter(), LocalKeySigner::new(VOTER_PRIVATE_KEY).public_key()),
+        (other_validator(), LocalKeySigner::new(OTHER_PRIVATE_KEY).public_key()),
+    ]))
+}
+
+#[test_case(VoteType::Prevote, Some(Felt::ONE); "prevote")]
+#[test_case(VoteType::Precommit, Some(Felt::ONE); "precommit")]
+#[test_case(VoteType::Prevote, None; "nil_prevote")]
+fn local_key_signed_vote(vote_type: VoteType, block_felt: Option<Felt>) {
+    let signer = LocalKeySigner::new(VOTER_PRIVATE_KEY);
+    let verifier = local_key_verifier();
+
+    let signed_vote = signer.sign_vote(vote(vote_type, block_felt)).unwrap();
+    assert_eq!(verifier.verify_vote(&signed_vote), Ok(()));
+
+    // The signature doesn't carry over to a vote with different content.
+    let tampered_vote = Vote { round: 1, ..signed_vote.clone() };
+    assert_matches!(
+        verifier.verify_vote(&tampered_vote),
+        Err(SigningError::InvalidSignature { signer, .. }) if signer == voter()
+    );
+    let other_type = match signed_vote.vote_type {
+        VoteType::Prevote => VoteType::Precommit,
+        VoteType::Precommit => VoteType::Prevote,
+    };
+    let tampered_vote = Vote { vote_type: other_type, ..signed_vote.clone() };
+    assert_matches!(
+        verifier.verify_vote(&tampered_vote),
+        Err(SigningError::InvalidSignature { .. })
+    );
+}
+
+#[test]
+fn local_key_rejects_impersonation() {
+    let verifier = local_key_verifier();
+
+    // Another validator signs a vote in the name of the voter.
+    let forged_vote = LocalKeySigner::new(OTHER_PRIVATE_KEY)
+        .sign_vote(vote(VoteType::Prevote, Some(Felt::ONE)))
+        .unwrap();
+    assert_matches!(
+        verifier.verify_vote(&forged_vote),
+        Err(SigningError::InvalidSignature { signer, .. }) if signer == voter()
+    );
+}
+
+#[test]
+fn unsigned_vote_and_unknown_signer() {
+    let unsigned_vote = vote(VoteType::Prevote, Some(Felt::ONE));
+    assert_eq!(
+        local_key_verifier().verify_vote(&unsigned_vote),
+        Err(SigningError::MissingSignature(voter()))
+    );
+    assert_eq!(
+        TestSignatureVerifier.verify_vote(&unsigned_vote),
+        Err(SigningError::MissingSignature(voter()))
+    );
+
+    let signed_vote = LocalKeySigner::new(VOTER_PRIVATE_KEY).sign_vote(unsigned_vote).unwrap();
+    assert_eq!(
+        PublicKeyVerifier::default().verify_vote(&signed_vote),
+        Err(SigningError::UnknownSigner(voter()))
+    );
+}
+
+#[test]
+fn proposal_fin_is_bound_to_height_and_round() {
+    let signer = LocalKeySigner::new(VOTER_PRIVATE_KEY);
+    let verifier = local_key_verifier();
+    let height = BlockNumber(1);
+    let fin = ProposalFin { proposal_content_id: BlockHash(Felt::ONE), signature: None };
+
+    let signed_fin = signer.sign_proposal_fin(height, 0, fin).unwrap();
+    assert_eq!(verifier.verify_proposal_fin(voter(), height, 0, &signed_fin), Ok(()));
+
+    assert_matches!(
+        verifier.verify_proposal_fin(voter(), height, 1, &signed_fin),
+        Err(SigningError::InvalidSignature { .. })
+    );
+    assert_matches!(
+        verifier.verify_proposal_fin(voter(), height.unchecked_next(), 0, &signed_fin),
+        Err(SigningError::InvalidSignature { .. })
+    );
+    assert_matches!(
+        verifier.verify_proposal_fin(other_validator(), height, 0, &signed_fin),
+        Err(SigningError::InvalidSignature { .. })
+    );
+}
+
+#[test]
+fn test_signer() {
+    let signed_vote =
+        TestSigner::new(voter()).sign_vote(vote(VoteType::Precommit, Some(Felt::ONE))).unwrap();
+    assert_eq!(TestSignatureVerifier.verify_vote(&signed_vote), Ok(()));
+
+    let tampered_vote = Vote { block_hash: Some(BlockHash(Felt::TWO)), ..signed_vote };
+    assert_matches!(
+        TestSignatureVerifier.verify_vote(&tampered_vote),
+        Err(SigningError::InvalidSignature { .. })
+    );
+}
+
+#[test]
+fn signer_and_verifier_from_config() {
+    let unsigned_vote = vote(VoteType::Precommit, Some(Felt::ONE));
+    let test_signed_vote = TestSigner::new(voter()).sign_vote(unsigned_vote.clone()).unwrap();
+
+    // Test signatures are only used when explicitly requested.
+    let config = ConsensusConfig {
+        validator_id: voter(),
+        insecure_test_signatures: true,
+        ..Default::default()
+    };
+    let (signer, verifier) = create_signer_and_verifier(&config).unwrap();
+    assert_eq!(signer.sign_vote(unsigned_vote.clone()).unwrap(), test_signed_vote);
+    assert_eq!(verifier.verify_vote(&test_signed_vote), Ok(()));
+
+    let config = ConsensusConfig {
+        validator_id: voter(),
+        private_key: Some(VOTER_PRIVATE_KEY),
+        validator_public_keys: BTreeMap::from([(
+            voter(),
+            LocalKeySigner::new(VOTER_PRIVATE_KEY).public_key(),
+        )]),
+        ..Default::default()
+    };
+    let (signer, verifier) = create_signer_and_verifier(&config).unwrap();
+    let signed_vote = signer.sign_vote(unsigned_vote).unwrap();
+    assert_eq!(verifier.verify_vote(&signed_vote), Ok(()));
+    assert_matches!(
+        verifier.verify_vote(&test_signed_vote),
+        Err(SigningError::InvalidSignature { .. })
+    );
+}
+
+#[test]
+fn missing_keys_are_rejected() {
+    let config = ConsensusConfig { validator_id: voter(), ..Default::default() };
+    assert_matches!(create_signer_and_verifier(&config).err(), Some(SigningError::MissingKeys(_)));
+
+    let config = ConsensusConfig {
+        validator_id: voter(),
+        private_key: Some(VOTER_PRIVATE_KEY),
+        ..Default::default()
+    };
+    assert_matches!(create_signer_and_verifier(&config).err(), Some(SigningError::MissingKeys(_)));
+}
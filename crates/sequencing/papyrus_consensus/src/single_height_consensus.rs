@@ -3,26 +3,32 @@
 mod single_height_consensus_test;
 
 use std::collections::hash_map::Entry;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 use std::time::Duration;
 
 #[cfg(test)]
 use enum_as_inner::EnumAsInner;
 use futures::channel::{mpsc, oneshot};
+use papyrus_common::metrics::PAPYRUS_CONSENSUS_EQUIVOCATION_COUNT;
 use papyrus_protobuf::consensus::{ConsensusMessage, ProposalFin, ProposalInit, Vote, VoteType};
 use starknet_api::block::BlockNumber;
 use tracing::{debug, info, instrument, trace, warn};
 
 use crate::config::TimeoutsConfig;
+use crate::signing::{ConsensusSigner, SignatureVerifier};
 use crate::state_machine::{StateMachine, StateMachineEvent};
 use crate::types::{
     ConsensusContext,
     ConsensusError,
     Decision,
+    Evidence,
     ProposalContentId,
     Round,
     ValidatorId,
 };
+use crate::validator_set::{ValidatorSet, VotingPower};
+use crate::wal::{ConsensusWal, WalEntry};
 
 #[derive(Debug, PartialEq)]
 #[cfg_attr(test, derive(EnumAsInner))]
@@ -39,7 +45,6 @@ pub enum ShcEvent {
     Prevote(StateMachineEvent),
     Precommit(StateMachineEvent),
     BuildProposal(StateMachineEvent),
-    // TODO: Replace ProposalContentId with the unvalidated signature from the proposer.
     ValidateProposal(StateMachineEvent, Option<ProposalFin>),
 }
 
@@ -141,7 +146,7 @@ impl ShcTask {
 /// out messages "directly" to the network, and returning a decision to the caller.
 pub(crate) struct SingleHeightConsensus {
     height: BlockNumber,
-    validators: Vec<ValidatorId>,
+    validators: ValidatorSet,
     id: ValidatorId,
     timeouts: TimeoutsConfig,
     state_machine: StateMachine,
@@ -150,6 +155,19 @@ pub(crate) struct SingleHeightConsensus {
     precommits: HashMap<(Round, ValidatorId), Vote>,
     last_prevote: Option<Vote>,
     last_precommit: Option<Vote>,
+    // Equivocations which were already reported, so that they are reported once per vote.
+    equivocations: HashSet<(VoteType, Round, ValidatorId)>,
+    signer: Arc<dyn ConsensusSigner>,
+    signature_verifier: Arc<dyn SignatureVerifier>,
+    // Own votes and the locked and valid values are persisted before the votes are broadcast, and
+    // replayed on start, so that a restarted node doesn't send conflicting votes.
+    wal: Arc<dyn ConsensusWal>,
+    // The locked and valid values which were last persisted to the WAL.
+    persisted_values: (Option<(ProposalContentId, Round)>, Option<(ProposalContentId, Round)>),
+    // The rounds this node already proposed in before restarting, with the content id of the fin
+    // it signed if the proposal was built. Those rounds aren't proposed again, since the content
+    // of the proposal is gone and a new one would conflict with the signed fin.
+    replayed_proposals: HashMap<Round, Option<ProposalContentId>>,
 }
 
 impl SingleHeightConsensus {
@@ -157,13 +175,13 @@ impl SingleHeightConsensus {
         height: BlockNumber,
         is_observer: bool,
         id: ValidatorId,
-        validators: Vec<ValidatorId>,
+        validators: ValidatorSet,
         timeouts: TimeoutsConfig,
+        signer: Arc<dyn ConsensusSigner>,
+        signature_verifier: Arc<dyn SignatureVerifier>,
+        wal: Arc<dyn ConsensusWal>,
     ) -> Self {
-        // TODO(matan): Use actual weights, not just `len`.
-        let n_validators =
-            u32::try_from(validators.len()).expect("Should have way less than u32::MAX validators");
-        let state_machine = StateMachine::new(id, n_validators, is_observer);
+        let state_machine = StateMachine::new(id, &validators, is_observer);
         Self {
             height,
             validators,
@@ -175,6 +193,12 @@ impl SingleHeightConsensus {
             precommits: HashMap::new(),
             last_prevote: None,
             last_precommit: None,
+            equivocations: HashSet::new(),
+            signer,
+            signature_verifier,
+            wal,
+            persisted_values: (None, None),
+            replayed_proposals: HashMap::new(),
         }
     }
 
@@ -184,12 +208,82 @@ impl SingleHeightConsensus {
         context: &mut ContextT,
     ) -> Result<ShcReturn, ConsensusError> {
         info!("Starting consensus with validators {:?}", self.validators);
+        let mut tasks = self.replay_wal(context).await?;
         context.set_height_and_round(self.height, self.state_machine.round()).await;
         let leader_fn = |round: Round| -> ValidatorId { context.proposer(self.height, round) };
         let events = self.state_machine.start(&leader_fn);
-        let ret = self.handle_state_machine_events(context, events).await;
+        let ret = match self.handle_state_machine_events(context, events).await? {
+            ShcReturn::Tasks(start_tasks) => {
+                tasks.extend(start_tasks);
+                ShcReturn::Tasks(tasks)
+            }
+            decision => decision,
+        };
         context.set_height_and_round(self.height, self.state_machine.round()).await;
-        ret
+        Ok(ret)
+    }
+
+    // Restores the votes and proposals this node sent and the values it locked on before
+    // restarting. Rebroadcasts the latest restored votes, since the node may have crashed before
+    // broadcasting them, and returns the tasks which keep rebroadcasting them.
+    async fn replay_wal<ContextT: ConsensusContext>(
+        &mut self,
+        context: &mut ContextT,
+    ) -> Result<Vec<ShcTask>, ConsensusError> {
+        let entries = self.wal.entries(self.height).await?;
+        if entries.is_empty() {
+            return Ok(Vec::new());
+        }
+        info!("Replaying {} WAL entries", entries.len());
+        let mut own_votes = Vec::new();
+        for entry in entries {
+            match entry {
+                WalEntry::Vote(vote) => {
+                    let (votes, last_vote, sm_vote) = match vote.vote_type {
+                        VoteType::Prevote => (
+                            &mut self.prevotes,
+                            &mut self.last_prevote,
+                            StateMachineEvent::Prevote(vote.block_hash, vote.round),
+                        ),
+                        VoteType::Precommit => (
+                            &mut self.precommits,
+                            &mut self.last_precommit,
+                            StateMachineEvent::Precommit(vote.block_hash, vote.round),
+                        ),
+                    };
+                    if last_vote.as_ref().map_or(true, |last| last.round < vote.round) {
+                        *last_vote = Some(vote.clone());
+                    }
+                    votes.insert((vote.round, vote.voter), vote);
+                    own_votes.push(sm_vote);
+                }
+                WalEntry::Values { locked_value_round, valid_value_round } => {
+                    self.persisted_values = (locked_value_round, valid_value_round);
+                }
+                WalEntry::Proposal { round, content_id } => {
+                    self.replayed_proposals.insert(round, content_id);
+                }
+            }
+        }
+        let (locked_value_round, valid_value_round) = self.persisted_values;
+        self.state_machine.replay(own_votes, locked_value_round, valid_value_round);
+
+        let mut tasks = Vec::new();
+        if let Some(vote) = &self.last_prevote {
+            context.broadcast(ConsensusMessage::Vote(vote.clone())).await?;
+            tasks.push(ShcTask::Prevote(
+                self.timeouts.prevote_timeout,
+                StateMachineEvent::Prevote(vote.block_hash, vote.round),
+            ));
+        }
+        if let Some(vote) = &self.last_precommit {
+            context.broadcast(ConsensusMessage::Vote(vote.clone())).await?;
+            tasks.push(ShcTask::Precommit(
+                self.timeouts.precommit_timeout,
+                StateMachineEvent::Precommit(vote.block_hash, vote.round),
+            ));
+        }
+        Ok(tasks)
     }
 
     /// Process the proposal init and initiate block validation. See [`ShcTask::ValidateProposal`]
@@ -312,15 +406,19 @@ impl SingleHeightConsensus {
             }
             ShcEvent::ValidateProposal(
                 StateMachineEvent::Proposal(built_content_id, round, valid_round),
-                received_proposal_id,
+                received_fin,
             ) => {
-                // TODO(matan): Switch to signature validation.
-
                 let mut id = None;
-                if let (Some(built_content_id), Some(ProposalFin { proposal_content_id })) =
-                    (built_content_id, received_proposal_id.clone())
-                {
-                    if built_content_id == proposal_content_id {
+                if let (Some(built_content_id), Some(fin)) = (built_content_id, &received_fin) {
+                    let proposer = context.proposer(self.height, round);
+                    if let Err(err) = self.signature_verifier.verify_proposal_fin(
+                        proposer,
+                        self.height,
+                        round,
+                        fin,
+                    ) {
+                        warn!("Invalid signature on proposal fin: {err}");
+                    } else if built_content_id == fin.proposal_content_id {
                         id = Some(built_content_id);
                     }
                 }
@@ -328,7 +426,7 @@ impl SingleHeightConsensus {
                     warn!(
                         "proposal_id built from content receiver does not match fin: {:#064x?} != \
                          {:#064x?}",
-                        built_content_id, received_proposal_id
+                        built_content_id, received_fin
                     );
                 }
                 // Retaining the entry for this round prevents us from receiving another proposal on
@@ -345,6 +443,11 @@ impl SingleHeightConsensus {
                 .await
             }
             ShcEvent::BuildProposal(StateMachineEvent::GetProposal(proposal_id, round)) => {
+                if proposal_id.is_some() {
+                    self.wal
+                        .append(self.height, WalEntry::Proposal { round, content_id: proposal_id })
+                        .await?;
+                }
                 let old = self.proposals.insert(round, proposal_id);
                 assert!(old.is_none(), "There should be no entry for this round.");
                 let leader_fn =
@@ -370,6 +473,7 @@ impl SingleHeightConsensus {
             debug!("Ignoring vote from voter not in validators: vote={:?}", vote);
             return Ok(ShcReturn::Tasks(Vec::new()));
         }
+        self.signature_verifier.verify_vote(&vote).map_err(ConsensusError::InvalidSignature)?;
 
         let (votes, sm_vote) = match vote.vote_type {
             VoteType::Prevote => {
@@ -386,20 +490,27 @@ impl SingleHeightConsensus {
             }
             Entry::Occupied(entry) => {
                 let old = entry.get();
-                if old.block_hash != vote.block_hash {
-                    return Err(ConsensusError::Equivocation(
-                        self.height,
-                        ConsensusMessage::Vote(old.clone()),
-                        ConsensusMessage::Vote(vote),
-                    ));
-                } else {
-                    // Replay, ignore.
-                    return Ok(ShcReturn::Tasks(Vec::new()));
+                if old.block_hash != vote.block_hash
+                    && self.equivocations.insert((vote.vote_type.clone(), vote.round, vote.voter))
+                {
+                    let evidence = Evidence { first_vote: old.clone(), second_vote: vote };
+                    warn!("Equivocation: {:?}", evidence);
+                    metrics::increment_counter!(
+                        PAPYRUS_CONSENSUS_EQUIVOCATION_COUNT,
+                        "validator" => evidence.validator().to_string()
+                    );
+                    // Failing to report the evidence must not stop this node from deciding.
+                    if let Err(err) = context.report_evidence(evidence).await {
+                        warn!("Failed to report equivocation evidence: {err}");
+                    }
                 }
+                // Only the first vote counts, replays and conflicting votes are ignored.
+                return Ok(ShcReturn::Tasks(Vec::new()));
             }
         }
         let leader_fn = |round: Round| -> ValidatorId { context.proposer(self.height, round) };
-        let sm_events = self.state_machine.handle_event(sm_vote, &leader_fn);
+        let voting_power = self.validators.voting_power(&vote.voter);
+        let sm_events = self.state_machine.handle_vote(sm_vote, voting_power, &leader_fn);
         self.handle_state_machine_events(context, sm_events).await
     }
 
@@ -416,7 +527,7 @@ impl SingleHeightConsensus {
             match event {
                 StateMachineEvent::GetProposal(proposal_id, round) => {
                     ret_val.extend(
-                        self.handle_state_machine_get_proposal(context, proposal_id, round).await,
+                        self.handle_state_machine_get_proposal(context, proposal_id, round).await?,
                     );
                 }
                 StateMachineEvent::Proposal(proposal_id, round, valid_round) => {
@@ -459,9 +570,26 @@ impl SingleHeightConsensus {
                 }
             }
         }
+        self.persist_values().await?;
         Ok(ShcReturn::Tasks(ret_val))
     }
 
+    // Persists the locked and valid values of the state machine, if they changed since they were
+    // last persisted.
+    async fn persist_values(&mut self) -> Result<(), ConsensusError> {
+        let values =
+            (self.state_machine.locked_value_round(), self.state_machine.valid_value_round());
+        if values == self.persisted_values {
+            return Ok(());
+        }
+        let (locked_value_round, valid_value_round) = values;
+        self.wal
+            .append(self.height, WalEntry::Values { locked_value_round, valid_value_round })
+            .await?;
+        self.persisted_values = values;
+        Ok(())
+    }
+
     /// Initiate block building. See [`ShcTask::BuildProposal`] for more details on the full
     /// proposal flow.
     #[instrument(skip(self, context), level = "debug")]
@@ -470,7 +598,7 @@ impl SingleHeightConsensus {
         context: &mut ContextT,
         proposal_id: Option<ProposalContentId>,
         round: Round,
-    ) -> Vec<ShcTask> {
+    ) -> Result<Vec<ShcTask>, ConsensusError> {
         assert!(
             proposal_id.is_none(),
             "ProposalContentId must be None since the state machine is requesting a \
@@ -478,12 +606,23 @@ impl SingleHeightConsensus {
         );
         debug!("Proposer");
 
+        if let Some(content_id) = self.replayed_proposals.get(&round) {
+            // Building another proposal would sign a second fin for this round. Dropping the sender
+            // makes the proposal fail to build, like any other failure to build it.
+            warn!(
+                "Already proposed {content_id:?} in round {round} before restarting, not \
+                 proposing again"
+            );
+            let (_, fin_receiver) = oneshot::channel();
+            return Ok(vec![ShcTask::BuildProposal(round, fin_receiver)]);
+        }
+        self.wal.append(self.height, WalEntry::Proposal { round, content_id: None }).await?;
         // TODO: Figure out how to handle failed proposal building. I believe this should be handled
         // by applying timeoutPropose when we are the leader.
         let init =
             ProposalInit { height: self.height, round, proposer: self.id, valid_round: None };
         let fin_receiver = context.build_proposal(init, self.timeouts.proposal_timeout).await;
-        vec![ShcTask::BuildProposal(round, fin_receiver)]
+        Ok(vec![ShcTask::BuildProposal(round, fin_receiver)])
     }
 
     #[instrument(skip(self, context), level = "debug")]
@@ -524,6 +663,8 @@ impl SingleHeightConsensus {
         round: Round,
         vote_type: VoteType,
     ) -> Result<Vec<ShcTask>, ConsensusError> {
+        // The values which led to this vote must be restored along with it.
+        self.persist_values().await?;
         let (votes, last_vote, task) = match vote_type {
             VoteType::Prevote => (
                 &mut self.prevotes,
@@ -542,13 +683,15 @@ impl SingleHeightConsensus {
                 ),
             ),
         };
-        let vote = Vote {
+        let vote = self.signer.sign_vote(Vote {
             vote_type,
             height: self.height.0,
             round,
             block_hash: proposal_id,
             voter: self.id,
-        };
+            signature: None,
+        })?;
+        self.wal.append(self.height, WalEntry::Vote(vote.clone())).await?;
         if let Some(old) = votes.insert((round, self.id), vote.clone()) {
             // TODO(matan): Consider refactoring not to panic, rather log and return the error.
             panic!("State machine should not send repeat votes: old={:?}, new={:?}", old, vote);
@@ -576,17 +719,16 @@ impl SingleHeightConsensus {
         let supporting_precommits: Vec<Vote> = self
             .validators
             .iter()
-            .filter_map(|v| {
+            .filter_map(|(v, _)| {
                 let vote = self.precommits.get(&(round, *v))?;
-                if vote.block_hash == Some(proposal_id) { Some(vote.clone()) } else { None }
+                (vote.block_hash == Some(proposal_id)).then(|| vote.clone())
             })
             .collect();
-        // TODO(matan): Check actual weights.
-        assert!(
-            supporting_precommits.len()
-                >= usize::try_from(self.state_machine.quorum_size())
-                    .expect("u32 should fit in usize")
-        );
+        let supporting_voting_power: VotingPower = supporting_precommits
+            .iter()
+            .map(|precommit| self.validators.voting_power(&precommit.voter))
+            .sum();
+        assert!(supporting_voting_power >= self.state_machine.quorum_size());
         Ok(ShcReturn::Decision(Decision { precommits: supporting_precommits, block }))
     }
 }
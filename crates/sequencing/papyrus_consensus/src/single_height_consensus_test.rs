@@ -1,6 +1,10 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
 use futures::channel::{mpsc, oneshot};
 use futures::SinkExt;
 use lazy_static::lazy_static;
+use mockall::predicate::eq;
 use papyrus_protobuf::consensus::{ConsensusMessage, ProposalFin, ProposalInit};
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_types_core::felt::Felt;
@@ -9,18 +13,28 @@ use tokio;
 
 use super::SingleHeightConsensus;
 use crate::config::TimeoutsConfig;
+use crate::signing::{ConsensusSigner, TestSignatureVerifier, TestSigner};
 use crate::single_height_consensus::{ShcEvent, ShcReturn, ShcTask};
 use crate::state_machine::StateMachineEvent;
-use crate::test_utils::{precommit, prevote, MockProposalPart, MockTestContext, TestBlock};
-use crate::types::{ConsensusError, ValidatorId};
+use crate::test_utils::{
+    precommit,
+    prevote,
+    proposal_fin,
+    MockProposalPart,
+    MockTestContext,
+    TestBlock,
+};
+use crate::types::{ConsensusError, Evidence, ValidatorId};
+use crate::validator_set::ValidatorSet;
+use crate::wal::{ConsensusWal, InMemoryWal, WalEntry};
 
 lazy_static! {
     static ref PROPOSER_ID: ValidatorId = 0_u32.into();
     static ref VALIDATOR_ID_1: ValidatorId = 1_u32.into();
     static ref VALIDATOR_ID_2: ValidatorId = 2_u32.into();
     static ref VALIDATOR_ID_3: ValidatorId = 3_u32.into();
-    static ref VALIDATORS: Vec<ValidatorId> =
-        vec![*PROPOSER_ID, *VALIDATOR_ID_1, *VALIDATOR_ID_2, *VALIDATOR_ID_3];
+    static ref VALIDATORS: ValidatorSet =
+        ValidatorSet::uniform([*PROPOSER_ID, *VALIDATOR_ID_1, *VALIDATOR_ID_2, *VALIDATOR_ID_3]);
     static ref BLOCK: TestBlock = TestBlock { content: vec![1, 2, 3], id: BlockHash(Felt::ONE) };
     static ref PROPOSAL_INIT: ProposalInit = ProposalInit {
         height: BlockNumber(0),
@@ -31,9 +45,9 @@ lazy_static! {
     static ref TIMEOUTS: TimeoutsConfig = TimeoutsConfig::default();
     static ref VALIDATE_PROPOSAL_EVENT: ShcEvent = ShcEvent::ValidateProposal(
         StateMachineEvent::Proposal(Some(BLOCK.id), PROPOSAL_INIT.round, PROPOSAL_INIT.valid_round,),
-        Some(ProposalFin { proposal_content_id: BLOCK.id }),
+        Some(PROPOSAL_FIN.clone()),
     );
-    static ref PROPOSAL_FIN: ProposalFin = ProposalFin { proposal_content_id: BLOCK.id };
+    static ref PROPOSAL_FIN: ProposalFin = proposal_fin(BLOCK.id.0, 0, 0, *PROPOSER_ID);
 }
 
 const CHANNEL_SIZE: usize = 1;
@@ -82,8 +96,11 @@ async fn proposer() {
         BlockNumber(0),
         false,
         *PROPOSER_ID,
-        VALIDATORS.to_vec(),
+        VALIDATORS.clone(),
         TIMEOUTS.clone(),
+        Arc::new(TestSigner::new(*PROPOSER_ID)),
+        Arc::new(TestSignatureVerifier),
+        Arc::new(InMemoryWal::default()),
     );
 
     context.expect_proposer().times(1).returning(move |_, _| *PROPOSER_ID);
@@ -168,8 +185,11 @@ async fn validator(repeat_proposal: bool) {
         BlockNumber(0),
         false,
         *VALIDATOR_ID_1,
-        VALIDATORS.to_vec(),
+        VALIDATORS.clone(),
         TIMEOUTS.clone(),
+        Arc::new(TestSigner::new(*VALIDATOR_ID_1)),
+        Arc::new(TestSignatureVerifier),
+        Arc::new(InMemoryWal::default()),
     );
 
     context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
@@ -238,21 +258,26 @@ async fn validator(repeat_proposal: bool) {
     );
 }
 
-#[test_case(true; "repeat")]
-#[test_case(false; "equivocation")]
+#[test_case(true, false; "repeat")]
+#[test_case(false, false; "equivocation")]
+#[test_case(false, true; "equivocation_report_fails")]
 #[tokio::test]
-async fn vote_twice(same_vote: bool) {
+async fn vote_twice(same_vote: bool, report_fails: bool) {
     let mut context = MockTestContext::new();
 
     let mut shc = SingleHeightConsensus::new(
         BlockNumber(0),
         false,
         *VALIDATOR_ID_1,
-        VALIDATORS.to_vec(),
+        VALIDATORS.clone(),
         TIMEOUTS.clone(),
+        Arc::new(TestSigner::new(*VALIDATOR_ID_1)),
+        Arc::new(TestSignatureVerifier),
+        Arc::new(InMemoryWal::default()),
     );
 
-    context.expect_proposer().times(1).returning(move |_, _| *PROPOSER_ID);
+    // Called when receiving the proposal and when verifying its fin.
+    context.expect_proposer().times(2).returning(move |_, _| *PROPOSER_ID);
     context.expect_validate_proposal().times(1).returning(move |_, _, _, _, _| {
         let (block_sender, block_receiver) = oneshot::channel();
         block_sender.send((BLOCK.id, PROPOSAL_FIN.clone())).unwrap();
@@ -293,11 +318,25 @@ async fn vote_twice(same_vote: bool) {
 
     let second_vote =
         if same_vote { first_vote.clone() } else { precommit(Some(Felt::TWO), 0, 0, *PROPOSER_ID) };
-    let res = shc.handle_message(&mut context, second_vote.clone()).await;
-    if same_vote {
+    if !same_vote {
+        let (ConsensusMessage::Vote(first_vote), ConsensusMessage::Vote(second_vote)) =
+            (first_vote, second_vote.clone())
+        else {
+            panic!("Expected votes");
+        };
+        let evidence = Evidence { first_vote, second_vote };
+        // Reported only once, even though the conflicting vote is received twice.
+        context.expect_report_evidence().times(1).with(eq(evidence)).returning(move |_| {
+            if report_fails {
+                Err(ConsensusError::InternalNetworkError("Failed to report".to_string()))
+            } else {
+                Ok(())
+            }
+        });
+    }
+    for _ in 0..2 {
+        let res = shc.handle_message(&mut context, second_vote.clone()).await;
         assert_eq!(res, Ok(ShcReturn::Tasks(Vec::new())));
-    } else {
-        assert!(matches!(res, Err(ConsensusError::Equivocation(_, _, _))));
     }
 
     let ShcReturn::Decision(decision) = shc
@@ -318,8 +357,11 @@ async fn rebroadcast_votes() {
         BlockNumber(0),
         false,
         *PROPOSER_ID,
-        VALIDATORS.to_vec(),
+        VALIDATORS.clone(),
         TIMEOUTS.clone(),
+        Arc::new(TestSigner::new(*PROPOSER_ID)),
+        Arc::new(TestSignatureVerifier),
+        Arc::new(InMemoryWal::default()),
     );
 
     context.expect_proposer().times(1).returning(move |_, _| *PROPOSER_ID);
@@ -381,8 +423,11 @@ async fn repropose() {
         BlockNumber(0),
         false,
         *PROPOSER_ID,
-        VALIDATORS.to_vec(),
+        VALIDATORS.clone(),
         TIMEOUTS.clone(),
+        Arc::new(TestSigner::new(*PROPOSER_ID)),
+        Arc::new(TestSignatureVerifier),
+        Arc::new(InMemoryWal::default()),
     );
 
     context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
@@ -466,3 +511,227 @@ async fn repropose() {
             .all(|item| precommits.contains(&ConsensusMessage::Vote(item)))
     );
 }
+
+#[test_case(None; "unsigned")]
+#[test_case(Some(*VALIDATOR_ID_2); "signed_by_another_validator")]
+#[tokio::test]
+async fn reject_vote_with_invalid_signature(signer: Option<ValidatorId>) {
+    let mut context = MockTestContext::new();
+
+    let mut shc = SingleHeightConsensus::new(
+        BlockNumber(0),
+        false,
+        *VALIDATOR_ID_1,
+        VALIDATORS.clone(),
+        TIMEOUTS.clone(),
+        Arc::new(TestSigner::new(*VALIDATOR_ID_1)),
+        Arc::new(TestSignatureVerifier),
+        Arc::new(InMemoryWal::default()),
+    );
+    context.expect_set_height_and_round().returning(move |_, _| ());
+
+    let ConsensusMessage::Vote(mut vote) = prevote(Some(BLOCK.id.0), 0, 0, *PROPOSER_ID) else {
+        unreachable!("prevote returns a vote");
+    };
+    vote.signature = None;
+    if let Some(signer) = signer {
+        vote = TestSigner::new(signer).sign_vote(vote).unwrap();
+    }
+
+    let res = shc.handle_message(&mut context, ConsensusMessage::Vote(vote)).await;
+    assert!(matches!(res, Err(ConsensusError::InvalidSignature(_))));
+
+    // The rejected vote isn't counted: a properly signed vote from the same voter is accepted.
+    assert_eq!(
+        shc.handle_message(&mut context, prevote(Some(Felt::TWO), 0, 0, *PROPOSER_ID)).await,
+        Ok(ShcReturn::Tasks(Vec::new()))
+    );
+}
+
+#[tokio::test]
+async fn reject_proposal_with_invalid_fin_signature() {
+    let mut context = MockTestContext::new();
+
+    let mut shc = SingleHeightConsensus::new(
+        BlockNumber(0),
+        false,
+        *VALIDATOR_ID_1,
+        VALIDATORS.clone(),
+        TIMEOUTS.clone(),
+        Arc::new(TestSigner::new(*VALIDATOR_ID_1)),
+        Arc::new(TestSignatureVerifier),
+        Arc::new(InMemoryWal::default()),
+    );
+
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context.expect_validate_proposal().times(1).returning(move |_, _, _, _, _| {
+        let (block_sender, block_receiver) = oneshot::channel();
+        block_sender.send((BLOCK.id, PROPOSAL_FIN.clone())).unwrap();
+        block_receiver
+    });
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    // The proposal is treated as invalid, so the node votes nil.
+    context
+        .expect_broadcast()
+        .times(1)
+        .withf(move |msg: &ConsensusMessage| msg == &prevote(None, 0, 0, *VALIDATOR_ID_1))
+        .returning(move |_| Ok(()));
+    handle_proposal(&mut shc, &mut context).await;
+
+    // The fin is signed by a validator other than the proposer.
+    let fin = proposal_fin(BLOCK.id.0, 0, 0, *VALIDATOR_ID_2);
+    let event = ShcEvent::ValidateProposal(
+        StateMachineEvent::Proposal(Some(BLOCK.id), PROPOSAL_INIT.round, PROPOSAL_INIT.valid_round),
+        Some(fin),
+    );
+    assert_eq!(
+        shc.handle_event(&mut context, event).await,
+        Ok(ShcReturn::Tasks(vec![prevote_task(None, 0)]))
+    );
+}
+
+// The inputs of a validator at height 0, in the order they lead to a decision.
+async fn send_validator_input(
+    shc: &mut SingleHeightConsensus,
+    context: &mut MockTestContext,
+    input: usize,
+) -> ShcReturn {
+    let message = match input {
+        0 => {
+            handle_proposal(shc, context).await;
+            return shc.handle_event(context, VALIDATE_PROPOSAL_EVENT.clone()).await.unwrap();
+        }
+        1 => prevote(Some(BLOCK.id.0), 0, 0, *PROPOSER_ID),
+        2 => prevote(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_2),
+        3 => precommit(Some(BLOCK.id.0), 0, 0, *PROPOSER_ID),
+        4 => precommit(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_2),
+        _ => panic!("Unexpected input {input}"),
+    };
+    shc.handle_message(context, message).await.unwrap()
+}
+
+const NUM_VALIDATOR_INPUTS: usize = 5;
+
+#[test_case(0; "before_prevote")]
+#[test_case(1; "after_prevote")]
+#[test_case(2; "after_prevote_quorum")]
+#[test_case(3; "after_precommit")]
+#[test_case(4; "after_precommit_from_peer")]
+#[tokio::test]
+async fn restart_doesnt_send_conflicting_votes(inputs_before_crash: usize) {
+    let mut context = MockTestContext::new();
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context.expect_validate_proposal().returning(move |_, _, _, _, _| {
+        let (block_sender, block_receiver) = oneshot::channel();
+        block_sender.send((BLOCK.id, PROPOSAL_FIN.clone())).unwrap();
+        block_receiver
+    });
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    let broadcasts = Arc::new(Mutex::new(Vec::new()));
+    let broadcasts_clone = Arc::clone(&broadcasts);
+    context.expect_broadcast().returning(move |message| {
+        broadcasts_clone.lock().unwrap().push(message);
+        Ok(())
+    });
+
+    let wal = Arc::new(InMemoryWal::default());
+    let new_shc = || {
+        SingleHeightConsensus::new(
+            BlockNumber(0),
+            false,
+            *VALIDATOR_ID_1,
+            VALIDATORS.clone(),
+            TIMEOUTS.clone(),
+            Arc::new(TestSigner::new(*VALIDATOR_ID_1)),
+            Arc::new(TestSignatureVerifier),
+            wal.clone(),
+        )
+    };
+
+    let mut shc = new_shc();
+    shc.start(&mut context).await.unwrap();
+    for input in 0..inputs_before_crash {
+        send_validator_input(&mut shc, &mut context, input).await;
+    }
+
+    // Crash and restart. The restarted node times out waiting for the proposal, and then receives
+    // all the inputs again, as peers rebroadcast them.
+    drop(shc);
+    let mut shc = new_shc();
+    shc.start(&mut context).await.unwrap();
+    let timeout_propose = ShcEvent::TimeoutPropose(StateMachineEvent::TimeoutPropose(0));
+    shc.handle_event(&mut context, timeout_propose).await.unwrap();
+    let mut decision = None;
+    for input in 0..NUM_VALIDATOR_INPUTS {
+        if let ShcReturn::Decision(d) = send_validator_input(&mut shc, &mut context, input).await {
+            decision = Some(d);
+            break;
+        }
+    }
+
+    let mut votes = HashMap::new();
+    for message in broadcasts.lock().unwrap().iter() {
+        let ConsensusMessage::Vote(vote) = message else {
+            panic!("Expected a vote, got {message:?}");
+        };
+        let first_vote = votes.entry((vote.vote_type.clone(), vote.round)).or_insert(vote);
+        assert_eq!(first_vote.block_hash, vote.block_hash, "Conflicting votes were sent");
+    }
+    // Without a prevote for the proposal before the crash, the node prevotes nil when timing out,
+    // so it can't decide along with just 2 of the 3 other validators.
+    assert_eq!(
+        decision.map(|decision| decision.block),
+        (inputs_before_crash > 0).then_some(BLOCK.id)
+    );
+}
+
+#[tokio::test]
+async fn restarted_proposer_doesnt_propose_again() {
+    let mut context = MockTestContext::new();
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    // The proposal is built only once, before the crash.
+    context.expect_build_proposal().times(1).returning(move |_, _| {
+        let (block_sender, block_receiver) = oneshot::channel();
+        block_sender.send(BLOCK.id).unwrap();
+        block_receiver
+    });
+    context.expect_set_height_and_round().returning(move |_, _| ());
+
+    let wal = Arc::new(InMemoryWal::default());
+    let new_shc = || {
+        SingleHeightConsensus::new(
+            BlockNumber(0),
+            false,
+            *PROPOSER_ID,
+            VALIDATORS.clone(),
+            TIMEOUTS.clone(),
+            Arc::new(TestSigner::new(*PROPOSER_ID)),
+            Arc::new(TestSignatureVerifier),
+            wal.clone(),
+        )
+    };
+
+    // Crash while the proposal is being built, after its fin may have been signed.
+    let mut shc = new_shc();
+    shc.start(&mut context).await.unwrap();
+    assert_eq!(
+        wal.entries(BlockNumber(0)).await.unwrap(),
+        vec![WalEntry::Proposal { round: 0, content_id: None }]
+    );
+    drop(shc);
+
+    // The restarted node doesn't build a new proposal for the round, and prevotes nil instead.
+    context
+        .expect_broadcast()
+        .times(1)
+        .withf(move |msg: &ConsensusMessage| msg == &prevote(None, 0, 0, *PROPOSER_ID))
+        .returning(move |_| Ok(()));
+    let mut shc = new_shc();
+    let mut tasks = shc.start(&mut context).await.unwrap().into_tasks().unwrap();
+    assert_eq!(tasks.len(), 1);
+    let event = tasks.remove(0).run().await;
+    assert_eq!(
+        shc.handle_event(&mut context, event).await,
+        Ok(ShcReturn::Tasks(vec![prevote_task(None, 0)]))
+    );
+}
@@ -12,6 +12,7 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use tracing::trace;
 
 use crate::types::{ProposalContentId, Round, ValidatorId};
+use crate::validator_set::{ValidatorSet, VotingPower};
 
 /// Events which the state machine sends/receives.
 #[derive(Debug, Clone, PartialEq)]
@@ -40,7 +41,7 @@ pub enum StateMachineEvent {
     TimeoutPrecommit(Round),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Step {
     Propose,
     Prevote,
@@ -55,18 +56,20 @@ pub struct StateMachine {
     id: ValidatorId,
     round: Round,
     step: Step,
-    quorum: u32,
-    round_skip_threshold: u32,
+    voting_power: VotingPower,
+    quorum: VotingPower,
+    round_skip_threshold: VotingPower,
     is_observer: bool,
     // {round: (proposal_id, valid_round)}
     proposals: HashMap<Round, (Option<ProposalContentId>, Option<Round>)>,
-    // {round: {proposal_id: vote_count}
-    prevotes: HashMap<Round, HashMap<Option<ProposalContentId>, u32>>,
-    precommits: HashMap<Round, HashMap<Option<ProposalContentId>, u32>>,
+    // {round: {proposal_id: voting_power}
+    prevotes: HashMap<Round, HashMap<Option<ProposalContentId>, VotingPower>>,
+    precommits: HashMap<Round, HashMap<Option<ProposalContentId>, VotingPower>>,
     // When true, the state machine will wait for a GetProposal event, buffering all other input
     // events in `events_queue`.
     awaiting_get_proposal: bool,
-    events_queue: VecDeque<StateMachineEvent>,
+    // Events are queued along with the voting power of their sender, which only matters for votes.
+    events_queue: VecDeque<(StateMachineEvent, VotingPower)>,
     locked_value_round: Option<(ProposalContentId, Round)>,
     valid_value_round: Option<(ProposalContentId, Round)>,
     prevote_quorum: HashSet<Round>,
@@ -75,14 +78,15 @@ pub struct StateMachine {
 }
 
 impl StateMachine {
-    /// total_weight - the total voting weight of all validators for this height.
-    pub fn new(id: ValidatorId, total_weight: u32, is_observer: bool) -> Self {
+    /// validators - the validators of this height, whose voting power determines the quorums.
+    pub fn new(id: ValidatorId, validators: &ValidatorSet, is_observer: bool) -> Self {
         Self {
             id,
             round: 0,
             step: Step::Propose,
-            quorum: (2 * total_weight / 3) + 1,
-            round_skip_threshold: total_weight / 3 + 1,
+            voting_power: validators.voting_power(&id),
+            quorum: validators.quorum(),
+            round_skip_threshold: validators.round_skip_threshold(),
             is_observer,
             proposals: HashMap::new(),
             prevotes: HashMap::new(),
@@ -101,21 +105,66 @@ impl StateMachine {
         self.round
     }
 
-    pub fn quorum_size(&self) -> u32 {
+    pub fn quorum_size(&self) -> VotingPower {
         self.quorum
     }
 
+    pub fn locked_value_round(&self) -> Option<(ProposalContentId, Round)> {
+        self.locked_value_round
+    }
+
+    pub fn valid_value_round(&self) -> Option<(ProposalContentId, Round)> {
+        self.valid_value_round
+    }
+
+    /// Restores the state of a node which restarted mid-height: the votes it already sent and the
+    /// values it locked on and saw as valid. Must be called before [`start`](Self::start), which
+    /// then resumes from the latest step the node voted in, instead of starting round 0. The node
+    /// therefore never sends a vote which conflicts with one it sent before restarting.
+    pub fn replay(
+        &mut self,
+        own_votes: impl IntoIterator<Item = StateMachineEvent>,
+        locked_value_round: Option<(ProposalContentId, Round)>,
+        valid_value_round: Option<(ProposalContentId, Round)>,
+    ) {
+        for vote in own_votes {
+            let (votes, proposal_id, round, step) = match vote {
+                StateMachineEvent::Prevote(proposal_id, round) => {
+                    (&mut self.prevotes, proposal_id, round, Step::Prevote)
+                }
+                StateMachineEvent::Precommit(proposal_id, round) => {
+                    (&mut self.precommits, proposal_id, round, Step::Precommit)
+                }
+                _ => panic!("Only votes can be replayed: {vote:?}"),
+            };
+            *votes.entry(round).or_default().entry(proposal_id).or_default() += self.voting_power;
+            if (round, &step) > (self.round, &self.step) {
+                self.round = round;
+                self.step = step;
+            }
+        }
+        self.locked_value_round = locked_value_round;
+        self.valid_value_round = valid_value_round;
+    }
+
     /// Starts the state machine, effectively calling `StartRound(0)` from the paper. This is
     /// needed to trigger the first leader to propose.
     /// See [`GetProposal`](StateMachineEvent::GetProposal)
+    ///
+    /// After a [`replay`](Self::replay), resumes from the replayed round and step instead.
     pub fn start<LeaderFn>(&mut self, leader_fn: &LeaderFn) -> VecDeque<StateMachineEvent>
     where
         LeaderFn: Fn(Round) -> ValidatorId,
     {
+        if self.step != Step::Propose {
+            // The node already voted in this round before restarting, so it shouldn't propose or
+            // schedule the propose timeout again. It resumes as votes and proposals arrive.
+            return VecDeque::new();
+        }
         self.advance_to_round(0, leader_fn)
     }
 
-    /// Process the incoming event.
+    /// Process the incoming event. Votes are passed to [`handle_vote`](Self::handle_vote) instead.
     ///
     /// If we are waiting for a response to `GetProposal` all other incoming events are buffered
     /// until that response arrives.
@@ -129,6 +178,40 @@ impl StateMachine {
         event: StateMachineEvent,
         leader_fn: &LeaderFn,
     ) -> VecDeque<StateMachineEvent>
+    where
+        LeaderFn: Fn(Round) -> ValidatorId,
+    {
+        assert!(
+            !matches!(event, StateMachineEvent::Prevote(_, _) | StateMachineEvent::Precommit(_, _)),
+            "Votes must be passed with the voting power of the voter: {event:?}"
+        );
+        self.handle_weighted_event(event, 0, leader_fn)
+    }
+
+    /// Process a vote from a peer, which counts towards quorums with `voting_power`. See
+    /// [`handle_event`](Self::handle_event).
+    pub fn handle_vote<LeaderFn>(
+        &mut self,
+        vote: StateMachineEvent,
+        voting_power: VotingPower,
+        leader_fn: &LeaderFn,
+    ) -> VecDeque<StateMachineEvent>
+    where
+        LeaderFn: Fn(Round) -> ValidatorId,
+    {
+        assert!(
+            matches!(vote, StateMachineEvent::Prevote(_, _) | StateMachineEvent::Precommit(_, _)),
+            "Expected a vote: {vote:?}"
+        );
+        self.handle_weighted_event(vote, voting_power, leader_fn)
+    }
+
+    fn handle_weighted_event<LeaderFn>(
+        &mut self,
+        event: StateMachineEvent,
+        voting_power: VotingPower,
+        leader_fn: &LeaderFn,
+    ) -> VecDeque<StateMachineEvent>
     where
         LeaderFn: Fn(Round) -> ValidatorId,
     {
@@ -138,15 +221,15 @@ impl StateMachine {
         if self.awaiting_get_proposal {
             match event {
                 StateMachineEvent::GetProposal(_, round) if round == self.round => {
-                    self.events_queue.push_front(event);
+                    self.events_queue.push_front((event, voting_power));
                 }
                 _ => {
-                    self.events_queue.push_back(event);
+                    self.events_queue.push_back((event, voting_power));
                     return VecDeque::new();
                 }
             }
         } else {
-            self.events_queue.push_back(event);
+            self.events_queue.push_back((event, voting_power));
         }
 
         self.handle_enqueued_events(leader_fn)
@@ -160,10 +243,10 @@ impl StateMachine {
         LeaderFn: Fn(Round) -> ValidatorId,
     {
         let mut output_events = VecDeque::new();
-        while let Some(event) = self.events_queue.pop_front() {
+        while let Some((event, voting_power)) = self.events_queue.pop_front() {
             // Handle a specific event and then decide which of the output events should also be
             // sent to self.
-            let mut resultant_events = self.handle_event_internal(event, leader_fn);
+            let mut resultant_events = self.handle_event_internal(event, voting_power, leader_fn);
             while let Some(e) = resultant_events.pop_front() {
                 match e {
                     StateMachineEvent::Proposal(_, _, _)
@@ -172,7 +255,7 @@ impl StateMachine {
                         if self.is_observer {
                             continue;
                         }
-                        self.events_queue.push_back(e.clone());
+                        self.events_queue.push_back((e.clone(), self.voting_power));
                     }
                     StateMachineEvent::Decision(_, _) => {
                         output_events.push_back(e);
@@ -196,6 +279,7 @@ impl StateMachine {
     fn handle_event_internal<LeaderFn>(
         &mut self,
         event: StateMachineEvent,
+        voting_power: VotingPower,
         leader_fn: &LeaderFn,
     ) -> VecDeque<StateMachineEvent>
     where
@@ -213,10 +297,10 @@ impl StateMachine {
                 self.handle_proposal(proposal_id, round, valid_round, leader_fn)
             }
             StateMachineEvent::Prevote(proposal_id, round) => {
-                self.handle_prevote(proposal_id, round, leader_fn)
+                self.handle_prevote(proposal_id, round, voting_power, leader_fn)
             }
             StateMachineEvent::Precommit(proposal_id, round) => {
-                self.handle_precommit(proposal_id, round, leader_fn)
+                self.handle_precommit(proposal_id, round, voting_power, leader_fn)
             }
             StateMachineEvent::Decision(_, _) => {
                 unimplemented!(
@@ -273,14 +357,13 @@ impl StateMachine {
         &mut self,
         proposal_id: Option<ProposalContentId>,
         round: u32,
+        voting_power: VotingPower,
         leader_fn: &LeaderFn,
     ) -> VecDeque<StateMachineEvent>
     where
         LeaderFn: Fn(Round) -> ValidatorId,
     {
-        let prevote_count = self.prevotes.entry(round).or_default().entry(proposal_id).or_insert(0);
-        // TODO(matan): Use variable weight.
-        *prevote_count += 1;
+        *self.prevotes.entry(round).or_default().entry(proposal_id).or_insert(0) += voting_power;
         self.map_round_to_upons(round, leader_fn)
     }
 
@@ -298,15 +381,13 @@ impl StateMachine {
         &mut self,
         proposal_id: Option<ProposalContentId>,
         round: u32,
+        voting_power: VotingPower,
         leader_fn: &LeaderFn,
     ) -> VecDeque<StateMachineEvent>
     where
         LeaderFn: Fn(Round) -> ValidatorId,
     {
-        let precommit_count =
-            self.precommits.entry(round).or_default().entry(proposal_id).or_insert(0);
-        // TODO(matan): Use variable weight.
-        *precommit_count += 1;
+        *self.precommits.entry(round).or_default().entry(proposal_id).or_insert(0) += voting_power;
         self.map_round_to_upons(round, leader_fn)
     }
 
@@ -547,18 +628,18 @@ impl StateMachine {
 }
 
 fn round_has_enough_votes(
-    votes: &HashMap<u32, HashMap<Option<ProposalContentId>, u32>>,
+    votes: &HashMap<u32, HashMap<Option<ProposalContentId>, VotingPower>>,
     round: u32,
-    threshold: u32,
+    threshold: VotingPower,
 ) -> bool {
     votes.get(&round).map_or(0, |v| v.values().sum()) >= threshold
 }
 
 fn value_has_enough_votes(
-    votes: &HashMap<u32, HashMap<Option<ProposalContentId>, u32>>,
+    votes: &HashMap<u32, HashMap<Option<ProposalContentId>, VotingPower>>,
     round: u32,
     value: &Option<ProposalContentId>,
-    threshold: u32,
+    threshold: VotingPower,
 ) -> bool {
     votes.get(&round).map_or(0, |v| *v.get(value).unwrap_or(&0)) >= threshold
 }
@@ -8,10 +8,12 @@ use test_case::test_case;
 use super::Round;
 use crate::state_machine::{StateMachine, StateMachineEvent};
 use crate::types::{ProposalContentId, ValidatorId};
+use crate::validator_set::{ValidatorSet, VotingPower};
 
 lazy_static! {
     static ref PROPOSER_ID: ValidatorId = 0_u32.into();
     static ref VALIDATOR_ID: ValidatorId = 1_u32.into();
+    static ref HEAVY_VALIDATOR_ID: ValidatorId = 2_u32.into();
 }
 
 const PROPOSAL_ID: Option<ProposalContentId> = Some(BlockHash(Felt::ONE));
@@ -24,9 +26,25 @@ struct TestWrapper<LeaderFn: Fn(Round) -> ValidatorId> {
 }
 
 impl<LeaderFn: Fn(Round) -> ValidatorId> TestWrapper<LeaderFn> {
-    pub fn new(id: ValidatorId, total_weight: u32, leader_fn: LeaderFn, is_observer: bool) -> Self {
+    /// Creates a state machine with `num_validators` validators, each with a voting power of 1.
+    pub fn new(
+        id: ValidatorId,
+        num_validators: u32,
+        leader_fn: LeaderFn,
+        is_observer: bool,
+    ) -> Self {
+        let validators = ValidatorSet::uniform((0..num_validators).map(ValidatorId::from));
+        Self::with_validators(id, validators, leader_fn, is_observer)
+    }
+
+    pub fn with_validators(
+        id: ValidatorId,
+        validators: ValidatorSet,
+        leader_fn: LeaderFn,
+        is_observer: bool,
+    ) -> Self {
         Self {
-            state_machine: StateMachine::new(id, total_weight, is_observer),
+            state_machine: StateMachine::new(id, &validators, is_observer),
             leader_fn,
             events: VecDeque::new(),
         }
@@ -36,6 +54,14 @@ impl<LeaderFn: Fn(Round) -> ValidatorId> TestWrapper<LeaderFn> {
         self.events.pop_front()
     }
 
+    pub fn replay(
+        &mut self,
+        own_votes: Vec<StateMachineEvent>,
+        locked_value_round: Option<(ProposalContentId, Round)>,
+    ) {
+        self.state_machine.replay(own_votes, locked_value_round, locked_value_round)
+    }
+
     pub fn start(&mut self) {
         self.events.append(&mut self.state_machine.start(&self.leader_fn))
     }
@@ -49,11 +75,19 @@ impl<LeaderFn: Fn(Round) -> ValidatorId> TestWrapper<LeaderFn> {
     }
 
     pub fn send_prevote(&mut self, proposal_id: Option<ProposalContentId>, round: Round) {
-        self.send_event(StateMachineEvent::Prevote(proposal_id, round))
+        self.send_vote(StateMachineEvent::Prevote(proposal_id, round), 1)
     }
 
     pub fn send_precommit(&mut self, proposal_id: Option<ProposalContentId>, round: Round) {
-        self.send_event(StateMachineEvent::Precommit(proposal_id, round))
+        self.send_vote(StateMachineEvent::Precommit(proposal_id, round), 1)
+    }
+
+    pub fn send_vote(&mut self, vote: StateMachineEvent, voting_power: VotingPower) {
+        self.events.append(&mut self.state_machine.handle_vote(
+            vote,
+            voting_power,
+            &self.leader_fn,
+        ));
     }
 
     pub fn send_timeout_propose(&mut self, round: Round) {
@@ -415,3 +449,116 @@ fn observer_node_reaches_decision() {
     );
     assert!(wrapper.next_event().is_none());
 }
+
+#[test]
+fn votes_are_weighted_by_voting_power() {
+    // The quorum is 4 out of a total voting power of 5.
+    let validators =
+        ValidatorSet::new([(*PROPOSER_ID, 1), (*VALIDATOR_ID, 1), (*HEAVY_VALIDATOR_ID, 3)]);
+    let mut wrapper =
+        TestWrapper::with_validators(*VALIDATOR_ID, validators, |_: Round| *PROPOSER_ID, false);
+
+    wrapper.start();
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPropose(ROUND));
+    assert!(wrapper.next_event().is_none());
+    wrapper.send_proposal(PROPOSAL_ID, ROUND);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND));
+    assert!(wrapper.next_event().is_none());
+
+    // Along with the node's own prevote, the proposer's prevote isn't enough for a quorum.
+    wrapper.send_vote(StateMachineEvent::Prevote(PROPOSAL_ID, ROUND), 1);
+    assert!(wrapper.next_event().is_none());
+
+    wrapper.send_vote(StateMachineEvent::Prevote(PROPOSAL_ID, ROUND), 3);
+    // The Node got a Prevote quorum.
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrevote(ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(PROPOSAL_ID, ROUND));
+    assert!(wrapper.next_event().is_none());
+
+    // Along with the node's own precommit, a single precommit of the heavy validator is a quorum.
+    wrapper.send_vote(StateMachineEvent::Precommit(PROPOSAL_ID, ROUND), 3);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrecommit(ROUND));
+    assert_eq!(
+        wrapper.next_event().unwrap(),
+        StateMachineEvent::Decision(PROPOSAL_ID.unwrap(), ROUND)
+    );
+    assert!(wrapper.next_event().is_none());
+}
+
+#[test]
+fn replayed_vote_is_not_sent_again() {
+    let mut wrapper = TestWrapper::new(*VALIDATOR_ID, 4, |_: Round| *PROPOSER_ID, false);
+
+    // The node prevoted for the proposal before restarting.
+    wrapper.replay(vec![StateMachineEvent::Prevote(PROPOSAL_ID, ROUND)], None);
+    wrapper.start();
+    // The node doesn't wait for the proposal again.
+    assert!(wrapper.next_event().is_none());
+    wrapper.send_timeout_propose(ROUND);
+    assert!(wrapper.next_event().is_none());
+    wrapper.send_proposal(PROPOSAL_ID, ROUND);
+    assert!(wrapper.next_event().is_none());
+
+    // The replayed prevote counts towards the quorum.
+    wrapper.send_prevote(PROPOSAL_ID, ROUND);
+    wrapper.send_prevote(PROPOSAL_ID, ROUND);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrevote(ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(PROPOSAL_ID, ROUND));
+    assert!(wrapper.next_event().is_none());
+}
+
+#[test]
+fn replay_resumes_from_the_latest_vote() {
+    let mut wrapper = TestWrapper::new(*VALIDATOR_ID, 4, |_: Round| *PROPOSER_ID, false);
+
+    // The node locked on the proposal in round 0, and moved on to prevote nil in round 1.
+    wrapper.replay(
+        vec![
+            StateMachineEvent::Prevote(PROPOSAL_ID, ROUND),
+            StateMachineEvent::Precommit(PROPOSAL_ID, ROUND),
+            StateMachineEvent::Prevote(None, ROUND + 1),
+        ],
+        PROPOSAL_ID.map(|proposal_id| (proposal_id, ROUND)),
+    );
+    wrapper.start();
+    assert!(wrapper.next_event().is_none());
+    assert_eq!(wrapper.state_machine.round(), ROUND + 1);
+
+    // Without a precommit in round 1, the node precommits nil upon a nil prevote quorum.
+    wrapper.send_prevote(None, ROUND + 1);
+    wrapper.send_prevote(None, ROUND + 1);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrevote(ROUND + 1));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(None, ROUND + 1));
+    assert!(wrapper.next_event().is_none());
+}
+
+#[test]
+fn replayed_lock_is_kept() {
+    let mut wrapper = TestWrapper::new(*PROPOSER_ID, 4, |_: Round| *PROPOSER_ID, false);
+
+    // The proposer locked on its proposal before restarting.
+    wrapper.replay(
+        vec![
+            StateMachineEvent::Prevote(PROPOSAL_ID, ROUND),
+            StateMachineEvent::Precommit(PROPOSAL_ID, ROUND),
+        ],
+        PROPOSAL_ID.map(|proposal_id| (proposal_id, ROUND)),
+    );
+    wrapper.start();
+    assert!(wrapper.next_event().is_none());
+
+    wrapper.send_prevote(PROPOSAL_ID, ROUND);
+    wrapper.send_prevote(PROPOSAL_ID, ROUND);
+    wrapper.send_precommit(None, ROUND);
+    wrapper.send_precommit(None, ROUND);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrecommit(ROUND));
+    assert!(wrapper.next_event().is_none());
+    wrapper.send_timeout_precommit(ROUND);
+
+    // The locked value is reproposed, instead of building a new proposal.
+    assert_eq!(
+        wrapper.next_event().unwrap(),
+        StateMachineEvent::Proposal(PROPOSAL_ID, ROUND + 1, Some(ROUND))
+    );
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND + 1));
+}
@@ -61,8 +61,9 @@ pub struct StreamHandler<
     T: Clone + Into<Vec<u8>> + TryFrom<Vec<u8>, Error = ProtobufConversionError> + 'static,
 > {
     // For each stream ID from the network, send the application a Receiver
-    // that will receive the messages in order. This allows sending such Receivers.
-    inbound_channel_sender: mpsc::Sender<mpsc::Receiver<T>>,
+    // that will receive the messages in order, along with the peer which sent the stream.
+    // This allows sending such (peer_id, Receiver) pairs.
+    inbound_channel_sender: mpsc::Sender<(PeerId, mpsc::Receiver<T>)>,
     // This receives messages from the network.
     inbound_receiver: BroadcastTopicServer<StreamMessage<T>>,
     // A map from (peer_id, stream_id) to a struct that contains all the information
@@ -86,7 +87,7 @@ impl<T: Clone + Send + Into<Vec<u8>> + TryFrom<Vec<u8>, Error = ProtobufConversi
 {
     /// Create a new StreamHandler.
     pub fn new(
-        inbound_channel_sender: mpsc::Sender<mpsc::Receiver<T>>,
+        inbound_channel_sender: mpsc::Sender<(PeerId, mpsc::Receiver<T>)>,
         inbound_receiver: BroadcastTopicServer<StreamMessage<T>>,
         outbound_channel_receiver: mpsc::Receiver<(StreamId, mpsc::Receiver<T>)>,
         outbound_sender: BroadcastTopicClient<StreamMessage<T>>,
@@ -110,15 +111,16 @@ impl<T: Clone + Send + Into<Vec<u8>> + TryFrom<Vec<u8>, Error = ProtobufConversi
         outbound_network_sender: BroadcastTopicClient<StreamMessage<T>>,
     ) -> (
         mpsc::Sender<(StreamId, mpsc::Receiver<T>)>,
-        mpsc::Receiver<mpsc::Receiver<T>>,
+        mpsc::Receiver<(PeerId, mpsc::Receiver<T>)>,
         tokio::task::JoinHandle<()>,
     ) {
         // The inbound messages come into StreamHandler via inbound_network_receiver.
         // The application gets the messages from inbound_internal_receiver
         // (the StreamHandler keeps the inbound_internal_sender to pass the messages).
+        // Each Receiver is paired with the peer which sent the stream.
         let (inbound_internal_sender, inbound_internal_receiver): (
-            mpsc::Sender<mpsc::Receiver<T>>,
-            mpsc::Receiver<mpsc::Receiver<T>>,
+            mpsc::Sender<(PeerId, mpsc::Receiver<T>)>,
+            mpsc::Receiver<(PeerId, mpsc::Receiver<T>)>,
         ) = mpsc::channel(CHANNEL_BUFFER_LENGTH);
         // The outbound messages that an application would like to send are:
         //  1. Sent into outbound_internal_sender as tuples of (StreamId, Receiver)
@@ -238,7 +240,9 @@ impl<T: Clone + Send + Into<Vec<u8>> + TryFrom<Vec<u8>, Error = ProtobufConversi
                 // we need to create a new receiver for it.
                 let (sender, receiver) = mpsc::channel(CHANNEL_BUFFER_LENGTH);
                 // TODO(guyn): reconsider the "expect" here.
-                self.inbound_channel_sender.try_send(receiver).expect("Send should succeed");
+                self.inbound_channel_sender
+                    .try_send((key.0.clone(), receiver))
+                    .expect("Send should succeed");
 
                 let data = StreamData::new(sender);
                 e.insert(data)
@@ -9,7 +9,7 @@ use papyrus_network::network_manager::test_utils::{
     TestSubscriberChannels,
 };
 use papyrus_network::network_manager::BroadcastTopicChannels;
-use papyrus_network_types::network_types::BroadcastedMessageMetadata;
+use papyrus_network_types::network_types::{BroadcastedMessageMetadata, OpaquePeerId};
 use papyrus_protobuf::consensus::{ConsensusMessage, Proposal, StreamMessage, StreamMessageBody};
 use papyrus_test_utils::{get_rng, GetTestInstance};
 
@@ -52,7 +52,7 @@ mod tests {
     fn setup_test() -> (
         StreamHandler<ConsensusMessage>,
         MockBroadcastedMessagesSender<StreamMessage<ConsensusMessage>>,
-        mpsc::Receiver<mpsc::Receiver<ConsensusMessage>>,
+        mpsc::Receiver<(OpaquePeerId, mpsc::Receiver<ConsensusMessage>)>,
         BroadcastedMessageMetadata,
         mpsc::Sender<(StreamId, mpsc::Receiver<ConsensusMessage>)>,
         futures::stream::Map<
@@ -93,7 +93,7 @@ mod tests {
         // each stream. The inbound_channel_receiver is given to the "mock consensus" that
         // gets new channels and inbounds to them.
         let (inbound_channel_sender, inbound_channel_receiver) =
-            mpsc::channel::<mpsc::Receiver<ConsensusMessage>>(CHANNEL_SIZE);
+            mpsc::channel::<(OpaquePeerId, mpsc::Receiver<ConsensusMessage>)>(CHANNEL_SIZE);
 
         // TODO(guyn): We should also give the broadcast_topic_client to the StreamHandler
         // This will allow reporting to the network things like bad peers.
@@ -133,7 +133,8 @@ mod tests {
 
         join_handle.await.expect("Task should succeed");
 
-        let mut receiver = inbound_channel_receiver.next().await.unwrap();
+        let (peer_id, mut receiver) = inbound_channel_receiver.next().await.unwrap();
+        assert_eq!(peer_id, metadata.originator_id);
         for _ in 0..9 {
             // message number 9 is Fin, so it will not be sent!
             let _ = receiver.next().await.unwrap();
@@ -168,7 +169,7 @@ mod tests {
         let mut stream_handler = join_handle.await.expect("Task should succeed");
 
         // Get the receiver for the stream.
-        let mut receiver = inbound_channel_receiver.next().await.unwrap();
+        let (_, mut receiver) = inbound_channel_receiver.next().await.unwrap();
         // Check that the channel is empty (no messages were sent yet).
         assert!(receiver.try_next().is_err());
 
@@ -288,19 +289,19 @@ mod tests {
         ));
 
         // Get the receiver for the first stream.
-        let mut receiver1 = inbound_channel_receiver.next().await.unwrap();
+        let (_, mut receiver1) = inbound_channel_receiver.next().await.unwrap();
 
         // Check that the channel is empty (no messages were sent yet).
         assert!(receiver1.try_next().is_err());
 
         // Get the receiver for the second stream.
-        let mut receiver2 = inbound_channel_receiver.next().await.unwrap();
+        let (_, mut receiver2) = inbound_channel_receiver.next().await.unwrap();
 
         // Check that the channel is empty (no messages were sent yet).
         assert!(receiver2.try_next().is_err());
 
         // Get the receiver for the third stream.
-        let mut receiver3 = inbound_channel_receiver.next().await.unwrap();
+        let (_, mut receiver3) = inbound_channel_receiver.next().await.unwrap();
 
         // Check that the channel is empty (no messages were sent yet).
         assert!(receiver3.try_next().is_err());
@@ -3,19 +3,21 @@ use std::time::Duration;
 use async_trait::async_trait;
 use futures::channel::{mpsc, oneshot};
 use mockall::mock;
-use papyrus_protobuf::consensus::{
-    ConsensusMessage,
-    Proposal,
-    ProposalFin,
-    ProposalInit,
-    Vote,
-    VoteType,
-};
+use papyrus_protobuf::consensus::{ConsensusMessage, ProposalFin, ProposalInit, Vote, VoteType};
 use papyrus_protobuf::converters::ProtobufConversionError;
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_types_core::felt::Felt;
 
-use crate::types::{ConsensusContext, ConsensusError, ProposalContentId, Round, ValidatorId};
+use crate::signing::{ConsensusSigner, TestSigner};
+use crate::types::{
+    ConsensusContext,
+    ConsensusError,
+    Evidence,
+    ProposalContentId,
+    Round,
+    ValidatorId,
+};
+use crate::validator_set::ValidatorSet;
 
 /// Define a consensus block which can be used to enable auto mocking Context.
 #[derive(Debug, PartialEq, Clone)]
@@ -89,7 +91,7 @@ mock! {
             init: ProposalInit,
         );
 
-        async fn validators(&self, height: BlockNumber) -> Vec<ValidatorId>;
+        async fn validators(&self, height: BlockNumber) -> ValidatorSet;
 
         fn proposer(&self, height: BlockNumber, round: Round) -> ValidatorId;
 
@@ -102,6 +104,8 @@ mock! {
         ) -> Result<(), ConsensusError>;
 
         async fn set_height_and_round(&mut self, height: BlockNumber, round: Round);
+
+        async fn report_evidence(&mut self, evidence: Evidence) -> Result<(), ConsensusError>;
     }
 }
 
@@ -112,7 +116,9 @@ pub fn prevote(
     voter: ValidatorId,
 ) -> ConsensusMessage {
     let block_hash = block_felt.map(BlockHash);
-    ConsensusMessage::Vote(Vote { vote_type: VoteType::Prevote, height, round, block_hash, voter })
+    let vote =
+        Vote { vote_type: VoteType::Prevote, height, round, block_hash, voter, signature: None };
+    ConsensusMessage::Vote(TestSigner::new(voter).sign_vote(vote).unwrap())
 }
 
 pub fn precommit(
@@ -122,30 +128,20 @@ pub fn precommit(
     voter: ValidatorId,
 ) -> ConsensusMessage {
     let block_hash = block_felt.map(BlockHash);
-    ConsensusMessage::Vote(Vote {
-        vote_type: VoteType::Precommit,
-        height,
-        round,
-        block_hash,
-        voter,
-    })
+    let vote =
+        Vote { vote_type: VoteType::Precommit, height, round, block_hash, voter, signature: None };
+    ConsensusMessage::Vote(TestSigner::new(voter).sign_vote(vote).unwrap())
 }
 
-pub fn proposal(
+/// A fin of the proposal of `height` and `round`, signed by `proposer`.
+pub fn proposal_fin(
     block_felt: Felt,
     height: u64,
     round: u32,
     proposer: ValidatorId,
-) -> ConsensusMessage {
-    let block_hash = BlockHash(block_felt);
-    ConsensusMessage::Proposal(Proposal {
-        height,
-        block_hash,
-        round,
-        proposer,
-        transactions: Vec::new(),
-        valid_round: None,
-    })
+) -> ProposalFin {
+    let fin = ProposalFin { proposal_content_id: BlockHash(block_felt), signature: None };
+    TestSigner::new(proposer).sign_proposal_fin(BlockNumber(height), round, fin).unwrap()
 }
 
 pub fn proposal_init(height: u64, round: u32, proposer: ValidatorId) -> ProposalInit {
@@ -14,6 +14,10 @@ use papyrus_protobuf::converters::ProtobufConversionError;
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::core::ContractAddress;
 
+use crate::signing::SigningError;
+use crate::validator_set::ValidatorSet;
+use crate::wal::WalError;
+
 /// Used to identify the node by consensus.
 /// 1. This ID is derived from the id registered with Starknet's L2 staking contract.
 /// 2. We must be able to derive the public key associated with this ID for the sake of validating
@@ -90,14 +94,12 @@ pub trait ConsensusContext {
     /// - `init`: The `ProposalInit` that is broadcast to the network.
     async fn repropose(&mut self, id: ProposalContentId, init: ProposalInit);
 
-    /// Get the set of validators for a given height. These are the nodes that can propose and vote
-    /// on blocks.
-    // TODO(matan): We expect this to change in the future to BTreeMap. Why?
-    // 1. Map - The nodes will have associated information (e.g. voting weight).
-    // 2. BTreeMap - We want a stable ordering of the nodes for deterministic leader selection.
-    async fn validators(&self, height: BlockNumber) -> Vec<ValidatorId>;
+    /// Get the set of validators for a given height, along with their voting power. These are the
+    /// nodes that can propose and vote on blocks.
+    async fn validators(&self, height: BlockNumber) -> ValidatorSet;
 
-    /// Calculates the ID of the Proposer based on the inputs.
+    /// Calculates the ID of the Proposer based on the inputs. Must be deterministic, so that all
+    /// the validators agree on the proposer (e.g., [`ValidatorSet::proposer`]).
     fn proposer(&self, height: BlockNumber, round: Round) -> ValidatorId;
 
     async fn broadcast(&mut self, message: ConsensusMessage) -> Result<(), ConsensusError>;
@@ -115,6 +117,10 @@ pub trait ConsensusContext {
     /// Update the context with the current height and round.
     /// Must be called at the beginning of each height.
     async fn set_height_and_round(&mut self, height: BlockNumber, round: Round);
+
+    /// Report that a validator equivocated, so that the node can persist the evidence, gossip it
+    /// and eventually slash the validator. Called once per conflicting vote.
+    async fn report_evidence(&mut self, evidence: Evidence) -> Result<(), ConsensusError>;
 }
 
 #[derive(PartialEq)]
@@ -132,6 +138,23 @@ impl Debug for Decision {
     }
 }
 
+/// Proof that a validator equivocated, i.e., signed two different votes of the same type for the
+/// same height and round.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Evidence {
+    /// The vote which was received first.
+    pub first_vote: Vote,
+    /// The vote which conflicts with `first_vote`.
+    pub second_vote: Vote,
+}
+
+impl Evidence {
+    /// The validator which signed both votes.
+    pub fn validator(&self) -> ValidatorId {
+        self.first_vote.voter
+    }
+}
+
 pub struct BroadcastConsensusMessageChannel {
     pub broadcasted_messages_receiver: GenericReceiver<(
         Result<ConsensusMessage, ProtobufConversionError>,
@@ -164,12 +187,19 @@ pub enum ConsensusError {
     InvalidProposal(ValidatorId, BlockNumber, String),
     #[error(transparent)]
     SendError(#[from] mpsc::SendError),
-    #[error("Conflicting messages for block {0}. Old: {1:?}, New: {2:?}")]
-    Equivocation(BlockNumber, ConsensusMessage, ConsensusMessage),
     // Indicates an error in communication between consensus and the node's networking component.
     // As opposed to an error between this node and peer nodes.
     #[error("{0}")]
     InternalNetworkError(String),
     #[error("{0}")]
     SyncError(String),
+    /// A peer sent a message which is unsigned or whose signature is invalid.
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(SigningError),
+    /// This node failed to sign its own message.
+    #[error(transparent)]
+    SigningError(#[from] SigningError),
+    /// Failed to persist or restore this node's consensus state.
+    #[error(transparent)]
+    WalError(#[from] WalError),
 }
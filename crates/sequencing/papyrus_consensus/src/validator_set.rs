@@ -0,0 +1,174 @@
+//! The validators which take part in consensus and their voting power, see [`ValidatorSet`].
+//!
+//! The validator set can only change at epoch boundaries, see [`EpochValidatorSets`].
+
+#[cfg(test)]
+#[path = "validator_set_test.rs"]
+mod validator_set_test;
+
+use std::collections::BTreeMap;
+
+use starknet_api::block::BlockNumber;
+use starknet_types_core::felt::Felt;
+
+use crate::config::ConsensusConfig;
+use crate::types::{Round, ValidatorId};
+
+// Prefixed to the hashed height and round, to separate the proposer selection from other hashes.
+const PROPOSER_SEED_PREFIX: &[u8] = b"CONSENSUS_PROPOSER";
+
+/// The weight of a validator's votes.
+pub type VotingPower = u64;
+
+/// The validators of a height, along with their voting power.
+///
+/// The validators are kept in a `BTreeMap` so that all nodes iterate them in the same order, which
+/// keeps leader selection deterministic.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidatorSet {
+    validators: BTreeMap<ValidatorId, VotingPower>,
+    total_voting_power: VotingPower,
+}
+
+impl ValidatorSet {
+    /// Creates a validator set. Validators without voting power are left out.
+    pub fn new(validators: impl IntoIterator<Item = (ValidatorId, VotingPower)>) -> Self {
+        let validators: BTreeMap<_, _> =
+            validators.into_iter().filter(|(_, voting_power)| *voting_power > 0).collect();
+        let total_voting_power = validators
+            .values()
+            .try_fold(0, |total: VotingPower, voting_power| total.checked_add(*voting_power))
+            .expect("The total voting power should fit in a u64.");
+        Self { validators, total_voting_power }
+    }
+
+    /// Creates a validator set in which all the validators have the same voting power.
+    pub fn uniform(validators: impl IntoIterator<Item = ValidatorId>) -> Self {
+        Self::new(validators.into_iter().map(|validator| (validator, 1)))
+    }
+
+    /// Creates the validator set configured in `config`: the validators with a configured voting
+    /// power or, if there are none, the first `num_validators` IDs with equal voting power.
+    pub fn from_config(config: &ConsensusConfig) -> Self {
+        if config.validator_weights.is_empty() {
+            return Self::uniform((0..config.num_validators).map(ValidatorId::from));
+        }
+        Self::new(config.validator_weights.clone())
+    }
+
+    /// Returns whether `validator` is in the set.
+    pub fn contains(&self, validator: &ValidatorId) -> bool {
+        self.validators.contains_key(validator)
+    }
+
+    /// The voting power of `validator`, which is 0 if it isn't in the set.
+    pub fn voting_power(&self, validator: &ValidatorId) -> VotingPower {
+        self.validators.get(validator).copied().unwrap_or_default()
+    }
+
+    /// The sum of the voting power of all the validators.
+    pub fn total_voting_power(&self) -> VotingPower {
+        self.total_voting_power
+    }
+
+    /// The voting power needed for a quorum, i.e., more than 2/3 of the total voting power.
+    pub fn quorum(&self) -> VotingPower {
+        let two_thirds = u128::from(self.total_voting_power) * 2 / 3;
+        VotingPower::try_from(two_thirds).expect("2/3 of a u64 should fit in a u64.") + 1
+    }
+
+    /// The voting power which guarantees that at least one honest validator is included, i.e., more
+    /// than 1/3 of the total voting power.
+    pub fn round_skip_threshold(&self) -> VotingPower {
+        self.total_voting_power / 3 + 1
+    }
+
+    /// The number of validators in the set.
+    pub fn len(&self) -> usize {
+        self.validators.len()
+    }
+
+    /// Returns whether the set has no validators.
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+    }
+
+    /// Iterates over the validators and their voting power, ordered by validator ID.
+    pub fn iter(&self) -> impl Iterator<Item = (&ValidatorId, &VotingPower)> {
+        self.validators.iter()
+    }
+
+    /// Selects the proposer for `round` of `height`.
+    ///
+    /// Each validator holds a run of consecutive slots, one per unit of its voting power, and the
+    /// slot is picked by a hash of `height` and `round`. Each validator therefore proposes with a
+    /// probability proportional to its voting power, interleaved with the other validators rather
+    /// than in runs of consecutive heights, while all nodes pick the same proposer.
+    pub fn proposer(&self, height: BlockNumber, round: Round) -> ValidatorId {
+        assert!(!self.is_empty(), "There should be at least one validator.");
+        let seed = starknet_crypto::poseidon_hash_many(&[
+            Felt::from_bytes_be_slice(PROPOSER_SEED_PREFIX),
+            Felt::from(height.0),
+            Felt::from(round),
+        ]);
+        // The low 128 bits of the hash; the modulo bias over a u64 total is negligible.
+        let seed_bytes = seed.to_bytes_be();
+        let seed = u128::from_be_bytes(
+            seed_bytes[16..].try_into().expect("The low half of a felt should be 16 bytes."),
+        );
+        let mut slot = seed % u128::from(self.total_voting_power);
+        for (validator, voting_power) in &self.validators {
+            let voting_power = u128::from(*voting_power);
+            if slot < voting_power {
+                return *validator;
+            }
+            slot -= voting_power;
+        }
+        unreachable!("The slot is smaller than the total voting power.")
+    }
+}
+
+/// The validator sets of the chain, by epoch.
+///
+/// The heights are split into epochs of `epoch_length` consecutive heights, starting from height 0.
+/// The validator set can only change at the first height of an epoch, and applies until the next
+/// epoch which has a validator set of its own.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EpochValidatorSets {
+    epoch_length: u64,
+    // {first epoch: validator set}
+    validator_sets: BTreeMap<u64, ValidatorSet>,
+}
+
+impl EpochValidatorSets {
+    /// Creates the validator sets of a chain, starting from `initial_validator_set` at epoch 0.
+    pub fn new(epoch_length: u64, initial_validator_set: ValidatorSet) -> Self {
+        assert!(epoch_length > 0, "The epoch length must be positive.");
+        Self { epoch_length, validator_sets: BTreeMap::from([(0, initial_validator_set)]) }
+    }
+
+    /// Creates the validator sets configured in `config`, see [`ValidatorSet::from_config`].
+    pub fn from_config(config: &ConsensusConfig) -> Self {
+        Self::new(config.epoch_length, ValidatorSet::from_config(config))
+    }
+
+    /// The epoch which `height` is part of.
+    pub fn epoch(&self, height: BlockNumber) -> u64 {
+        height.0 / self.epoch_length
+    }
+
+    /// Sets the validator set from the beginning of `epoch`.
+    pub fn insert(&mut self, epoch: u64, validator_set: ValidatorSet) {
+        self.validator_sets.insert(epoch, validator_set);
+    }
+
+    /// The validator set of `height`, i.e., the one set at the latest epoch up to the epoch of
+    /// `height`.
+    pub fn validator_set(&self, height: BlockNumber) -> &ValidatorSet {
+        self.validator_sets
+            .range(..=self.epoch(height))
+            .next_back()
+            .map(|(_, validator_set)| validator_set)
+            .expect("Epoch 0 always has a validator set.")
+    }
+}
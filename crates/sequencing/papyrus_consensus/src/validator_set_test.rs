@@ -0,0 +1,107 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use starknet_api::block::BlockNumber;
+use test_case::test_case;
+
+use crate::config::ConsensusConfig;
+use crate::types::ValidatorId;
+use crate::validator_set::{EpochValidatorSets, ValidatorSet, VotingPower};
+
+fn validator(id: u32) -> ValidatorId {
+    id.into()
+}
+
+#[test_case(1, 1, 1; "single_validator")]
+#[test_case(3, 3, 2; "three")]
+#[test_case(4, 3, 2; "four")]
+#[test_case(100, 67, 34; "hundred")]
+fn thresholds(total_voting_power: VotingPower, quorum: VotingPower, round_skip: VotingPower) {
+    let validator_set = ValidatorSet::new([(validator(0), total_voting_power)]);
+    assert_eq!(validator_set.total_voting_power(), total_voting_power);
+    assert_eq!(validator_set.quorum(), quorum);
+    assert_eq!(validator_set.round_skip_threshold(), round_skip);
+}
+
+#[test]
+fn voting_power() {
+    let validator_set =
+        ValidatorSet::new([(validator(1), 2), (validator(2), 0), (validator(3), 5)]);
+
+    assert_eq!(validator_set.len(), 2);
+    assert_eq!(validator_set.total_voting_power(), 7);
+    assert_eq!(validator_set.voting_power(&validator(3)), 5);
+    // Validators without voting power are left out of the set.
+    assert!(!validator_set.contains(&validator(2)));
+    assert_eq!(validator_set.voting_power(&validator(2)), 0);
+    assert!(!validator_set.contains(&validator(4)));
+}
+
+#[test]
+fn proposer_is_weighted_by_voting_power() {
+    // Inserted out of order, to check that the selection depends only on the validator IDs.
+    let validator_set = ValidatorSet::new([(validator(2), 1), (validator(1), 3)]);
+
+    let mut proposals = HashMap::new();
+    for height in 0..4000 {
+        *proposals.entry(validator_set.proposer(BlockNumber(height), 0)).or_insert(0) += 1;
+    }
+    // Validator 1 is expected to propose at 3000 of the heights.
+    assert!((2800..3200).contains(&proposals[&validator(1)]), "{proposals:?}");
+    assert_eq!(proposals[&validator(1)] + proposals[&validator(2)], 4000);
+
+    // The proposer is the same for any node that builds the same set.
+    let same_set = ValidatorSet::new([(validator(1), 3), (validator(2), 1)]);
+    for round in 0..8 {
+        assert_eq!(
+            validator_set.proposer(BlockNumber(5), round),
+            same_set.proposer(BlockNumber(5), round)
+        );
+    }
+}
+
+#[test]
+fn proposers_are_interleaved() {
+    // With slots assigned in order, validator 0 would propose at the first 100 heights.
+    let validator_set = ValidatorSet::new([(validator(0), 100), (validator(1), 100)]);
+    let proposers: HashSet<_> =
+        (0..10).map(|height| validator_set.proposer(BlockNumber(height), 0)).collect();
+    assert_eq!(proposers, HashSet::from([validator(0), validator(1)]));
+
+    // Rounds of the same height are interleaved as well.
+    let proposers: HashSet<_> =
+        (0..10).map(|round| validator_set.proposer(BlockNumber(0), round)).collect();
+    assert_eq!(proposers, HashSet::from([validator(0), validator(1)]));
+}
+
+#[test]
+fn validator_set_changes_at_epoch_boundaries() {
+    let first_set = ValidatorSet::uniform([validator(0)]);
+    let second_set = ValidatorSet::uniform([validator(0), validator(1)]);
+    let mut validator_sets = EpochValidatorSets::new(10, first_set.clone());
+    validator_sets.insert(2, second_set.clone());
+
+    assert_eq!(validator_sets.epoch(BlockNumber(9)), 0);
+    assert_eq!(validator_sets.epoch(BlockNumber(10)), 1);
+    // The set of epoch 0 carries over to epoch 1, which has no set of its own.
+    assert_eq!(validator_sets.validator_set(BlockNumber(19)), &first_set);
+    assert_eq!(validator_sets.validator_set(BlockNumber(20)), &second_set);
+    assert_eq!(validator_sets.validator_set(BlockNumber(1000)), &second_set);
+}
+
+#[test]
+fn validator_sets_from_config() {
+    let config = ConsensusConfig { num_validators: 3, epoch_length: 10, ..Default::default() };
+    assert_eq!(ValidatorSet::from_config(&config), ValidatorSet::uniform((0..3).map(validator)));
+
+    // Configured weights take precedence over the number of validators.
+    let config = ConsensusConfig {
+        validator_weights: BTreeMap::from([(validator(1), 3), (validator(5), 1)]),
+        ..config
+    };
+    let validator_sets = EpochValidatorSets::from_config(&config);
+    assert_eq!(validator_sets.epoch(BlockNumber(10)), 1);
+    assert_eq!(
+        validator_sets.validator_set(BlockNumber(0)),
+        &ValidatorSet::new([(validator(1), 3), (validator(5), 1)])
+    );
+}
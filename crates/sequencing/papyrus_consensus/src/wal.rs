@@ -0,0 +1,328 @@
+//! A write-ahead log (WAL) of the consensus state which a validator must not forget when it
+//! restarts in the middle of a height, see [`ConsensusWal`].
+//!
+//! The validator persists its votes and the values it locked on before broadcasting the votes, so
+//! after a crash it can replay them into the state machine and never sign a vote which conflicts
+//! with one it already sent. Likewise, it persists its proposals before signing their fin, so that
+//! it never signs two different proposals for the same round.
+
+#[cfg(test)]
+#[path = "wal_test.rs"]
+mod wal_test;
+
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use papyrus_protobuf::consensus::Vote;
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_types_core::felt::Felt;
+
+use crate::types::{ProposalContentId, Round};
+
+const WAL_FILE_EXTENSION: &str = "wal";
+const VOTE_ENTRY_KIND: u8 = 0;
+const VALUES_ENTRY_KIND: u8 = 1;
+const PROPOSAL_ENTRY_KIND: u8 = 2;
+const FELT_LENGTH: usize = 32;
+
+/// Errors from reading or writing the WAL.
+#[derive(thiserror::Error, Clone, Debug, PartialEq)]
+pub enum WalError {
+    /// Failed to access the storage of the WAL.
+    #[error("Failed to access the WAL: {0}")]
+    Io(String),
+    /// An entry of the WAL couldn't be decoded.
+    #[error("Corrupted WAL entry at height {height}: {reason}")]
+    Corrupted {
+        /// The height whose log holds the entry.
+        height: BlockNumber,
+        /// Why the entry couldn't be decoded.
+        reason: String,
+    },
+}
+
+impl From<std::io::Error> for WalError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error.to_string())
+    }
+}
+
+impl From<tokio::task::JoinError> for WalError {
+    fn from(error: tokio::task::JoinError) -> Self {
+        Self::Io(error.to_string())
+    }
+}
+
+/// An entry in the log of a height.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WalEntry {
+    /// A vote this node signed, persisted before it is broadcast.
+    Vote(Vote),
+    /// The value this node locked on and the latest value it saw as valid, along with their
+    /// rounds. Replaces the values of earlier entries.
+    Values {
+        /// The locked value and round (LOC 38 in the paper).
+        locked_value_round: Option<(ProposalContentId, Round)>,
+        /// The valid value and round (LOC 42 in the paper).
+        valid_value_round: Option<(ProposalContentId, Round)>,
+    },
+    /// A proposal of this node, persisted before it is built and its fin is signed, and again with
+    /// the content id of the fin once it is built.
+    Proposal {
+        /// The round of the proposal.
+        round: Round,
+        /// The content id signed in the fin, or `None` if the proposal wasn't built yet.
+        content_id: Option<ProposalContentId>,
+    },
+}
+
+/// Durable storage of the consensus state a validator must restore after restarting mid-height.
+#[async_trait]
+pub trait ConsensusWal: Send + Sync {
+    /// Appends `entry` to the log of `height`. Returns only once the entry is persisted.
+    async fn append(&self, height: BlockNumber, entry: WalEntry) -> Result<(), WalError>;
+
+    /// The entries of `height`, in the order they were appended.
+    async fn entries(&self, height: BlockNumber) -> Result<Vec<WalEntry>, WalError>;
+
+    /// Drops the logs of all the heights below `height`.
+    async fn prune(&self, height: BlockNumber) -> Result<(), WalError>;
+}
+
+/// Opens the WAL stored in `wal_dir`, or a WAL which isn't persisted if there is no directory.
+pub fn open_wal(wal_dir: Option<&Path>) -> Result<Arc<dyn ConsensusWal>, WalError> {
+    Ok(match wal_dir {
+        Some(wal_dir) => Arc::new(FileWal::new(wal_dir.to_path_buf())?),
+        None => Arc::new(InMemoryWal::default()),
+    })
+}
+
+/// A WAL which is kept in memory, and therefore doesn't survive restarts.
+#[derive(Debug, Default)]
+pub struct InMemoryWal {
+    logs: Mutex<BTreeMap<BlockNumber, Vec<WalEntry>>>,
+}
+
+#[async_trait]
+impl ConsensusWal for InMemoryWal {
+    async fn append(&self, height: BlockNumber, entry: WalEntry) -> Result<(), WalError> {
+        self.logs.lock().expect("Lock poisoned").entry(height).or_default().push(entry);
+        Ok(())
+    }
+
+    async fn entries(&self, height: BlockNumber) -> Result<Vec<WalEntry>, WalError> {
+        Ok(self.logs.lock().expect("Lock poisoned").get(&height).cloned().unwrap_or_default())
+    }
+
+    async fn prune(&self, height: BlockNumber) -> Result<(), WalError> {
+        let mut logs = self.logs.lock().expect("Lock poisoned");
+        *logs = logs.split_off(&height);
+        Ok(())
+    }
+}
+
+/// A WAL which keeps the log of each height in its own file, `<height>.wal`, in a directory.
+///
+/// Each entry is written as a record of its length (4 bytes, big endian) followed by its content,
+/// and synced to disk before `append` returns. A crash in the middle of an append can leave a
+/// partial record at the end of the file. The entry wasn't acted upon, so it is dropped when the
+/// log is read.
+///
+/// The file operations block, so they run on the blocking thread pool of the tokio runtime.
+#[derive(Clone, Debug)]
+pub struct FileWal {
+    dir: PathBuf,
+    // Serializes the appends, so that concurrent records don't interleave.
+    write_lock: Arc<Mutex<()>>,
+}
+
+impl FileWal {
+    /// Opens the WAL in `dir`, creating the directory if it doesn't exist.
+    pub fn new(dir: PathBuf) -> Result<Self, WalError> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir, write_lock: Arc::new(Mutex::new(())) })
+    }
+
+    fn path(&self, height: BlockNumber) -> PathBuf {
+        self.dir.join(format!("{}.{WAL_FILE_EXTENSION}", height.0))
+    }
+
+    fn append_blocking(&self, height: BlockNumber, entry: &WalEntry) -> Result<(), WalError> {
+        let content = encode_entry(entry);
+        let length = u32::try_from(content.len()).expect("A WAL entry should fit in 4GB.");
+        let mut record = length.to_be_bytes().to_vec();
+        record.extend(content);
+
+        let _guard = self.write_lock.lock().expect("Lock poisoned");
+        let path = self.path(height);
+        let is_new_file = !path.exists();
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        file.write_all(&record)?;
+        file.sync_data()?;
+        if is_new_file {
+            // Persist the directory entry of the new file as well.
+            File::open(&self.dir)?.sync_all()?;
+        }
+        Ok(())
+    }
+
+    fn entries_blocking(&self, height: BlockNumber) -> Result<Vec<WalEntry>, WalError> {
+        let _guard = self.write_lock.lock().expect("Lock poisoned");
+        let path = self.path(height);
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error.into()),
+        };
+        let mut entries = Vec::new();
+        let mut remaining = bytes.as_slice();
+        while let Some((length, rest)) = remaining.split_first_chunk::<4>() {
+            let length = usize::try_from(u32::from_be_bytes(*length)).expect("u32 fits in usize");
+            if rest.len() < length {
+                break;
+            }
+            let (content, rest) = rest.split_at(length);
+            entries.push(
+                decode_entry(content).map_err(|reason| WalError::Corrupted { height, reason })?,
+            );
+            remaining = rest;
+        }
+        if !remaining.is_empty() {
+            // Drop the partial record, so that the next records are appended after the valid ones.
+            let valid_length = bytes.len() - remaining.len();
+            let file = OpenOptions::new().write(true).open(&path)?;
+            file.set_len(u64::try_from(valid_length).expect("usize fits in u64"))?;
+            file.sync_data()?;
+        }
+        Ok(entries)
+    }
+
+    fn prune_blocking(&self, height: BlockNumber) -> Result<(), WalError> {
+        for dir_entry in fs::read_dir(&self.dir)? {
+            let path = dir_entry?.path();
+            if path.extension() != Some(OsStr::new(WAL_FILE_EXTENSION)) {
+                continue;
+            }
+            let Some(log_height) =
+                path.file_stem().and_then(|stem| stem.to_str()?.parse::<u64>().ok())
+            else {
+                continue;
+            };
+            if log_height < height.0 {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ConsensusWal for FileWal {
+    async fn append(&self, height: BlockNumber, entry: WalEntry) -> Result<(), WalError> {
+        let wal = self.clone();
+        tokio::task::spawn_blocking(move || wal.append_blocking(height, &entry)).await?
+    }
+
+    async fn entries(&self, height: BlockNumber) -> Result<Vec<WalEntry>, WalError> {
+        let wal = self.clone();
+        tokio::task::spawn_blocking(move || wal.entries_blocking(height)).await?
+    }
+
+    async fn prune(&self, height: BlockNumber) -> Result<(), WalError> {
+        let wal = self.clone();
+        tokio::task::spawn_blocking(move || wal.prune_blocking(height)).await?
+    }
+}
+
+fn encode_entry(entry: &WalEntry) -> Vec<u8> {
+    match entry {
+        WalEntry::Vote(vote) => {
+            let mut content = vec![VOTE_ENTRY_KIND];
+            content.extend(Vec::<u8>::from(vote.clone()));
+            content
+        }
+        WalEntry::Values { locked_value_round, valid_value_round } => {
+            let mut content = vec![VALUES_ENTRY_KIND];
+            for value_round in [locked_value_round, valid_value_round] {
+                match value_round {
+                    Some((value, round)) => {
+                        content.push(1);
+                        content.extend(value.0.to_bytes_be());
+                        content.extend(round.to_be_bytes());
+                    }
+                    None => content.push(0),
+                }
+            }
+            content
+        }
+        WalEntry::Proposal { round, content_id } => {
+            let mut content = vec![PROPOSAL_ENTRY_KIND];
+            content.extend(round.to_be_bytes());
+            match content_id {
+                Some(content_id) => {
+                    content.push(1);
+                    content.extend(content_id.0.to_bytes_be());
+                }
+                None => content.push(0),
+            }
+            content
+        }
+    }
+}
+
+fn decode_entry(content: &[u8]) -> Result<WalEntry, String> {
+    let Some((kind, mut content)) = content.split_first() else {
+        return Err("empty entry".to_string());
+    };
+    match *kind {
+        VOTE_ENTRY_KIND => {
+            Vote::try_from(content.to_vec()).map(WalEntry::Vote).map_err(|err| err.to_string())
+        }
+        VALUES_ENTRY_KIND => {
+            let locked_value_round = decode_value_round(&mut content)?;
+            let valid_value_round = decode_value_round(&mut content)?;
+            if !content.is_empty() {
+                return Err(format!("{} unexpected trailing bytes", content.len()));
+            }
+            Ok(WalEntry::Values { locked_value_round, valid_value_round })
+        }
+        PROPOSAL_ENTRY_KIND => {
+            let Some((round, rest)) = content.split_first_chunk::<4>() else {
+                return Err("truncated round".to_string());
+            };
+            let round = Round::from_be_bytes(*round);
+            let content_id = match rest {
+                [0] => None,
+                [1, content_id @ ..] if content_id.len() == FELT_LENGTH => {
+                    Some(BlockHash(Felt::from_bytes_be_slice(content_id)))
+                }
+                _ => return Err("malformed proposal content id".to_string()),
+            };
+            Ok(WalEntry::Proposal { round, content_id })
+        }
+        kind => Err(format!("unknown entry kind {kind}")),
+    }
+}
+
+fn decode_value_round(content: &mut &[u8]) -> Result<Option<(ProposalContentId, Round)>, String> {
+    let Some((is_some, rest)) = content.split_first() else {
+        return Err("missing value".to_string());
+    };
+    *content = rest;
+    if *is_some == 0 {
+        return Ok(None);
+    }
+    let Some((value, rest)) = content.split_first_chunk::<FELT_LENGTH>() else {
+        return Err("truncated value".to_string());
+    };
+    let Some((round, rest)) = rest.split_first_chunk::<4>() else {
+        return Err("truncated round".to_string());
+    };
+    *content = rest;
+    Ok(Some((BlockHash(Felt::from_bytes_be(value)), Round::from_be_bytes(*round))))
+}
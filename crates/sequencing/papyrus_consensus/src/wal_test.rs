@@ -0,0 +1,102 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use papyrus_protobuf::consensus::{Vote, VoteType};
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_types_core::felt::Felt;
+
+use crate::signing::{ConsensusSigner, TestSigner};
+use crate::types::ValidatorId;
+use crate::wal::{ConsensusWal, FileWal, InMemoryWal, WalEntry};
+
+const HEIGHT: BlockNumber = BlockNumber(7);
+
+fn vote(vote_type: VoteType, round: u32, block_felt: Option<Felt>) -> WalEntry {
+    let voter = ValidatorId::from(1_u32);
+    let vote = TestSigner::new(voter)
+        .sign_vote(Vote {
+            vote_type,
+            height: HEIGHT.0,
+            round,
+            block_hash: block_felt.map(BlockHash),
+            voter,
+            signature: None,
+        })
+        .unwrap();
+    WalEntry::Vote(vote)
+}
+
+fn entries() -> Vec<WalEntry> {
+    vec![
+        WalEntry::Proposal { round: 0, content_id: None },
+        WalEntry::Proposal { round: 0, content_id: Some(BlockHash(Felt::ONE)) },
+        vote(VoteType::Prevote, 0, Some(Felt::ONE)),
+        WalEntry::Values {
+            locked_value_round: Some((BlockHash(Felt::ONE), 0)),
+            valid_value_round: Some((BlockHash(Felt::ONE), 0)),
+        },
+        vote(VoteType::Precommit, 0, Some(Felt::ONE)),
+        WalEntry::Values {
+            locked_value_round: Some((BlockHash(Felt::ONE), 0)),
+            valid_value_round: None,
+        },
+        vote(VoteType::Prevote, 1, None),
+    ]
+}
+
+#[tokio::test]
+async fn file_wal_survives_reopening() {
+    let dir = tempfile::tempdir().unwrap();
+    let wal = FileWal::new(dir.path().to_path_buf()).unwrap();
+    for entry in entries() {
+        wal.append(HEIGHT, entry).await.unwrap();
+    }
+    drop(wal);
+
+    let wal = FileWal::new(dir.path().to_path_buf()).unwrap();
+    assert_eq!(wal.entries(HEIGHT).await.unwrap(), entries());
+    assert_eq!(wal.entries(HEIGHT.unchecked_next()).await.unwrap(), vec![]);
+}
+
+#[tokio::test]
+async fn file_wal_drops_partial_record() {
+    let dir = tempfile::tempdir().unwrap();
+    let wal = FileWal::new(dir.path().to_path_buf()).unwrap();
+    let (first, second) = (entries()[0].clone(), entries()[1].clone());
+    wal.append(HEIGHT, first.clone()).await.unwrap();
+
+    // Simulate a crash in the middle of writing a record.
+    let mut file =
+        OpenOptions::new().append(true).open(dir.path().join(format!("{}.wal", HEIGHT.0))).unwrap();
+    file.write_all(&[0, 0, 0, 100, 1, 2, 3]).unwrap();
+    drop(file);
+
+    assert_eq!(wal.entries(HEIGHT).await.unwrap(), vec![first.clone()]);
+    // New records follow the last valid one.
+    wal.append(HEIGHT, second.clone()).await.unwrap();
+    assert_eq!(wal.entries(HEIGHT).await.unwrap(), vec![first, second]);
+}
+
+async fn prune_drops_lower_heights(wal: &dyn ConsensusWal) {
+    let first = entries()[0].clone();
+    for height in 0..3 {
+        wal.append(BlockNumber(height), first.clone()).await.unwrap();
+    }
+
+    wal.prune(BlockNumber(2)).await.unwrap();
+
+    assert_eq!(wal.entries(BlockNumber(0)).await.unwrap(), vec![]);
+    assert_eq!(wal.entries(BlockNumber(1)).await.unwrap(), vec![]);
+    assert_eq!(wal.entries(BlockNumber(2)).await.unwrap(), vec![first]);
+}
+
+#[tokio::test]
+async fn file_wal_prune() {
+    let dir = tempfile::tempdir().unwrap();
+    prune_drops_lower_heights(&FileWal::new(dir.path().to_path_buf()).unwrap()).await;
+}
+
+#[tokio::test]
+async fn in_memory_wal_prune() {
+    prune_drops_lower_heights(&InMemoryWal::default()).await;
+}
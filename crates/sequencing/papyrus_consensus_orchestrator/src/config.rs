@@ -0,0 +1,73 @@
+//! This module contains the configuration of the sequencer's consensus context, i.e., the
+//! node-local rules it uses to accept the proposals of other nodes.
+
+use std::collections::BTreeMap;
+
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Configuration for the sequencer's consensus context.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Validate)]
+pub struct ContextConfig {
+    /// The maximal deviation, in percent, of the proposer's L1 gas prices from our own for the
+    /// proposal to be accepted.
+    pub l1_gas_price_tolerance_percent: u64,
+    /// The maximal difference, in seconds, between the proposal's timestamp and our clock for the
+    /// proposal to be accepted. Covers clock drift, and the time it takes the proposal to arrive.
+    pub block_timestamp_window_seconds: u64,
+    /// The number of STRK an ETH is worth, i.e., of fri a wei is worth, by which the L1 gas prices
+    /// are converted to STRK. The gas prices are part of the block, so all the validators must use
+    /// the same rate.
+    #[validate(range(min = 1))]
+    pub eth_to_strk_rate: u64,
+    /// The price of L2 gas, in fri. Its price in wei is converted by `eth_to_strk_rate`.
+    #[validate(range(min = 1))]
+    pub l2_gas_price_fri: u64,
+}
+
+impl SerializeConfig for ContextConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "l1_gas_price_tolerance_percent",
+                &self.l1_gas_price_tolerance_percent,
+                "The maximal deviation, in percent, of the proposer's L1 gas prices from our own \
+                 for the proposal to be accepted.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "block_timestamp_window_seconds",
+                &self.block_timestamp_window_seconds,
+                "The maximal difference, in seconds, between the proposal's timestamp and our \
+                 clock for the proposal to be accepted.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "eth_to_strk_rate",
+                &self.eth_to_strk_rate,
+                "The number of STRK an ETH is worth, by which the L1 gas prices are converted to \
+                 STRK. Must be the same on all the validators.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "l2_gas_price_fri",
+                &self.l2_gas_price_fri,
+                "The price of L2 gas, in fri. Must be the same on all the validators.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+impl Default for ContextConfig {
+    fn default() -> Self {
+        Self {
+            l1_gas_price_tolerance_percent: 10,
+            block_timestamp_window_seconds: 60,
+            eth_to_strk_rate: 10_000,
+            l2_gas_price_fri: 1,
+        }
+    }
+}
@@ -15,13 +15,16 @@ use std::time::Duration;
 use async_trait::async_trait;
 use futures::channel::{mpsc, oneshot};
 use futures::{SinkExt, StreamExt};
+use papyrus_consensus::signing::ConsensusSigner;
 use papyrus_consensus::types::{
     ConsensusContext,
     ConsensusError,
+    Evidence,
     ProposalContentId,
     Round,
     ValidatorId,
 };
+use papyrus_consensus::validator_set::{EpochValidatorSets, ValidatorSet};
 use papyrus_network::network_manager::{BroadcastTopicClient, BroadcastTopicClientTrait};
 use papyrus_protobuf::consensus::{
     ConsensusMessage,
@@ -49,12 +52,14 @@ pub struct PapyrusConsensusContext {
     storage_reader: StorageReader,
     network_broadcast_client: BroadcastTopicClient<ConsensusMessage>,
     network_proposal_sender: mpsc::Sender<(u64, mpsc::Receiver<ProposalPart>)>,
-    validators: Vec<ValidatorId>,
+    validator_sets: EpochValidatorSets,
     sync_broadcast_sender: Option<BroadcastTopicClient<Vote>>,
     // Proposal building/validating returns immediately, leaving the actual processing to a spawned
     // task. The spawned task processes the proposal asynchronously and updates the
     // valid_proposals map upon completion, ensuring consistency across tasks.
     valid_proposals: Arc<Mutex<HeightToIdToContent>>,
+    // Signs the fins of the proposals built by this node.
+    signer: Arc<dyn ConsensusSigner>,
 }
 
 impl PapyrusConsensusContext {
@@ -62,16 +67,18 @@ impl PapyrusConsensusContext {
         storage_reader: StorageReader,
         network_broadcast_client: BroadcastTopicClient<ConsensusMessage>,
         network_proposal_sender: mpsc::Sender<(u64, mpsc::Receiver<ProposalPart>)>,
-        num_validators: u64,
+        validator_sets: EpochValidatorSets,
         sync_broadcast_sender: Option<BroadcastTopicClient<Vote>>,
+        signer: Arc<dyn ConsensusSigner>,
     ) -> Self {
         Self {
             storage_reader,
             network_broadcast_client,
             network_proposal_sender,
-            validators: (0..num_validators).map(ValidatorId::from).collect(),
+            validator_sets,
             sync_broadcast_sender,
             valid_proposals: Arc::new(Mutex::new(BTreeMap::new())),
+            signer,
         }
     }
 }
@@ -91,6 +98,7 @@ impl ConsensusContext for PapyrusConsensusContext {
 
         let storage_reader = self.storage_reader.clone();
         let valid_proposals = Arc::clone(&self.valid_proposals);
+        let signer = Arc::clone(&self.signer);
         tokio::spawn(
             async move {
                 // TODO(dvir): consider fix this for the case of reverts. If between the check that
@@ -139,10 +147,14 @@ impl ConsensusContext for PapyrusConsensusContext {
                     }))
                     .await
                     .expect("Failed to send transactions");
-                proposal_sender
-                    .send(ProposalPart::Fin(ProposalFin { proposal_content_id: block_hash }))
-                    .await
-                    .expect("Failed to send fin");
+                let fin = signer
+                    .sign_proposal_fin(
+                        proposal_init.height,
+                        proposal_init.round,
+                        ProposalFin { proposal_content_id: block_hash, signature: None },
+                    )
+                    .expect("Failed to sign fin");
+                proposal_sender.send(ProposalPart::Fin(fin)).await.expect("Failed to send fin");
                 {
                     let mut proposals = valid_proposals
                         .lock()
@@ -191,15 +203,17 @@ impl ConsensusContext for PapyrusConsensusContext {
 
                 // First gather all the non-fin transactions.
                 let mut content_transactions: Vec<Transaction> = Vec::new();
-                let received_block_hash = loop {
+                let received_fin = loop {
                     match content.next().await {
+                        // The block info is checked against the storage through the block hash.
+                        Some(ProposalPart::BlockInfo(_)) => {}
                         Some(ProposalPart::Transactions(batch)) => {
                             for tx in batch.transactions {
                                 content_transactions.push(tx);
                             }
                         }
                         Some(ProposalPart::Fin(fin)) => {
-                            break fin.proposal_content_id;
+                            break fin;
                         }
                         msg => panic!("Unexpected message: {msg:?}"),
                     }
@@ -235,11 +249,9 @@ impl ConsensusContext for PapyrusConsensusContext {
                 // Done after inserting the proposal into the map to avoid race conditions between
                 // insertion and calls to `repropose`.
                 // This can happen as a result of sync interrupting `run_height`.
-                fin_sender
-                    .send((block_hash, ProposalFin { proposal_content_id: received_block_hash }))
-                    .unwrap_or_else(|_| {
-                        warn!("Failed to send block to consensus. height={height}");
-                    })
+                fin_sender.send((block_hash, received_fin)).unwrap_or_else(|_| {
+                    warn!("Failed to send block to consensus. height={height}");
+                })
             }
             .instrument(debug_span!("consensus_validate_proposal")),
         );
@@ -272,12 +284,18 @@ impl ConsensusContext for PapyrusConsensusContext {
             .expect("Failed to send proposal");
     }
 
-    async fn validators(&self, _height: BlockNumber) -> Vec<ValidatorId> {
-        self.validators.clone()
+    async fn validators(&self, height: BlockNumber) -> ValidatorSet {
+        self.validator_sets.validator_set(height).clone()
     }
 
-    fn proposer(&self, _height: BlockNumber, _round: Round) -> ValidatorId {
-        *self.validators.first().expect("there should be at least one validator")
+    fn proposer(&self, height: BlockNumber, _round: Round) -> ValidatorId {
+        *self
+            .validator_sets
+            .validator_set(height)
+            .iter()
+            .next()
+            .expect("there should be at least one validator")
+            .0
     }
 
     async fn broadcast(&mut self, message: ConsensusMessage) -> Result<(), ConsensusError> {
@@ -308,6 +326,12 @@ impl ConsensusContext for PapyrusConsensusContext {
     async fn set_height_and_round(&mut self, _height: BlockNumber, _round: Round) {
         // No-op
     }
+
+    async fn report_evidence(&mut self, evidence: Evidence) -> Result<(), ConsensusError> {
+        // TODO(matan): persist and gossip the evidence, so that the validator can be slashed.
+        warn!("Validator {} equivocated: {evidence:?}", evidence.validator());
+        Ok(())
+    }
 }
 
 const SLEEP_BETWEEN_CHECK_FOR_BLOCK: Duration = Duration::from_secs(10);
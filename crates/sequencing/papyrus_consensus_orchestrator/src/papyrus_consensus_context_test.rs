@@ -1,9 +1,12 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use futures::channel::{mpsc, oneshot};
 use futures::StreamExt;
+use papyrus_consensus::signing::TestSigner;
 use papyrus_consensus::stream_handler::StreamHandler;
 use papyrus_consensus::types::{ConsensusContext, ValidatorId};
+use papyrus_consensus::validator_set::{EpochValidatorSets, ValidatorSet};
 use papyrus_network::network_manager::test_utils::{
     mock_register_broadcast_topic,
     BroadcastNetworkMock,
@@ -62,7 +65,10 @@ async fn validate_proposal_success() {
         });
         validate_sender.try_send(tx_part).unwrap();
     }
-    let fin_part = ProposalPart::Fin(ProposalFin { proposal_content_id: block.header.block_hash });
+    let fin_part = ProposalPart::Fin(ProposalFin {
+        proposal_content_id: block.header.block_hash,
+        signature: None,
+    });
     validate_sender.try_send(fin_part).unwrap();
     validate_sender.close_channel();
 
@@ -154,8 +160,9 @@ fn test_setup() -> (
         storage_reader.clone(),
         network_channels.subscriber_channels.broadcast_topic_client,
         outbound_internal_sender,
-        4,
+        EpochValidatorSets::new(100, ValidatorSet::uniform((0..4_u64).map(ValidatorId::from))),
         Some(sync_channels.subscriber_channels.broadcast_topic_client),
+        Arc::new(TestSigner::new(ValidatorId::default())),
     );
     (block, papyrus_context, network_channels.mock_network, sync_channels.mock_network)
 }
@@ -1,6 +1,7 @@
 //! Implementation of the ConsensusContext interface for running the sequencer.
 //!
-//! It connects to the Batcher who is responsible for building/validating blocks.
+//! It connects to the Batcher who is responsible for building/validating blocks, and to the L1 gas
+//! price provider which determines the gas prices of the blocks.
 #[cfg(test)]
 #[path = "sequencer_consensus_context_test.rs"]
 mod sequencer_consensus_context_test;
@@ -12,15 +13,19 @@ use std::time::Duration;
 use async_trait::async_trait;
 use futures::channel::{mpsc, oneshot};
 use futures::{SinkExt, StreamExt};
+use papyrus_consensus::signing::ConsensusSigner;
 use papyrus_consensus::types::{
     ConsensusContext,
     ConsensusError,
+    Evidence,
     ProposalContentId,
     Round,
     ValidatorId,
 };
+use papyrus_consensus::validator_set::{EpochValidatorSets, ValidatorSet};
 use papyrus_network::network_manager::{BroadcastTopicClient, BroadcastTopicClientTrait};
 use papyrus_protobuf::consensus::{
+    ConsensusBlockInfo,
     ConsensusMessage,
     ProposalFin,
     ProposalInit,
@@ -34,6 +39,7 @@ use starknet_api::block::{
     BlockInfo,
     BlockNumber,
     BlockTimestamp,
+    GasPrice,
     GasPriceVector,
     GasPrices,
     NonzeroGasPrice,
@@ -53,23 +59,13 @@ use starknet_batcher_types::batcher_types::{
     ValidateBlockInput,
 };
 use starknet_batcher_types::communication::BatcherClient;
-use tokio::sync::Notify;
+use starknet_l1_gas_price_types::communication::L1GasPriceProviderClient;
+use starknet_l1_gas_price_types::l1_gas_price_types::PriceInfo;
+use tokio::sync::{watch, Notify};
 use tokio::task::JoinHandle;
 use tracing::{debug, debug_span, error, info, trace, warn, Instrument};
 
-// TODO(Dan, Matan): Remove this once and replace with real gas prices.
-const TEMPORARY_GAS_PRICES: GasPrices = GasPrices {
-    eth_gas_prices: GasPriceVector {
-        l1_gas_price: NonzeroGasPrice::MIN,
-        l1_data_gas_price: NonzeroGasPrice::MIN,
-        l2_gas_price: NonzeroGasPrice::MIN,
-    },
-    strk_gas_prices: GasPriceVector {
-        l1_gas_price: NonzeroGasPrice::MIN,
-        l1_data_gas_price: NonzeroGasPrice::MIN,
-        l2_gas_price: NonzeroGasPrice::MIN,
-    },
-};
+use crate::config::ContextConfig;
 
 // {height: {proposal_id: (content, [proposal_ids])}}
 // Note that multiple proposals IDs can be associated with the same content, but we only need to
@@ -81,8 +77,10 @@ type ValidationParams = (BlockNumber, ValidatorId, Duration, mpsc::Receiver<Prop
 const CHANNEL_SIZE: usize = 100;
 
 pub struct SequencerConsensusContext {
+    config: ContextConfig,
     batcher: Arc<dyn BatcherClient>,
-    validators: Vec<ValidatorId>,
+    l1_gas_price_provider: Arc<dyn L1GasPriceProviderClient>,
+    validator_sets: EpochValidatorSets,
     // Proposal building/validating returns immediately, leaving the actual processing to a spawned
     // task. The spawned task processes the proposal asynchronously and updates the
     // valid_proposals map upon completion, ensuring consistency across tasks.
@@ -107,30 +105,47 @@ pub struct SequencerConsensusContext {
     outbound_proposal_sender: mpsc::Sender<(u64, mpsc::Receiver<ProposalPart>)>,
     // Used to broadcast votes to other consensus nodes.
     vote_broadcast_client: BroadcastTopicClient<ConsensusMessage>,
+    // Signs the fins of the proposals built by this node.
+    signer: Arc<dyn ConsensusSigner>,
+    // Publishes the height consensus is working on, e.g., for health checks.
+    height_sender: watch::Sender<Option<BlockNumber>>,
 }
 
 impl SequencerConsensusContext {
     pub fn new(
+        config: ContextConfig,
         batcher: Arc<dyn BatcherClient>,
+        l1_gas_price_provider: Arc<dyn L1GasPriceProviderClient>,
         _proposal_streaming_client: BroadcastTopicClient<ProposalPart>,
         outbound_proposal_sender: mpsc::Sender<(u64, mpsc::Receiver<ProposalPart>)>,
         vote_broadcast_client: BroadcastTopicClient<ConsensusMessage>,
-        num_validators: u64,
+        validator_sets: EpochValidatorSets,
+        signer: Arc<dyn ConsensusSigner>,
     ) -> Self {
         Self {
+            config,
             batcher,
+            l1_gas_price_provider,
             _proposal_streaming_client,
             outbound_proposal_sender,
             vote_broadcast_client,
-            validators: (0..num_validators).map(ValidatorId::from).collect(),
+            validator_sets,
             valid_proposals: Arc::new(Mutex::new(HeightToIdToContent::new())),
             proposal_id: 0,
             current_height: None,
             current_round: 0,
             active_proposal: None,
             queued_proposals: BTreeMap::new(),
+            signer,
+            height_sender: watch::channel(None).0,
         }
     }
+
+    /// Returns a receiver of the height consensus is working on, which is updated whenever
+    /// consensus moves on to a new height.
+    pub fn subscribe_to_height(&self) -> watch::Receiver<Option<BlockNumber>> {
+        self.height_sender.subscribe()
+    }
 }
 
 #[async_trait]
@@ -154,12 +169,20 @@ impl ConsensusContext for SequencerConsensusContext {
 
         let batcher = Arc::clone(&self.batcher);
         let valid_proposals = Arc::clone(&self.valid_proposals);
+        let signer = Arc::clone(&self.signer);
 
         let proposal_id = ProposalId(self.proposal_id);
         self.proposal_id += 1;
         let timeout =
             chrono::Duration::from_std(timeout).expect("Can't convert timeout to chrono::Duration");
         let now = chrono::Utc::now();
+        let timestamp: u64 = now.timestamp().try_into().expect("Failed to convert timestamp");
+        let price_info = self.l1_gas_price_provider.get_price_info(BlockTimestamp(timestamp)).await;
+        // TODO(Dan, Matan): Decide how to propose when L1 gas prices are unavailable.
+        let price_info = price_info.unwrap_or_else(|e| {
+            warn!("Failed to get L1 gas prices, using the minimal prices: {e:?}");
+            PriceInfo::default()
+        });
         let build_proposal_input = ProposeBlockInput {
             proposal_id,
             // TODO: Discuss with batcher team passing std Duration instead.
@@ -169,13 +192,10 @@ impl ConsensusContext for SequencerConsensusContext {
                 number: BlockNumber::default(),
                 hash: BlockHash::default(),
             }),
-            // TODO(Dan, Matan): Fill block info.
             block_info: BlockInfo {
                 block_number: proposal_init.height,
-                gas_prices: TEMPORARY_GAS_PRICES,
-                block_timestamp: BlockTimestamp(
-                    now.timestamp().try_into().expect("Failed to convert timestamp"),
-                ),
+                gas_prices: gas_prices_from_price_info(&self.config, &price_info),
+                block_timestamp: BlockTimestamp(timestamp),
                 use_kzg_da: true,
                 sequencer_address: proposal_init.proposer,
             },
@@ -199,13 +219,26 @@ impl ConsensusContext for SequencerConsensusContext {
             .send(ProposalPart::Init(proposal_init.clone()))
             .await
             .expect("Failed to send proposal init");
+        let block_info = ConsensusBlockInfo {
+            height: proposal_init.height,
+            timestamp,
+            builder: proposal_init.proposer,
+            l1_gas_price_wei: price_info.base_fee_per_gas,
+            l1_data_gas_price_wei: price_info.blob_fee,
+        };
+        debug!("Broadcasting proposal block info: {block_info:?}");
+        proposal_sender
+            .send(ProposalPart::BlockInfo(block_info))
+            .await
+            .expect("Failed to send proposal block info");
         tokio::spawn(
             async move {
                 stream_build_proposal(
-                    proposal_init.height,
+                    proposal_init,
                     proposal_id,
                     batcher,
                     valid_proposals,
+                    signer,
                     proposal_sender,
                     fin_sender,
                 )
@@ -264,17 +297,12 @@ impl ConsensusContext for SequencerConsensusContext {
         // TODO(guyn): Stream the TXs to the network.
     }
 
-    async fn validators(&self, _height: BlockNumber) -> Vec<ValidatorId> {
-        self.validators.clone()
+    async fn validators(&self, height: BlockNumber) -> ValidatorSet {
+        self.validator_sets.validator_set(height).clone()
     }
 
     fn proposer(&self, height: BlockNumber, round: Round) -> ValidatorId {
-        let height: usize = height.0.try_into().expect("Cannot convert to usize");
-        let round: usize = round.try_into().expect("Cannot convert to usize");
-        *self
-            .validators
-            .get((height + round) % self.validators.len())
-            .expect("There should be at least one validator")
+        self.validator_sets.validator_set(height).proposer(height, round)
     }
 
     async fn broadcast(&mut self, message: ConsensusMessage) -> Result<(), ConsensusError> {
@@ -310,6 +338,7 @@ impl ConsensusContext for SequencerConsensusContext {
     async fn set_height_and_round(&mut self, height: BlockNumber, round: Round) {
         if self.current_height.map(|h| height > h).unwrap_or(true) {
             self.current_height = Some(height);
+            self.height_sender.send_replace(Some(height));
             assert_eq!(round, 0);
             self.current_round = round;
             self.interrupt_active_proposal();
@@ -351,6 +380,12 @@ impl ConsensusContext for SequencerConsensusContext {
         };
         self.validate_current_round_proposal(height, validator, timeout, content, fin_sender).await;
     }
+
+    async fn report_evidence(&mut self, evidence: Evidence) -> Result<(), ConsensusError> {
+        // TODO(matan): persist and gossip the evidence, so that the validator can be slashed.
+        warn!("Validator {} equivocated: {evidence:?}", evidence.validator());
+        Ok(())
+    }
 }
 
 impl SequencerConsensusContext {
@@ -363,34 +398,16 @@ impl SequencerConsensusContext {
         fin_sender: oneshot::Sender<(ProposalContentId, ProposalFin)>,
     ) {
         debug!("Validating proposal for height: {height} with timeout: {timeout:?}");
+        let config = self.config.clone();
         let batcher = Arc::clone(&self.batcher);
+        let l1_gas_price_provider = Arc::clone(&self.l1_gas_price_provider);
         let valid_proposals = Arc::clone(&self.valid_proposals);
         let proposal_id = ProposalId(self.proposal_id);
         self.proposal_id += 1;
 
         let chrono_timeout =
             chrono::Duration::from_std(timeout).expect("Can't convert timeout to chrono::Duration");
-        let now = chrono::Utc::now();
-        let input = ValidateBlockInput {
-            proposal_id,
-            deadline: now + chrono_timeout,
-            // TODO(Matan 3/11/2024): Add the real value of the retrospective block hash.
-            retrospective_block_hash: Some(BlockHashAndNumber {
-                number: BlockNumber::default(),
-                hash: BlockHash::default(),
-            }),
-            // TODO(Dan, Matan): Fill block info.
-            block_info: BlockInfo {
-                block_number: height,
-                gas_prices: TEMPORARY_GAS_PRICES,
-                block_timestamp: BlockTimestamp(
-                    now.timestamp().try_into().expect("Failed to convert timestamp"),
-                ),
-                use_kzg_da: true,
-                sequencer_address: proposer,
-            },
-        };
-        batcher.validate_block(input).await.expect("Failed to initiate proposal validation");
+        let deadline = chrono::Utc::now() + chrono_timeout;
 
         let notify = Arc::new(Notify::new());
         let notify_clone = Arc::clone(&notify);
@@ -398,9 +415,13 @@ impl SequencerConsensusContext {
         let handle = tokio::spawn(
             async move {
                 let validate_fut = stream_validate_proposal(
+                    config,
                     height,
+                    proposer,
                     proposal_id,
+                    deadline,
                     batcher,
+                    l1_gas_price_provider,
                     valid_proposals,
                     content_receiver,
                     fin_sender,
@@ -431,15 +452,17 @@ impl SequencerConsensusContext {
 // 2. Forward these to the stream handler to be streamed out to the network.
 // 3. Once finished, receive the commitment from the batcher.
 // 4. Store the proposal for re-proposal.
-// 5. Send the commitment to the stream handler (to send fin).
+// 5. Sign the commitment and send it to the stream handler (to send fin).
 async fn stream_build_proposal(
-    height: BlockNumber,
+    proposal_init: ProposalInit,
     proposal_id: ProposalId,
     batcher: Arc<dyn BatcherClient>,
     valid_proposals: Arc<Mutex<HeightToIdToContent>>,
+    signer: Arc<dyn ConsensusSigner>,
     mut proposal_sender: mpsc::Sender<ProposalPart>,
     fin_sender: oneshot::Sender<ProposalContentId>,
 ) {
+    let height = proposal_init.height;
     let mut content = Vec::new();
     loop {
         let response =
@@ -483,8 +506,19 @@ async fn stream_build_proposal(
                     height
                 );
                 debug!("Broadcasting proposal fin: {proposal_content_id:?}");
+                let fin = match signer.sign_proposal_fin(
+                    height,
+                    proposal_init.round,
+                    ProposalFin { proposal_content_id, signature: None },
+                ) {
+                    Ok(fin) => fin,
+                    Err(e) => {
+                        warn!("Failed to sign proposal fin: {e:?}");
+                        return;
+                    }
+                };
                 proposal_sender
-                    .send(ProposalPart::Fin(ProposalFin { proposal_content_id }))
+                    .send(ProposalPart::Fin(fin))
                     .await
                     .expect("Failed to broadcast proposal fin");
                 // Update valid_proposals before sending fin to avoid a race condition
@@ -505,21 +539,75 @@ async fn stream_build_proposal(
 }
 
 // Handles receiving a proposal from another node without blocking consensus:
-// 1. Receives the proposal content from the network.
-// 2. Pass this to the batcher.
-// 3. Once finished, receive the commitment from the batcher.
-// 4. Store the proposal for re-proposal.
-// 5. Send the commitment to consensus.
+// 1. Receives the block info from the network, and checks it against our own view.
+// 2. Starts the validation in the batcher.
+// 3. Receives the proposal content from the network.
+// 4. Pass this to the batcher.
+// 5. Once finished, receive the commitment from the batcher.
+// 6. Store the proposal for re-proposal.
+// 7. Send the commitment to consensus.
+#[allow(clippy::too_many_arguments)]
 async fn stream_validate_proposal(
+    config: ContextConfig,
     height: BlockNumber,
+    proposer: ValidatorId,
     proposal_id: ProposalId,
+    deadline: chrono::DateTime<chrono::Utc>,
     batcher: Arc<dyn BatcherClient>,
+    l1_gas_price_provider: Arc<dyn L1GasPriceProviderClient>,
     valid_proposals: Arc<Mutex<HeightToIdToContent>>,
     mut content_receiver: mpsc::Receiver<ProposalPart>,
     fin_sender: oneshot::Sender<(ProposalContentId, ProposalFin)>,
 ) {
+    let Some(ProposalPart::BlockInfo(block_info)) = content_receiver.next().await else {
+        warn!("Failed to receive the block info of proposal: {proposal_id:?}");
+        return;
+    };
+    if !is_block_info_valid(&config, &block_info, height, proposer) {
+        warn!("Invalid block info {block_info:?}: {proposal_id:?}");
+        return;
+    }
+    let proposed_price_info = PriceInfo {
+        base_fee_per_gas: block_info.l1_gas_price_wei,
+        blob_fee: block_info.l1_data_gas_price_wei,
+    };
+    let price_info =
+        l1_gas_price_provider.get_price_info(BlockTimestamp(block_info.timestamp)).await;
+    // Fall back to the same prices the proposer uses when L1 gas prices are unavailable.
+    let price_info = price_info.unwrap_or_else(|e| {
+        warn!("Failed to get L1 gas prices, using the minimal prices: {e:?}");
+        PriceInfo::default()
+    });
+    if !proposed_price_info
+        .is_within_tolerance(&price_info, config.l1_gas_price_tolerance_percent.into())
+    {
+        warn!(
+            "Proposed L1 gas prices {proposed_price_info:?} deviate from ours {price_info:?}: \
+             {proposal_id:?}"
+        );
+        return;
+    }
+
+    let input = ValidateBlockInput {
+        proposal_id,
+        deadline,
+        // TODO(Matan 3/11/2024): Add the real value of the retrospective block hash.
+        retrospective_block_hash: Some(BlockHashAndNumber {
+            number: BlockNumber::default(),
+            hash: BlockHash::default(),
+        }),
+        block_info: BlockInfo {
+            block_number: height,
+            gas_prices: gas_prices_from_price_info(&config, &proposed_price_info),
+            block_timestamp: BlockTimestamp(block_info.timestamp),
+            use_kzg_da: true,
+            sequencer_address: proposer,
+        },
+    };
+    batcher.validate_block(input).await.expect("Failed to initiate proposal validation");
+
     let mut content = Vec::new();
-    let network_block_id = loop {
+    let network_fin = loop {
         let Some(prop_part) = content_receiver.next().await else {
             warn!("Failed to receive proposal content: {proposal_id:?}");
             return;
@@ -548,9 +636,9 @@ async fn stream_validate_proposal(
                     status => panic!("Unexpected status: for {proposal_id:?}, {status:?}"),
                 }
             }
-            ProposalPart::Fin(ProposalFin { proposal_content_id: id }) => {
+            ProposalPart::Fin(fin) => {
                 // Output this along with the ID from batcher, to compare them.
-                break id;
+                break fin;
             }
             _ => panic!("Invalid proposal part: {:?}", prop_part),
         }
@@ -573,21 +661,65 @@ async fn stream_validate_proposal(
         "Finished validating proposal {:?}: network_block_id: {:?}, batcher_block_id = {:?}, \
          num_txs = {:?}, height = {:?}",
         proposal_id,
-        network_block_id,
+        network_fin.proposal_content_id,
         batcher_block_id,
         content.len(),
         height
     );
     // Update valid_proposals before sending fin to avoid a race condition
     // with `get_proposal` being called before `valid_proposals` is updated.
-    // TODO(Matan): Consider validating the ProposalFin signature here.
+    // The signature of the fin is validated by consensus.
     let mut valid_proposals = valid_proposals.lock().unwrap();
     valid_proposals.entry(height).or_default().insert(batcher_block_id, (content, proposal_id));
-    if fin_sender
-        .send((batcher_block_id, ProposalFin { proposal_content_id: network_block_id }))
-        .is_err()
-    {
+    if fin_sender.send((batcher_block_id, network_fin)).is_err() {
         // Consensus may exit early (e.g. sync).
         warn!("Failed to send proposal content ids");
     }
 }
+
+// Checks the parts of the block info that don't depend on L1: that it was built for this height by
+// the proposer, at about the current time.
+fn is_block_info_valid(
+    config: &ContextConfig,
+    block_info: &ConsensusBlockInfo,
+    height: BlockNumber,
+    proposer: ValidatorId,
+) -> bool {
+    if block_info.height != height {
+        debug!("Block info height doesn't match the proposal height {height}.");
+        return false;
+    }
+    if block_info.builder != proposer {
+        debug!("Block info builder doesn't match the proposer {proposer:?}.");
+        return false;
+    }
+    let now: u64 = chrono::Utc::now().timestamp().try_into().expect("Failed to convert timestamp");
+    if block_info.timestamp.abs_diff(now) > config.block_timestamp_window_seconds {
+        debug!("Block info timestamp is too far from the current time {now}.");
+        return false;
+    }
+    true
+}
+
+// The L1 prices are converted from wei to fri, and the L2 price from fri to wei, by the configured
+// ETH to STRK rate. Zero prices (e.g. before EIP-4844 blob fees, or when the L1 prices are
+// unavailable) are raised to the minimal price.
+fn gas_prices_from_price_info(config: &ContextConfig, price_info: &PriceInfo) -> GasPrices {
+    let to_nonzero =
+        |price: u128| NonzeroGasPrice::new(GasPrice(price)).unwrap_or(NonzeroGasPrice::MIN);
+    let eth_to_strk_rate = u128::from(config.eth_to_strk_rate);
+    let to_fri = |price_in_wei: u128| to_nonzero(price_in_wei.saturating_mul(eth_to_strk_rate));
+    let l2_gas_price_fri = u128::from(config.l2_gas_price_fri);
+    GasPrices {
+        eth_gas_prices: GasPriceVector {
+            l1_gas_price: to_nonzero(price_info.base_fee_per_gas),
+            l1_data_gas_price: to_nonzero(price_info.blob_fee),
+            l2_gas_price: to_nonzero(l2_gas_price_fri.checked_div(eth_to_strk_rate).unwrap_or(0)),
+        },
+        strk_gas_prices: GasPriceVector {
+            l1_gas_price: to_fri(price_info.base_fee_per_gas),
+            l1_data_gas_price: to_fri(price_info.blob_fee),
+            l2_gas_price: to_nonzero(l2_gas_price_fri),
+        },
+    }
+}
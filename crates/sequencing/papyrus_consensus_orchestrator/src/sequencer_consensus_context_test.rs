@@ -5,8 +5,10 @@ use std::vec;
 use futures::channel::mpsc;
 use futures::{FutureExt, SinkExt};
 use lazy_static::lazy_static;
+use papyrus_consensus::signing::TestSigner;
 use papyrus_consensus::stream_handler::StreamHandler;
 use papyrus_consensus::types::{ConsensusContext, ValidatorId};
+use papyrus_consensus::validator_set::{EpochValidatorSets, ValidatorSet};
 use papyrus_network::network_manager::test_utils::{
     mock_register_broadcast_topic,
     BroadcastNetworkMock,
@@ -14,6 +16,7 @@ use papyrus_network::network_manager::test_utils::{
 };
 use papyrus_network::network_manager::BroadcastTopicChannels;
 use papyrus_protobuf::consensus::{
+    ConsensusBlockInfo,
     ConsensusMessage,
     ProposalFin,
     ProposalInit,
@@ -21,7 +24,7 @@ use papyrus_protobuf::consensus::{
     StreamMessage,
     TransactionBatch,
 };
-use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::block::{BlockHash, BlockNumber, GasPrice};
 use starknet_api::core::StateDiffCommitment;
 use starknet_api::executable_transaction::{
     AccountTransaction,
@@ -43,14 +46,20 @@ use starknet_batcher_types::batcher_types::{
     ValidateBlockInput,
 };
 use starknet_batcher_types::communication::MockBatcherClient;
+use starknet_l1_gas_price_types::communication::MockL1GasPriceProviderClient;
+use starknet_l1_gas_price_types::l1_gas_price_types::PriceInfo;
 use starknet_types_core::felt::Felt;
+use test_case::test_case;
 
+use crate::config::ContextConfig;
 use crate::sequencer_consensus_context::SequencerConsensusContext;
 
 const TIMEOUT: Duration = Duration::from_millis(100);
 const CHANNEL_SIZE: usize = 5000;
 const NUM_VALIDATORS: u64 = 4;
+const EPOCH_LENGTH: u64 = 100;
 const STATE_DIFF_COMMITMENT: StateDiffCommitment = StateDiffCommitment(PoseidonHash(Felt::ZERO));
+const PRICE_INFO: PriceInfo = PriceInfo { base_fee_per_gas: 100, blob_fee: 10 };
 
 lazy_static! {
     static ref TX_BATCH: Vec<ExecutableTransaction> =
@@ -68,6 +77,20 @@ fn generate_executable_invoke_tx(tx_hash: Felt) -> ExecutableTransaction {
     })))
 }
 
+fn block_info(height: BlockNumber) -> ProposalPart {
+    ProposalPart::BlockInfo(consensus_block_info(height))
+}
+
+fn consensus_block_info(height: BlockNumber) -> ConsensusBlockInfo {
+    ConsensusBlockInfo {
+        height,
+        timestamp: chrono::Utc::now().timestamp().try_into().unwrap(),
+        builder: ValidatorId::default(),
+        l1_gas_price_wei: PRICE_INFO.base_fee_per_gas,
+        l1_data_gas_price_wei: PRICE_INFO.blob_fee,
+    }
+}
+
 // Structs which aren't utilized but should not be dropped.
 struct NetworkDependencies {
     _vote_network: BroadcastNetworkMock<ConsensusMessage>,
@@ -96,12 +119,21 @@ fn setup(batcher: MockBatcherClient) -> (SequencerConsensusContext, NetworkDepen
     let BroadcastTopicChannels { broadcast_topic_client: votes_topic_client, .. } =
         subscriber_channels;
 
+    let mut l1_gas_price_provider = MockL1GasPriceProviderClient::new();
+    l1_gas_price_provider.expect_get_price_info().returning(|_| Ok(PRICE_INFO));
+
     let context = SequencerConsensusContext::new(
+        ContextConfig::default(),
         Arc::new(batcher),
+        Arc::new(l1_gas_price_provider),
         proposal_streaming_client,
         outbound_proposal_stream_sender,
         votes_topic_client,
-        NUM_VALIDATORS,
+        EpochValidatorSets::new(
+            EPOCH_LENGTH,
+            ValidatorSet::uniform((0..NUM_VALIDATORS).map(ValidatorId::from)),
+        ),
+        Arc::new(TestSigner::new(ValidatorId::default())),
     );
 
     let network_dependencies = NetworkDependencies {
@@ -119,6 +151,20 @@ async fn build_proposal() {
     let proposal_id = Arc::new(OnceLock::new());
     let proposal_id_clone = Arc::clone(&proposal_id);
     batcher.expect_propose_block().returning(move |input: ProposeBlockInput| {
+        let eth_gas_prices = input.block_info.gas_prices.eth_gas_prices;
+        assert_eq!(eth_gas_prices.l1_gas_price.get(), GasPrice(PRICE_INFO.base_fee_per_gas));
+        assert_eq!(eth_gas_prices.l1_data_gas_price.get(), GasPrice(PRICE_INFO.blob_fee));
+        // The STRK prices are converted by the ETH to STRK rate.
+        let eth_to_strk_rate = u128::from(ContextConfig::default().eth_to_strk_rate);
+        let strk_gas_prices = input.block_info.gas_prices.strk_gas_prices;
+        assert_eq!(
+            strk_gas_prices.l1_gas_price.get(),
+            GasPrice(PRICE_INFO.base_fee_per_gas * eth_to_strk_rate)
+        );
+        assert_eq!(
+            strk_gas_prices.l1_data_gas_price.get(),
+            GasPrice(PRICE_INFO.blob_fee * eth_to_strk_rate)
+        );
         proposal_id_clone.set(input.proposal_id).unwrap();
         Ok(())
     });
@@ -195,6 +241,7 @@ async fn validate_proposal_success() {
     context.set_height_and_round(BlockNumber(0), 0).await;
 
     let (mut content_sender, content_receiver) = mpsc::channel(CHANNEL_SIZE);
+    content_sender.send(block_info(BlockNumber(0))).await.unwrap();
     let tx_hash = TX_BATCH.first().unwrap().tx_hash();
     let txs =
         TX_BATCH.clone().into_iter().map(starknet_api::transaction::Transaction::from).collect();
@@ -208,6 +255,7 @@ async fn validate_proposal_success() {
     content_sender
         .send(ProposalPart::Fin(ProposalFin {
             proposal_content_id: BlockHash(STATE_DIFF_COMMITMENT.0.0),
+            signature: None,
         }))
         .await
         .unwrap();
@@ -250,6 +298,7 @@ async fn repropose() {
 
     // Receive a valid proposal.
     let (mut content_sender, content_receiver) = mpsc::channel(CHANNEL_SIZE);
+    content_sender.send(block_info(BlockNumber(0))).await.unwrap();
     let prop_part = ProposalPart::Transactions(TransactionBatch {
         transactions: vec![generate_invoke_tx()],
         tx_hashes: vec![TransactionHash(Felt::TWO)],
@@ -257,6 +306,7 @@ async fn repropose() {
     content_sender.send(prop_part).await.unwrap();
     let prop_part = ProposalPart::Fin(ProposalFin {
         proposal_content_id: BlockHash(STATE_DIFF_COMMITMENT.0.0),
+        signature: None,
     });
     content_sender.send(prop_part).await.unwrap();
     let fin_receiver = context
@@ -322,10 +372,12 @@ async fn proposals_from_different_rounds() {
     });
     let prop_part_fin = ProposalPart::Fin(ProposalFin {
         proposal_content_id: BlockHash(STATE_DIFF_COMMITMENT.0.0),
+        signature: None,
     });
 
     // The proposal from the past round is ignored.
     let (mut content_sender, content_receiver) = mpsc::channel(CHANNEL_SIZE);
+    content_sender.send(block_info(BlockNumber(0))).await.unwrap();
     content_sender.send(prop_part_txs.clone()).await.unwrap();
 
     let fin_receiver_past_round = context
@@ -336,6 +388,7 @@ async fn proposals_from_different_rounds() {
 
     // The proposal from the current round should be validated.
     let (mut content_sender, content_receiver) = mpsc::channel(CHANNEL_SIZE);
+    content_sender.send(block_info(BlockNumber(0))).await.unwrap();
     content_sender.send(prop_part_txs.clone()).await.unwrap();
     content_sender.send(prop_part_fin.clone()).await.unwrap();
     let fin_receiver_curr_round = context
@@ -345,6 +398,7 @@ async fn proposals_from_different_rounds() {
 
     // The proposal from the future round should not be processed.
     let (mut content_sender, content_receiver) = mpsc::channel(CHANNEL_SIZE);
+    content_sender.send(block_info(BlockNumber(0))).await.unwrap();
     content_sender.send(prop_part_txs.clone()).await.unwrap();
     content_sender.send(prop_part_fin.clone()).await.unwrap();
     let fin_receiver_future_round = context
@@ -402,12 +456,14 @@ async fn interrupt_active_proposal() {
 
     // Keep the sender open, as closing it or sending Fin would cause the validate to complete
     // without needing interrupt.
-    let (mut _content_sender_0, content_receiver) = mpsc::channel(CHANNEL_SIZE);
+    let (mut content_sender_0, content_receiver) = mpsc::channel(CHANNEL_SIZE);
+    content_sender_0.send(block_info(BlockNumber(0))).await.unwrap();
     let fin_receiver_0 = context
         .validate_proposal(BlockNumber(0), 0, ValidatorId::default(), TIMEOUT, content_receiver)
         .await;
 
     let (mut content_sender_1, content_receiver) = mpsc::channel(CHANNEL_SIZE);
+    content_sender_1.send(block_info(BlockNumber(0))).await.unwrap();
     content_sender_1
         .send(ProposalPart::Transactions(TransactionBatch {
             transactions: TX_BATCH.clone().into_iter().map(Transaction::from).collect(),
@@ -418,6 +474,7 @@ async fn interrupt_active_proposal() {
     content_sender_1
         .send(ProposalPart::Fin(ProposalFin {
             proposal_content_id: BlockHash(STATE_DIFF_COMMITMENT.0.0),
+            signature: None,
         }))
         .await
         .unwrap();
@@ -431,3 +488,78 @@ async fn interrupt_active_proposal() {
     assert!(fin_receiver_0.await.is_err());
     assert_eq!(fin_receiver_1.await.unwrap().0.0, STATE_DIFF_COMMITMENT.0.0);
 }
+
+#[tokio::test]
+async fn validate_proposal_gas_prices_out_of_tolerance() {
+    let mut batcher = MockBatcherClient::new();
+    batcher
+        .expect_start_height()
+        .withf(|input| input.height == BlockNumber(0))
+        .return_once(|_| Ok(()));
+    batcher.expect_validate_block().never();
+    let (mut context, _network) = setup(batcher);
+    // Initialize the context for a specific height, starting with round 0.
+    context.set_height_and_round(BlockNumber(0), 0).await;
+
+    let (mut content_sender, content_receiver) = mpsc::channel(CHANNEL_SIZE);
+    content_sender
+        .send(ProposalPart::BlockInfo(ConsensusBlockInfo {
+            l1_gas_price_wei: PRICE_INFO.base_fee_per_gas * 2,
+            ..consensus_block_info(BlockNumber(0))
+        }))
+        .await
+        .unwrap();
+    let fin_receiver = context
+        .validate_proposal(BlockNumber(0), 0, ValidatorId::default(), TIMEOUT, content_receiver)
+        .await;
+    assert!(fin_receiver.await.is_err());
+}
+
+#[test_case(
+    ConsensusBlockInfo { height: BlockNumber(1), ..consensus_block_info(BlockNumber(0)) };
+    "wrong height"
+)]
+#[test_case(
+    ConsensusBlockInfo { builder: 1_u64.into(), ..consensus_block_info(BlockNumber(0)) };
+    "wrong builder"
+)]
+#[test_case(
+    ConsensusBlockInfo { timestamp: 0, ..consensus_block_info(BlockNumber(0)) };
+    "stale timestamp"
+)]
+#[tokio::test]
+async fn validate_proposal_invalid_block_info(block_info: ConsensusBlockInfo) {
+    let mut batcher = MockBatcherClient::new();
+    batcher
+        .expect_start_height()
+        .withf(|input| input.height == BlockNumber(0))
+        .return_once(|_| Ok(()));
+    batcher.expect_validate_block().never();
+    let (mut context, _network) = setup(batcher);
+    // Initialize the context for a specific height, starting with round 0.
+    context.set_height_and_round(BlockNumber(0), 0).await;
+
+    let (mut content_sender, content_receiver) = mpsc::channel(CHANNEL_SIZE);
+    content_sender.send(ProposalPart::BlockInfo(block_info)).await.unwrap();
+    let fin_receiver = context
+        .validate_proposal(BlockNumber(0), 0, ValidatorId::default(), TIMEOUT, content_receiver)
+        .await;
+    assert!(fin_receiver.await.is_err());
+}
+
+#[tokio::test]
+async fn height_is_published() {
+    let mut batcher = MockBatcherClient::new();
+    batcher.expect_start_height().times(2).returning(|_| Ok(()));
+    let (mut context, _network) = setup(batcher);
+    let height_receiver = context.subscribe_to_height();
+    assert_eq!(*height_receiver.borrow(), None);
+
+    context.set_height_and_round(BlockNumber(0), 0).await;
+    assert_eq!(*height_receiver.borrow(), Some(BlockNumber(0)));
+    // Moving on to the next round doesn't change the height.
+    context.set_height_and_round(BlockNumber(0), 1).await;
+    assert_eq!(*height_receiver.borrow(), Some(BlockNumber(0)));
+    context.set_height_and_round(BlockNumber(1), 0).await;
+    assert_eq!(*height_receiver.borrow(), Some(BlockNumber(1)));
+}
@@ -1,18 +1,23 @@
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+use async_trait::async_trait;
 use blockifier::abi::constants;
 use blockifier::state::global_cache::GlobalContractCache;
 use chrono::Utc;
 #[cfg(test)]
 use mockall::automock;
 use papyrus_storage::state::{StateStorageReader, StateStorageWriter};
+use papyrus_storage::{StorageReader, StorageWriter};
 use starknet_api::block::{BlockHashAndNumber, BlockNumber};
+use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::executable_transaction::Transaction;
-use starknet_api::state::ThinStateDiff;
+use starknet_api::state::{StateNumber, ThinStateDiff};
 use starknet_batcher_types::batcher_types::{
     BatcherResult,
     DecisionReachedInput,
+    GetAccountNoncesInput,
+    GetAccountNoncesResponse,
     GetProposalContent,
     GetProposalContentInput,
     GetProposalContentResponse,
@@ -26,9 +31,14 @@ use starknet_batcher_types::batcher_types::{
     ValidateBlockInput,
 };
 use starknet_batcher_types::errors::BatcherError;
+use starknet_l1_provider_types::communication::SharedL1ProviderClient;
 use starknet_mempool_types::communication::SharedMempoolClient;
 use starknet_mempool_types::mempool_types::CommitBlockArgs;
-use starknet_sequencer_infra::component_definitions::ComponentStarter;
+use starknet_sequencer_infra::component_definitions::{
+    ComponentHealth,
+    ComponentStarter,
+    HealthStatus,
+};
 use tracing::{debug, error, info, instrument, trace};
 
 use crate::block_builder::{
@@ -47,11 +57,7 @@ use crate::proposal_manager::{
     ProposalManagerTrait,
     ProposalOutput,
 };
-use crate::transaction_provider::{
-    DummyL1ProviderClient,
-    ProposeTransactionProvider,
-    ValidateTransactionProvider,
-};
+use crate::transaction_provider::{ProposeTransactionProvider, ValidateTransactionProvider};
 
 type OutputStreamReceiver = tokio::sync::mpsc::UnboundedReceiver<Transaction>;
 type InputStreamSender = tokio::sync::mpsc::Sender<Transaction>;
@@ -61,8 +67,12 @@ pub struct Batcher {
     pub storage_reader: Arc<dyn BatcherStorageReaderTrait>,
     pub storage_writer: Box<dyn BatcherStorageWriterTrait>,
     pub mempool_client: SharedMempoolClient,
+    pub l1_provider_client: SharedL1ProviderClient,
 
     active_height: Option<BlockNumber>,
+    // The last height consensus asked to work on, shared with the health to measure how far the
+    // storage lags behind.
+    requested_height: Arc<Mutex<Option<BlockNumber>>>,
     proposal_manager: Box<dyn ProposalManagerTrait>,
 
     block_builder_factory: Box<dyn BlockBuilderFactoryTrait>,
@@ -76,6 +86,7 @@ impl Batcher {
         storage_reader: Arc<dyn BatcherStorageReaderTrait>,
         storage_writer: Box<dyn BatcherStorageWriterTrait>,
         mempool_client: SharedMempoolClient,
+        l1_provider_client: SharedL1ProviderClient,
         block_builder_factory: Box<dyn BlockBuilderFactoryTrait>,
         proposal_manager: Box<dyn ProposalManagerTrait>,
     ) -> Self {
@@ -84,7 +95,9 @@ impl Batcher {
             storage_reader,
             storage_writer,
             mempool_client,
+            l1_provider_client,
             active_height: None,
+            requested_height: Arc::new(Mutex::new(None)),
             block_builder_factory,
             proposal_manager,
             propose_tx_streams: HashMap::new(),
@@ -92,8 +105,20 @@ impl Batcher {
         }
     }
 
+    /// Returns a handle that reports the height of the storage to the monitoring endpoint.
+    pub fn health(&self) -> BatcherHealth {
+        BatcherHealth {
+            storage_reader: self.storage_reader.clone(),
+            requested_height: self.requested_height.clone(),
+            max_state_sync_lag: self.config.max_state_sync_lag,
+        }
+    }
+
     #[instrument(skip(self), err)]
     pub async fn start_height(&mut self, input: StartHeightInput) -> BatcherResult<()> {
+        // Recorded before the checks, as a height the storage hasn't synced to yet is exactly what
+        // the health should report.
+        *self.requested_height.lock().expect("Lock should not be poisoned.") = Some(input.height);
         if self.active_height == Some(input.height) {
             return Err(BatcherError::HeightInProgress);
         }
@@ -136,10 +161,14 @@ impl Batcher {
             propose_block_input.retrospective_block_hash,
         )?;
 
+        self.abort_l1_provider_cycle().await?;
+        self.l1_provider_client.proposal_start().await.map_err(|err| {
+            error!("Failed to start a proposal in the L1 provider: {}", err);
+            BatcherError::InternalError
+        })?;
         let tx_provider = ProposeTransactionProvider::new(
             self.mempool_client.clone(),
-            // TODO: use a real L1 provider client.
-            Arc::new(DummyL1ProviderClient),
+            self.l1_provider_client.clone(),
             self.config.max_l1_handler_txs_per_block_proposal,
         );
 
@@ -152,6 +181,7 @@ impl Batcher {
                 BlockMetadata {
                     height: active_height,
                     retrospective_block_hash: propose_block_input.retrospective_block_hash,
+                    gas_prices: propose_block_input.block_info.gas_prices,
                 },
                 BlockBuilderExecutionParams {
                     deadline: deadline_as_instant(propose_block_input.deadline)?,
@@ -186,10 +216,14 @@ impl Batcher {
         let (input_tx_sender, input_tx_receiver) =
             tokio::sync::mpsc::channel(self.config.input_stream_content_buffer_size);
 
+        self.abort_l1_provider_cycle().await?;
+        self.l1_provider_client.validation_start().await.map_err(|err| {
+            error!("Failed to start a validation in the L1 provider: {}", err);
+            BatcherError::InternalError
+        })?;
         let tx_provider = ValidateTransactionProvider {
             tx_receiver: input_tx_receiver,
-            // TODO: use a real L1 provider client.
-            l1_provider_client: Arc::new(DummyL1ProviderClient),
+            l1_provider_client: self.l1_provider_client.clone(),
         };
 
         let (block_builder, abort_signal_sender) = self
@@ -198,6 +232,7 @@ impl Batcher {
                 BlockMetadata {
                     height: active_height,
                     retrospective_block_hash: validate_block_input.retrospective_block_hash,
+                    gas_prices: validate_block_input.block_info.gas_prices,
                 },
                 BlockBuilderExecutionParams {
                     deadline: deadline_as_instant(validate_block_input.deadline)?,
@@ -235,7 +270,17 @@ impl Batcher {
                 self.close_tx_channel_and_get_commitment(proposal_id).await
             }
             SendProposalContent::Abort => {
+                // Only a block that is still being proposed holds transactions taken from the
+                // mempool.
+                let is_active_propose_proposal = self.propose_tx_streams.contains_key(&proposal_id)
+                    && matches!(
+                        self.proposal_manager.get_proposal_status(proposal_id).await,
+                        InternalProposalStatus::Processing
+                    );
                 self.proposal_manager.abort_proposal(proposal_id).await;
+                if is_active_propose_proposal {
+                    self.rewind_mempool().await;
+                }
                 Ok(SendProposalContentResponse { response: ProposalStatus::Aborted })
             }
         }
@@ -354,6 +399,11 @@ impl Batcher {
             error!("Failed to commit proposal to storage: {}", err);
             BatcherError::InternalError
         })?;
+        if let Err(l1_provider_err) =
+            self.l1_provider_client.commit_block(tx_hashes.iter().copied().collect()).await
+        {
+            error!("Failed to commit block to L1 provider: {}", l1_provider_err);
+        }
         if let Err(mempool_err) =
             self.mempool_client.commit_block(CommitBlockArgs { address_to_nonce, tx_hashes }).await
         {
@@ -362,12 +412,50 @@ impl Batcher {
         }
         Ok(())
     }
-}
 
-pub fn create_batcher(config: BatcherConfig, mempool_client: SharedMempoolClient) -> Batcher {
-    let (storage_reader, storage_writer) = papyrus_storage::open_storage(config.storage.clone())
-        .expect("Failed to open batcher's storage");
+    #[instrument(skip(self, input), err)]
+    pub fn get_account_nonces(
+        &self,
+        input: GetAccountNoncesInput,
+    ) -> BatcherResult<GetAccountNoncesResponse> {
+        let address_to_nonce = input
+            .addresses
+            .into_iter()
+            .map(|address| Ok((address, self.storage_reader.get_nonce(address)?)))
+            .collect::<papyrus_storage::StorageResult<_>>()
+            .map_err(|err| {
+                error!("Failed to get account nonces from storage: {}", err);
+                BatcherError::InternalError
+            })?;
+        Ok(GetAccountNoncesResponse { address_to_nonce })
+    }
+
+    // Ends the proposal or validation of an earlier round of the height in the L1 provider, if
+    // any, so that it can start a new one.
+    async fn abort_l1_provider_cycle(&self) -> BatcherResult<()> {
+        self.l1_provider_client.abort_cycle().await.map_err(|err| {
+            error!("Failed to abort the current cycle of the L1 provider: {}", err);
+            BatcherError::InternalError
+        })
+    }
 
+    // Returns the transactions taken from the mempool by proposals that will not be committed.
+    async fn rewind_mempool(&self) {
+        if let Err(mempool_err) = self.mempool_client.rewind().await {
+            error!("Failed to rewind the mempool: {}", mempool_err);
+        }
+    }
+}
+
+/// Creates a batcher over the storage opened from `config.storage`. The storage is opened by the
+/// caller, so that other components of the node may read from it too.
+pub fn create_batcher(
+    config: BatcherConfig,
+    storage_reader: StorageReader,
+    storage_writer: StorageWriter,
+    mempool_client: SharedMempoolClient,
+    l1_provider_client: SharedL1ProviderClient,
+) -> Batcher {
     let block_builder_factory = Box::new(BlockBuilderFactory {
         block_builder_config: config.block_builder_config.clone(),
         storage_reader: storage_reader.clone(),
@@ -381,21 +469,68 @@ pub fn create_batcher(config: BatcherConfig, mempool_client: SharedMempoolClient
         storage_reader,
         storage_writer,
         mempool_client,
+        l1_provider_client,
         block_builder_factory,
         proposal_manager,
     )
 }
 
+/// Reports the height of the batcher's storage, which is unhealthy if it can't be read or if state
+/// sync lags more than `max_state_sync_lag` blocks behind the height requested by consensus.
+#[derive(Clone)]
+pub struct BatcherHealth {
+    storage_reader: Arc<dyn BatcherStorageReaderTrait>,
+    requested_height: Arc<Mutex<Option<BlockNumber>>>,
+    max_state_sync_lag: u64,
+}
+
+#[async_trait]
+impl ComponentHealth for BatcherHealth {
+    async fn check_health(&self) -> HealthStatus {
+        let height = match self.storage_reader.height() {
+            Ok(height) => height,
+            Err(err) => {
+                return HealthStatus::Unhealthy {
+                    reason: format!("Failed to read the storage height: {err}"),
+                };
+            }
+        };
+
+        let requested_height = *self.requested_height.lock().expect("Lock should not be poisoned.");
+        if let Some(requested_height) = requested_height {
+            let lag = requested_height.0.saturating_sub(height.0);
+            if lag > self.max_state_sync_lag {
+                return HealthStatus::Unhealthy {
+                    reason: format!(
+                        "State sync lags {lag} blocks behind: storage height {height}, requested \
+                         height {requested_height}."
+                    ),
+                };
+            }
+        }
+        HealthStatus::Healthy { details: format!("Storage height: {height}.") }
+    }
+}
+
 #[cfg_attr(test, automock)]
 pub trait BatcherStorageReaderTrait: Send + Sync {
     /// Returns the next height that the batcher should work on.
     fn height(&self) -> papyrus_storage::StorageResult<BlockNumber>;
+
+    /// Returns the nonce of the account in the state after the last committed block.
+    fn get_nonce(&self, address: ContractAddress) -> papyrus_storage::StorageResult<Nonce>;
 }
 
 impl BatcherStorageReaderTrait for papyrus_storage::StorageReader {
     fn height(&self) -> papyrus_storage::StorageResult<BlockNumber> {
         self.begin_ro_txn()?.get_state_marker()
     }
+
+    fn get_nonce(&self, address: ContractAddress) -> papyrus_storage::StorageResult<Nonce> {
+        let txn = self.begin_ro_txn()?;
+        let state_number = StateNumber(txn.get_state_marker()?);
+        Ok(txn.get_state_reader()?.get_nonce_at(state_number, &address)?.unwrap_or_default())
+    }
 }
 
 #[cfg_attr(test, automock)]
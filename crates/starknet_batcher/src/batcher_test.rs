@@ -9,6 +9,7 @@ use futures::future::BoxFuture;
 use futures::FutureExt;
 use mockall::automock;
 use mockall::predicate::{always, eq};
+use papyrus_storage::StorageError;
 use rstest::rstest;
 use starknet_api::block::{BlockInfo, BlockNumber};
 use starknet_api::core::{ContractAddress, Nonce, StateDiffCommitment};
@@ -19,6 +20,8 @@ use starknet_api::transaction::TransactionHash;
 use starknet_api::{contract_address, felt, nonce, tx_hash};
 use starknet_batcher_types::batcher_types::{
     DecisionReachedInput,
+    GetAccountNoncesInput,
+    GetAccountNoncesResponse,
     GetProposalContent,
     GetProposalContentInput,
     GetProposalContentResponse,
@@ -33,8 +36,11 @@ use starknet_batcher_types::batcher_types::{
     ValidateBlockInput,
 };
 use starknet_batcher_types::errors::BatcherError;
+use starknet_l1_provider_types::communication::{L1ProviderClientError, MockL1ProviderClient};
+use starknet_l1_provider_types::errors::L1ProviderError;
 use starknet_mempool_types::communication::MockMempoolClient;
 use starknet_mempool_types::mempool_types::CommitBlockArgs;
+use starknet_sequencer_infra::component_definitions::{ComponentHealth, HealthStatus};
 
 use crate::batcher::{Batcher, MockBatcherStorageReaderTrait, MockBatcherStorageWriterTrait};
 use crate::block_builder::{
@@ -76,6 +82,7 @@ struct MockDependencies {
     storage_reader: MockBatcherStorageReaderTrait,
     storage_writer: MockBatcherStorageWriterTrait,
     mempool_client: MockMempoolClient,
+    l1_provider_client: MockL1ProviderClient,
     proposal_manager: MockProposalManagerTraitWrapper,
     block_builder_factory: MockBlockBuilderFactoryTrait,
 }
@@ -84,10 +91,16 @@ impl Default for MockDependencies {
     fn default() -> Self {
         let mut storage_reader = MockBatcherStorageReaderTrait::new();
         storage_reader.expect_height().returning(|| Ok(INITIAL_HEIGHT));
+        let mut l1_provider_client = MockL1ProviderClient::new();
+        l1_provider_client.expect_abort_cycle().returning(|| Ok(()));
+        l1_provider_client.expect_proposal_start().returning(|| Ok(()));
+        l1_provider_client.expect_validation_start().returning(|| Ok(()));
+        l1_provider_client.expect_commit_block().returning(|_| Ok(()));
         Self {
             storage_reader,
             storage_writer: MockBatcherStorageWriterTrait::new(),
             mempool_client: MockMempoolClient::new(),
+            l1_provider_client,
             proposal_manager: MockProposalManagerTraitWrapper::new(),
             block_builder_factory: MockBlockBuilderFactoryTrait::new(),
         }
@@ -100,6 +113,7 @@ fn create_batcher(mock_dependencies: MockDependencies) -> Batcher {
         Arc::new(mock_dependencies.storage_reader),
         Box::new(mock_dependencies.storage_writer),
         Arc::new(mock_dependencies.mempool_client),
+        Arc::new(mock_dependencies.l1_provider_client),
         Box::new(mock_dependencies.block_builder_factory),
         Box::new(mock_dependencies.proposal_manager),
     )
@@ -217,6 +231,41 @@ async fn duplicate_start_height() {
     assert_eq!(batcher.start_height(initial_height).await, Err(BatcherError::HeightInProgress));
 }
 
+#[rstest]
+#[tokio::test]
+async fn propose_block_fails_if_l1_provider_does_not_start() {
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_reset().times(1).return_once(|| async {}.boxed());
+    proposal_manager.expect_wrap_spawn_proposal().never();
+    let mut l1_provider_client = MockL1ProviderClient::new();
+    l1_provider_client.expect_abort_cycle().times(1).returning(|| Ok(()));
+    l1_provider_client.expect_proposal_start().times(1).returning(|| {
+        Err(L1ProviderClientError::L1ProviderError(
+            L1ProviderError::UnexpectedProviderStateTransition {
+                from: "Validate".to_owned(),
+                to: "Propose".to_owned(),
+            },
+        ))
+    });
+    let mut batcher = create_batcher(MockDependencies {
+        proposal_manager,
+        l1_provider_client,
+        block_builder_factory: MockBlockBuilderFactoryTrait::new(),
+        ..Default::default()
+    });
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+
+    let result = batcher
+        .propose_block(ProposeBlockInput {
+            proposal_id: PROPOSAL_ID,
+            retrospective_block_hash: None,
+            deadline: deadline(),
+            block_info: BlockInfo { block_number: INITIAL_HEIGHT, ..Default::default() },
+        })
+        .await;
+    assert_eq!(result, Err(BatcherError::InternalError));
+}
+
 #[rstest]
 #[tokio::test]
 async fn no_active_height() {
@@ -487,6 +536,56 @@ async fn propose_block_without_retrospective_block_hash() {
     assert_matches!(result, Err(BatcherError::MissingRetrospectiveBlockHash));
 }
 
+#[rstest]
+#[tokio::test]
+async fn health_reports_storage_height() {
+    let batcher = create_batcher(MockDependencies::default());
+    assert_eq!(
+        batcher.health().check_health().await,
+        HealthStatus::Healthy { details: format!("Storage height: {INITIAL_HEIGHT}.") }
+    );
+
+    let mut storage_reader = MockBatcherStorageReaderTrait::new();
+    storage_reader
+        .expect_height()
+        .returning(|| Err(StorageError::DBInconsistency { msg: "inconsistent".to_owned() }));
+    let batcher = create_batcher(MockDependencies { storage_reader, ..Default::default() });
+    assert_matches!(batcher.health().check_health().await, HealthStatus::Unhealthy { .. });
+}
+
+#[rstest]
+#[tokio::test]
+async fn health_reports_state_sync_lag() {
+    let mut batcher = create_batcher(MockDependencies::default());
+    let max_state_sync_lag = batcher.config.max_state_sync_lag;
+
+    // Consensus is ahead of the storage, but within the allowed lag.
+    let height = INITIAL_HEIGHT.unchecked_next();
+    assert_matches!(
+        batcher.start_height(StartHeightInput { height }).await,
+        Err(BatcherError::StorageNotSynced { .. })
+    );
+    assert_matches!(batcher.health().check_health().await, HealthStatus::Healthy { .. });
+
+    let height = BlockNumber(INITIAL_HEIGHT.0 + max_state_sync_lag + 1);
+    assert_matches!(
+        batcher.start_height(StartHeightInput { height }).await,
+        Err(BatcherError::StorageNotSynced { .. })
+    );
+    assert_matches!(batcher.health().check_health().await, HealthStatus::Unhealthy { .. });
+}
+
+#[rstest]
+fn get_account_nonces_from_storage() {
+    let mut storage_reader = MockBatcherStorageReaderTrait::new();
+    storage_reader.expect_get_nonce().returning(|address| Ok(test_contract_nonces()[&address]));
+    let batcher = create_batcher(MockDependencies { storage_reader, ..Default::default() });
+
+    let addresses = test_contract_nonces().into_keys().collect();
+    let response = batcher.get_account_nonces(GetAccountNoncesInput { addresses });
+    assert_eq!(response, Ok(GetAccountNoncesResponse { address_to_nonce: test_contract_nonces() }));
+}
+
 #[rstest]
 #[tokio::test]
 async fn get_content_from_unknown_proposal() {
@@ -500,6 +599,73 @@ async fn get_content_from_unknown_proposal() {
     assert_eq!(result, Err(BatcherError::ProposalNotFound { proposal_id: PROPOSAL_ID }));
 }
 
+#[rstest]
+#[tokio::test]
+async fn abort_propose_proposal_rewinds_mempool() {
+    let block_builder_factory = mock_create_builder_for_propose_block(vec![]);
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager.expect_wrap_reset().times(1).return_once(|| async {}.boxed());
+    proposal_manager
+        .expect_wrap_spawn_proposal()
+        .times(1)
+        .return_once(|_, _, _| { async move { Ok(()) } }.boxed());
+    proposal_manager
+        .expect_wrap_get_proposal_status()
+        .times(1)
+        .with(eq(PROPOSAL_ID))
+        .returning(move |_| async move { InternalProposalStatus::Processing }.boxed());
+    proposal_manager
+        .expect_wrap_abort_proposal()
+        .times(1)
+        .with(eq(PROPOSAL_ID))
+        .return_once(|_| async {}.boxed());
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_rewind().times(1).returning(|| Ok(()));
+
+    let mut batcher = create_batcher(MockDependencies {
+        proposal_manager,
+        mempool_client,
+        block_builder_factory,
+        ..Default::default()
+    });
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    batcher
+        .propose_block(ProposeBlockInput {
+            proposal_id: PROPOSAL_ID,
+            retrospective_block_hash: None,
+            deadline: deadline(),
+            block_info: BlockInfo { block_number: INITIAL_HEIGHT, ..Default::default() },
+        })
+        .await
+        .unwrap();
+
+    let send_abort_input =
+        SendProposalContentInput { proposal_id: PROPOSAL_ID, content: SendProposalContent::Abort };
+    let result = batcher.send_proposal_content(send_abort_input).await;
+    assert_eq!(result, Ok(SendProposalContentResponse { response: ProposalStatus::Aborted }));
+}
+
+#[rstest]
+#[tokio::test]
+async fn abort_validate_proposal_does_not_rewind_mempool() {
+    let mut proposal_manager = MockProposalManagerTraitWrapper::new();
+    proposal_manager
+        .expect_wrap_abort_proposal()
+        .times(1)
+        .with(eq(PROPOSAL_ID))
+        .return_once(|_| async {}.boxed());
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_rewind().times(0);
+
+    let mut batcher =
+        create_batcher(MockDependencies { proposal_manager, mempool_client, ..Default::default() });
+
+    let send_abort_input =
+        SendProposalContentInput { proposal_id: PROPOSAL_ID, content: SendProposalContent::Abort };
+    let result = batcher.send_proposal_content(send_abort_input).await;
+    assert_eq!(result, Ok(SendProposalContentResponse { response: ProposalStatus::Aborted }));
+}
+
 #[rstest]
 #[tokio::test]
 async fn decision_reached() {
@@ -27,13 +27,7 @@ use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use papyrus_state_reader::papyrus_state::PapyrusReader;
 use papyrus_storage::StorageReader;
 use serde::{Deserialize, Serialize};
-use starknet_api::block::{
-    BlockHashAndNumber,
-    BlockInfo,
-    BlockNumber,
-    BlockTimestamp,
-    NonzeroGasPrice,
-};
+use starknet_api::block::{BlockHashAndNumber, BlockInfo, BlockNumber, BlockTimestamp, GasPrices};
 use starknet_api::core::ContractAddress;
 use starknet_api::executable_transaction::Transaction;
 use starknet_api::transaction::TransactionHash;
@@ -240,6 +234,8 @@ async fn collect_execution_results_and_stream_txs(
 pub struct BlockMetadata {
     pub height: BlockNumber,
     pub retrospective_block_hash: Option<BlockHashAndNumber>,
+    /// The gas prices agreed on in consensus, derived from L1 gas prices.
+    pub gas_prices: GasPrices,
 }
 
 // Type definitions for the abort channel required to abort the block builder.
@@ -330,10 +326,17 @@ impl BlockBuilderFactory {
             block_number: block_metadata.height,
             block_timestamp: BlockTimestamp(chrono::Utc::now().timestamp().try_into()?),
             sequencer_address: block_builder_config.sequencer_address,
-            // TODO (yael 7/10/2024): add logic to compute gas prices
             gas_prices: {
-                let tmp_val = NonzeroGasPrice::MIN;
-                validated_gas_prices(tmp_val, tmp_val, tmp_val, tmp_val, tmp_val, tmp_val)
+                let GasPrices { eth_gas_prices: eth, strk_gas_prices: strk } =
+                    &block_metadata.gas_prices;
+                validated_gas_prices(
+                    eth.l1_gas_price,
+                    strk.l1_gas_price,
+                    eth.l1_data_gas_price,
+                    strk.l1_data_gas_price,
+                    eth.l2_gas_price,
+                    strk.l2_gas_price,
+                )
             },
             use_kzg_da: block_builder_config.use_kzg_da,
         };
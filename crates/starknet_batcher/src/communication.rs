@@ -30,6 +30,9 @@ impl ComponentRequestHandler<BatcherRequest, BatcherResponse> for Batcher {
             BatcherRequest::SendProposalContent(input) => {
                 BatcherResponse::SendProposalContent(self.send_proposal_content(input).await)
             }
+            BatcherRequest::GetAccountNonces(input) => {
+                BatcherResponse::GetAccountNonces(self.get_account_nonces(input))
+            }
         }
     }
 }
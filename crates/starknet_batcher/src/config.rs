@@ -16,6 +16,7 @@ pub struct BatcherConfig {
     pub block_builder_config: BlockBuilderConfig,
     pub global_contract_cache_size: usize,
     pub max_l1_handler_txs_per_block_proposal: usize,
+    pub max_state_sync_lag: u64,
 }
 
 impl SerializeConfig for BatcherConfig {
@@ -48,6 +49,13 @@ impl SerializeConfig for BatcherConfig {
                 "The maximum number of L1 handler transactions to include in a block proposal.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "max_state_sync_lag",
+                &self.max_state_sync_lag,
+                "The maximum number of blocks the storage may lag behind the height requested by \
+                 consensus before the batcher is reported as unhealthy.",
+                ParamPrivacyInput::Public,
+            ),
         ]);
         dump.append(&mut append_sub_config_name(self.storage.dump(), "storage"));
         dump.append(&mut append_sub_config_name(
@@ -77,6 +85,7 @@ impl Default for BatcherConfig {
             block_builder_config: BlockBuilderConfig::default(),
             global_contract_cache_size: 400,
             max_l1_handler_txs_per_block_proposal: 3,
+            max_state_sync_lag: 10,
         }
     }
 }
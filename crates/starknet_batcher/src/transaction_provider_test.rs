@@ -5,11 +5,14 @@ use mockall::predicate::eq;
 use rstest::{fixture, rstest};
 use starknet_api::executable_transaction::{AccountTransaction, L1HandlerTransaction, Transaction};
 use starknet_api::test_utils::invoke::{executable_invoke_tx, InvokeTxArgs};
+use starknet_api::transaction::TransactionHash;
 use starknet_api::tx_hash;
+use starknet_l1_provider_types::communication::{L1ProviderClientError, MockL1ProviderClient};
+use starknet_l1_provider_types::errors::L1ProviderError;
+use starknet_l1_provider_types::l1_provider_types::ValidationStatus;
 use starknet_mempool_types::communication::MockMempoolClient;
 
 use crate::transaction_provider::{
-    MockL1ProviderClient,
     NextTxs,
     ProposeTransactionProvider,
     TransactionProvider,
@@ -33,7 +36,7 @@ impl MockDependencies {
         self.l1_provider_client
             .expect_get_txs()
             .with(eq(n_to_request))
-            .returning(move |_| vec![L1HandlerTransaction::default(); n_to_return]);
+            .returning(move |_| Ok(vec![L1HandlerTransaction::default(); n_to_return]));
     }
 
     fn expect_get_mempool_txs(&mut self, n_to_request: usize) {
@@ -45,11 +48,8 @@ impl MockDependencies {
         });
     }
 
-    fn expect_validate_l1handler(&mut self, tx: L1HandlerTransaction, result: bool) {
-        self.l1_provider_client
-            .expect_validate()
-            .withf(move |tx_arg| tx_arg == &tx)
-            .returning(move |_| result);
+    fn expect_validate_l1handler(&mut self, tx_hash: TransactionHash, result: ValidationStatus) {
+        self.l1_provider_client.expect_validate().with(eq(tx_hash)).returning(move |_| Ok(result));
     }
 
     async fn simulate_input_txs(&mut self, txs: Vec<Transaction>) {
@@ -162,11 +162,26 @@ async fn no_more_l1_handler(mut mock_dependencies: MockDependencies) {
     assert!(data.iter().all(|tx| matches!(tx, Transaction::Account(_))));
 }
 
+#[rstest]
+#[tokio::test]
+async fn l1_provider_error_falls_back_to_mempool(mut mock_dependencies: MockDependencies) {
+    mock_dependencies.l1_provider_client.expect_get_txs().times(1).returning(|_| {
+        Err(L1ProviderClientError::L1ProviderError(L1ProviderError::GetTransactionsInPendingState))
+    });
+    mock_dependencies.expect_get_mempool_txs(MAX_TXS_PER_FETCH);
+
+    let mut tx_provider = mock_dependencies.propose_tx_provider();
+
+    let txs = tx_provider.get_txs(MAX_TXS_PER_FETCH).await.unwrap();
+    let data = assert_matches!(txs, NextTxs::Txs(txs) if txs.len() == MAX_TXS_PER_FETCH => txs);
+    assert!(data.iter().all(|tx| matches!(tx, Transaction::Account(_))));
+}
+
 #[rstest]
 #[tokio::test]
 async fn validate_flow(mut mock_dependencies: MockDependencies) {
     let test_tx = test_l1handler_tx();
-    mock_dependencies.expect_validate_l1handler(test_tx.clone(), true);
+    mock_dependencies.expect_validate_l1handler(test_tx.tx_hash, ValidationStatus::Validated);
     mock_dependencies
         .simulate_input_txs(vec![
             Transaction::L1Handler(test_tx),
@@ -188,7 +203,8 @@ async fn validate_flow(mut mock_dependencies: MockDependencies) {
 #[tokio::test]
 async fn validate_fails(mut mock_dependencies: MockDependencies) {
     let test_tx = test_l1handler_tx();
-    mock_dependencies.expect_validate_l1handler(test_tx.clone(), false);
+    mock_dependencies
+        .expect_validate_l1handler(test_tx.tx_hash, ValidationStatus::AlreadyIncludedOnL2);
     mock_dependencies
         .simulate_input_txs(vec![
             Transaction::L1Handler(test_tx),
@@ -1,9 +1,10 @@
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockHashAndNumber, BlockInfo, BlockNumber};
-use starknet_api::core::StateDiffCommitment;
+use starknet_api::core::{ContractAddress, Nonce, StateDiffCommitment};
 use starknet_api::executable_transaction::Transaction;
 
 use crate::errors::BatcherError;
@@ -105,4 +106,14 @@ pub struct DecisionReachedInput {
     pub proposal_id: ProposalId,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetAccountNoncesInput {
+    pub addresses: Vec<ContractAddress>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GetAccountNoncesResponse {
+    pub address_to_nonce: HashMap<ContractAddress, Nonce>,
+}
+
 pub type BatcherResult<T> = Result<T, BatcherError>;
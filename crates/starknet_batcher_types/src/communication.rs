@@ -12,6 +12,7 @@ use starknet_sequencer_infra::component_client::{
 };
 use starknet_sequencer_infra::component_definitions::{
     ComponentClient,
+    ComponentRequest,
     ComponentRequestAndResponseSender,
 };
 use thiserror::Error;
@@ -19,6 +20,8 @@ use thiserror::Error;
 use crate::batcher_types::{
     BatcherResult,
     DecisionReachedInput,
+    GetAccountNoncesInput,
+    GetAccountNoncesResponse,
     GetProposalContentInput,
     GetProposalContentResponse,
     ProposeBlockInput,
@@ -29,6 +32,10 @@ use crate::batcher_types::{
 };
 use crate::errors::BatcherError;
 
+#[cfg(test)]
+#[path = "communication_test.rs"]
+mod communication_test;
+
 pub type LocalBatcherClient = LocalComponentClient<BatcherRequest, BatcherResponse>;
 pub type RemoteBatcherClient = RemoteComponentClient<BatcherRequest, BatcherResponse>;
 pub type BatcherClientResult<T> = Result<T, BatcherClientError>;
@@ -68,6 +75,11 @@ pub trait BatcherClient: Send + Sync {
     /// Notifies the batcher that a decision has been reached.
     /// This closes the process of the given height, and the accepted proposal is committed.
     async fn decision_reached(&self, input: DecisionReachedInput) -> BatcherClientResult<()>;
+    /// Returns the nonces of the given accounts in the state after the last committed block.
+    async fn get_account_nonces(
+        &self,
+        input: GetAccountNoncesInput,
+    ) -> BatcherClientResult<GetAccountNoncesResponse>;
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -78,6 +90,13 @@ pub enum BatcherRequest {
     SendProposalContent(SendProposalContentInput),
     StartHeight(StartHeightInput),
     DecisionReached(DecisionReachedInput),
+    GetAccountNonces(GetAccountNoncesInput),
+}
+
+impl ComponentRequest for BatcherRequest {
+    fn is_idempotent(&self) -> bool {
+        matches!(self, BatcherRequest::GetAccountNonces(_))
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -88,6 +107,7 @@ pub enum BatcherResponse {
     SendProposalContent(BatcherResult<SendProposalContentResponse>),
     StartHeight(BatcherResult<()>),
     DecisionReached(BatcherResult<()>),
+    GetAccountNonces(BatcherResult<GetAccountNoncesResponse>),
 }
 
 #[derive(Clone, Debug, Error)]
@@ -159,4 +179,18 @@ where
             BatcherError
         )
     }
+
+    async fn get_account_nonces(
+        &self,
+        input: GetAccountNoncesInput,
+    ) -> BatcherClientResult<GetAccountNoncesResponse> {
+        let request = BatcherRequest::GetAccountNonces(input);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            BatcherResponse,
+            GetAccountNonces,
+            BatcherClientError,
+            BatcherError
+        )
+    }
 }
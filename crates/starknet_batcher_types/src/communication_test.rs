@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use rstest::rstest;
+use starknet_api::block::{BlockHash, BlockHashAndNumber, BlockInfo, BlockNumber};
+use starknet_api::executable_transaction::{AccountTransaction, Transaction};
+use starknet_api::test_utils::invoke::{executable_invoke_tx, InvokeTxArgs};
+use starknet_api::{contract_address, nonce};
+use starknet_sequencer_infra::codec::Codec;
+use starknet_sequencer_infra::test_utils::assert_round_trip_with_codecs;
+use starknet_types_core::felt::Felt;
+
+use crate::batcher_types::{
+    DecisionReachedInput,
+    GetAccountNoncesInput,
+    GetAccountNoncesResponse,
+    GetProposalContent,
+    GetProposalContentInput,
+    GetProposalContentResponse,
+    ProposalCommitment,
+    ProposalId,
+    ProposalStatus,
+    ProposeBlockInput,
+    SendProposalContent,
+    SendProposalContentInput,
+    SendProposalContentResponse,
+    StartHeightInput,
+    ValidateBlockInput,
+};
+use crate::communication::{BatcherRequest, BatcherResponse};
+use crate::errors::BatcherError;
+
+fn txs() -> Vec<Transaction> {
+    vec![Transaction::Account(AccountTransaction::Invoke(executable_invoke_tx(
+        InvokeTxArgs::default(),
+    )))]
+}
+
+fn retrospective_block_hash() -> Option<BlockHashAndNumber> {
+    Some(BlockHashAndNumber { hash: BlockHash(Felt::ONE), number: BlockNumber(1) })
+}
+
+#[rstest]
+#[case::propose_block(BatcherRequest::ProposeBlock(ProposeBlockInput {
+    proposal_id: ProposalId(1),
+    deadline: DateTime::from_timestamp(1, 2).unwrap(),
+    retrospective_block_hash: retrospective_block_hash(),
+    block_info: BlockInfo::default(),
+}))]
+#[case::get_proposal_content(BatcherRequest::GetProposalContent(GetProposalContentInput {
+    proposal_id: ProposalId(1),
+}))]
+#[case::validate_block(BatcherRequest::ValidateBlock(ValidateBlockInput {
+    proposal_id: ProposalId(1),
+    deadline: DateTime::from_timestamp(1, 2).unwrap(),
+    retrospective_block_hash: None,
+    block_info: BlockInfo::default(),
+}))]
+#[case::send_txs(BatcherRequest::SendProposalContent(SendProposalContentInput {
+    proposal_id: ProposalId(1),
+    content: SendProposalContent::Txs(txs()),
+}))]
+#[case::send_finish(BatcherRequest::SendProposalContent(SendProposalContentInput {
+    proposal_id: ProposalId(1),
+    content: SendProposalContent::Finish,
+}))]
+#[case::start_height(BatcherRequest::StartHeight(StartHeightInput { height: BlockNumber(1) }))]
+#[case::decision_reached(BatcherRequest::DecisionReached(DecisionReachedInput {
+    proposal_id: ProposalId(1),
+}))]
+#[case::get_account_nonces(BatcherRequest::GetAccountNonces(GetAccountNoncesInput {
+    addresses: vec![contract_address!("0x1")],
+}))]
+fn test_request_round_trip(#[case] request: BatcherRequest) {
+    // Bincode doesn't decode the data availability modes of transactions.
+    assert_round_trip_with_codecs(&request, &Codec::SELF_DESCRIBING);
+}
+
+#[rstest]
+#[case::propose_block(BatcherResponse::ProposeBlock(Err(BatcherError::TimeToDeadlineError {
+    deadline: DateTime::from_timestamp(1, 2).unwrap(),
+})))]
+#[case::get_txs(BatcherResponse::GetProposalContent(Ok(GetProposalContentResponse {
+    content: GetProposalContent::Txs(txs()),
+})))]
+#[case::get_finished(BatcherResponse::GetProposalContent(Ok(GetProposalContentResponse {
+    content: GetProposalContent::Finished(ProposalCommitment::default()),
+})))]
+#[case::validate_block(BatcherResponse::ValidateBlock(Err(BatcherError::ServerBusy {
+    active_proposal_id: ProposalId(1),
+    new_proposal_id: ProposalId(2),
+})))]
+#[case::send_proposal_content(BatcherResponse::SendProposalContent(Ok(
+    SendProposalContentResponse { response: ProposalStatus::Finished(ProposalCommitment::default()) }
+)))]
+#[case::start_height(BatcherResponse::StartHeight(Err(BatcherError::HeightAlreadyPassed {
+    storage_height: BlockNumber(2),
+    requested_height: BlockNumber(1),
+})))]
+#[case::decision_reached(BatcherResponse::DecisionReached(Ok(())))]
+#[case::get_account_nonces(BatcherResponse::GetAccountNonces(Ok(GetAccountNoncesResponse {
+    address_to_nonce: HashMap::from([(contract_address!("0x1"), nonce!(2))]),
+})))]
+fn test_response_round_trip(#[case] response: BatcherResponse) {
+    assert_round_trip_with_codecs(&response, &Codec::SELF_DESCRIBING);
+}
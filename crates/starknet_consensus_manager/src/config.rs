@@ -1,23 +1,46 @@
 use std::collections::BTreeMap;
+use std::time::Duration;
 
-use papyrus_config::dumping::{append_sub_config_name, SerializeConfig};
-use papyrus_config::{ParamPath, SerializedParam};
+use papyrus_config::converters::deserialize_seconds_to_duration;
+use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use papyrus_consensus::config::ConsensusConfig;
+use papyrus_consensus_orchestrator::config::ContextConfig;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
 /// The consensus manager related configuration.
-/// TODO(Matan): Remove ConsensusManagerConfig if it's only field remains ConsensusConfig.
-#[derive(Clone, Default, Debug, Serialize, Deserialize, Validate, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
 pub struct ConsensusManagerConfig {
+    #[validate]
     pub consensus_config: ConsensusConfig,
+    #[validate]
+    pub context_config: ContextConfig,
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub max_height_duration: Duration,
 }
 
 impl SerializeConfig for ConsensusManagerConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        let sub_configs =
-            vec![append_sub_config_name(self.consensus_config.dump(), "consensus_config")];
+        let mut dump = BTreeMap::from([ser_param(
+            "max_height_duration",
+            &self.max_height_duration.as_secs(),
+            "The time in seconds consensus may work on a single height before it is reported as \
+             unhealthy.",
+            ParamPrivacyInput::Public,
+        )]);
+        dump.append(&mut append_sub_config_name(self.consensus_config.dump(), "consensus_config"));
+        dump.append(&mut append_sub_config_name(self.context_config.dump(), "context_config"));
+        dump
+    }
+}
 
-        sub_configs.into_iter().flatten().collect()
+impl Default for ConsensusManagerConfig {
+    fn default() -> Self {
+        Self {
+            consensus_config: ConsensusConfig::default(),
+            context_config: ContextConfig::default(),
+            max_height_duration: Duration::from_secs(300),
+        }
     }
 }
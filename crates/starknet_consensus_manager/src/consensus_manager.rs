@@ -1,17 +1,28 @@
 use std::any::type_name;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use futures::StreamExt;
+use papyrus_consensus::signing::create_signer_and_verifier;
 use papyrus_consensus::stream_handler::StreamHandler;
 use papyrus_consensus::types::ConsensusError;
+use papyrus_consensus::validator_set::EpochValidatorSets;
+use papyrus_consensus::wal::open_wal;
 use papyrus_consensus_orchestrator::sequencer_consensus_context::SequencerConsensusContext;
 use papyrus_network::gossipsub_impl::Topic;
 use papyrus_network::network_manager::{BroadcastTopicChannels, NetworkManager};
 use papyrus_protobuf::consensus::{ConsensusMessage, ProposalPart, StreamMessage};
+use starknet_api::block::BlockNumber;
 use starknet_batcher_types::communication::SharedBatcherClient;
-use starknet_sequencer_infra::component_definitions::ComponentStarter;
+use starknet_l1_gas_price_types::communication::SharedL1GasPriceProviderClient;
+use starknet_sequencer_infra::component_definitions::{
+    ComponentHealth,
+    ComponentStarter,
+    HealthStatus,
+};
 use starknet_sequencer_infra::errors::ComponentError;
+use tokio::sync::watch;
 use tracing::{error, info};
 
 use crate::config::ConsensusManagerConfig;
@@ -27,11 +38,23 @@ pub const NETWORK_TOPIC2: &str = "streamed_consensus_proposals";
 pub struct ConsensusManager {
     pub config: ConsensusManagerConfig,
     pub batcher_client: SharedBatcherClient,
+    pub l1_gas_price_provider_client: SharedL1GasPriceProviderClient,
+    health: ConsensusHealth,
 }
 
 impl ConsensusManager {
-    pub fn new(config: ConsensusManagerConfig, batcher_client: SharedBatcherClient) -> Self {
-        Self { config, batcher_client }
+    pub fn new(
+        config: ConsensusManagerConfig,
+        batcher_client: SharedBatcherClient,
+        l1_gas_price_provider_client: SharedL1GasPriceProviderClient,
+    ) -> Self {
+        let health = ConsensusHealth::new(config.max_height_duration);
+        Self { config, batcher_client, l1_gas_price_provider_client, health }
+    }
+
+    /// Returns a handle that reports the height of consensus to the monitoring endpoint.
+    pub fn health(&self) -> ConsensusHealth {
+        self.health.clone()
     }
 
     pub async fn run(&self) -> Result<(), ConsensusError> {
@@ -68,14 +91,21 @@ impl ConsensusManager {
         let (outbound_internal_sender, inbound_internal_receiver, mut stream_handler_task_handle) =
             StreamHandler::get_channels(inbound_network_receiver, outbound_network_sender);
 
+        let (signer, signature_verifier) =
+            create_signer_and_verifier(&self.config.consensus_config)?;
+        let wal = open_wal(self.config.consensus_config.wal_dir.as_deref())?;
         let context = SequencerConsensusContext::new(
+            self.config.context_config.clone(),
             Arc::clone(&self.batcher_client),
+            Arc::clone(&self.l1_gas_price_provider_client),
             old_proposals_broadcast_channels.broadcast_topic_client.clone(),
             outbound_internal_sender,
             votes_broadcast_channels.broadcast_topic_client.clone(),
-            self.config.consensus_config.num_validators,
+            EpochValidatorSets::from_config(&self.config.consensus_config),
+            signer.clone(),
         );
 
+        let height_receiver = context.subscribe_to_height();
         let mut network_handle = tokio::task::spawn(network_manager.run());
         let consensus_task = papyrus_consensus::run_consensus(
             context,
@@ -85,6 +115,10 @@ impl ConsensusManager {
             self.config.consensus_config.validator_id,
             self.config.consensus_config.consensus_delay,
             self.config.consensus_config.timeouts.clone(),
+            self.config.consensus_config.future_messages.clone(),
+            signer,
+            signature_verifier,
+            wal,
             votes_broadcast_channels.into(),
             inbound_internal_receiver,
             futures::stream::pending(),
@@ -103,6 +137,9 @@ impl ConsensusManager {
             stream_handler_result = &mut stream_handler_task_handle => {
                 panic!("Consensus' stream handler task finished unexpectedly: {:?}", stream_handler_result);
             }
+            _ = track_height(height_receiver, self.health.clone()) => {
+                unreachable!("Tracking the consensus height never returns.");
+            }
             _ = async {
                 while let Some(_broadcasted_message) =
                     old_proposals_broadcast_channels.broadcasted_messages_receiver.next().await
@@ -116,11 +153,71 @@ impl ConsensusManager {
     }
 }
 
+// Records the heights consensus works on in its health. Never returns.
+async fn track_height(
+    mut height_receiver: watch::Receiver<Option<BlockNumber>>,
+    health: ConsensusHealth,
+) {
+    while height_receiver.changed().await.is_ok() {
+        if let Some(height) = *height_receiver.borrow_and_update() {
+            health.set_height(height, Instant::now());
+        }
+    }
+    // The context was dropped, so consensus is over and its result is reported instead.
+    std::future::pending().await
+}
+
+/// The height consensus is working on, shared with the monitoring endpoint. Consensus is unhealthy
+/// if it works on a single height for longer than `max_height_duration`, e.g., when it can't reach
+/// a quorum.
+#[derive(Clone, Debug)]
+pub struct ConsensusHealth {
+    max_height_duration: Duration,
+    // The current height, and when consensus started working on it.
+    current_height: Arc<Mutex<Option<(BlockNumber, Instant)>>>,
+}
+
+impl ConsensusHealth {
+    pub fn new(max_height_duration: Duration) -> Self {
+        Self { max_height_duration, current_height: Arc::new(Mutex::new(None)) }
+    }
+
+    pub fn set_height(&self, height: BlockNumber, now: Instant) {
+        *self.current_height.lock().expect("Lock should not be poisoned.") = Some((height, now));
+    }
+
+    pub fn health_status(&self, now: Instant) -> HealthStatus {
+        let current_height = *self.current_height.lock().expect("Lock should not be poisoned.");
+        let Some((height, started_at)) = current_height else {
+            return HealthStatus::Healthy { details: "Consensus hasn't started yet.".to_owned() };
+        };
+
+        let height_duration = now.saturating_duration_since(started_at);
+        if height_duration > self.max_height_duration {
+            return HealthStatus::Unhealthy {
+                reason: format!(
+                    "Consensus is stuck at height {height} for {} seconds.",
+                    height_duration.as_secs()
+                ),
+            };
+        }
+        HealthStatus::Healthy { details: format!("Consensus height: {height}.") }
+    }
+}
+
+#[async_trait]
+impl ComponentHealth for ConsensusHealth {
+    async fn check_health(&self) -> HealthStatus {
+        self.health_status(Instant::now())
+    }
+}
+
 pub fn create_consensus_manager(
     config: ConsensusManagerConfig,
     batcher_client: SharedBatcherClient,
+    l1_gas_price_provider_client: SharedL1GasPriceProviderClient,
 ) -> ConsensusManager {
-    ConsensusManager::new(config, batcher_client)
+    ConsensusManager::new(config, batcher_client, l1_gas_price_provider_client)
 }
 
 #[async_trait]
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use blockifier::execution::contract_class::RunnableCompiledClass;
+use blockifier::state::errors::StateError;
+use blockifier::state::global_cache::GlobalContractCache;
+use blockifier::state::state_api::{StateReader as BlockifierStateReader, StateResult};
+use starknet_api::block::{BlockInfo, BlockNumber};
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_types_core::felt::Felt;
+
+use crate::config::StateReaderCacheConfig;
+use crate::state_reader::{MempoolStateReader, StateReaderFactory};
+
+#[cfg(test)]
+#[path = "cached_state_reader_test.rs"]
+mod cached_state_reader_test;
+
+/// Values read from the state of a single block.
+///
+/// The state of a committed block never changes, so the entries stay valid until a newer block is
+/// observed, at which point the whole cache is dropped.
+#[derive(Debug, Default)]
+struct BlockStateCache {
+    latest_block_info: Option<(BlockInfo, Instant)>,
+    block_number: Option<BlockNumber>,
+    storage: HashMap<(ContractAddress, StorageKey), Felt>,
+    nonces: HashMap<ContractAddress, Nonce>,
+    class_hashes: HashMap<ContractAddress, ClassHash>,
+    compiled_class_hashes: HashMap<ClassHash, CompiledClassHash>,
+}
+
+impl BlockStateCache {
+    /// Moves the cache to `block_number` if it is newer than the cached block, dropping all the
+    /// entries of the previous block.
+    fn advance_to(&mut self, block_number: BlockNumber) {
+        if self.block_number.is_some_and(|cached| cached >= block_number) {
+            return;
+        }
+        *self = Self { latest_block_info: self.latest_block_info.take(), ..Self::default() };
+        self.block_number = Some(block_number);
+    }
+
+    /// Returns whether reads of `block_number` may be served from, and stored in, the cache.
+    fn is_cacheable(&mut self, block_number: BlockNumber) -> bool {
+        self.advance_to(block_number);
+        self.block_number == Some(block_number)
+    }
+}
+
+/// A [`StateReaderFactory`] that caches, across transactions, the latest block info and the
+/// state read from it.
+///
+/// The latest block info is re-fetched once it is older than the configured TTL, or as soon as the
+/// underlying factory reports a newer latest block; when it reports a new block, the nonces, class
+/// hashes and storage cached for the previous block are dropped.
+/// Compiled classes are immutable and are kept in an LRU cache shared by all blocks.
+#[derive(Clone)]
+pub struct CachedStateReaderFactory {
+    state_reader_factory: Arc<dyn StateReaderFactory>,
+    block_info_ttl: Duration,
+    block_state: Arc<Mutex<BlockStateCache>>,
+    compiled_classes: GlobalContractCache<RunnableCompiledClass>,
+}
+
+impl CachedStateReaderFactory {
+    pub fn new(
+        config: StateReaderCacheConfig,
+        state_reader_factory: Arc<dyn StateReaderFactory>,
+    ) -> Self {
+        Self {
+            state_reader_factory,
+            block_info_ttl: Duration::from_millis(config.block_info_ttl_ms),
+            block_state: Arc::new(Mutex::new(BlockStateCache::default())),
+            compiled_classes: GlobalContractCache::new(config.compiled_class_cache_size),
+        }
+    }
+
+    /// Invalidates the cached latest block info if the underlying factory reports that a newer
+    /// block was committed since it was fetched.
+    fn invalidate_if_outdated(&self) -> StateResult<()> {
+        let Some(latest_block_number) = self.state_reader_factory.latest_block_number()? else {
+            return Ok(());
+        };
+        let mut block_state = self.lock_block_state();
+        let is_outdated = block_state
+            .latest_block_info
+            .as_ref()
+            .is_some_and(|(block_info, _)| block_info.block_number < latest_block_number);
+        if is_outdated {
+            block_state.latest_block_info = None;
+        }
+        Ok(())
+    }
+
+    fn lock_block_state(&self) -> MutexGuard<'_, BlockStateCache> {
+        self.block_state.lock().expect("Block state cache is poisoned.")
+    }
+
+    fn create_reader(
+        &self,
+        state_reader: Box<dyn MempoolStateReader>,
+        block_number: Option<BlockNumber>,
+    ) -> Box<dyn MempoolStateReader> {
+        Box::new(CachedStateReader { state_reader, block_number, cache: self.clone() })
+    }
+}
+
+impl StateReaderFactory for CachedStateReaderFactory {
+    fn get_state_reader_from_latest_block(&self) -> Box<dyn MempoolStateReader> {
+        self.create_reader(self.state_reader_factory.get_state_reader_from_latest_block(), None)
+    }
+
+    fn get_state_reader(&self, block_number: BlockNumber) -> Box<dyn MempoolStateReader> {
+        self.create_reader(
+            self.state_reader_factory.get_state_reader(block_number),
+            Some(block_number),
+        )
+    }
+}
+
+/// A state reader that serves its reads from the cache of a [`CachedStateReaderFactory`].
+///
+/// Readers of the latest block only cache the block info, as the block they read from may change
+/// between calls.
+struct CachedStateReader {
+    state_reader: Box<dyn MempoolStateReader>,
+    block_number: Option<BlockNumber>,
+    cache: CachedStateReaderFactory,
+}
+
+impl CachedStateReader {
+    /// Returns the cached value of `key`, or reads it from the underlying state reader and caches
+    /// it, if this reader's block is the cached one.
+    fn get_or_read<K, V>(
+        &self,
+        entries: impl Fn(&mut BlockStateCache) -> &mut HashMap<K, V>,
+        key: K,
+        read: impl FnOnce(&dyn MempoolStateReader) -> StateResult<V>,
+    ) -> StateResult<V>
+    where
+        K: Eq + std::hash::Hash,
+        V: Clone,
+    {
+        let Some(block_number) = self.block_number else {
+            return read(self.state_reader.as_ref());
+        };
+
+        {
+            let mut block_state = self.cache.lock_block_state();
+            if !block_state.is_cacheable(block_number) {
+                drop(block_state);
+                return read(self.state_reader.as_ref());
+            }
+            if let Some(value) = entries(&mut block_state).get(&key) {
+                return Ok(value.clone());
+            }
+        }
+
+        // The lock is not held while reading, so concurrent misses may read the same value twice.
+        let value = read(self.state_reader.as_ref())?;
+        let mut block_state = self.cache.lock_block_state();
+        if block_state.block_number == Some(block_number) {
+            entries(&mut block_state).insert(key, value.clone());
+        }
+        Ok(value)
+    }
+}
+
+impl MempoolStateReader for CachedStateReader {
+    fn get_block_info(&self) -> Result<BlockInfo, StateError> {
+        if self.block_number.is_some() {
+            return self.state_reader.get_block_info();
+        }
+
+        self.cache.invalidate_if_outdated()?;
+        if let Some((block_info, fetched_at)) = &self.cache.lock_block_state().latest_block_info {
+            if fetched_at.elapsed() < self.cache.block_info_ttl {
+                return Ok(block_info.clone());
+            }
+        }
+
+        let block_info = self.state_reader.get_block_info()?;
+        let mut block_state = self.cache.lock_block_state();
+        block_state.advance_to(block_info.block_number);
+        block_state.latest_block_info = Some((block_info.clone(), Instant::now()));
+        Ok(block_info)
+    }
+}
+
+impl BlockifierStateReader for CachedStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        self.get_or_read(
+            |block_state| &mut block_state.storage,
+            (contract_address, key),
+            |state_reader| state_reader.get_storage_at(contract_address, key),
+        )
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        self.get_or_read(
+            |block_state| &mut block_state.nonces,
+            contract_address,
+            |state_reader| state_reader.get_nonce_at(contract_address),
+        )
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        self.get_or_read(
+            |block_state| &mut block_state.class_hashes,
+            contract_address,
+            |state_reader| state_reader.get_class_hash_at(contract_address),
+        )
+    }
+
+    fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        if let Some(compiled_class) = self.cache.compiled_classes.get(&class_hash) {
+            return Ok(compiled_class);
+        }
+
+        let compiled_class = self.state_reader.get_compiled_class(class_hash)?;
+        self.cache.compiled_classes.set(class_hash, compiled_class.clone());
+        Ok(compiled_class)
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        self.get_or_read(
+            |block_state| &mut block_state.compiled_class_hashes,
+            class_hash,
+            |state_reader| state_reader.get_compiled_class_hash(class_hash),
+        )
+    }
+}
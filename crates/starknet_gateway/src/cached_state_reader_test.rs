@@ -0,0 +1,174 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use blockifier::execution::contract_class::RunnableCompiledClass;
+use blockifier::state::errors::StateError;
+use blockifier::state::state_api::{StateReader as BlockifierStateReader, StateResult};
+use blockifier::test_utils::contracts::FeatureContract;
+use blockifier::test_utils::struct_impls::BlockInfoExt;
+use blockifier::test_utils::CairoVersion;
+use rstest::{fixture, rstest};
+use starknet_api::block::{BlockInfo, BlockNumber};
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_api::{class_hash, contract_address};
+use starknet_types_core::felt::Felt;
+
+use crate::cached_state_reader::CachedStateReaderFactory;
+use crate::config::StateReaderCacheConfig;
+use crate::state_reader::{MempoolStateReader, StateReaderFactory};
+
+/// A state reader that counts the reads reaching it. All the readers it creates share the counters
+/// and the latest block number, which, as a factory, it reports only if `reports_latest_block`.
+#[derive(Clone, Default)]
+struct CountingStateReader {
+    reports_latest_block: bool,
+    latest_block_number: Arc<AtomicU64>,
+    n_block_info_reads: Arc<AtomicUsize>,
+    n_nonce_reads: Arc<AtomicUsize>,
+    n_compiled_class_reads: Arc<AtomicUsize>,
+}
+
+impl CountingStateReader {
+    fn commit_block(&self) {
+        self.latest_block_number.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl MempoolStateReader for CountingStateReader {
+    fn get_block_info(&self) -> Result<BlockInfo, StateError> {
+        self.n_block_info_reads.fetch_add(1, Ordering::SeqCst);
+        let block_number = BlockNumber(self.latest_block_number.load(Ordering::SeqCst));
+        Ok(BlockInfo { block_number, ..BlockInfo::create_for_testing() })
+    }
+}
+
+impl BlockifierStateReader for CountingStateReader {
+    fn get_storage_at(
+        &self,
+        _contract_address: ContractAddress,
+        _key: StorageKey,
+    ) -> StateResult<Felt> {
+        Ok(Felt::ZERO)
+    }
+
+    fn get_nonce_at(&self, _contract_address: ContractAddress) -> StateResult<Nonce> {
+        self.n_nonce_reads.fetch_add(1, Ordering::SeqCst);
+        Ok(Nonce(Felt::ONE))
+    }
+
+    fn get_class_hash_at(&self, _contract_address: ContractAddress) -> StateResult<ClassHash> {
+        Ok(ClassHash::default())
+    }
+
+    fn get_compiled_class(&self, _class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        self.n_compiled_class_reads.fetch_add(1, Ordering::SeqCst);
+        Ok(FeatureContract::TestContract(CairoVersion::Cairo0).get_runnable_class())
+    }
+
+    fn get_compiled_class_hash(&self, _class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        Ok(CompiledClassHash::default())
+    }
+}
+
+impl StateReaderFactory for CountingStateReader {
+    fn get_state_reader_from_latest_block(&self) -> Box<dyn MempoolStateReader> {
+        Box::new(self.clone())
+    }
+
+    fn get_state_reader(&self, _block_number: BlockNumber) -> Box<dyn MempoolStateReader> {
+        Box::new(self.clone())
+    }
+
+    fn latest_block_number(&self) -> StateResult<Option<BlockNumber>> {
+        Ok(self
+            .reports_latest_block
+            .then(|| BlockNumber(self.latest_block_number.load(Ordering::SeqCst))))
+    }
+}
+
+#[fixture]
+fn state_reader() -> CountingStateReader {
+    CountingStateReader::default()
+}
+
+fn cached_factory(
+    state_reader: &CountingStateReader,
+    block_info_ttl_ms: u64,
+) -> CachedStateReaderFactory {
+    let config = StateReaderCacheConfig { block_info_ttl_ms, ..Default::default() };
+    CachedStateReaderFactory::new(config, Arc::new(state_reader.clone()))
+}
+
+fn latest_block_number(factory: &CachedStateReaderFactory) -> BlockNumber {
+    factory.get_state_reader_from_latest_block().get_block_info().unwrap().block_number
+}
+
+#[rstest]
+#[case::cached_within_ttl(60_000, 1)]
+#[case::disabled_by_zero_ttl(0, 3)]
+fn latest_block_info_is_cached(
+    state_reader: CountingStateReader,
+    #[case] block_info_ttl_ms: u64,
+    #[case] expected_n_reads: usize,
+) {
+    let factory = cached_factory(&state_reader, block_info_ttl_ms);
+
+    for _ in 0..3 {
+        latest_block_number(&factory);
+    }
+
+    assert_eq!(state_reader.n_block_info_reads.load(Ordering::SeqCst), expected_n_reads);
+}
+
+#[rstest]
+fn new_latest_block_invalidates_latest_block_info() {
+    let state_reader = CountingStateReader { reports_latest_block: true, ..Default::default() };
+    let factory = cached_factory(&state_reader, 60_000);
+    assert_eq!(latest_block_number(&factory), BlockNumber(0));
+    assert_eq!(latest_block_number(&factory), BlockNumber(0));
+
+    state_reader.commit_block();
+    assert_eq!(latest_block_number(&factory), BlockNumber(1));
+    assert_eq!(state_reader.n_block_info_reads.load(Ordering::SeqCst), 2);
+}
+
+#[rstest]
+fn state_is_cached_until_new_block(state_reader: CountingStateReader) {
+    let factory = cached_factory(&state_reader, 0);
+    let address = contract_address!("0x1");
+    let n_nonce_reads = || state_reader.n_nonce_reads.load(Ordering::SeqCst);
+
+    // Reads of the same block are shared between readers.
+    let block_number = latest_block_number(&factory);
+    let previous_block_reader = factory.get_state_reader(block_number);
+    previous_block_reader.get_nonce_at(address).unwrap();
+    factory.get_state_reader(block_number).get_nonce_at(address).unwrap();
+    assert_eq!(n_nonce_reads(), 1);
+
+    // A new block drops the cached state.
+    state_reader.commit_block();
+    let block_number = latest_block_number(&factory);
+    factory.get_state_reader(block_number).get_nonce_at(address).unwrap();
+    factory.get_state_reader(block_number).get_nonce_at(address).unwrap();
+    assert_eq!(n_nonce_reads(), 2);
+
+    // Readers of an older block bypass the cache.
+    previous_block_reader.get_nonce_at(address).unwrap();
+    assert_eq!(n_nonce_reads(), 3);
+}
+
+#[rstest]
+fn compiled_classes_are_cached_across_blocks(state_reader: CountingStateReader) {
+    let factory = cached_factory(&state_reader, 0);
+    let class_hash = class_hash!("0x2");
+
+    let block_number = latest_block_number(&factory);
+    factory.get_state_reader(block_number).get_compiled_class(class_hash).unwrap();
+
+    state_reader.commit_block();
+    let block_number = latest_block_number(&factory);
+    factory.get_state_reader(block_number).get_compiled_class(class_hash).unwrap();
+
+    assert_eq!(state_reader.n_compiled_class_reads.load(Ordering::SeqCst), 1);
+}
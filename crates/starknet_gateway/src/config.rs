@@ -15,6 +15,10 @@ use crate::compiler_version::VersionId;
 pub struct GatewayConfig {
     pub stateless_tx_validator_config: StatelessTransactionValidatorConfig,
     pub stateful_tx_validator_config: StatefulTransactionValidatorConfig,
+    pub state_reader_cache_config: StateReaderCacheConfig,
+    // If set, transactions are validated against the local storage of the node, shared with the
+    // batcher, instead of against the RPC node.
+    pub read_state_from_storage: bool,
     pub chain_info: ChainInfo,
 }
 
@@ -29,6 +33,17 @@ impl SerializeConfig for GatewayConfig {
                 self.stateful_tx_validator_config.dump(),
                 "stateful_tx_validator_config",
             ),
+            append_sub_config_name(
+                self.state_reader_cache_config.dump(),
+                "state_reader_cache_config",
+            ),
+            BTreeMap::from([ser_param(
+                "read_state_from_storage",
+                &self.read_state_from_storage,
+                "If true, transactions are validated against the local storage of the node, \
+                 shared with the batcher, instead of against the RPC node.",
+                ParamPrivacyInput::Public,
+            )]),
             append_sub_config_name(self.chain_info.dump(), "chain_info"),
         ]
         .into_iter()
@@ -149,6 +164,7 @@ impl SerializeConfig for RpcStateReaderConfig {
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
 pub struct StatefulTransactionValidatorConfig {
     pub max_nonce_for_validation_skip: Nonce,
+    pub max_allowed_nonce_gap: u32,
     pub versioned_constants_overrides: VersionedConstantsOverrides,
 }
 
@@ -156,6 +172,7 @@ impl Default for StatefulTransactionValidatorConfig {
     fn default() -> Self {
         StatefulTransactionValidatorConfig {
             max_nonce_for_validation_skip: Nonce(Felt::ONE),
+            max_allowed_nonce_gap: 64,
             versioned_constants_overrides: VersionedConstantsOverrides::default(),
         }
     }
@@ -163,12 +180,20 @@ impl Default for StatefulTransactionValidatorConfig {
 
 impl SerializeConfig for StatefulTransactionValidatorConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        let mut dump = BTreeMap::from_iter([ser_param(
-            "max_nonce_for_validation_skip",
-            &self.max_nonce_for_validation_skip,
-            "Maximum nonce for which the validation is skipped.",
-            ParamPrivacyInput::Public,
-        )]);
+        let mut dump = BTreeMap::from_iter([
+            ser_param(
+                "max_nonce_for_validation_skip",
+                &self.max_nonce_for_validation_skip,
+                "Maximum nonce for which the validation is skipped.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_allowed_nonce_gap",
+                &self.max_allowed_nonce_gap,
+                "Maximal difference between a transaction nonce and its account nonce.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
         dump.append(&mut append_sub_config_name(
             self.versioned_constants_overrides.dump(),
             "versioned_constants_overrides",
@@ -176,3 +201,35 @@ impl SerializeConfig for StatefulTransactionValidatorConfig {
         dump
     }
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct StateReaderCacheConfig {
+    pub block_info_ttl_ms: u64,
+    pub compiled_class_cache_size: usize,
+}
+
+impl Default for StateReaderCacheConfig {
+    fn default() -> Self {
+        StateReaderCacheConfig { block_info_ttl_ms: 1000, compiled_class_cache_size: 400 }
+    }
+}
+
+impl SerializeConfig for StateReaderCacheConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "block_info_ttl_ms",
+                &self.block_info_ttl_ms,
+                "Maximal age, in milliseconds, of the cached latest block info before it is \
+                 fetched again. Cached state is dropped once a newer block is fetched.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "compiled_class_cache_size",
+                &self.compiled_class_cache_size,
+                "Maximal number of compiled classes kept in the state reader cache.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
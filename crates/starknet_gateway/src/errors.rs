@@ -1,11 +1,15 @@
 use axum::http::StatusCode;
 use blockifier::state::errors::StateError;
+use papyrus_storage::StorageError;
 use serde_json::{Error as SerdeError, Value};
 use starknet_api::block::GasPrice;
 use starknet_api::transaction::fields::{Resource, ResourceBounds};
 use starknet_api::StarknetApiError;
 use starknet_gateway_types::errors::GatewaySpecError;
+use starknet_mempool_types::communication::MempoolClientError;
+use starknet_mempool_types::errors::MempoolError;
 use thiserror::Error;
+use tracing::error;
 
 use crate::compiler_version::{VersionId, VersionIdError};
 use crate::rpc_objects::{RpcErrorCode, RpcErrorResponse};
@@ -119,3 +123,25 @@ impl From<RPCStateReaderError> for StateError {
 pub fn serde_err_to_state_err(err: SerdeError) -> StateError {
     StateError::StateReadError(format!("Failed to parse rpc result {:?}", err.to_string()))
 }
+
+// Converts a storage error to the error type of the state reader.
+pub fn storage_err_to_state_err(err: StorageError) -> StateError {
+    StateError::StateReadError(format!("Failed to read from storage: {err}"))
+}
+
+// Converts a mempool client error to a gateway error, keeping the errors that the mempool reports
+// on the transaction itself.
+pub fn mempool_client_err_to_gateway_err(err: MempoolClientError) -> GatewaySpecError {
+    match err {
+        MempoolClientError::MempoolError(
+            MempoolError::DuplicateNonce { .. } | MempoolError::DuplicateTransaction { .. },
+        ) => GatewaySpecError::DuplicateTx,
+        MempoolClientError::MempoolError(
+            MempoolError::NonceTooLarge(_) | MempoolError::NonceTooOld { .. },
+        ) => GatewaySpecError::InvalidTransactionNonce,
+        _ => {
+            error!("Mempool request failed: {}", err);
+            GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
+        }
+    }
+}
@@ -1,25 +1,34 @@
 use std::clone::Clone;
 use std::sync::Arc;
 
+use async_trait::async_trait;
 use blockifier::context::ChainInfo;
+use blockifier::state::global_cache::GlobalContractCache;
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
+use papyrus_storage::StorageReader;
 use starknet_api::executable_transaction::AccountTransaction;
 use starknet_api::rpc_transaction::RpcTransaction;
 use starknet_api::transaction::TransactionHash;
 use starknet_gateway_types::errors::GatewaySpecError;
 use starknet_mempool_types::communication::{AddTransactionArgsWrapper, SharedMempoolClient};
 use starknet_mempool_types::mempool_types::{AccountState, AddTransactionArgs};
-use starknet_sequencer_infra::component_definitions::ComponentStarter;
+use starknet_sequencer_infra::component_definitions::{
+    ComponentHealth,
+    ComponentStarter,
+    HealthStatus,
+};
 use starknet_sierra_compile::config::SierraToCasmCompilationConfig;
 use tracing::{error, instrument, Span};
 
+use crate::cached_state_reader::CachedStateReaderFactory;
 use crate::compilation::GatewayCompiler;
 use crate::config::{GatewayConfig, RpcStateReaderConfig};
-use crate::errors::GatewayResult;
+use crate::errors::{mempool_client_err_to_gateway_err, GatewayResult};
 use crate::rpc_state_reader::RpcStateReaderFactory;
-use crate::state_reader::StateReaderFactory;
+use crate::state_reader::{MempoolStateReader, StateReaderFactory};
 use crate::stateful_transaction_validator::StatefulTransactionValidator;
 use crate::stateless_transaction_validator::StatelessTransactionValidator;
+use crate::storage_state_reader::StorageStateReaderFactory;
 use crate::utils::compile_contract_and_build_executable_tx;
 
 #[cfg(test)]
@@ -58,6 +67,12 @@ impl Gateway {
         }
     }
 
+    /// Returns a handle that reports the state the gateway validates against to the monitoring
+    /// endpoint.
+    pub fn health(&self) -> GatewayHealth {
+        GatewayHealth { state_reader_factory: self.state_reader_factory.clone() }
+    }
+
     #[instrument(skip(self))]
     pub async fn add_tx(
         &self,
@@ -78,10 +93,8 @@ impl Gateway {
         let tx_hash = add_tx_args.tx.tx_hash();
 
         let add_tx_args = AddTransactionArgsWrapper { args: add_tx_args, p2p_message_metadata };
-        self.mempool_client.add_tx(add_tx_args).await.map_err(|e| {
-            error!("Failed to send tx to mempool: {}", e);
-            GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
-        })?;
+        // The mempool rejects duplicate transactions and used nonces.
+        self.mempool_client.add_tx(add_tx_args).await.map_err(mempool_client_err_to_gateway_err)?;
         // TODO: Also return `ContractAddress` for deploy and `ClassHash` for Declare.
         Ok(tx_hash)
     }
@@ -145,16 +158,65 @@ impl ProcessTxBlockingTask {
     }
 }
 
+/// Creates a gateway that reads the state from `storage_reader`, if given, or from the RPC node
+/// otherwise. The storage reader is shared with the component that opened the storage, as a
+/// storage may only be opened once per process.
 pub fn create_gateway(
     config: GatewayConfig,
     rpc_state_reader_config: RpcStateReaderConfig,
     compiler_config: SierraToCasmCompilationConfig,
     mempool_client: SharedMempoolClient,
+    storage_reader: Option<StorageReader>,
 ) -> Gateway {
-    let state_reader_factory = Arc::new(RpcStateReaderFactory { config: rpc_state_reader_config });
+    let state_reader_factory: Arc<dyn StateReaderFactory> = match storage_reader {
+        Some(storage_reader) => Arc::new(StorageStateReaderFactory {
+            storage_reader,
+            compiled_classes: GlobalContractCache::new(
+                config.state_reader_cache_config.compiled_class_cache_size,
+            ),
+        }),
+        None => Arc::new(RpcStateReaderFactory { config: rpc_state_reader_config }),
+    };
+    let state_reader_factory = Arc::new(CachedStateReaderFactory::new(
+        config.state_reader_cache_config.clone(),
+        state_reader_factory,
+    ));
     let gateway_compiler = GatewayCompiler::new_command_line_compiler(compiler_config);
 
     Gateway::new(config, state_reader_factory, gateway_compiler, mempool_client)
 }
 
 impl ComponentStarter for Gateway {}
+
+/// Reports the latest block of the state the gateway validates transactions against, which is
+/// unhealthy if it can't be read, as all transactions are then rejected.
+#[derive(Clone)]
+pub struct GatewayHealth {
+    state_reader_factory: Arc<dyn StateReaderFactory>,
+}
+
+#[async_trait]
+impl ComponentHealth for GatewayHealth {
+    async fn check_health(&self) -> HealthStatus {
+        // Reading the state may block, e.g., on a request to the RPC node.
+        let state_reader_factory = self.state_reader_factory.clone();
+        let block_info = tokio::task::spawn_blocking(move || {
+            state_reader_factory
+                .get_state_reader_from_latest_block()
+                .get_block_info()
+                .map_err(|err| err.to_string())
+        })
+        .await;
+        match block_info {
+            Ok(Ok(block_info)) => HealthStatus::Healthy {
+                details: format!("Latest block: {}.", block_info.block_number),
+            },
+            Ok(Err(err)) => HealthStatus::Unhealthy {
+                reason: format!("Failed to read the latest block info: {err}"),
+            },
+            Err(join_err) => HealthStatus::Unhealthy {
+                reason: format!("Failed to read the latest block info: {join_err}"),
+            },
+        }
+    }
+}
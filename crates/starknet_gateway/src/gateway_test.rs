@@ -12,13 +12,21 @@ use starknet_api::core::{ChainId, CompiledClassHash, ContractAddress};
 use starknet_api::executable_transaction::{AccountTransaction, InvokeTransaction};
 use starknet_api::rpc_transaction::{RpcDeclareTransaction, RpcTransaction};
 use starknet_gateway_types::errors::GatewaySpecError;
-use starknet_mempool_types::communication::{AddTransactionArgsWrapper, MockMempoolClient};
+use starknet_mempool_types::communication::{
+    AddTransactionArgsWrapper,
+    MempoolClientError,
+    MempoolClientResult,
+    MockMempoolClient,
+};
+use starknet_mempool_types::errors::MempoolError;
 use starknet_mempool_types::mempool_types::{AccountState, AddTransactionArgs};
+use starknet_sequencer_infra::component_definitions::{ComponentHealth, HealthStatus};
 use starknet_sierra_compile::config::SierraToCasmCompilationConfig;
 
 use crate::compilation::GatewayCompiler;
 use crate::config::{
     GatewayConfig,
+    StateReaderCacheConfig,
     StatefulTransactionValidatorConfig,
     StatelessTransactionValidatorConfig,
 };
@@ -30,6 +38,8 @@ fn config() -> GatewayConfig {
     GatewayConfig {
         stateless_tx_validator_config: StatelessTransactionValidatorConfig::default(),
         stateful_tx_validator_config: StatefulTransactionValidatorConfig::default(),
+        state_reader_cache_config: StateReaderCacheConfig::default(),
+        read_state_from_storage: false,
         chain_info: ChainInfo::create_for_testing(),
     }
 }
@@ -71,8 +81,8 @@ impl MockDependencies {
         )
     }
 
-    fn expect_add_tx(&mut self, args: AddTransactionArgsWrapper) {
-        self.mock_mempool_client.expect_add_tx().once().with(eq(args)).return_once(|_| Ok(()));
+    fn expect_add_tx(&mut self, args: AddTransactionArgsWrapper, result: MempoolClientResult<()>) {
+        self.mock_mempool_client.expect_add_tx().once().with(eq(args)).return_once(|_| result);
     }
 }
 
@@ -89,16 +99,20 @@ fn create_tx() -> (RpcTransaction, SenderAddress) {
     (tx, sender_address)
 }
 
+fn create_executable_tx(rpc_tx: &RpcTransaction) -> AccountTransaction {
+    let rpc_invoke_tx =
+        assert_matches!(rpc_tx.clone(), RpcTransaction::Invoke(rpc_invoke_tx) => rpc_invoke_tx);
+    AccountTransaction::Invoke(
+        InvokeTransaction::from_rpc_tx(rpc_invoke_tx, &ChainId::create_for_testing()).unwrap(),
+    )
+}
+
 // TODO: add test with Some broadcasted message metadata
 #[rstest]
 #[tokio::test]
 async fn test_add_tx(mut mock_dependencies: MockDependencies) {
     let (rpc_tx, address) = create_tx();
-    let rpc_invoke_tx =
-        assert_matches!(rpc_tx.clone(), RpcTransaction::Invoke(rpc_invoke_tx) => rpc_invoke_tx);
-    let executable_tx = AccountTransaction::Invoke(
-        InvokeTransaction::from_rpc_tx(rpc_invoke_tx, &ChainId::create_for_testing()).unwrap(),
-    );
+    let executable_tx = create_executable_tx(&rpc_tx);
 
     let tx_hash = executable_tx.tx_hash();
 
@@ -107,10 +121,13 @@ async fn test_add_tx(mut mock_dependencies: MockDependencies) {
         tx: executable_tx,
         account_state: AccountState { address, nonce: *rpc_tx.nonce() },
     };
-    mock_dependencies.expect_add_tx(AddTransactionArgsWrapper {
-        args: add_tx_args,
-        p2p_message_metadata: p2p_message_metadata.clone(),
-    });
+    mock_dependencies.expect_add_tx(
+        AddTransactionArgsWrapper {
+            args: add_tx_args,
+            p2p_message_metadata: p2p_message_metadata.clone(),
+        },
+        Ok(()),
+    );
 
     let gateway = mock_dependencies.gateway();
 
@@ -119,6 +136,59 @@ async fn test_add_tx(mut mock_dependencies: MockDependencies) {
     assert_eq!(tx_hash, response_tx_hash);
 }
 
+#[rstest]
+#[case::duplicate_transaction(
+    |tx| MempoolError::DuplicateTransaction { tx_hash: tx.tx_hash() },
+    GatewaySpecError::DuplicateTx
+)]
+#[case::duplicate_nonce(
+    |tx| MempoolError::DuplicateNonce { address: tx.contract_address(), nonce: tx.nonce() },
+    GatewaySpecError::DuplicateTx
+)]
+#[case::nonce_too_old(
+    |tx| MempoolError::NonceTooOld { address: tx.contract_address(), nonce: tx.nonce() },
+    GatewaySpecError::InvalidTransactionNonce
+)]
+#[case::nonce_too_large(
+    |tx| MempoolError::NonceTooLarge(tx.nonce()),
+    GatewaySpecError::InvalidTransactionNonce
+)]
+#[tokio::test]
+async fn test_mempool_rejection(
+    mut mock_dependencies: MockDependencies,
+    #[case] mempool_error: fn(&AccountTransaction) -> MempoolError,
+    #[case] expected_error: GatewaySpecError,
+) {
+    let (rpc_tx, address) = create_tx();
+    let nonce = *rpc_tx.nonce();
+    let executable_tx = create_executable_tx(&rpc_tx);
+    let mempool_error = mempool_error(&executable_tx);
+
+    mock_dependencies.expect_add_tx(
+        AddTransactionArgsWrapper {
+            args: AddTransactionArgs {
+                tx: executable_tx,
+                account_state: AccountState { address, nonce },
+            },
+            p2p_message_metadata: None,
+        },
+        Err(MempoolClientError::MempoolError(mempool_error)),
+    );
+
+    let gateway = mock_dependencies.gateway();
+
+    let err = gateway.add_tx(rpc_tx, None).await.unwrap_err();
+    assert_eq!(err, expected_error);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_health_reports_latest_block(mock_dependencies: MockDependencies) {
+    let gateway = mock_dependencies.gateway();
+
+    assert_matches!(gateway.health().check_health().await, HealthStatus::Healthy { .. });
+}
+
 // Gateway spec errors tests.
 // TODO(Arni): Add tests for all the error cases. Check the response (use `into_response` on the
 // result of `add_tx`).
@@ -1,3 +1,4 @@
+pub mod cached_state_reader;
 pub mod communication;
 pub mod compilation;
 mod compiler_version;
@@ -13,6 +14,7 @@ pub mod state_reader;
 mod state_reader_test_utils;
 mod stateful_transaction_validator;
 mod stateless_transaction_validator;
+pub mod storage_state_reader;
 #[cfg(test)]
 mod test_utils;
 mod utils;
@@ -29,6 +29,7 @@ use crate::rpc_objects::{
     RPC_ERROR_BLOCK_NOT_FOUND,
     RPC_ERROR_CONTRACT_ADDRESS_NOT_FOUND,
     RPC_ERROR_INVALID_PARAMS,
+    RPC_ERROR_NO_BLOCKS,
 };
 use crate::state_reader::{MempoolStateReader, StateReaderFactory};
 
@@ -189,4 +190,18 @@ impl StateReaderFactory for RpcStateReaderFactory {
     fn get_state_reader(&self, block_number: BlockNumber) -> Box<dyn MempoolStateReader> {
         Box::new(RpcStateReader::from_number(&self.config, block_number))
     }
+
+    fn latest_block_number(&self) -> StateResult<Option<BlockNumber>> {
+        let result = RpcStateReader::from_latest(&self.config)
+            .send_rpc_request("starknet_blockNumber", json!([]));
+        match result {
+            Ok(value) => {
+                let block_number: BlockNumber =
+                    serde_json::from_value(value).map_err(serde_err_to_state_err)?;
+                Ok(Some(block_number))
+            }
+            Err(RPCStateReaderError::UnexpectedErrorCode(RPC_ERROR_NO_BLOCKS)) => Ok(None),
+            Err(e) => Err(e)?,
+        }
+    }
 }
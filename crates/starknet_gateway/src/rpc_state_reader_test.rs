@@ -20,8 +20,8 @@ use crate::rpc_objects::{
     RpcResponse,
     RpcSuccessResponse,
 };
-use crate::rpc_state_reader::RpcStateReader;
-use crate::state_reader::MempoolStateReader;
+use crate::rpc_state_reader::{RpcStateReader, RpcStateReaderFactory};
+use crate::state_reader::{MempoolStateReader, StateReaderFactory};
 
 async fn run_rpc_server() -> mockito::ServerGuard {
     mockito::Server::new_async().await
@@ -92,6 +92,31 @@ async fn test_get_block_info() {
     mock.assert_async().await;
 }
 
+#[tokio::test]
+async fn test_latest_block_number() {
+    let mut server = run_rpc_server().await;
+    let config = RpcStateReaderConfig { url: server.url(), ..Default::default() };
+
+    let expected_result = BlockNumber(100);
+
+    let mock = mock_rpc_interaction(
+        &mut server,
+        &config.json_rpc_version,
+        "starknet_blockNumber",
+        json!([]),
+        &RpcResponse::Success(RpcSuccessResponse {
+            result: serde_json::to_value(expected_result).unwrap(),
+            ..Default::default()
+        }),
+    );
+
+    let factory = RpcStateReaderFactory { config };
+    let result =
+        tokio::task::spawn_blocking(move || factory.latest_block_number()).await.unwrap().unwrap();
+    assert_eq!(result, Some(expected_result));
+    mock.assert_async().await;
+}
+
 #[tokio::test]
 async fn test_get_storage_at() {
     let mut server = run_rpc_server().await;
@@ -16,6 +16,12 @@ pub trait MempoolStateReader: BlockifierStateReader + Send + Sync {
 pub trait StateReaderFactory: Send + Sync {
     fn get_state_reader_from_latest_block(&self) -> Box<dyn MempoolStateReader>;
     fn get_state_reader(&self, block_number: BlockNumber) -> Box<dyn MempoolStateReader>;
+
+    /// Returns the number of the latest block, if it can be read without fetching its block info,
+    /// which lets caches notice new blocks as soon as they are committed.
+    fn latest_block_number(&self) -> StateResult<Option<BlockNumber>> {
+        Ok(None)
+    }
 }
 
 // By default, a Box<dyn Trait> does not implement the trait of the object it contains.
@@ -17,7 +17,7 @@ use starknet_api::executable_transaction::{
 };
 use starknet_gateway_types::errors::GatewaySpecError;
 use starknet_types_core::felt::Felt;
-use tracing::error;
+use tracing::{debug, error};
 
 use crate::config::StatefulTransactionValidatorConfig;
 use crate::errors::StatefulTransactionValidatorResult;
@@ -74,7 +74,12 @@ impl StatefulTransactionValidator {
         account_nonce: Nonce,
         mut validator: V,
     ) -> StatefulTransactionValidatorResult<()> {
-        let skip_validate = skip_stateful_validations(executable_tx, account_nonce);
+        self.validate_nonce(executable_tx, account_nonce)?;
+        let skip_validate = skip_stateful_validations(
+            executable_tx,
+            account_nonce,
+            self.config.max_nonce_for_validation_skip,
+        );
         let account_tx = AccountTransaction { tx: executable_tx.clone(), only_query: false };
         validator
             .validate(account_tx, skip_validate)
@@ -82,6 +87,29 @@ impl StatefulTransactionValidator {
         Ok(())
     }
 
+    // Rejects transactions whose nonce was already used by the account, or is too far ahead of the
+    // account nonce to be sequenced soon.
+    fn validate_nonce(
+        &self,
+        executable_tx: &ExecutableTransaction,
+        account_nonce: Nonce,
+    ) -> StatefulTransactionValidatorResult<()> {
+        let tx_nonce = executable_tx.nonce();
+        let max_allowed_nonce =
+            Nonce(account_nonce.0 + Felt::from(self.config.max_allowed_nonce_gap));
+        if tx_nonce < account_nonce || tx_nonce > max_allowed_nonce {
+            debug!(
+                "Transaction nonce {} of account {} is out of the allowed range [{}, {}].",
+                tx_nonce.0,
+                executable_tx.contract_address(),
+                account_nonce.0,
+                max_allowed_nonce.0
+            );
+            return Err(GatewaySpecError::InvalidTransactionNonce);
+        }
+        Ok(())
+    }
+
     pub fn instantiate_validator(
         &self,
         state_reader_factory: &dyn StateReaderFactory,
@@ -113,13 +141,21 @@ impl StatefulTransactionValidator {
 // Check if validation of an invoke transaction should be skipped due to deploy_account not being
 // proccessed yet. This feature is used to improve UX for users sending deploy_account + invoke at
 // once.
-fn skip_stateful_validations(tx: &ExecutableTransaction, account_nonce: Nonce) -> bool {
+fn skip_stateful_validations(
+    tx: &ExecutableTransaction,
+    account_nonce: Nonce,
+    max_nonce_for_validation_skip: Nonce,
+) -> bool {
     match tx {
         ExecutableTransaction::Invoke(ExecutableInvokeTransaction { tx, .. }) => {
-            // check if the transaction nonce is 1, meaning it is post deploy_account, and the
-            // account nonce is zero, meaning the account was not deployed yet. The mempool also
-            // verifies that the deploy_account transaction exists.
-            tx.nonce() == Nonce(Felt::ONE) && account_nonce == Nonce(Felt::ZERO)
+            // check if the account nonce is zero, meaning the account was not deployed yet, and the
+            // transaction nonce is positive, meaning it is post deploy_account. The mempool holds
+            // the transaction back until the transactions preceding it, starting with the
+            // deploy_account transaction, arrive.
+            let nonce = tx.nonce();
+            account_nonce == Nonce(Felt::ZERO)
+                && nonce > account_nonce
+                && nonce <= max_nonce_for_validation_skip
         }
         ExecutableTransaction::DeployAccount(_) | ExecutableTransaction::Declare(_) => false,
     }
@@ -27,6 +27,7 @@ use starknet_api::{deploy_account_tx_args, invoke_tx_args, nonce};
 use starknet_gateway_types::errors::GatewaySpecError;
 
 use crate::config::StatefulTransactionValidatorConfig;
+use crate::errors::StatefulTransactionValidatorResult;
 use crate::state_reader::{MockStateReaderFactory, StateReaderFactory};
 use crate::state_reader_test_utils::local_test_state_reader_factory;
 use crate::stateful_transaction_validator::{
@@ -81,6 +82,31 @@ fn test_stateful_tx_validator(
     assert_eq!(result, expected_result_as_stateful_transaction_result);
 }
 
+#[rstest]
+#[case::nonce_already_used(nonce!(4), Err(GatewaySpecError::InvalidTransactionNonce))]
+#[case::account_nonce(nonce!(5), Ok(()))]
+#[case::max_allowed_nonce(nonce!(5 + 64), Ok(()))]
+#[case::nonce_too_far_ahead(nonce!(5 + 65), Err(GatewaySpecError::InvalidTransactionNonce))]
+fn test_nonce_window(
+    #[case] tx_nonce: Nonce,
+    #[case] expected_result: StatefulTransactionValidatorResult<()>,
+) {
+    let stateful_validator = StatefulTransactionValidator {
+        config: StatefulTransactionValidatorConfig {
+            max_allowed_nonce_gap: 64,
+            ..Default::default()
+        },
+    };
+    let executable_tx =
+        AccountTransaction::Invoke(executable_invoke_tx(invoke_tx_args!(nonce: tx_nonce)));
+
+    let mut mock_validator = MockStatefulTransactionValidatorTrait::new();
+    mock_validator.expect_validate().returning(|_, _| Ok(()));
+
+    let result = stateful_validator.run_validate(&executable_tx, nonce!(5), mock_validator);
+    assert_eq!(result, expected_result);
+}
+
 #[rstest]
 fn test_instantiate_validator(stateful_validator: StatefulTransactionValidator) {
     let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
@@ -0,0 +1,168 @@
+use blockifier::blockifier::block::validated_gas_prices;
+use blockifier::execution::contract_class::RunnableCompiledClass;
+use blockifier::state::errors::StateError;
+use blockifier::state::global_cache::GlobalContractCache;
+use blockifier::state::state_api::{StateReader as BlockifierStateReader, StateResult};
+use papyrus_state_reader::papyrus_state::PapyrusReader;
+use papyrus_storage::header::HeaderStorageReader;
+use papyrus_storage::state::StateStorageReader;
+use papyrus_storage::StorageReader;
+use starknet_api::block::{BlockInfo, BlockNumber, GasPrice, NonzeroGasPrice};
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::data_availability::L1DataAvailabilityMode;
+use starknet_api::state::StorageKey;
+use starknet_types_core::felt::Felt;
+
+use crate::errors::storage_err_to_state_err;
+use crate::state_reader::{MempoolStateReader, StateReaderFactory};
+
+#[cfg(test)]
+#[path = "storage_state_reader_test.rs"]
+mod storage_state_reader_test;
+
+/// Reads the state from a local papyrus storage, e.g., the storage of a colocated state sync.
+///
+/// Reads the state after `block_number`, or after the latest block whose state is in the storage
+/// if it is not set.
+#[derive(Clone)]
+pub struct StorageStateReader {
+    storage_reader: StorageReader,
+    block_number: Option<BlockNumber>,
+    compiled_classes: GlobalContractCache<RunnableCompiledClass>,
+}
+
+impl StorageStateReader {
+    fn block_number(&self) -> StateResult<BlockNumber> {
+        if let Some(block_number) = self.block_number {
+            return Ok(block_number);
+        }
+
+        let state_marker = self
+            .storage_reader
+            .begin_ro_txn()
+            .and_then(|txn| txn.get_state_marker())
+            .map_err(storage_err_to_state_err)?;
+        state_marker
+            .prev()
+            .ok_or_else(|| StateError::StateReadError("The storage has no blocks.".to_string()))
+    }
+
+    fn papyrus_reader(&self) -> StateResult<PapyrusReader> {
+        // The papyrus reader reads the state before the given block.
+        let block_number = self.block_number()?.unchecked_next();
+        Ok(PapyrusReader::new(
+            self.storage_reader.clone(),
+            block_number,
+            self.compiled_classes.clone(),
+        ))
+    }
+}
+
+impl MempoolStateReader for StorageStateReader {
+    fn get_block_info(&self) -> StateResult<BlockInfo> {
+        let block_number = self.block_number()?;
+        let header = self
+            .storage_reader
+            .begin_ro_txn()
+            .and_then(|txn| txn.get_block_header(block_number))
+            .map_err(storage_err_to_state_err)?
+            .ok_or_else(|| {
+                StateError::StateReadError(format!("Block {block_number} header not found."))
+            })?
+            .block_header_without_hash;
+
+        let parse_gas_price = |gas_price: GasPrice| {
+            NonzeroGasPrice::new(gas_price).map_err(|_| {
+                StateError::StateReadError(format!(
+                    "Block {block_number} header has a zero gas price."
+                ))
+            })
+        };
+        Ok(BlockInfo {
+            block_number,
+            sequencer_address: header.sequencer.0,
+            block_timestamp: header.timestamp,
+            gas_prices: validated_gas_prices(
+                parse_gas_price(header.l1_gas_price.price_in_wei)?,
+                parse_gas_price(header.l1_gas_price.price_in_fri)?,
+                parse_gas_price(header.l1_data_gas_price.price_in_wei)?,
+                parse_gas_price(header.l1_data_gas_price.price_in_fri)?,
+                parse_gas_price(header.l2_gas_price.price_in_wei)?,
+                parse_gas_price(header.l2_gas_price.price_in_fri)?,
+            ),
+            use_kzg_da: matches!(header.l1_da_mode, L1DataAvailabilityMode::Blob),
+        })
+    }
+}
+
+impl BlockifierStateReader for StorageStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        self.papyrus_reader()?.get_storage_at(contract_address, key)
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        self.papyrus_reader()?.get_nonce_at(contract_address)
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        self.papyrus_reader()?.get_class_hash_at(contract_address)
+    }
+
+    fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        self.papyrus_reader()?.get_compiled_class(class_hash)
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        // The compiled class hash is only stored in the state diff of the declaring block.
+        let block_number = self.block_number()?;
+        let txn = self.storage_reader.begin_ro_txn().map_err(storage_err_to_state_err)?;
+        let declaration_block_number = txn
+            .get_state_reader()
+            .and_then(|state_reader| state_reader.get_class_definition_block_number(&class_hash))
+            .map_err(storage_err_to_state_err)?
+            .filter(|declaration_block_number| *declaration_block_number <= block_number)
+            .ok_or(StateError::UndeclaredClassHash(class_hash))?;
+        txn.get_state_diff(declaration_block_number)
+            .map_err(storage_err_to_state_err)?
+            .and_then(|state_diff| state_diff.declared_classes.get(&class_hash).copied())
+            .ok_or(StateError::UndeclaredClassHash(class_hash))
+    }
+}
+
+pub struct StorageStateReaderFactory {
+    pub storage_reader: StorageReader,
+    pub compiled_classes: GlobalContractCache<RunnableCompiledClass>,
+}
+
+impl StorageStateReaderFactory {
+    fn create_reader(&self, block_number: Option<BlockNumber>) -> Box<dyn MempoolStateReader> {
+        Box::new(StorageStateReader {
+            storage_reader: self.storage_reader.clone(),
+            block_number,
+            compiled_classes: self.compiled_classes.clone(),
+        })
+    }
+}
+
+impl StateReaderFactory for StorageStateReaderFactory {
+    fn get_state_reader_from_latest_block(&self) -> Box<dyn MempoolStateReader> {
+        self.create_reader(None)
+    }
+
+    fn get_state_reader(&self, block_number: BlockNumber) -> Box<dyn MempoolStateReader> {
+        self.create_reader(Some(block_number))
+    }
+
+    fn latest_block_number(&self) -> StateResult<Option<BlockNumber>> {
+        let state_marker = self
+            .storage_reader
+            .begin_ro_txn()
+            .and_then(|txn| txn.get_state_marker())
+            .map_err(storage_err_to_state_err)?;
+        Ok(state_marker.prev())
+    }
+}
@@ -0,0 +1,88 @@
+use assert_matches::assert_matches;
+use blockifier::state::errors::StateError;
+use blockifier::state::global_cache::{GlobalContractCache, GLOBAL_CONTRACT_CACHE_SIZE_FOR_TEST};
+use blockifier::state::state_api::StateReader as BlockifierStateReader;
+use indexmap::IndexMap;
+use papyrus_storage::header::HeaderStorageWriter;
+use papyrus_storage::state::StateStorageWriter;
+use papyrus_storage::test_utils::get_test_storage;
+use starknet_api::block::{
+    BlockHeader,
+    BlockHeaderWithoutHash,
+    BlockNumber,
+    BlockTimestamp,
+    GasPrice,
+    GasPricePerToken,
+};
+use starknet_api::core::{CompiledClassHash, Nonce};
+use starknet_api::state::ThinStateDiff;
+use starknet_api::{class_hash, contract_address, felt};
+
+use crate::state_reader::{MempoolStateReader, StateReaderFactory};
+use crate::storage_state_reader::StorageStateReaderFactory;
+
+fn header(block_number: BlockNumber) -> BlockHeader {
+    let gas_price = GasPricePerToken { price_in_fri: GasPrice(1), price_in_wei: GasPrice(1) };
+    BlockHeader {
+        block_header_without_hash: BlockHeaderWithoutHash {
+            block_number,
+            timestamp: BlockTimestamp(block_number.0 + 1000),
+            l1_gas_price: gas_price,
+            l1_data_gas_price: gas_price,
+            l2_gas_price: gas_price,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn reads_state_of_requested_block() {
+    let ((storage_reader, mut storage_writer), _temp_dir) = get_test_storage();
+    let factory = StorageStateReaderFactory {
+        storage_reader,
+        compiled_classes: GlobalContractCache::new(GLOBAL_CONTRACT_CACHE_SIZE_FOR_TEST),
+    };
+    let address = contract_address!("0x1");
+    let class_hash = class_hash!("0x2");
+    let compiled_class_hash = CompiledClassHash(felt!("0x3"));
+
+    assert_matches!(
+        factory.get_state_reader_from_latest_block().get_block_info(),
+        Err(StateError::StateReadError(_))
+    );
+    assert_eq!(factory.latest_block_number().unwrap(), None);
+
+    let mut txn = storage_writer.begin_rw_txn().unwrap();
+    for (block_number, nonce) in
+        [(BlockNumber(0), Nonce(felt!(1_u8))), (BlockNumber(1), Nonce(felt!(2_u8)))]
+    {
+        let mut state_diff =
+            ThinStateDiff { nonces: IndexMap::from([(address, nonce)]), ..Default::default() };
+        if block_number == BlockNumber(1) {
+            state_diff.declared_classes = IndexMap::from([(class_hash, compiled_class_hash)]);
+        }
+        txn = txn
+            .append_header(block_number, &header(block_number))
+            .unwrap()
+            .append_state_diff(block_number, state_diff)
+            .unwrap();
+    }
+    txn.commit().unwrap();
+    assert_eq!(factory.latest_block_number().unwrap(), Some(BlockNumber(1)));
+
+    let latest_reader = factory.get_state_reader_from_latest_block();
+    let block_info = latest_reader.get_block_info().unwrap();
+    assert_eq!(block_info.block_number, BlockNumber(1));
+    assert_eq!(block_info.block_timestamp, BlockTimestamp(1001));
+    assert_eq!(latest_reader.get_nonce_at(address).unwrap(), Nonce(felt!(2_u8)));
+    assert_eq!(latest_reader.get_compiled_class_hash(class_hash).unwrap(), compiled_class_hash);
+
+    let first_block_reader = factory.get_state_reader(BlockNumber(0));
+    assert_eq!(first_block_reader.get_block_info().unwrap().block_number, BlockNumber(0));
+    assert_eq!(first_block_reader.get_nonce_at(address).unwrap(), Nonce(felt!(1_u8)));
+    assert_matches!(
+        first_block_reader.get_compiled_class_hash(class_hash),
+        Err(StateError::UndeclaredClassHash(hash)) if hash == class_hash
+    );
+}
@@ -13,6 +13,7 @@ use starknet_sequencer_infra::component_client::{
 };
 use starknet_sequencer_infra::component_definitions::{
     ComponentClient,
+    ComponentRequest,
     ComponentRequestAndResponseSender,
 };
 use thiserror::Error;
@@ -20,6 +21,10 @@ use thiserror::Error;
 use crate::errors::GatewayError;
 use crate::gateway_types::{GatewayInput, GatewayResult};
 
+#[cfg(test)]
+#[path = "communication_test.rs"]
+mod communication_test;
+
 pub type LocalGatewayClient = LocalComponentClient<GatewayRequest, GatewayResponse>;
 pub type RemoteGatewayClient = RemoteComponentClient<GatewayRequest, GatewayResponse>;
 pub type GatewayClientResult<T> = Result<T, GatewayClientError>;
@@ -41,6 +46,12 @@ pub enum GatewayRequest {
     AddTransaction(GatewayInput),
 }
 
+impl ComponentRequest for GatewayRequest {
+    fn is_idempotent(&self) -> bool {
+        false
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum GatewayResponse {
     AddTransaction(GatewayResult<TransactionHash>),
@@ -0,0 +1,35 @@
+use papyrus_network_types::network_types::BroadcastedMessageMetadata;
+use papyrus_test_utils::{get_rng, GetTestInstance};
+use rstest::rstest;
+use starknet_api::test_utils::invoke::{rpc_invoke_tx, InvokeTxArgs};
+use starknet_api::tx_hash;
+use starknet_sequencer_infra::codec::Codec;
+use starknet_sequencer_infra::test_utils::{
+    assert_codec_round_trip,
+    assert_round_trip_with_codecs,
+};
+
+use crate::communication::{GatewayRequest, GatewayResponse};
+use crate::errors::{GatewayError, GatewaySpecError};
+use crate::gateway_types::GatewayInput;
+
+#[rstest]
+fn test_request_round_trip(#[values(true, false)] with_message_metadata: bool) {
+    let request = GatewayRequest::AddTransaction(GatewayInput {
+        rpc_tx: rpc_invoke_tx(InvokeTxArgs::default()),
+        message_metadata: with_message_metadata
+            .then(|| BroadcastedMessageMetadata::get_test_instance(&mut get_rng())),
+    });
+    // `RpcTransaction` is internally tagged, which only JSON decodes.
+    assert_round_trip_with_codecs(&request, &[Codec::Json]);
+}
+
+#[rstest]
+#[case::ok(GatewayResponse::AddTransaction(Ok(tx_hash!(1))))]
+#[case::err(GatewayResponse::AddTransaction(Err(GatewayError::GatewaySpecError {
+    source: GatewaySpecError::ValidationFailure { data: "data".to_owned() },
+    p2p_message_metadata: Some(BroadcastedMessageMetadata::get_test_instance(&mut get_rng())),
+})))]
+fn test_response_round_trip(#[case] response: GatewayResponse) {
+    assert_codec_round_trip(&response);
+}
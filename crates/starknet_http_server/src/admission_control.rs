@@ -0,0 +1,196 @@
+//! Limits on the requests admitted by the http server, protecting the gateway from being saturated
+//! by a single client or by a burst of traffic.
+
+use std::collections::HashSet;
+use std::net::{IpAddr, SocketAddr};
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::extract::{ConnectInfo, State};
+use axum::http::{header, Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use lru::LruCache;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::debug;
+
+use crate::config::{HttpServerConfig, RateLimitKey};
+use crate::http_server::AppState;
+use crate::metrics::{
+    HTTP_SERVER_OVERLOADED_REQUESTS,
+    HTTP_SERVER_OVERSIZED_REQUESTS,
+    HTTP_SERVER_RATE_LIMITED_REQUESTS,
+};
+
+#[cfg(test)]
+#[path = "admission_control_test.rs"]
+pub mod admission_control_test;
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+// When this many clients are tracked, the least recently seen one is forgotten, which is likely to
+// have a full bucket anyway.
+const MAX_TRACKED_CLIENTS: usize = 100_000;
+
+// Sent with requests rejected because the server is at its concurrency limit.
+const OVERLOADED_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum ClientId {
+    Ip(IpAddr),
+    ApiKey(String),
+    // Requests whose origin is unknown, e.g., when the server is not bound to a socket, share a
+    // single quota.
+    Unknown,
+}
+
+/// A token bucket: holds up to `capacity` tokens, refilled at `refill_rate` tokens per second, and
+/// each admitted call consumes one.
+#[derive(Debug)]
+pub(crate) struct TokenBucket {
+    capacity: f64,
+    refill_rate: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub(crate) fn new(capacity: u32, refill_rate: u32, now: Instant) -> Self {
+        let capacity = f64::from(capacity);
+        Self { capacity, refill_rate: f64::from(refill_rate), tokens: capacity, last_refill: now }
+    }
+
+    /// Consumes `tokens` tokens if they are available, and otherwise returns the time until they
+    /// are.
+    pub(crate) fn try_consume(&mut self, tokens: u32, now: Instant) -> Result<(), Duration> {
+        self.refill(now);
+        let tokens = f64::from(tokens);
+        if self.tokens >= tokens {
+            self.tokens -= tokens;
+            return Ok(());
+        }
+
+        Err(Duration::from_secs_f64((tokens - self.tokens) / self.refill_rate))
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_rate).min(self.capacity);
+        self.last_refill = now;
+    }
+}
+
+pub struct AdmissionControl {
+    rate_limit_requests_per_second: u32,
+    rate_limit_burst_size: u32,
+    rate_limit_key: RateLimitKey,
+    api_keys: HashSet<String>,
+    client_buckets: Mutex<LruCache<ClientId, TokenBucket>>,
+    concurrency_limit: Arc<Semaphore>,
+}
+
+impl AdmissionControl {
+    pub fn new(config: &HttpServerConfig) -> Self {
+        Self {
+            rate_limit_requests_per_second: config.rate_limit_requests_per_second,
+            rate_limit_burst_size: config.rate_limit_burst_size,
+            rate_limit_key: config.rate_limit_key,
+            api_keys: config.api_keys.iter().cloned().collect(),
+            client_buckets: Mutex::new(LruCache::new(
+                NonZeroUsize::new(MAX_TRACKED_CLIENTS).unwrap(),
+            )),
+            concurrency_limit: Arc::new(Semaphore::new(config.max_concurrent_requests)),
+        }
+    }
+
+    pub(crate) fn client_id<B>(&self, request: &Request<B>) -> ClientId {
+        if self.rate_limit_key == RateLimitKey::ApiKey {
+            let api_key = request.headers().get(API_KEY_HEADER).and_then(|key| key.to_str().ok());
+            // Unknown keys are ignored, otherwise a client could get a fresh quota per request.
+            if let Some(api_key) = api_key.filter(|api_key| self.api_keys.contains(*api_key)) {
+                return ClientId::ApiKey(api_key.to_owned());
+            }
+        }
+
+        match request.extensions().get::<ConnectInfo<SocketAddr>>() {
+            Some(ConnectInfo(address)) => ClientId::Ip(address.ip()),
+            None => ClientId::Unknown,
+        }
+    }
+
+    /// Consumes `calls` calls from the quota of the client, and otherwise returns the time until
+    /// they are allowed.
+    pub(crate) fn check_rate_limit(
+        &self,
+        client_id: ClientId,
+        calls: u32,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let mut client_buckets = self.client_buckets.lock().expect("Lock should not be poisoned.");
+        client_buckets
+            .get_or_insert_mut(client_id, || {
+                TokenBucket::new(
+                    self.rate_limit_burst_size,
+                    self.rate_limit_requests_per_second,
+                    now,
+                )
+            })
+            .try_consume(calls, now)
+    }
+
+    /// Like `check_rate_limit`, but returns the response rejecting the request if the client is
+    /// over its quota.
+    pub(crate) fn rate_limit(&self, client_id: &ClientId, calls: u32) -> Result<(), Response> {
+        self.check_rate_limit(client_id.clone(), calls, Instant::now()).map_err(|retry_after| {
+            debug!("Rate limited request from {:?}.", client_id);
+            metrics::increment_counter!(HTTP_SERVER_RATE_LIMITED_REQUESTS);
+            too_many_requests(retry_after)
+        })
+    }
+
+    /// Returns a permit to handle a request, held for the duration of its handling, or `None` if
+    /// the server is at its concurrency limit.
+    pub(crate) fn try_acquire_permit(&self) -> Option<OwnedSemaphorePermit> {
+        self.concurrency_limit.clone().try_acquire_owned().ok()
+    }
+}
+
+/// A middleware that sheds requests exceeding the rate limit of their client or the concurrency
+/// limit of the server.
+/// Each request is charged as a single call. The client is passed on to the handlers as an
+/// extension, so that requests consisting of several calls, i.e., JSON-RPC batches, are charged for
+/// the rest of their calls.
+pub(crate) async fn admission_control<B>(
+    State(app_state): State<AppState>,
+    mut request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let admission_control = &app_state.admission_control;
+
+    let client_id = admission_control.client_id(&request);
+    if let Err(response) = admission_control.rate_limit(&client_id, 1) {
+        return response;
+    }
+
+    let Some(_permit) = admission_control.try_acquire_permit() else {
+        debug!("Rejected request from {:?}: too many concurrent requests.", client_id);
+        metrics::increment_counter!(HTTP_SERVER_OVERLOADED_REQUESTS);
+        return too_many_requests(OVERLOADED_RETRY_AFTER);
+    };
+
+    request.extensions_mut().insert(client_id);
+    let response = next.run(request).await;
+    // The body size is limited by the extractors of the handlers.
+    if response.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        metrics::increment_counter!(HTTP_SERVER_OVERSIZED_REQUESTS);
+    }
+    response
+}
+
+fn too_many_requests(retry_after: Duration) -> Response {
+    // Rounded up, so that a client retrying on time is admitted.
+    let retry_after_secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    (StatusCode::TOO_MANY_REQUESTS, [(header::RETRY_AFTER, retry_after_secs.to_string())])
+        .into_response()
+}
@@ -0,0 +1,126 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use pretty_assertions::assert_eq;
+use starknet_gateway_types::communication::MockGatewayClient;
+use tower::ServiceExt;
+
+use crate::admission_control::{AdmissionControl, ClientId, TokenBucket, API_KEY_HEADER};
+use crate::config::{HttpServerConfig, RateLimitKey};
+use crate::http_server::HttpServer;
+
+#[test]
+fn test_token_bucket() {
+    let start = Instant::now();
+    let mut bucket = TokenBucket::new(2, 4, start);
+
+    // The burst is admitted at once.
+    assert_eq!(bucket.try_consume(1, start), Ok(()));
+    assert_eq!(bucket.try_consume(1, start), Ok(()));
+    assert_eq!(bucket.try_consume(1, start), Err(Duration::from_millis(250)));
+
+    // Refilled at the configured rate, up to the burst size.
+    assert_eq!(bucket.try_consume(1, start + Duration::from_millis(250)), Ok(()));
+    let later = start + Duration::from_secs(10);
+    assert_eq!(bucket.try_consume(2, later), Ok(()));
+    assert_eq!(bucket.try_consume(2, later), Err(Duration::from_millis(500)));
+}
+
+#[test]
+fn test_rate_limit_is_per_client() {
+    let config = HttpServerConfig { rate_limit_burst_size: 1, ..Default::default() };
+    let admission_control = AdmissionControl::new(&config);
+    let now = Instant::now();
+    let client = ClientId::Ip("1.1.1.1".parse().unwrap());
+    let other_client = ClientId::Ip("2.2.2.2".parse().unwrap());
+
+    assert_eq!(admission_control.check_rate_limit(client.clone(), 1, now), Ok(()));
+    assert!(admission_control.check_rate_limit(client, 1, now).is_err());
+    assert_eq!(admission_control.check_rate_limit(other_client, 1, now), Ok(()));
+}
+
+#[test]
+fn test_client_id_by_api_key() {
+    let config = HttpServerConfig {
+        rate_limit_key: RateLimitKey::ApiKey,
+        api_keys: vec!["key".to_owned()],
+        ..Default::default()
+    };
+    let admission_control = AdmissionControl::new(&config);
+
+    let request = Request::post("/add_tx").header(API_KEY_HEADER, "key").body(()).unwrap();
+    assert_eq!(admission_control.client_id(&request), ClientId::ApiKey("key".to_owned()));
+    // Unknown keys don't identify a client.
+    let request = Request::post("/add_tx").header(API_KEY_HEADER, "other").body(()).unwrap();
+    assert_eq!(admission_control.client_id(&request), ClientId::Unknown);
+    let request = Request::post("/add_tx").body(()).unwrap();
+    assert_eq!(admission_control.client_id(&request), ClientId::Unknown);
+}
+
+#[test]
+fn test_concurrency_limit() {
+    let config = HttpServerConfig { max_concurrent_requests: 1, ..Default::default() };
+    let admission_control = AdmissionControl::new(&config);
+
+    let permit = admission_control.try_acquire_permit();
+    assert!(permit.is_some());
+    assert!(admission_control.try_acquire_permit().is_none());
+    drop(permit);
+    assert!(admission_control.try_acquire_permit().is_some());
+}
+
+#[tokio::test]
+async fn test_rate_limited_request_is_rejected() {
+    let config = HttpServerConfig {
+        rate_limit_requests_per_second: 1,
+        rate_limit_burst_size: 1,
+        ..Default::default()
+    };
+    let app = HttpServer::new(config, Arc::new(MockGatewayClient::new())).app();
+    let request = || Request::post("/add_tx").body(Body::from("invalid tx")).unwrap();
+
+    // The first request is admitted, and fails on its content.
+    let response = app.clone().oneshot(request()).await.unwrap();
+    assert_ne!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    let response = app.oneshot(request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "1");
+}
+
+#[tokio::test]
+async fn test_json_rpc_batch_is_charged_per_call() {
+    let config = HttpServerConfig {
+        rate_limit_requests_per_second: 1,
+        rate_limit_burst_size: 2,
+        max_json_rpc_batch_size: 2,
+        ..Default::default()
+    };
+    let app = HttpServer::new(config, Arc::new(MockGatewayClient::new())).app();
+    let request = |body: &'static str| Request::post("/rpc").body(Body::from(body)).unwrap();
+
+    // A single invalid call is admitted, and fails on its content.
+    let response = app.clone().oneshot(request("1")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // A batch of two calls exceeds the rest of the burst, although it is a single request.
+    let response = app.oneshot(request("[1, 1]")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "1");
+}
+
+#[tokio::test]
+async fn test_oversized_request_is_rejected() {
+    let config = HttpServerConfig { max_request_body_size: 10, ..Default::default() };
+    let app = HttpServer::new(config, Arc::new(MockGatewayClient::new())).app();
+    let request = Request::post("/add_tx")
+        .header("content-type", "application/json")
+        .body(Body::from(vec![b' '; 11]))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
@@ -3,14 +3,38 @@ use std::net::IpAddr;
 
 use papyrus_config::dumping::{ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
-use serde::{Deserialize, Serialize};
-use validator::Validate;
+use serde::{Deserialize, Deserializer, Serialize};
+use validator::{Validate, ValidationError};
 
 /// The http server connection related configuration.
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+#[validate(schema(function = "validate_http_server_config"))]
 pub struct HttpServerConfig {
     pub ip: IpAddr,
     pub port: u16,
+    #[validate(range(min = 1))]
+    pub max_request_body_size: usize,
+    #[validate(range(min = 1))]
+    pub max_concurrent_requests: usize,
+    #[validate(range(min = 1))]
+    pub max_json_rpc_batch_size: usize,
+    #[validate(range(min = 1))]
+    pub rate_limit_requests_per_second: u32,
+    #[validate(range(min = 1))]
+    pub rate_limit_burst_size: u32,
+    pub rate_limit_key: RateLimitKey,
+    #[serde(deserialize_with = "deserialize_api_keys")]
+    pub api_keys: Vec<String>,
+}
+
+/// What identifies a client for rate limiting.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RateLimitKey {
+    /// The IP address the request was received from.
+    ClientIp,
+    /// The `x-api-key` header of the request, if it is one of the configured `api_keys`, falling
+    /// back to the client IP otherwise.
+    ApiKey,
 }
 
 impl SerializeConfig for HttpServerConfig {
@@ -18,12 +42,91 @@ impl SerializeConfig for HttpServerConfig {
         BTreeMap::from_iter([
             ser_param("ip", &self.ip.to_string(), "The http server ip.", ParamPrivacyInput::Public),
             ser_param("port", &self.port, "The http server port.", ParamPrivacyInput::Public),
+            ser_param(
+                "max_request_body_size",
+                &self.max_request_body_size,
+                "The maximum size of a request body, in bytes. Larger requests are rejected.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_concurrent_requests",
+                &self.max_concurrent_requests,
+                "The maximum number of requests handled concurrently. Requests beyond this limit \
+                 are rejected with 429.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_json_rpc_batch_size",
+                &self.max_json_rpc_batch_size,
+                "The maximum number of requests in a JSON-RPC batch. Larger batches are rejected \
+                 as invalid requests. Each request of a batch is charged to the rate limit, so \
+                 this is at most rate_limit_burst_size.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "rate_limit_requests_per_second",
+                &self.rate_limit_requests_per_second,
+                "The sustained number of requests per second allowed for a single client.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "rate_limit_burst_size",
+                &self.rate_limit_burst_size,
+                "The number of requests a single client may send at once after being idle.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "rate_limit_key",
+                &self.rate_limit_key,
+                "What identifies a client for rate limiting: ClientIp or ApiKey.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "api_keys",
+                &self.api_keys.join(" "),
+                "'key1 key2 ...' the API keys that identify a client for rate limiting, when \
+                 rate_limit_key is ApiKey.",
+                ParamPrivacyInput::Private,
+            ),
         ])
     }
 }
 
 impl Default for HttpServerConfig {
     fn default() -> Self {
-        Self { ip: "0.0.0.0".parse().unwrap(), port: 8080 }
+        Self {
+            ip: "0.0.0.0".parse().unwrap(),
+            port: 8080,
+            max_request_body_size: 10 * 1024 * 1024,
+            max_concurrent_requests: 100,
+            max_json_rpc_batch_size: 100,
+            rate_limit_requests_per_second: 100,
+            rate_limit_burst_size: 200,
+            rate_limit_key: RateLimitKey::ClientIp,
+            api_keys: Vec::new(),
+        }
     }
 }
+
+fn validate_http_server_config(config: &HttpServerConfig) -> Result<(), ValidationError> {
+    // Otherwise, the largest batches are never admitted by the rate limit.
+    let fits_in_burst =
+        u32::try_from(config.max_json_rpc_batch_size).is_ok_and(|max_json_rpc_batch_size| {
+            max_json_rpc_batch_size <= config.rate_limit_burst_size
+        });
+    if !fits_in_burst {
+        return Err(ValidationError::new(
+            "max_json_rpc_batch_size should be at most rate_limit_burst_size",
+        ));
+    }
+    Ok(())
+}
+
+/// Deserializes the API keys from a "key1 key2 ..." string.
+fn deserialize_api_keys<'de, D>(de: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw_str: String = Deserialize::deserialize(de)?;
+    Ok(raw_str.split_whitespace().map(str::to_owned).collect())
+}
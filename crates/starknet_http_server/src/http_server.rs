@@ -1,8 +1,10 @@
 use std::any::type_name;
 use std::clone::Clone;
 use std::net::SocketAddr;
+use std::sync::Arc;
 
-use axum::extract::State;
+use axum::extract::{DefaultBodyLimit, State};
+use axum::middleware::from_fn_with_state;
 use axum::routing::post;
 use axum::{async_trait, Json, Router};
 use starknet_api::rpc_transaction::RpcTransaction;
@@ -14,8 +16,10 @@ use starknet_sequencer_infra::component_definitions::ComponentStarter;
 use starknet_sequencer_infra::errors::ComponentError;
 use tracing::{error, info, instrument};
 
+use crate::admission_control::{admission_control, AdmissionControl};
 use crate::config::HttpServerConfig;
 use crate::errors::HttpServerRunError;
+use crate::json_rpc::handle_json_rpc;
 
 #[cfg(test)]
 #[path = "http_server_test.rs"]
@@ -31,27 +35,41 @@ pub struct HttpServer {
 #[derive(Clone)]
 pub struct AppState {
     pub gateway_client: SharedGatewayClient,
+    pub admission_control: Arc<AdmissionControl>,
+    pub max_json_rpc_batch_size: usize,
 }
 
 impl HttpServer {
     pub fn new(config: HttpServerConfig, gateway_client: SharedGatewayClient) -> Self {
-        let app_state = AppState { gateway_client };
+        let admission_control = Arc::new(AdmissionControl::new(&config));
+        let app_state = AppState {
+            gateway_client,
+            admission_control,
+            max_json_rpc_batch_size: config.max_json_rpc_batch_size,
+        };
         HttpServer { config, app_state }
     }
 
     pub async fn run(&mut self) -> Result<(), HttpServerRunError> {
         // Parses the bind address from HttpServerConfig, returning an error for invalid addresses.
-        let HttpServerConfig { ip, port } = self.config;
+        let HttpServerConfig { ip, port, .. } = self.config;
         let addr = SocketAddr::new(ip, port);
         let app = self.app();
         info!("HttpServer running using socket: {}", addr);
 
         // Create a server that runs forever.
-        Ok(axum::Server::bind(&addr).serve(app.into_make_service()).await?)
+        // The client address is used for rate limiting.
+        let make_service = app.into_make_service_with_connect_info::<SocketAddr>();
+        Ok(axum::Server::bind(&addr).serve(make_service).await?)
     }
 
     pub fn app(&self) -> Router {
-        Router::new().route("/add_tx", post(add_tx)).with_state(self.app_state.clone())
+        Router::new()
+            .route("/add_tx", post(add_tx))
+            .route("/rpc", post(handle_json_rpc))
+            .route_layer(from_fn_with_state(self.app_state.clone(), admission_control))
+            .layer(DefaultBodyLimit::max(self.config.max_request_body_size))
+            .with_state(self.app_state.clone())
     }
 }
 
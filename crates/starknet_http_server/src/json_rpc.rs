@@ -0,0 +1,242 @@
+//! A JSON-RPC 2.0 endpoint serving the write methods of the Starknet specs.
+//!
+//! [`Starknet specs`]: https://github.com/starkware-libs/starknet-specs/blob/master/api/starknet_write_api.json
+
+use axum::body::Bytes;
+use axum::extract::{Extension, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use papyrus_common::class_hash::calculate_class_hash;
+use serde::Serialize;
+use serde_json::{json, Value};
+use starknet_api::rpc_transaction::{RpcDeclareTransaction, RpcTransaction};
+use starknet_gateway_types::communication::GatewayClientError;
+use starknet_gateway_types::errors::{GatewayError, GatewaySpecError};
+use starknet_gateway_types::gateway_types::GatewayInput;
+use tracing::{error, instrument};
+
+use crate::admission_control::ClientId;
+use crate::http_server::AppState;
+
+#[cfg(test)]
+#[path = "json_rpc_test.rs"]
+pub mod json_rpc_test;
+
+pub const JSON_RPC_VERSION: &str = "2.0";
+
+pub const ADD_DECLARE_TRANSACTION: &str = "starknet_addDeclareTransaction";
+pub const ADD_DEPLOY_ACCOUNT_TRANSACTION: &str = "starknet_addDeployAccountTransaction";
+pub const ADD_INVOKE_TRANSACTION: &str = "starknet_addInvokeTransaction";
+
+/// An error object of a JSON-RPC response.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct JsonRpcErrorObject {
+    pub code: i32,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl JsonRpcErrorObject {
+    fn new(code: i32, message: &str) -> Self {
+        Self { code, message: message.to_owned(), data: None }
+    }
+
+    fn parse_error() -> Self {
+        Self::new(-32700, "Parse error")
+    }
+
+    fn invalid_request() -> Self {
+        Self::new(-32600, "Invalid Request")
+    }
+
+    fn method_not_found() -> Self {
+        Self::new(-32601, "Method not found")
+    }
+
+    fn invalid_params(details: String) -> Self {
+        Self { data: Some(Value::String(details)), ..Self::new(-32602, "Invalid params") }
+    }
+}
+
+impl From<GatewaySpecError> for JsonRpcErrorObject {
+    fn from(error: GatewaySpecError) -> Self {
+        let as_rpc = error.into_rpc();
+        Self {
+            code: as_rpc.code,
+            message: as_rpc.message.to_owned(),
+            data: as_rpc.data.map(Value::from),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum JsonRpcOutcome {
+    Result(Value),
+    Error(JsonRpcErrorObject),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct JsonRpcResponse {
+    jsonrpc: &'static str,
+    #[serde(flatten)]
+    outcome: JsonRpcOutcome,
+    id: Value,
+}
+
+impl JsonRpcResponse {
+    fn new(id: Value, result: Result<Value, JsonRpcErrorObject>) -> Self {
+        let outcome = match result {
+            Ok(value) => JsonRpcOutcome::Result(value),
+            Err(error) => JsonRpcOutcome::Error(error),
+        };
+        Self { jsonrpc: JSON_RPC_VERSION, outcome, id }
+    }
+}
+
+/// Handles a single JSON-RPC request or a batch of at most `max_json_rpc_batch_size` of them.
+/// Requests of a batch are handled in order, so that transactions of the same account that are sent
+/// together reach the gateway in nonce order, and each of them is charged to the rate limit of the
+/// client.
+#[instrument(skip_all)]
+pub(crate) async fn handle_json_rpc(
+    State(app_state): State<AppState>,
+    Extension(client_id): Extension<ClientId>,
+    body: Bytes,
+) -> Response {
+    let request: Value = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(_) => {
+            return Json(JsonRpcResponse::new(Value::Null, Err(JsonRpcErrorObject::parse_error())))
+                .into_response();
+        }
+    };
+
+    match request {
+        Value::Array(requests)
+            if requests.is_empty() || requests.len() > app_state.max_json_rpc_batch_size =>
+        {
+            Json(JsonRpcResponse::new(Value::Null, Err(JsonRpcErrorObject::invalid_request())))
+                .into_response()
+        }
+        Value::Array(requests) => {
+            // The first request was charged by the admission control middleware.
+            let additional_calls = u32::try_from(requests.len() - 1).unwrap_or(u32::MAX);
+            if let Err(response) =
+                app_state.admission_control.rate_limit(&client_id, additional_calls)
+            {
+                return response;
+            }
+            let mut responses = Vec::with_capacity(requests.len());
+            for request in requests {
+                responses.extend(handle_request(&app_state, request).await);
+            }
+            if responses.is_empty() {
+                // The batch consists of notifications only.
+                return StatusCode::NO_CONTENT.into_response();
+            }
+            Json(responses).into_response()
+        }
+        request => match handle_request(&app_state, request).await {
+            Some(response) => Json(response).into_response(),
+            None => StatusCode::NO_CONTENT.into_response(),
+        },
+    }
+}
+
+// Returns `None` for notifications, i.e., requests without an id, which are not responded to.
+async fn handle_request(app_state: &AppState, request: Value) -> Option<JsonRpcResponse> {
+    let Value::Object(mut request) = request else {
+        return Some(JsonRpcResponse::new(Value::Null, Err(JsonRpcErrorObject::invalid_request())));
+    };
+
+    let id = request.remove("id");
+    let is_valid_id = match &id {
+        None | Some(Value::Null | Value::Number(_) | Value::String(_)) => true,
+        Some(_) => false,
+    };
+    let is_valid_version = request.get("jsonrpc").and_then(Value::as_str) == Some(JSON_RPC_VERSION);
+    let (true, true, Some(Value::String(method))) =
+        (is_valid_id, is_valid_version, request.remove("method"))
+    else {
+        return Some(JsonRpcResponse::new(Value::Null, Err(JsonRpcErrorObject::invalid_request())));
+    };
+    let params = request.remove("params").unwrap_or(Value::Null);
+
+    let result = handle_method(app_state, &method, params).await;
+    id.map(|id| JsonRpcResponse::new(id, result))
+}
+
+async fn handle_method(
+    app_state: &AppState,
+    method: &str,
+    params: Value,
+) -> Result<Value, JsonRpcErrorObject> {
+    let param_name = match method {
+        ADD_DECLARE_TRANSACTION => "declare_transaction",
+        ADD_DEPLOY_ACCOUNT_TRANSACTION => "deploy_account_transaction",
+        ADD_INVOKE_TRANSACTION => "invoke_transaction",
+        _ => return Err(JsonRpcErrorObject::method_not_found()),
+    };
+    let tx = parse_tx_param(params, param_name)?;
+    let is_expected_tx_type = match &tx {
+        RpcTransaction::Declare(_) => method == ADD_DECLARE_TRANSACTION,
+        RpcTransaction::DeployAccount(_) => method == ADD_DEPLOY_ACCOUNT_TRANSACTION,
+        RpcTransaction::Invoke(_) => method == ADD_INVOKE_TRANSACTION,
+    };
+    if !is_expected_tx_type {
+        return Err(JsonRpcErrorObject::invalid_params(format!(
+            "Transaction type does not match method {method}."
+        )));
+    }
+
+    add_tx(app_state, tx).await.map_err(JsonRpcErrorObject::from)
+}
+
+// Params are given either by name or by position.
+fn parse_tx_param(params: Value, param_name: &str) -> Result<RpcTransaction, JsonRpcErrorObject> {
+    let tx = match params {
+        Value::Object(mut params) if params.len() == 1 => params.remove(param_name),
+        Value::Array(params) if params.len() == 1 => params.into_iter().next(),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        JsonRpcErrorObject::invalid_params(format!("Expected a single param: {param_name}."))
+    })?;
+
+    serde_json::from_value(tx).map_err(|err| JsonRpcErrorObject::invalid_params(err.to_string()))
+}
+
+// Returns the result of an add transaction method, as defined in the specs.
+async fn add_tx(app_state: &AppState, tx: RpcTransaction) -> Result<Value, GatewaySpecError> {
+    let result_without_tx_hash = match &tx {
+        RpcTransaction::Declare(RpcDeclareTransaction::V3(declare_tx)) => {
+            json!({ "class_hash": calculate_class_hash(&declare_tx.contract_class) })
+        }
+        RpcTransaction::DeployAccount(_) => {
+            let contract_address = tx
+                .calculate_sender_address()
+                .map_err(|err| GatewaySpecError::ValidationFailure { data: err.to_string() })?;
+            json!({ "contract_address": contract_address })
+        }
+        RpcTransaction::Invoke(_) => json!({}),
+    };
+
+    let gateway_input = GatewayInput { rpc_tx: tx, message_metadata: None };
+    let tx_hash =
+        app_state.gateway_client.add_tx(gateway_input).await.map_err(|err| match err {
+            GatewayClientError::GatewayError(GatewayError::GatewaySpecError { source, .. }) => {
+                source
+            }
+            GatewayClientError::ClientError(err) => {
+                error!("Failed to process tx: {}", err);
+                GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
+            }
+        })?;
+
+    let mut result = result_without_tx_hash;
+    result["transaction_hash"] = json!(tx_hash);
+    Ok(result)
+}
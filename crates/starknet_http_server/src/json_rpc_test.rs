@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use hyper::body::to_bytes;
+use mockall::predicate::eq;
+use papyrus_common::class_hash::calculate_class_hash;
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use serde_json::{json, Value};
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::state::SierraContractClass;
+use starknet_api::test_utils::declare::rpc_declare_tx;
+use starknet_api::test_utils::deploy_account::rpc_deploy_account_tx;
+use starknet_api::test_utils::invoke::rpc_invoke_tx;
+use starknet_api::test_utils::rpc_tx_to_json;
+use starknet_api::transaction::TransactionHash;
+use starknet_api::{declare_tx_args, deploy_account_tx_args, invoke_tx_args, tx_hash};
+use starknet_gateway_types::communication::{GatewayClientError, MockGatewayClient};
+use starknet_gateway_types::errors::{GatewayError, GatewaySpecError};
+use starknet_gateway_types::gateway_types::GatewayInput;
+use tower::ServiceExt;
+
+use crate::config::HttpServerConfig;
+use crate::http_server::HttpServer;
+use crate::json_rpc::{
+    ADD_DECLARE_TRANSACTION,
+    ADD_DEPLOY_ACCOUNT_TRANSACTION,
+    ADD_INVOKE_TRANSACTION,
+};
+
+fn returned_tx_hash() -> TransactionHash {
+    tx_hash!(1)
+}
+
+fn tx_as_json(tx: &RpcTransaction) -> Value {
+    serde_json::from_str(&rpc_tx_to_json(tx)).unwrap()
+}
+
+fn json_rpc_request(id: u64, method: &str, tx: &RpcTransaction) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": [tx_as_json(tx)] })
+}
+
+fn mock_gateway_client_accepting(expected_txs: Vec<RpcTransaction>) -> MockGatewayClient {
+    let mut gateway_client = MockGatewayClient::new();
+    for rpc_tx in expected_txs {
+        gateway_client
+            .expect_add_tx()
+            .times(1)
+            .with(eq(GatewayInput { rpc_tx, message_metadata: None }))
+            .return_once(|_| Ok(returned_tx_hash()));
+    }
+    gateway_client
+}
+
+async fn send_json_rpc(gateway_client: MockGatewayClient, body: String) -> (StatusCode, Value) {
+    let app = HttpServer::new(HttpServerConfig::default(), Arc::new(gateway_client)).app();
+    let request = Request::post("/rpc")
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body = if body.is_empty() { Value::Null } else { serde_json::from_slice(&body).unwrap() };
+    (status, body)
+}
+
+#[tokio::test]
+async fn test_add_invoke_transaction() {
+    let tx = rpc_invoke_tx(invoke_tx_args!());
+    let gateway_client = mock_gateway_client_accepting(vec![tx.clone()]);
+
+    // Params by name.
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": "request",
+        "method": ADD_INVOKE_TRANSACTION,
+        "params": { "invoke_transaction": tx_as_json(&tx) },
+    });
+    let (status, response) = send_json_rpc(gateway_client, request.to_string()).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        response,
+        json!({
+            "jsonrpc": "2.0",
+            "result": { "transaction_hash": returned_tx_hash() },
+            "id": "request",
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_add_deploy_account_transaction() {
+    let tx = rpc_deploy_account_tx(deploy_account_tx_args!());
+    let contract_address = tx.calculate_sender_address().unwrap();
+    let gateway_client = mock_gateway_client_accepting(vec![tx.clone()]);
+
+    let request = json_rpc_request(1, ADD_DEPLOY_ACCOUNT_TRANSACTION, &tx);
+    let (_, response) = send_json_rpc(gateway_client, request.to_string()).await;
+
+    assert_eq!(
+        response["result"],
+        json!({ "transaction_hash": returned_tx_hash(), "contract_address": contract_address })
+    );
+}
+
+#[tokio::test]
+async fn test_add_declare_transaction() {
+    let contract_class = SierraContractClass::default();
+    let class_hash = calculate_class_hash(&contract_class);
+    let tx = rpc_declare_tx(declare_tx_args!(), contract_class);
+    let gateway_client = mock_gateway_client_accepting(vec![tx.clone()]);
+
+    let request = json_rpc_request(1, ADD_DECLARE_TRANSACTION, &tx);
+    let (_, response) = send_json_rpc(gateway_client, request.to_string()).await;
+
+    assert_eq!(
+        response["result"],
+        json!({ "transaction_hash": returned_tx_hash(), "class_hash": class_hash })
+    );
+}
+
+#[tokio::test]
+async fn test_gateway_error_is_mapped_to_spec_error() {
+    let spec_error = GatewaySpecError::InvalidTransactionNonce;
+    let mut gateway_client = MockGatewayClient::new();
+    let returned_error = spec_error.clone();
+    gateway_client.expect_add_tx().return_once(move |_| {
+        Err(GatewayClientError::GatewayError(GatewayError::GatewaySpecError {
+            source: returned_error,
+            p2p_message_metadata: None,
+        }))
+    });
+
+    let request = json_rpc_request(1, ADD_INVOKE_TRANSACTION, &rpc_invoke_tx(invoke_tx_args!()));
+    let (status, response) = send_json_rpc(gateway_client, request.to_string()).await;
+
+    let expected_error = spec_error.into_rpc();
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        response["error"],
+        json!({ "code": expected_error.code, "message": expected_error.message })
+    );
+}
+
+#[tokio::test]
+async fn test_batch() {
+    let invoke_tx = rpc_invoke_tx(invoke_tx_args!());
+    let deploy_account_tx = rpc_deploy_account_tx(deploy_account_tx_args!());
+    let gateway_client =
+        mock_gateway_client_accepting(vec![invoke_tx.clone(), deploy_account_tx.clone()]);
+
+    let mut notification = json_rpc_request(0, ADD_DEPLOY_ACCOUNT_TRANSACTION, &deploy_account_tx);
+    notification.as_object_mut().unwrap().remove("id");
+    let request = json!([
+        json_rpc_request(1, ADD_INVOKE_TRANSACTION, &invoke_tx),
+        notification,
+        json_rpc_request(2, "starknet_unknownMethod", &invoke_tx),
+        // The transaction type does not match the method.
+        json_rpc_request(3, ADD_DECLARE_TRANSACTION, &invoke_tx),
+    ]);
+    let (_, response) = send_json_rpc(gateway_client, request.to_string()).await;
+
+    let responses = response.as_array().unwrap();
+    let ids: Vec<_> = responses.iter().map(|response| response["id"].clone()).collect();
+    assert_eq!(ids, [json!(1), json!(2), json!(3)]);
+    assert_eq!(responses[0]["result"], json!({ "transaction_hash": returned_tx_hash() }));
+    assert_eq!(responses[1]["error"]["code"], json!(-32601));
+    assert_eq!(responses[2]["error"]["code"], json!(-32602));
+}
+
+#[tokio::test]
+async fn test_oversized_batch_is_rejected() {
+    let tx = rpc_invoke_tx(invoke_tx_args!());
+    let max_json_rpc_batch_size = HttpServerConfig::default().max_json_rpc_batch_size;
+    let request: Vec<_> = (0..=max_json_rpc_batch_size)
+        .map(|id| json_rpc_request(id.try_into().unwrap(), ADD_INVOKE_TRANSACTION, &tx))
+        .collect();
+
+    // None of the requests reach the gateway.
+    let (status, response) =
+        send_json_rpc(MockGatewayClient::new(), Value::from(request).to_string()).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response["error"]["code"], json!(-32600));
+}
+
+#[rstest]
+#[case::parse_error("{", -32700)]
+#[case::empty_batch("[]", -32600)]
+#[case::not_an_object("1", -32600)]
+#[case::wrong_version(r#"{"jsonrpc": "1.0", "id": 1, "method": "starknet_addInvokeTransaction"}"#,
+    -32600
+)]
+#[case::missing_params(r#"{"jsonrpc": "2.0", "id": 1, "method": "starknet_addInvokeTransaction"}"#,
+    -32602
+)]
+#[tokio::test]
+async fn test_invalid_request(#[case] body: &str, #[case] expected_code: i64) {
+    let (status, response) = send_json_rpc(MockGatewayClient::new(), body.to_owned()).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response["error"]["code"], json!(expected_code));
+}
+
+#[tokio::test]
+async fn test_notification_is_not_answered() {
+    let tx = rpc_invoke_tx(invoke_tx_args!());
+    let gateway_client = mock_gateway_client_accepting(vec![tx.clone()]);
+
+    let mut request = json_rpc_request(1, ADD_INVOKE_TRANSACTION, &tx);
+    request.as_object_mut().unwrap().remove("id");
+    let (status, response) = send_json_rpc(gateway_client, request.to_string()).await;
+
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(response, Value::Null);
+}
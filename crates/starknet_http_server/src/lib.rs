@@ -1,6 +1,9 @@
+pub mod admission_control;
 pub mod communication;
 pub mod config;
 pub mod errors;
 pub mod http_server;
+pub mod json_rpc;
+pub mod metrics;
 #[cfg(feature = "testing")]
 pub mod test_utils;
@@ -0,0 +1,9 @@
+/// The number of requests rejected because their client exceeded its rate limit.
+pub const HTTP_SERVER_RATE_LIMITED_REQUESTS: &str = "http_server_rate_limited_requests";
+
+/// The number of requests rejected because the server was handling too many requests
+/// concurrently.
+pub const HTTP_SERVER_OVERLOADED_REQUESTS: &str = "http_server_overloaded_requests";
+
+/// The number of requests rejected because their body exceeded the size limit.
+pub const HTTP_SERVER_OVERSIZED_REQUESTS: &str = "http_server_oversized_requests";
@@ -51,5 +51,5 @@ impl HttpTestClient {
 pub async fn create_http_server_config() -> HttpServerConfig {
     // TODO(Tsabary): use ser_generated_param.
     let socket = get_available_socket().await;
-    HttpServerConfig { ip: socket.ip(), port: socket.port() }
+    HttpServerConfig { ip: socket.ip(), port: socket.port(), ..Default::default() }
 }
@@ -62,7 +62,7 @@ impl FlowTestSetup {
 
         let (_clients, servers) = create_node_modules(&config);
 
-        let HttpServerConfig { ip, port } = config.http_server_config;
+        let HttpServerConfig { ip, port, .. } = config.http_server_config;
         let add_tx_http_client = HttpTestClient::new(SocketAddr::from((ip, port)));
 
         // Build and run the sequencer node.
@@ -60,10 +60,10 @@ impl IntegrationTestSetup {
         );
 
         // Wait for the node to start.
-        let MonitoringEndpointConfig { ip, port } = config.monitoring_endpoint_config;
+        let MonitoringEndpointConfig { ip, port, .. } = config.monitoring_endpoint_config;
         let is_alive_test_client = IsAliveClient::new(SocketAddr::from((ip, port)));
 
-        let HttpServerConfig { ip, port } = config.http_server_config;
+        let HttpServerConfig { ip, port, .. } = config.http_server_config;
         let add_tx_http_client = HttpTestClient::new(SocketAddr::from((ip, port)));
 
         IntegrationTestSetup {
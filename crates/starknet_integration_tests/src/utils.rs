@@ -6,6 +6,7 @@ use blockifier::context::ChainInfo;
 use blockifier::test_utils::contracts::FeatureContract;
 use blockifier::test_utils::CairoVersion;
 use mempool_test_utils::starknet_api_test_utils::{AccountId, MultiAccountTransactionGenerator};
+use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerConfig;
 use papyrus_consensus::config::ConsensusConfig;
 use papyrus_network::network_manager::test_utils::create_network_configs_connected_to_broadcast_channels;
 use papyrus_network::network_manager::BroadcastTopicChannels;
@@ -22,6 +23,7 @@ use starknet_consensus_manager::config::ConsensusManagerConfig;
 use starknet_gateway::config::{
     GatewayConfig,
     RpcStateReaderConfig,
+    StateReaderCacheConfig,
     StatefulTransactionValidatorConfig,
     StatelessTransactionValidatorConfig,
 };
@@ -29,6 +31,7 @@ use starknet_http_server::config::HttpServerConfig;
 use starknet_sequencer_infra::test_utils::get_available_socket;
 use starknet_sequencer_node::config::node_config::SequencerNodeConfig;
 use starknet_sequencer_node::config::test_utils::RequiredParams;
+use url::Url;
 
 pub fn create_chain_info() -> ChainInfo {
     let mut chain_info = ChainInfo::create_for_testing();
@@ -51,8 +54,13 @@ pub async fn create_config(
     let (mut consensus_manager_configs, consensus_proposals_channels) =
         create_consensus_manager_configs_and_channels(1);
     let consensus_manager_config = consensus_manager_configs.pop().unwrap();
+    // No L1 node runs in these tests, so the L1 scraper keeps retrying in the background.
+    let eth_node_url = Url::parse("http://localhost:8545").unwrap();
+    let base_layer_config =
+        EthereumBaseLayerConfig { node_url: eth_node_url.clone(), ..Default::default() };
     (
         SequencerNodeConfig {
+            base_layer_config,
             batcher_config,
             consensus_manager_config,
             gateway_config,
@@ -65,6 +73,7 @@ pub async fn create_config(
             eth_fee_token_address: fee_token_addresses.eth_fee_token_address,
             strk_fee_token_address: fee_token_addresses.strk_fee_token_address,
             sequencer_address: ContractAddress::from(1312_u128), // Arbitrary non-zero value.
+            eth_node_url,
         },
         consensus_proposals_channels,
     )
@@ -90,8 +99,12 @@ fn create_consensus_manager_configs_and_channels(
                 start_height: BlockNumber(1),
                 consensus_delay: Duration::from_secs(1),
                 network_config,
+                // The managers run in the same directory, so they can't share the default WAL.
+                wal_dir: None,
+                insecure_test_signatures: true,
                 ..Default::default()
             },
+            ..Default::default()
         })
         .collect();
 
@@ -210,13 +223,19 @@ pub async fn create_gateway_config(chain_info: ChainInfo) -> GatewayConfig {
     };
     let stateful_tx_validator_config = StatefulTransactionValidatorConfig::default();
 
-    GatewayConfig { stateless_tx_validator_config, stateful_tx_validator_config, chain_info }
+    GatewayConfig {
+        stateless_tx_validator_config,
+        stateful_tx_validator_config,
+        state_reader_cache_config: StateReaderCacheConfig::default(),
+        read_state_from_storage: false,
+        chain_info,
+    }
 }
 
 pub async fn create_http_server_config() -> HttpServerConfig {
     // TODO(Tsabary): use ser_generated_param.
     let socket = get_available_socket().await;
-    HttpServerConfig { ip: socket.ip(), port: socket.port() }
+    HttpServerConfig { ip: socket.ip(), port: socket.port(), ..Default::default() }
 }
 
 pub fn create_batcher_config(
@@ -2,6 +2,7 @@ use std::collections::HashSet;
 
 use futures::StreamExt;
 use mempool_test_utils::starknet_api_test_utils::MultiAccountTransactionGenerator;
+use papyrus_consensus::signing::{ConsensusSigner, TestSigner};
 use papyrus_network::network_manager::BroadcastTopicChannels;
 use papyrus_protobuf::consensus::{
     ProposalFin,
@@ -91,7 +92,13 @@ async fn listen_to_broadcasted_messages(
         valid_round: None,
         proposer: ContractAddress::default(),
     };
-    let expected_proposal_fin = ProposalFin { proposal_content_id: BlockHash(expected_content_id) };
+    let expected_proposal_fin = TestSigner::new(expected_proposal_init.proposer)
+        .sign_proposal_fin(
+            expected_height,
+            expected_proposal_init.round,
+            ProposalFin { proposal_content_id: BlockHash(expected_content_id), signature: None },
+        )
+        .unwrap();
 
     let StreamMessage {
         stream_id: first_stream_id,
@@ -117,6 +124,9 @@ async fn listen_to_broadcasted_messages(
             StreamMessageBody::Content(ProposalPart::Init(init)) => {
                 panic!("Unexpected init: {:?}", init)
             }
+            StreamMessageBody::Content(ProposalPart::BlockInfo(block_info)) => {
+                assert_eq!(block_info.height, expected_proposal_init.height);
+            }
             StreamMessageBody::Content(ProposalPart::Transactions(transactions)) => {
                 received_tx_hashes.extend(
                     transactions
@@ -95,7 +95,7 @@ async fn test_mempool_sends_tx_to_other_peer(mut tx_generator: MultiAccountTrans
 
     let (_clients, servers) = create_node_modules(&config);
 
-    let HttpServerConfig { ip, port } = config.http_server_config;
+    let HttpServerConfig { ip, port, .. } = config.http_server_config;
     let add_tx_http_client = HttpTestClient::new(SocketAddr::from((ip, port)));
 
     // Build and run the sequencer node.
@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use papyrus_base_layer::ethereum_base_layer_contract::{
+    EthereumBaseLayerContract,
+    EthereumBaseLayerError,
+};
+use papyrus_base_layer::BaseLayerContract;
+use starknet_l1_gas_price_types::communication::{L1GasPriceRequest, L1GasPriceResponse};
+use starknet_sequencer_infra::component_definitions::{ComponentRequestHandler, ComponentStarter};
+use starknet_sequencer_infra::component_server::{
+    LocalComponentServer,
+    RemoteComponentServer,
+    WrapperServer,
+};
+use starknet_sequencer_infra::errors::ComponentError;
+
+use crate::l1_gas_price_provider::L1GasPriceProvider;
+use crate::l1_gas_price_scraper::L1GasPriceScraper;
+
+pub type LocalL1GasPriceProviderServer =
+    LocalComponentServer<L1GasPriceProvider, L1GasPriceRequest, L1GasPriceResponse>;
+pub type RemoteL1GasPriceProviderServer =
+    RemoteComponentServer<L1GasPriceRequest, L1GasPriceResponse>;
+pub type L1GasPriceScraperServer = WrapperServer<L1GasPriceScraper<EthereumBaseLayerContract>>;
+
+#[async_trait]
+impl ComponentRequestHandler<L1GasPriceRequest, L1GasPriceResponse> for L1GasPriceProvider {
+    async fn handle_request(&mut self, request: L1GasPriceRequest) -> L1GasPriceResponse {
+        match request {
+            L1GasPriceRequest::AddPriceInfo(height, sample) => {
+                L1GasPriceResponse::AddPriceInfo(self.add_price_info(height, sample))
+            }
+            L1GasPriceRequest::GetPriceInfo(timestamp) => {
+                L1GasPriceResponse::GetPriceInfo(self.get_price_info(timestamp))
+            }
+        }
+    }
+}
+
+impl ComponentStarter for L1GasPriceProvider {}
+
+#[async_trait]
+impl<B> ComponentStarter for L1GasPriceScraper<B>
+where
+    B: BaseLayerContract<Error = EthereumBaseLayerError> + Send + Sync,
+{
+    async fn start(&mut self) -> Result<(), ComponentError> {
+        self.run().await;
+        Ok(())
+    }
+}
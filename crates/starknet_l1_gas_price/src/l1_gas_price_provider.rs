@@ -0,0 +1,149 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockTimestamp;
+use starknet_l1_gas_price_types::errors::L1GasPriceProviderError;
+use starknet_l1_gas_price_types::l1_gas_price_types::{
+    L1GasPriceProviderResult,
+    PriceInfo,
+    PriceSample,
+};
+use validator::{Validate, ValidationError};
+
+#[cfg(test)]
+#[path = "l1_gas_price_provider_tests.rs"]
+pub mod l1_gas_price_provider_tests;
+
+/// Stores the gas prices of recent L1 blocks, and serves their mean over a fixed window of blocks.
+/// The window is selected by the timestamp of the L2 block, so that all nodes that agree on the
+/// timestamp compute the same prices.
+#[derive(Clone, Debug, Default)]
+pub struct L1GasPriceProvider {
+    config: L1GasPriceProviderConfig,
+    // Ordered by L1 block height, with no gaps.
+    price_samples_by_height: VecDeque<(u64, PriceSample)>,
+}
+
+impl L1GasPriceProvider {
+    pub fn new(config: L1GasPriceProviderConfig) -> Self {
+        Self { config, price_samples_by_height: VecDeque::new() }
+    }
+
+    pub fn add_price_info(
+        &mut self,
+        height: u64,
+        sample: PriceSample,
+    ) -> L1GasPriceProviderResult<()> {
+        if let Some((last_height, _)) = self.price_samples_by_height.back() {
+            let expected = last_height + 1;
+            if height != expected {
+                return Err(L1GasPriceProviderError::UnexpectedHeight { expected, found: height });
+            }
+        }
+
+        self.price_samples_by_height.push_back((height, sample));
+        if self.price_samples_by_height.len() > self.config.storage_limit {
+            self.price_samples_by_height.pop_front();
+        }
+        Ok(())
+    }
+
+    /// Returns the mean gas prices of the `number_of_blocks_for_mean` L1 blocks that precede
+    /// `timestamp` by at least `lag_margin_seconds`. The margin gives all nodes time to see the
+    /// same L1 blocks.
+    pub fn get_price_info(&self, timestamp: BlockTimestamp) -> L1GasPriceProviderResult<PriceInfo> {
+        let cutoff = timestamp.0.saturating_sub(self.config.lag_margin_seconds);
+        // L1 timestamps are non-decreasing.
+        let n_candidates =
+            self.price_samples_by_height.partition_point(|(_, sample)| sample.timestamp <= cutoff);
+        let n_blocks = usize::try_from(self.config.number_of_blocks_for_mean)
+            .expect("number_of_blocks_for_mean should fit in usize.");
+        if n_candidates < n_blocks {
+            return Err(L1GasPriceProviderError::InsufficientHistory {
+                expected: n_blocks,
+                found: n_candidates,
+            });
+        }
+
+        let window = self.price_samples_by_height.range(n_candidates - n_blocks..n_candidates);
+        let (base_fee_sum, blob_fee_sum) =
+            window.fold((0_u128, 0_u128), |(base_fee_sum, blob_fee_sum), (_, sample)| {
+                (
+                    base_fee_sum.saturating_add(sample.base_fee_per_gas),
+                    blob_fee_sum.saturating_add(sample.blob_fee),
+                )
+            });
+        let n_blocks = u128::from(self.config.number_of_blocks_for_mean);
+        Ok(PriceInfo {
+            base_fee_per_gas: base_fee_sum / n_blocks,
+            blob_fee: blob_fee_sum / n_blocks,
+        })
+    }
+}
+
+pub fn create_l1_gas_price_provider(config: L1GasPriceProviderConfig) -> L1GasPriceProvider {
+    L1GasPriceProvider::new(config)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Validate)]
+#[validate(schema(function = "validate_l1_gas_price_provider_config"))]
+pub struct L1GasPriceProviderConfig {
+    #[validate(range(min = 1))]
+    pub number_of_blocks_for_mean: u64,
+    pub lag_margin_seconds: u64,
+    pub storage_limit: usize,
+}
+
+impl Default for L1GasPriceProviderConfig {
+    fn default() -> Self {
+        Self {
+            // An hour of 12 second L1 blocks.
+            number_of_blocks_for_mean: 300,
+            lag_margin_seconds: 60,
+            // Ten hours of L1 blocks.
+            storage_limit: 3000,
+        }
+    }
+}
+
+fn validate_l1_gas_price_provider_config(
+    config: &L1GasPriceProviderConfig,
+) -> Result<(), ValidationError> {
+    // Otherwise, the provider never holds enough blocks to serve prices.
+    let fits_in_storage = usize::try_from(config.number_of_blocks_for_mean)
+        .is_ok_and(|number_of_blocks_for_mean| number_of_blocks_for_mean <= config.storage_limit);
+    if !fits_in_storage {
+        return Err(ValidationError::new(
+            "number_of_blocks_for_mean should be at most storage_limit",
+        ));
+    }
+    Ok(())
+}
+
+impl SerializeConfig for L1GasPriceProviderConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "number_of_blocks_for_mean",
+                &self.number_of_blocks_for_mean,
+                "Number of L1 blocks to average the gas prices over.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "lag_margin_seconds",
+                &self.lag_margin_seconds,
+                "Only L1 blocks at least this many seconds older than the L2 block are used for \
+                 its gas prices.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "storage_limit",
+                &self.storage_limit,
+                "Maximal number of L1 blocks whose gas prices are kept.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
@@ -0,0 +1,96 @@
+use assert_matches::assert_matches;
+use pretty_assertions::assert_eq;
+use starknet_api::block::BlockTimestamp;
+use starknet_l1_gas_price_types::errors::L1GasPriceProviderError;
+use starknet_l1_gas_price_types::l1_gas_price_types::{PriceInfo, PriceSample};
+use validator::Validate;
+
+use crate::l1_gas_price_provider::{L1GasPriceProvider, L1GasPriceProviderConfig};
+
+const LAG_MARGIN_SECONDS: u64 = 10;
+
+// Block `i` has timestamp `i`, base fee `i` and blob fee `2 * i`.
+fn provider_with_blocks(n_blocks: u64, number_of_blocks_for_mean: u64) -> L1GasPriceProvider {
+    let mut provider = L1GasPriceProvider::new(L1GasPriceProviderConfig {
+        number_of_blocks_for_mean,
+        lag_margin_seconds: LAG_MARGIN_SECONDS,
+        storage_limit: 100,
+    });
+    for height in 0..n_blocks {
+        let sample = PriceSample {
+            timestamp: height,
+            base_fee_per_gas: height.into(),
+            blob_fee: (2 * height).into(),
+        };
+        provider.add_price_info(height, sample).unwrap();
+    }
+    provider
+}
+
+#[test]
+fn get_price_info_averages_blocks_before_lag_margin() {
+    // Setup.
+    let provider = provider_with_blocks(50, 3);
+
+    // Test.
+    // Blocks 18, 19 and 20 are the latest ones at least `LAG_MARGIN_SECONDS` before timestamp 30.
+    assert_eq!(
+        provider.get_price_info(BlockTimestamp(30)).unwrap(),
+        PriceInfo { base_fee_per_gas: 19, blob_fee: 38 }
+    );
+    // Blocks that are too recent don't affect the prices.
+    assert_eq!(
+        provider.get_price_info(BlockTimestamp(100)).unwrap(),
+        PriceInfo { base_fee_per_gas: 48, blob_fee: 96 }
+    );
+}
+
+#[test]
+fn get_price_info_insufficient_history() {
+    // Setup.
+    let provider = provider_with_blocks(50, 3);
+
+    // Test.
+    assert_eq!(
+        provider.get_price_info(BlockTimestamp(LAG_MARGIN_SECONDS + 1)).unwrap_err(),
+        L1GasPriceProviderError::InsufficientHistory { expected: 3, found: 2 }
+    );
+}
+
+#[test]
+fn add_price_info_rejects_non_consecutive_heights() {
+    // Setup.
+    let mut provider = provider_with_blocks(5, 3);
+
+    // Test.
+    assert_matches!(
+        provider.add_price_info(7, PriceSample::default()),
+        Err(L1GasPriceProviderError::UnexpectedHeight { expected: 5, found: 7 })
+    );
+}
+
+#[test]
+fn storage_limit_drops_oldest_blocks() {
+    // Setup.
+    let provider = provider_with_blocks(150, 3);
+
+    // Test.
+    // Only the latest 100 blocks are kept, so only blocks 50 and 51 precede the cutoff.
+    assert_matches!(
+        provider.get_price_info(BlockTimestamp(51 + LAG_MARGIN_SECONDS)),
+        Err(L1GasPriceProviderError::InsufficientHistory { expected: 3, found: 2 })
+    );
+}
+
+#[test]
+fn config_validation_rejects_mean_over_storage_limit() {
+    let config = L1GasPriceProviderConfig {
+        number_of_blocks_for_mean: 11,
+        storage_limit: 10,
+        ..Default::default()
+    };
+    assert!(config.validate().is_err());
+
+    let config = L1GasPriceProviderConfig { storage_limit: 11, ..config };
+    assert!(config.validate().is_ok());
+}
@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerError;
+use papyrus_base_layer::{BaseLayerContract, L1BlockNumber};
+use papyrus_config::converters::deserialize_seconds_to_duration;
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_l1_gas_price_types::communication::{
+    L1GasPriceProviderClientError,
+    SharedL1GasPriceProviderClient,
+};
+use starknet_l1_gas_price_types::errors::L1GasPriceProviderError;
+use starknet_l1_gas_price_types::l1_gas_price_types::PriceSample;
+use starknet_sequencer_infra::component_definitions::{ComponentHealth, HealthStatus};
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+#[cfg(test)]
+#[path = "l1_gas_price_scraper_tests.rs"]
+pub mod l1_gas_price_scraper_tests;
+
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+type L1GasPriceScraperResult<T> = Result<T, L1GasPriceScraperError>;
+
+/// Polls the base layer for block headers and feeds their gas prices to the L1 gas price provider.
+pub struct L1GasPriceScraper<B: BaseLayerContract> {
+    pub config: L1GasPriceScraperConfig,
+    pub base_layer: B,
+    pub next_block_number_to_scrape: L1BlockNumber,
+    pub l1_gas_price_provider: SharedL1GasPriceProviderClient,
+    health: L1GasPriceScraperHealth,
+}
+
+impl<B> L1GasPriceScraper<B>
+where
+    B: BaseLayerContract<Error = EthereumBaseLayerError> + Send + Sync,
+{
+    /// Creates a scraper which, once running, starts `startup_num_blocks` blocks back, so that the
+    /// provider can serve prices as soon as possible.
+    pub fn new(
+        config: L1GasPriceScraperConfig,
+        base_layer: B,
+        l1_gas_price_provider: SharedL1GasPriceProviderClient,
+    ) -> Self {
+        Self {
+            config,
+            base_layer,
+            next_block_number_to_scrape: 0,
+            l1_gas_price_provider,
+            health: L1GasPriceScraperHealth::default(),
+        }
+    }
+
+    /// Returns a handle that reports the progress of the scraper to the monitoring endpoint.
+    pub fn health(&self) -> L1GasPriceScraperHealth {
+        self.health.clone()
+    }
+
+    /// Sets the next block to scrape to `startup_num_blocks` before the latest L1 block.
+    pub async fn initialize(&mut self) -> L1GasPriceScraperResult<()> {
+        let latest_l1_block_number =
+            self.base_layer.latest_l1_block_number(self.config.finality).await?.unwrap_or_default();
+        self.next_block_number_to_scrape =
+            latest_l1_block_number.saturating_sub(self.config.startup_num_blocks);
+        info!("Starting L1 gas price scraping from L1 block {}.", self.next_block_number_to_scrape);
+        Ok(())
+    }
+
+    /// Sends the gas prices of all blocks since the last scrape, up to the latest finalized L1
+    /// block, to the provider.
+    pub async fn update_prices(&mut self) -> L1GasPriceScraperResult<()> {
+        let Some(latest_l1_block_number) =
+            self.base_layer.latest_l1_block_number(self.config.finality).await?
+        else {
+            debug!("No finalized L1 blocks yet, skipping scrape.");
+            return Ok(());
+        };
+
+        while self.next_block_number_to_scrape <= latest_l1_block_number {
+            let Some(header) =
+                self.base_layer.get_block_header(self.next_block_number_to_scrape).await?
+            else {
+                // The node is lagging behind the block number it reported, try again later.
+                break;
+            };
+            let sample = PriceSample {
+                timestamp: header.timestamp,
+                base_fee_per_gas: header.base_fee_per_gas,
+                blob_fee: header.blob_fee,
+            };
+            self.l1_gas_price_provider
+                .add_price_info(self.next_block_number_to_scrape, sample)
+                .await?;
+            self.next_block_number_to_scrape += 1;
+        }
+        Ok(())
+    }
+
+    /// Initializes, then scrapes L1 every `polling_interval`. Failures, e.g. due to an unavailable
+    /// base layer, are retried with an exponential backoff, up to `MAX_RETRY_INTERVAL` between
+    /// attempts. If the provider expects a different L1 block than the one sent, e.g. after either
+    /// of them restarted, scraping resumes from the block the provider expects. Never returns.
+    pub async fn run(&mut self) {
+        let mut is_initialized = false;
+        let mut retry_interval = self.config.polling_interval;
+        loop {
+            let result =
+                if is_initialized { self.update_prices().await } else { self.initialize().await };
+            match result {
+                Ok(()) => {
+                    is_initialized = true;
+                    retry_interval = self.config.polling_interval;
+                    self.health.record_success(self.next_block_number_to_scrape);
+                    tokio::time::sleep(self.config.polling_interval).await;
+                }
+                Err(L1GasPriceScraperError::L1GasPriceProviderClient(
+                    L1GasPriceProviderClientError::L1GasPriceProviderError(
+                        L1GasPriceProviderError::UnexpectedHeight { expected, found },
+                    ),
+                )) => {
+                    warn!(
+                        "L1 gas price provider expects L1 block {expected}, not {found}; resuming \
+                         scraping from it."
+                    );
+                    self.next_block_number_to_scrape = expected;
+                    tokio::time::sleep(self.config.polling_interval).await;
+                }
+                Err(err) => {
+                    warn!("Failed to scrape L1 gas prices, retrying in {retry_interval:?}: {err}");
+                    self.health.record_failure(&err, Instant::now());
+                    tokio::time::sleep(retry_interval).await;
+                    retry_interval = (retry_interval * 2).min(MAX_RETRY_INTERVAL);
+                }
+            }
+        }
+    }
+}
+
+/// The progress of the scraper, shared with the monitoring endpoint. The scraper is unhealthy if
+/// its scrapes keep failing for longer than `MAX_RETRY_INTERVAL`, e.g., when the base layer is
+/// unavailable, as the gas prices it serves then become stale.
+#[derive(Clone, Debug, Default)]
+pub struct L1GasPriceScraperHealth {
+    state: Arc<Mutex<ScraperProgress>>,
+}
+
+#[derive(Debug, Default)]
+struct ScraperProgress {
+    next_block_number_to_scrape: Option<L1BlockNumber>,
+    // When the current streak of failed scrapes started, and the last failure.
+    failing_since: Option<(Instant, String)>,
+}
+
+impl L1GasPriceScraperHealth {
+    pub fn record_success(&self, next_block_number_to_scrape: L1BlockNumber) {
+        let mut state = self.state.lock().expect("Lock should not be poisoned.");
+        state.next_block_number_to_scrape = Some(next_block_number_to_scrape);
+        state.failing_since = None;
+    }
+
+    pub fn record_failure(&self, err: &L1GasPriceScraperError, now: Instant) {
+        let mut state = self.state.lock().expect("Lock should not be poisoned.");
+        let failing_since = state.failing_since.as_ref().map_or(now, |(since, _)| *since);
+        state.failing_since = Some((failing_since, err.to_string()));
+    }
+
+    pub fn health_status(&self, now: Instant) -> HealthStatus {
+        let state = self.state.lock().expect("Lock should not be poisoned.");
+        if let Some((failing_since, err)) = &state.failing_since {
+            let failing_duration = now.saturating_duration_since(*failing_since);
+            if failing_duration > MAX_RETRY_INTERVAL {
+                return HealthStatus::Unhealthy {
+                    reason: format!(
+                        "L1 gas price scraping fails for {} seconds: {err}",
+                        failing_duration.as_secs()
+                    ),
+                };
+            }
+        }
+        match state.next_block_number_to_scrape {
+            Some(block_number) => HealthStatus::Healthy {
+                details: format!("Next L1 block to scrape: {block_number}."),
+            },
+            None => HealthStatus::Healthy {
+                details: "L1 gas price scraping hasn't started yet.".to_owned(),
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl ComponentHealth for L1GasPriceScraperHealth {
+    async fn check_health(&self) -> HealthStatus {
+        self.health_status(Instant::now())
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum L1GasPriceScraperError {
+    #[error(transparent)]
+    BaseLayer(#[from] EthereumBaseLayerError),
+    #[error(transparent)]
+    L1GasPriceProviderClient(#[from] L1GasPriceProviderClientError),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct L1GasPriceScraperConfig {
+    pub finality: u64,
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub polling_interval: Duration,
+    pub startup_num_blocks: u64,
+}
+
+impl Default for L1GasPriceScraperConfig {
+    fn default() -> Self {
+        Self {
+            finality: 0,
+            polling_interval: Duration::from_secs(1),
+            // Should cover the provider's `number_of_blocks_for_mean` and `lag_margin_seconds`.
+            startup_num_blocks: 310,
+        }
+    }
+}
+
+impl SerializeConfig for L1GasPriceScraperConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "finality",
+                &self.finality,
+                "Number of blocks to wait for finality on L1.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "polling_interval",
+                &self.polling_interval.as_secs(),
+                "Interval in seconds between each scraping attempt of L1.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "startup_num_blocks",
+                &self.startup_num_blocks,
+                "Number of L1 blocks to scrape back from the latest L1 block when starting.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
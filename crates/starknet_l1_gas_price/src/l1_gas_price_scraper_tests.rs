@@ -0,0 +1,144 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use mockall::predicate::eq;
+use mockall::Sequence;
+use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerError;
+use papyrus_base_layer::fake_base_layer_contract::FakeBaseLayerContract;
+use pretty_assertions::assert_eq;
+use starknet_l1_gas_price_types::communication::{
+    L1GasPriceProviderClientError,
+    MockL1GasPriceProviderClient,
+};
+use starknet_l1_gas_price_types::errors::L1GasPriceProviderError;
+use starknet_l1_gas_price_types::l1_gas_price_types::PriceSample;
+use starknet_sequencer_infra::component_definitions::HealthStatus;
+
+use crate::l1_gas_price_scraper::{
+    L1GasPriceScraper,
+    L1GasPriceScraperConfig,
+    L1GasPriceScraperError,
+    L1GasPriceScraperHealth,
+    MAX_RETRY_INTERVAL,
+};
+
+fn base_layer_with_blocks(n_blocks: u64) -> FakeBaseLayerContract {
+    let mut base_layer = FakeBaseLayerContract::new();
+    for i in 0..n_blocks {
+        base_layer.add_block(12 * i, i.into(), (2 * i).into(), vec![]);
+    }
+    base_layer
+}
+
+fn sample(block_number: u64) -> PriceSample {
+    PriceSample {
+        timestamp: 12 * block_number,
+        base_fee_per_gas: block_number.into(),
+        blob_fee: (2 * block_number).into(),
+    }
+}
+
+async fn scraper(
+    base_layer: FakeBaseLayerContract,
+    l1_gas_price_provider: MockL1GasPriceProviderClient,
+) -> L1GasPriceScraper<FakeBaseLayerContract> {
+    let config = L1GasPriceScraperConfig { startup_num_blocks: 2, ..Default::default() };
+    let mut scraper = L1GasPriceScraper::new(config, base_layer, Arc::new(l1_gas_price_provider));
+    scraper.initialize().await.unwrap();
+    scraper
+}
+
+#[tokio::test]
+async fn update_prices_sends_new_blocks_in_order() {
+    // Setup.
+    let mut l1_gas_price_provider = MockL1GasPriceProviderClient::new();
+    let mut seq = Sequence::new();
+    for block_number in 3..=5 {
+        l1_gas_price_provider
+            .expect_add_price_info()
+            .with(eq(block_number), eq(sample(block_number)))
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| Ok(()));
+    }
+    let mut scraper = scraper(base_layer_with_blocks(6), l1_gas_price_provider).await;
+    assert_eq!(scraper.next_block_number_to_scrape, 3);
+
+    // Test.
+    scraper.update_prices().await.unwrap();
+    assert_eq!(scraper.next_block_number_to_scrape, 6);
+
+    // Nothing new to scrape.
+    scraper.update_prices().await.unwrap();
+    assert_eq!(scraper.next_block_number_to_scrape, 6);
+}
+
+#[tokio::test]
+async fn update_prices_on_empty_l1() {
+    // Setup.
+    let mut l1_gas_price_provider = MockL1GasPriceProviderClient::new();
+    l1_gas_price_provider.expect_add_price_info().never();
+    let mut scraper = scraper(FakeBaseLayerContract::new(), l1_gas_price_provider).await;
+
+    // Test.
+    scraper.update_prices().await.unwrap();
+    assert_eq!(scraper.next_block_number_to_scrape, 0);
+}
+
+#[tokio::test]
+async fn run_resumes_from_height_expected_by_provider() {
+    // Setup.
+    let mut l1_gas_price_provider = MockL1GasPriceProviderClient::new();
+    let mut seq = Sequence::new();
+    // The provider already has the prices up to block 4, e.g. from before the scraper restarted.
+    l1_gas_price_provider
+        .expect_add_price_info()
+        .with(eq(3), eq(sample(3)))
+        .times(1)
+        .in_sequence(&mut seq)
+        .returning(|_, _| {
+            Err(L1GasPriceProviderClientError::L1GasPriceProviderError(
+                L1GasPriceProviderError::UnexpectedHeight { expected: 5, found: 3 },
+            ))
+        });
+    l1_gas_price_provider
+        .expect_add_price_info()
+        .with(eq(5), eq(sample(5)))
+        .times(1)
+        .in_sequence(&mut seq)
+        .returning(|_, _| Ok(()));
+    let config = L1GasPriceScraperConfig {
+        startup_num_blocks: 2,
+        polling_interval: Duration::ZERO,
+        ..Default::default()
+    };
+    let mut scraper =
+        L1GasPriceScraper::new(config, base_layer_with_blocks(6), Arc::new(l1_gas_price_provider));
+
+    // Test.
+    tokio::time::timeout(Duration::from_millis(100), scraper.run()).await.unwrap_err();
+    assert_eq!(scraper.next_block_number_to_scrape, 6);
+}
+
+#[test]
+fn health_reports_persistent_scrape_failures() {
+    // Setup.
+    let health = L1GasPriceScraperHealth::default();
+    let err = L1GasPriceScraperError::BaseLayer(EthereumBaseLayerError::UnsupportedEvent(vec![]));
+    let start = Instant::now();
+    assert!(health.health_status(start).is_healthy());
+
+    // Test.
+    health.record_failure(&err, start);
+    health.record_failure(&err, start + MAX_RETRY_INTERVAL / 2);
+    // Failures are tolerated while they are retried.
+    assert!(health.health_status(start + MAX_RETRY_INTERVAL).is_healthy());
+    let unhealthy_time = start + MAX_RETRY_INTERVAL + Duration::from_secs(1);
+    assert!(!health.health_status(unhealthy_time).is_healthy());
+
+    health.record_success(5);
+    assert_eq!(
+        health.health_status(unhealthy_time),
+        HealthStatus::Healthy { details: "Next L1 block to scrape: 5.".to_owned() }
+    );
+}
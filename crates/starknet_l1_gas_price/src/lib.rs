@@ -0,0 +1,3 @@
+pub mod communication;
+pub mod l1_gas_price_provider;
+pub mod l1_gas_price_scraper;
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+#[cfg(any(feature = "testing", test))]
+use mockall::automock;
+use papyrus_proc_macros::handle_response_variants;
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockTimestamp;
+use starknet_sequencer_infra::component_client::{
+    ClientError,
+    LocalComponentClient,
+    RemoteComponentClient,
+};
+use starknet_sequencer_infra::component_definitions::{
+    ComponentClient,
+    ComponentRequest,
+    ComponentRequestAndResponseSender,
+};
+use thiserror::Error;
+
+use crate::errors::L1GasPriceProviderError;
+use crate::l1_gas_price_types::{L1GasPriceProviderResult, PriceInfo, PriceSample};
+
+#[cfg(test)]
+#[path = "communication_test.rs"]
+mod communication_test;
+
+pub type LocalL1GasPriceProviderClient =
+    LocalComponentClient<L1GasPriceRequest, L1GasPriceResponse>;
+pub type RemoteL1GasPriceProviderClient =
+    RemoteComponentClient<L1GasPriceRequest, L1GasPriceResponse>;
+pub type L1GasPriceProviderClientResult<T> = Result<T, L1GasPriceProviderClientError>;
+pub type L1GasPriceRequestAndResponseSender =
+    ComponentRequestAndResponseSender<L1GasPriceRequest, L1GasPriceResponse>;
+pub type SharedL1GasPriceProviderClient = Arc<dyn L1GasPriceProviderClient>;
+
+/// Serves as the L1 gas price provider's shared interface. Requires `Send + Sync` to allow
+/// transferring and sharing resources (inputs, futures) across threads.
+#[cfg_attr(any(feature = "testing", test), automock)]
+#[async_trait]
+pub trait L1GasPriceProviderClient: Send + Sync {
+    /// Adds the gas prices of the L1 block at the given height. Heights must be consecutive.
+    async fn add_price_info(
+        &self,
+        height: u64,
+        sample: PriceSample,
+    ) -> L1GasPriceProviderClientResult<()>;
+
+    /// Returns the L1 gas prices for an L2 block with the given timestamp.
+    async fn get_price_info(
+        &self,
+        timestamp: BlockTimestamp,
+    ) -> L1GasPriceProviderClientResult<PriceInfo>;
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum L1GasPriceRequest {
+    AddPriceInfo(u64, PriceSample),
+    GetPriceInfo(BlockTimestamp),
+}
+
+impl ComponentRequest for L1GasPriceRequest {
+    fn is_idempotent(&self) -> bool {
+        match self {
+            L1GasPriceRequest::GetPriceInfo(_) => true,
+            L1GasPriceRequest::AddPriceInfo(..) => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum L1GasPriceResponse {
+    AddPriceInfo(L1GasPriceProviderResult<()>),
+    GetPriceInfo(L1GasPriceProviderResult<PriceInfo>),
+}
+
+#[derive(Clone, Debug, Error)]
+pub enum L1GasPriceProviderClientError {
+    #[error(transparent)]
+    ClientError(#[from] ClientError),
+    #[error(transparent)]
+    L1GasPriceProviderError(#[from] L1GasPriceProviderError),
+}
+
+#[async_trait]
+impl<ComponentClientType> L1GasPriceProviderClient for ComponentClientType
+where
+    ComponentClientType: Send + Sync + ComponentClient<L1GasPriceRequest, L1GasPriceResponse>,
+{
+    async fn add_price_info(
+        &self,
+        height: u64,
+        sample: PriceSample,
+    ) -> L1GasPriceProviderClientResult<()> {
+        let request = L1GasPriceRequest::AddPriceInfo(height, sample);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            L1GasPriceResponse,
+            AddPriceInfo,
+            L1GasPriceProviderClientError,
+            L1GasPriceProviderError
+        )
+    }
+
+    async fn get_price_info(
+        &self,
+        timestamp: BlockTimestamp,
+    ) -> L1GasPriceProviderClientResult<PriceInfo> {
+        let request = L1GasPriceRequest::GetPriceInfo(timestamp);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            L1GasPriceResponse,
+            GetPriceInfo,
+            L1GasPriceProviderClientError,
+            L1GasPriceProviderError
+        )
+    }
+}
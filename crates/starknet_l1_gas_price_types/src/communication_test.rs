@@ -0,0 +1,29 @@
+use rstest::rstest;
+use starknet_api::block::BlockTimestamp;
+use starknet_sequencer_infra::test_utils::assert_codec_round_trip;
+
+use crate::communication::{L1GasPriceRequest, L1GasPriceResponse};
+use crate::errors::L1GasPriceProviderError;
+use crate::l1_gas_price_types::{PriceInfo, PriceSample};
+
+#[rstest]
+#[case::add_price_info(L1GasPriceRequest::AddPriceInfo(
+    1,
+    PriceSample { timestamp: 2, base_fee_per_gas: 3, blob_fee: 4 }
+))]
+#[case::get_price_info(L1GasPriceRequest::GetPriceInfo(BlockTimestamp(1)))]
+fn test_request_round_trip(#[case] request: L1GasPriceRequest) {
+    assert_codec_round_trip(&request);
+}
+
+#[rstest]
+#[case::add_price_info(L1GasPriceResponse::AddPriceInfo(Err(
+    L1GasPriceProviderError::UnexpectedHeight { expected: 1, found: 2 }
+)))]
+#[case::get_price_info(L1GasPriceResponse::GetPriceInfo(Ok(PriceInfo {
+    base_fee_per_gas: u128::MAX,
+    blob_fee: 1,
+})))]
+fn test_response_round_trip(#[case] response: L1GasPriceResponse) {
+    assert_codec_round_trip(&response);
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
+pub enum L1GasPriceProviderError {
+    #[error("Insufficient block price history: expected at least {expected}, found only {found}")]
+    InsufficientHistory { expected: usize, found: usize },
+    #[error("Unexpected L1 block height: expected {expected}, got {found}")]
+    UnexpectedHeight { expected: u64, found: u64 },
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use crate::errors::L1GasPriceProviderError;
+
+pub type L1GasPriceProviderResult<T> = Result<T, L1GasPriceProviderError>;
+
+/// The gas prices of a single L1 block.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceSample {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// In wei.
+    pub base_fee_per_gas: u128,
+    /// In wei.
+    pub blob_fee: u128,
+}
+
+/// L1 gas prices, aggregated over several L1 blocks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceInfo {
+    /// In wei.
+    pub base_fee_per_gas: u128,
+    /// In wei.
+    pub blob_fee: u128,
+}
+
+impl PriceInfo {
+    /// Returns true if both prices are within `tolerance_percent` of the expected prices.
+    pub fn is_within_tolerance(&self, expected: &PriceInfo, tolerance_percent: u128) -> bool {
+        within_tolerance(self.base_fee_per_gas, expected.base_fee_per_gas, tolerance_percent)
+            && within_tolerance(self.blob_fee, expected.blob_fee, tolerance_percent)
+    }
+}
+
+fn within_tolerance(value: u128, expected: u128, tolerance_percent: u128) -> bool {
+    let max_deviation = expected.saturating_mul(tolerance_percent) / 100;
+    value.abs_diff(expected) <= max_deviation
+}
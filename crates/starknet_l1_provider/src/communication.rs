@@ -0,0 +1,68 @@
+use async_trait::async_trait;
+use papyrus_base_layer::ethereum_base_layer_contract::{
+    EthereumBaseLayerContract,
+    EthereumBaseLayerError,
+};
+use papyrus_base_layer::BaseLayerContract;
+use starknet_l1_provider_types::communication::{L1ProviderRequest, L1ProviderResponse};
+use starknet_sequencer_infra::component_definitions::{ComponentRequestHandler, ComponentStarter};
+use starknet_sequencer_infra::component_server::{
+    LocalComponentServer,
+    RemoteComponentServer,
+    WrapperServer,
+};
+use starknet_sequencer_infra::errors::ComponentError;
+
+use crate::l1_scraper::L1Scraper;
+use crate::L1Provider;
+
+pub type LocalL1ProviderServer =
+    LocalComponentServer<L1Provider, L1ProviderRequest, L1ProviderResponse>;
+pub type RemoteL1ProviderServer = RemoteComponentServer<L1ProviderRequest, L1ProviderResponse>;
+pub type L1ScraperServer = WrapperServer<L1Scraper<EthereumBaseLayerContract>>;
+
+#[async_trait]
+impl ComponentRequestHandler<L1ProviderRequest, L1ProviderResponse> for L1Provider {
+    async fn handle_request(&mut self, request: L1ProviderRequest) -> L1ProviderResponse {
+        match request {
+            L1ProviderRequest::AbortCycle => L1ProviderResponse::AbortCycle(self.abort_cycle()),
+            L1ProviderRequest::AddEvents(events) => {
+                self.add_events(events);
+                L1ProviderResponse::AddEvents(Ok(()))
+            }
+            L1ProviderRequest::CommitBlock(committed_txs) => {
+                self.commit_block(&committed_txs);
+                L1ProviderResponse::CommitBlock(Ok(()))
+            }
+            L1ProviderRequest::GetTransactions(n_txs) => {
+                L1ProviderResponse::GetTransactions(self.get_txs(n_txs))
+            }
+            L1ProviderRequest::GetTransactionStatus(tx_hash) => {
+                L1ProviderResponse::GetTransactionStatus(Ok(self.get_tx_status(tx_hash)))
+            }
+            L1ProviderRequest::HandleReorg => L1ProviderResponse::HandleReorg(self.handle_reorg()),
+            L1ProviderRequest::ProposalStart => {
+                L1ProviderResponse::ProposalStart(self.proposal_start())
+            }
+            L1ProviderRequest::Validate(tx_hash) => {
+                L1ProviderResponse::Validate(self.validate(tx_hash))
+            }
+            L1ProviderRequest::ValidationStart => {
+                L1ProviderResponse::ValidationStart(self.validation_start())
+            }
+        }
+    }
+}
+
+impl ComponentStarter for L1Provider {}
+
+#[async_trait]
+impl<B> ComponentStarter for L1Scraper<B>
+where
+    B: BaseLayerContract<Error = EthereumBaseLayerError> + Send + Sync,
+{
+    async fn start(&mut self) -> Result<(), ComponentError> {
+        self.run().await;
+        Ok(())
+    }
+}
@@ -1,12 +1,24 @@
+use std::sync::Arc;
+use std::time::Duration;
+
 use assert_matches::assert_matches;
 use pretty_assertions::assert_eq;
 use starknet_api::test_utils::l1_handler::executable_l1_handler_tx;
 use starknet_api::{l1_handler_tx_args, tx_hash};
+use starknet_l1_provider_types::communication::{L1ProviderClientError, MockL1ProviderClient};
+use starknet_l1_provider_types::errors::L1ProviderError;
+use starknet_l1_provider_types::l1_provider_types::{
+    Event,
+    L1HandlerTransactionStatus,
+    ValidationStatus,
+};
+use starknet_sequencer_infra::component_client::ClientError;
+use starknet_sequencer_infra::component_definitions::{ComponentHealth, HealthStatus};
 
-use crate::errors::L1ProviderError;
+use crate::l1_scraper::L1ScraperConfig;
 use crate::test_utils::L1ProviderContentBuilder;
 use crate::ProviderState::{Propose, Validate};
-use crate::{L1Provider, ValidationStatus};
+use crate::{L1Provider, L1ProviderConfig, L1ProviderHealth};
 
 macro_rules! tx {
     (tx_hash: $tx_hash:expr) => {{
@@ -51,6 +63,202 @@ fn validate_happy_flow() {
     assert_eq!(l1_provider.validate(tx_hash!(1)).unwrap(), ValidationStatus::Validated);
 }
 
+#[test]
+fn commit_block_purges_committed_txs_and_returns_uncommitted_to_pool() {
+    // Setup.
+    let txs = [tx!(tx_hash: 0), tx!(tx_hash: 1), tx!(tx_hash: 2)];
+    let mut l1_provider = L1ProviderContentBuilder::new()
+        .with_txs(txs.clone())
+        .with_state(Propose)
+        .build_into_l1_provider();
+    assert_eq!(l1_provider.get_txs(2).unwrap(), txs[..2]);
+
+    // Test.
+    l1_provider.commit_block(&[tx_hash!(1)]);
+
+    l1_provider.validation_start().unwrap();
+    assert_eq!(l1_provider.validate(tx_hash!(1)).unwrap(), ValidationStatus::AlreadyIncludedOnL2);
+    l1_provider.commit_block(&[]);
+    l1_provider.proposal_start().unwrap();
+    assert_eq!(l1_provider.get_txs(3).unwrap(), [txs[0].clone(), txs[2].clone()]);
+}
+
+#[test]
+fn abort_cycle_returns_proposed_txs_to_pool() {
+    // Setup.
+    let txs = [tx!(tx_hash: 0), tx!(tx_hash: 1)];
+    let mut l1_provider = L1ProviderContentBuilder::new()
+        .with_txs(txs.clone())
+        .with_state(Propose)
+        .build_into_l1_provider();
+    assert_eq!(l1_provider.get_txs(1).unwrap(), [txs[0].clone()]);
+
+    // Test.
+    l1_provider.abort_cycle().unwrap();
+
+    // The next round of the height proposes from the start of the pool.
+    l1_provider.proposal_start().unwrap();
+    assert_eq!(l1_provider.get_txs(2).unwrap(), txs);
+}
+
+#[test]
+fn add_events_ignores_txs_already_on_l2() {
+    // Setup.
+    let mut l1_provider = L1ProviderContentBuilder::new()
+        .with_on_l2_awaiting_l1_consumption([tx_hash!(1)])
+        .with_state(Propose)
+        .build_into_l1_provider();
+
+    // Test.
+    l1_provider.add_events(vec![
+        Event::L1HandlerTransaction(tx!(tx_hash: 1)),
+        Event::L1HandlerTransaction(tx!(tx_hash: 2)),
+    ]);
+
+    assert_eq!(l1_provider.get_txs(2).unwrap(), [tx!(tx_hash: 2)]);
+}
+
+#[test]
+fn add_events_consumption_drains_txs_on_l2() {
+    // Setup.
+    let mut l1_provider = L1ProviderContentBuilder::new()
+        .with_on_l2_awaiting_l1_consumption([tx_hash!(1)])
+        .with_state(Propose)
+        .build_into_l1_provider();
+
+    // Test.
+    l1_provider.add_events(vec![Event::TransactionConsumed(tx_hash!(1))]);
+    assert_eq!(l1_provider.get_tx_status(tx_hash!(1)), L1HandlerTransactionStatus::Consumed);
+
+    // Rescraping the message after a rewind doesn't make it proposable again.
+    l1_provider.add_events(vec![Event::L1HandlerTransaction(tx!(tx_hash: 1))]);
+    assert_eq!(l1_provider.get_txs(1).unwrap(), []);
+}
+
+#[test]
+fn commit_block_before_scrape_marks_tx_on_l2() {
+    // Setup.
+    let mut l1_provider = L1ProviderContentBuilder::new().build_into_l1_provider();
+
+    // Test.
+    l1_provider.commit_block(&[tx_hash!(1)]);
+    assert_eq!(l1_provider.get_tx_status(tx_hash!(1)), L1HandlerTransactionStatus::OnL2);
+
+    // The scraper catches up with the committed transaction, which isn't proposed again.
+    l1_provider.add_events(vec![
+        Event::L1HandlerTransaction(tx!(tx_hash: 1)),
+        Event::L1HandlerTransaction(tx!(tx_hash: 2)),
+    ]);
+    l1_provider.proposal_start().unwrap();
+    assert_eq!(l1_provider.get_txs(2).unwrap(), [tx!(tx_hash: 2)]);
+    assert_eq!(l1_provider.get_tx_status(tx_hash!(1)), L1HandlerTransactionStatus::OnL2);
+}
+
+#[test]
+fn reorg_keeps_txs_included_on_l2() {
+    // Setup.
+    let mut l1_provider = L1ProviderContentBuilder::new()
+        .with_txs([tx!(tx_hash: 2)])
+        .with_on_l2_awaiting_l1_consumption([tx_hash!(1)])
+        .build_into_l1_provider();
+
+    // Test.
+    l1_provider.handle_reorg().unwrap();
+    assert_eq!(l1_provider.get_tx_status(tx_hash!(1)), L1HandlerTransactionStatus::OnL2);
+    assert_eq!(l1_provider.get_tx_status(tx_hash!(2)), L1HandlerTransactionStatus::Unknown);
+
+    // The scraper rescrapes the messages after it rewinds.
+    l1_provider.add_events(vec![
+        Event::L1HandlerTransaction(tx!(tx_hash: 1)),
+        Event::L1HandlerTransaction(tx!(tx_hash: 2)),
+    ]);
+    l1_provider.proposal_start().unwrap();
+    assert_eq!(l1_provider.get_txs(2).unwrap(), [tx!(tx_hash: 2)]);
+}
+
+#[test]
+fn cancellation_started_tx_is_still_proposable() {
+    // Setup.
+    let mut l1_provider = L1ProviderContentBuilder::new()
+        .with_txs([tx!(tx_hash: 1), tx!(tx_hash: 2)])
+        .with_state(Propose)
+        .build_into_l1_provider();
+
+    // Test.
+    l1_provider.add_events(vec![Event::TransactionCancellationStarted(tx_hash!(1))]);
+    assert_eq!(
+        l1_provider.get_tx_status(tx_hash!(1)),
+        L1HandlerTransactionStatus::CancellationStarted
+    );
+    assert_eq!(l1_provider.get_txs(2).unwrap(), [tx!(tx_hash: 1), tx!(tx_hash: 2)]);
+
+    l1_provider.commit_block(&[tx_hash!(1)]);
+    assert_eq!(l1_provider.get_tx_status(tx_hash!(1)), L1HandlerTransactionStatus::OnL2);
+}
+
+#[test]
+fn canceled_tx_fails_validation() {
+    // Setup.
+    let mut l1_provider = L1ProviderContentBuilder::new()
+        .with_txs([tx!(tx_hash: 1), tx!(tx_hash: 2)])
+        .with_state(Validate)
+        .build_into_l1_provider();
+
+    // Test.
+    l1_provider.add_events(vec![
+        Event::TransactionCancellationStarted(tx_hash!(1)),
+        Event::TransactionCanceled(tx_hash!(1)),
+    ]);
+
+    assert_eq!(l1_provider.validate(tx_hash!(1)).unwrap(), ValidationStatus::CanceledOnL1);
+    assert_eq!(l1_provider.validate(tx_hash!(2)).unwrap(), ValidationStatus::Validated);
+}
+
+#[test]
+fn canceled_proposed_tx_is_dropped_on_commit() {
+    // Setup.
+    let txs = [tx!(tx_hash: 0), tx!(tx_hash: 1)];
+    let mut l1_provider = L1ProviderContentBuilder::new()
+        .with_txs(txs.clone())
+        .with_state(Propose)
+        .build_into_l1_provider();
+    assert_eq!(l1_provider.get_txs(2).unwrap(), txs);
+
+    // Test.
+    l1_provider.add_events(vec![Event::TransactionCanceled(tx_hash!(0))]);
+    assert_eq!(l1_provider.get_tx_status(tx_hash!(0)), L1HandlerTransactionStatus::Canceled);
+
+    l1_provider.commit_block(&[]);
+    l1_provider.proposal_start().unwrap();
+    assert_eq!(l1_provider.get_txs(2).unwrap(), [txs[1].clone()]);
+}
+
+#[test]
+fn get_tx_status_follows_tx_lifecycle() {
+    // Setup.
+    let mut l1_provider = L1Provider::default();
+    let status = |l1_provider: &L1Provider| l1_provider.get_tx_status(tx_hash!(1));
+
+    // Test.
+    assert_eq!(status(&l1_provider), L1HandlerTransactionStatus::Unknown);
+
+    l1_provider.add_events(vec![Event::L1HandlerTransaction(tx!(tx_hash: 1))]);
+    assert_eq!(status(&l1_provider), L1HandlerTransactionStatus::Pending);
+
+    l1_provider.proposal_start().unwrap();
+    l1_provider.get_txs(1).unwrap();
+    assert_eq!(status(&l1_provider), L1HandlerTransactionStatus::Proposed);
+
+    // Account transaction hashes in the block can't be told apart from transactions that weren't
+    // scraped yet, until they are pruned.
+    l1_provider.commit_block(&[tx_hash!(1), tx_hash!(2)]);
+    assert_eq!(status(&l1_provider), L1HandlerTransactionStatus::OnL2);
+    assert_eq!(l1_provider.get_tx_status(tx_hash!(2)), L1HandlerTransactionStatus::OnL2);
+
+    l1_provider.add_events(vec![Event::TransactionConsumed(tx_hash!(1))]);
+    assert_eq!(status(&l1_provider), L1HandlerTransactionStatus::Consumed);
+}
+
 #[test]
 fn pending_state_errors() {
     // Setup.
@@ -77,13 +285,19 @@ fn proposal_start_errors() {
     // Test.
     l1_provider.proposal_start().unwrap();
 
-    assert_matches!(
+    assert_eq!(
         l1_provider.proposal_start().unwrap_err(),
-        L1ProviderError::UnexpectedProviderStateTransition { from: Propose, to: Propose }
+        L1ProviderError::UnexpectedProviderStateTransition {
+            from: Propose.to_string(),
+            to: Propose.to_string()
+        }
     );
-    assert_matches!(
+    assert_eq!(
         l1_provider.validation_start().unwrap_err(),
-        L1ProviderError::UnexpectedProviderStateTransition { from: Propose, to: Validate }
+        L1ProviderError::UnexpectedProviderStateTransition {
+            from: Propose.to_string(),
+            to: Validate.to_string()
+        }
     );
 }
 
@@ -95,12 +309,83 @@ fn validation_start_errors() {
     // Test.
     l1_provider.validation_start().unwrap();
 
-    assert_matches!(
+    assert_eq!(
         l1_provider.validation_start().unwrap_err(),
-        L1ProviderError::UnexpectedProviderStateTransition { from: Validate, to: Validate }
+        L1ProviderError::UnexpectedProviderStateTransition {
+            from: Validate.to_string(),
+            to: Validate.to_string()
+        }
     );
-    assert_matches!(
+    assert_eq!(
         l1_provider.proposal_start().unwrap_err(),
-        L1ProviderError::UnexpectedProviderStateTransition { from: Validate, to: Propose }
+        L1ProviderError::UnexpectedProviderStateTransition {
+            from: Validate.to_string(),
+            to: Propose.to_string()
+        }
     );
 }
+
+#[test]
+fn commit_block_prunes_l1_history_outside_rewind_window() {
+    // Setup.
+    let config_with_rewind_time = |startup_rewind_time| L1ProviderConfig {
+        l1_scraper_config: L1ScraperConfig { startup_rewind_time, ..Default::default() },
+    };
+    let events = vec![
+        Event::L1HandlerTransaction(tx!(tx_hash: 1)),
+        Event::TransactionConsumed(tx_hash!(1)),
+        Event::L1HandlerTransaction(tx!(tx_hash: 2)),
+        Event::TransactionCanceled(tx_hash!(2)),
+    ];
+    let mut kept = L1ProviderContentBuilder::new()
+        .with_config(config_with_rewind_time(Duration::from_secs(3600)))
+        .build_into_l1_provider();
+    let mut pruned = L1ProviderContentBuilder::new()
+        .with_config(config_with_rewind_time(Duration::ZERO))
+        .build_into_l1_provider();
+
+    // Test.
+    for l1_provider in [&mut kept, &mut pruned] {
+        l1_provider.add_events(events.clone());
+        l1_provider.commit_block(&[]);
+    }
+
+    assert_eq!(kept.get_tx_status(tx_hash!(1)), L1HandlerTransactionStatus::Consumed);
+    assert_eq!(kept.get_tx_status(tx_hash!(2)), L1HandlerTransactionStatus::Canceled);
+    assert_eq!(pruned.get_tx_status(tx_hash!(1)), L1HandlerTransactionStatus::Unknown);
+    assert_eq!(pruned.get_tx_status(tx_hash!(2)), L1HandlerTransactionStatus::Unknown);
+
+    // Committed transactions that weren't scraped, e.g., account transactions, are pruned too.
+    for l1_provider in [&mut kept, &mut pruned] {
+        l1_provider.commit_block(&[tx_hash!(3)]);
+    }
+
+    assert_eq!(kept.get_tx_status(tx_hash!(3)), L1HandlerTransactionStatus::OnL2);
+    assert_eq!(pruned.get_tx_status(tx_hash!(3)), L1HandlerTransactionStatus::Unknown);
+}
+
+#[tokio::test]
+async fn health_reports_responsive_provider() {
+    // Setup.
+    let mut client = MockL1ProviderClient::new();
+    client.expect_get_tx_status().returning(|_| Ok(L1HandlerTransactionStatus::Unknown));
+    let health = L1ProviderHealth::new(Arc::new(client));
+
+    // Test and assert.
+    assert_matches!(health.check_health().await, HealthStatus::Healthy { .. });
+}
+
+#[tokio::test]
+async fn health_reports_unresponsive_provider() {
+    // Setup.
+    let mut client = MockL1ProviderClient::new();
+    client.expect_get_tx_status().returning(|_| {
+        Err(L1ProviderClientError::ClientError(ClientError::ComponentUnavailable(
+            "L1 provider".to_owned(),
+        )))
+    });
+    let health = L1ProviderHealth::new(Arc::new(client));
+
+    // Test and assert.
+    assert_matches!(health.check_health().await, HealthStatus::Unhealthy { .. });
+}
@@ -0,0 +1,332 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use papyrus_base_layer::constants::{
+    EventIdentifier,
+    CONSUMED_MESSAGE_TO_L2_EVENT_IDENTIFIER,
+    LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER,
+    MESSAGE_TO_L2_CANCELED_EVENT_IDENTIFIER,
+    MESSAGE_TO_L2_CANCELLATION_STARTED_EVENT_IDENTIFIER,
+};
+use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerError;
+use papyrus_base_layer::{BaseLayerContract, EventData, L1BlockHash, L1BlockNumber, L1Event};
+use papyrus_config::converters::deserialize_seconds_to_duration;
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::core::ChainId;
+use starknet_api::executable_transaction::L1HandlerTransaction;
+use starknet_api::transaction::{TransactionHash, TransactionHasher};
+use starknet_api::StarknetApiError;
+use starknet_l1_provider_types::communication::{L1ProviderClientError, SharedL1ProviderClient};
+use starknet_l1_provider_types::l1_provider_types::Event;
+use starknet_sequencer_infra::component_definitions::{ComponentHealth, HealthStatus};
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+#[cfg(test)]
+#[path = "l1_scraper_tests.rs"]
+pub mod l1_scraper_tests;
+
+// Used to translate the rewind time into L1 blocks; the exact value isn't important, as long as
+// the rewind covers roughly the configured time.
+const L1_BLOCK_TIME: Duration = Duration::from_secs(12);
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+type L1ScraperResult<T> = Result<T, L1ScraperError>;
+
+/// Polls the base layer for L1->L2 messaging events and feeds them to the L1 provider.
+pub struct L1Scraper<B: BaseLayerContract> {
+    pub config: L1ScraperConfig,
+    pub base_layer: B,
+    pub next_block_number_to_scrape: L1BlockNumber,
+    pub l1_provider_client: SharedL1ProviderClient,
+    // The number and hash of the last scraped L1 block, used to detect L1 reorgs that revert it.
+    last_scraped_block: Option<(L1BlockNumber, L1BlockHash)>,
+    tracked_event_identifiers: Vec<EventIdentifier>,
+    health: L1ScraperHealth,
+}
+
+impl<B> L1Scraper<B>
+where
+    B: BaseLayerContract<Error = EthereumBaseLayerError> + Send + Sync,
+{
+    /// Creates a scraper which, once running, starts scraping from ~`startup_rewind_time` ago, so
+    /// that messages sent while the node was down aren't missed.
+    pub fn new(
+        config: L1ScraperConfig,
+        base_layer: B,
+        l1_provider_client: SharedL1ProviderClient,
+    ) -> Self {
+        Self {
+            config,
+            base_layer,
+            next_block_number_to_scrape: 0,
+            l1_provider_client,
+            last_scraped_block: None,
+            tracked_event_identifiers: vec![
+                LOG_MESSAGE_TO_L2_EVENT_IDENTIFIER,
+                CONSUMED_MESSAGE_TO_L2_EVENT_IDENTIFIER,
+                MESSAGE_TO_L2_CANCELLATION_STARTED_EVENT_IDENTIFIER,
+                MESSAGE_TO_L2_CANCELED_EVENT_IDENTIFIER,
+            ],
+            health: L1ScraperHealth::default(),
+        }
+    }
+
+    /// Returns a handle that reports the progress of the scraper to the monitoring endpoint.
+    pub fn health(&self) -> L1ScraperHealth {
+        self.health.clone()
+    }
+
+    /// Sets the next block to scrape to ~`startup_rewind_time` before the latest L1 block.
+    pub async fn rewind(&mut self) -> L1ScraperResult<()> {
+        self.next_block_number_to_scrape =
+            rewound_l1_block_number(&self.base_layer, &self.config).await?;
+        self.last_scraped_block = None;
+        info!("Scraping L1 from L1 block {}.", self.next_block_number_to_scrape);
+        Ok(())
+    }
+
+    /// Scrapes all events emitted since the last scrape, up to the latest finalized L1 block.
+    /// If an L1 reorg reverted the last scraped block, resets the provider and rescrapes instead.
+    pub async fn fetch_events(&mut self) -> L1ScraperResult<()> {
+        if self.is_last_scraped_block_reverted().await? {
+            warn!("L1 reorg reverted the last scraped L1 block, rescraping.");
+            return self.reset().await;
+        }
+
+        let Some(latest_l1_block_number) =
+            self.base_layer.latest_l1_block_number(self.config.finality).await?
+        else {
+            debug!("No finalized L1 blocks yet, skipping scrape.");
+            return Ok(());
+        };
+        if latest_l1_block_number < self.next_block_number_to_scrape {
+            return Ok(());
+        }
+        // Taken before the events, so that a reorg between the two requests is detected by the
+        // next scrape.
+        let Some(latest_header) = self.base_layer.get_block_header(latest_l1_block_number).await?
+        else {
+            // The node is lagging behind the block number it reported, try again later.
+            return Ok(());
+        };
+
+        let events = self
+            .base_layer
+            .events(
+                self.next_block_number_to_scrape,
+                latest_l1_block_number,
+                &self.tracked_event_identifiers,
+            )
+            .await?
+            .into_iter()
+            .map(|event| self.event_from_l1_event(event))
+            .collect::<L1ScraperResult<Vec<_>>>()?;
+
+        self.l1_provider_client.add_events(events).await?;
+        self.last_scraped_block = Some((latest_l1_block_number, latest_header.hash));
+        self.next_block_number_to_scrape = latest_l1_block_number + 1;
+        Ok(())
+    }
+
+    /// Rewinds, then scrapes L1 every `polling_interval`. Failures, e.g. due to an unavailable
+    /// base layer, are retried with an exponential backoff, up to `MAX_RETRY_INTERVAL` between
+    /// attempts. Never returns.
+    pub async fn run(&mut self) {
+        let mut is_rewound = false;
+        let mut retry_interval = self.config.polling_interval;
+        loop {
+            let result = if is_rewound { self.fetch_events().await } else { self.rewind().await };
+            match result {
+                Ok(()) => {
+                    is_rewound = true;
+                    retry_interval = self.config.polling_interval;
+                    self.health.record_success(self.next_block_number_to_scrape);
+                    tokio::time::sleep(self.config.polling_interval).await;
+                }
+                Err(err) => {
+                    warn!("Failed to scrape L1, retrying in {retry_interval:?}: {err}");
+                    self.health.record_failure(&err, Instant::now());
+                    tokio::time::sleep(retry_interval).await;
+                    retry_interval = (retry_interval * 2).min(MAX_RETRY_INTERVAL);
+                }
+            }
+        }
+    }
+
+    /// Recovers from L1 reorgs by resetting the provider and rescraping from
+    /// ~`startup_rewind_time` ago.
+    pub async fn reset(&mut self) -> L1ScraperResult<()> {
+        self.l1_provider_client.handle_reorg().await?;
+        self.rewind().await
+    }
+
+    async fn is_last_scraped_block_reverted(&self) -> L1ScraperResult<bool> {
+        let Some((block_number, block_hash)) = self.last_scraped_block else {
+            return Ok(false);
+        };
+        let header = self.base_layer.get_block_header(block_number).await?;
+        Ok(header.map(|header| header.hash) != Some(block_hash))
+    }
+
+    fn event_from_l1_event(&self, event: L1Event) -> L1ScraperResult<Event> {
+        let chain_id = &self.config.chain_id;
+        match event {
+            L1Event::LogMessageToL2 { tx, fee } => {
+                Ok(Event::L1HandlerTransaction(L1HandlerTransaction::create(tx, chain_id, fee)?))
+            }
+            L1Event::ConsumedMessageToL2(event_data) => {
+                Ok(Event::TransactionConsumed(self.tx_hash(event_data)?))
+            }
+            L1Event::MessageToL2CancellationStarted(event_data) => {
+                Ok(Event::TransactionCancellationStarted(self.tx_hash(event_data)?))
+            }
+            L1Event::MessageToL2Canceled(event_data) => {
+                Ok(Event::TransactionCanceled(self.tx_hash(event_data)?))
+            }
+        }
+    }
+
+    /// Calculates the hash of the L1 handler transaction the message was (or would have been)
+    /// turned into.
+    fn tx_hash(&self, event_data: EventData) -> L1ScraperResult<TransactionHash> {
+        let tx = starknet_api::transaction::L1HandlerTransaction::from(event_data);
+        Ok(tx.calculate_transaction_hash(&self.config.chain_id, &tx.version)?)
+    }
+}
+
+/// The progress of the scraper, shared with the monitoring endpoint. The scraper is unhealthy if
+/// its scrapes keep failing for longer than `MAX_RETRY_INTERVAL`, e.g., when the base layer is
+/// unavailable.
+#[derive(Clone, Debug, Default)]
+pub struct L1ScraperHealth {
+    state: Arc<Mutex<ScraperProgress>>,
+}
+
+#[derive(Debug, Default)]
+struct ScraperProgress {
+    next_block_number_to_scrape: Option<L1BlockNumber>,
+    // When the current streak of failed scrapes started, and the last failure.
+    failing_since: Option<(Instant, String)>,
+}
+
+impl L1ScraperHealth {
+    pub fn record_success(&self, next_block_number_to_scrape: L1BlockNumber) {
+        let mut state = self.state.lock().expect("Lock should not be poisoned.");
+        state.next_block_number_to_scrape = Some(next_block_number_to_scrape);
+        state.failing_since = None;
+    }
+
+    pub fn record_failure(&self, err: &L1ScraperError, now: Instant) {
+        let mut state = self.state.lock().expect("Lock should not be poisoned.");
+        let failing_since = state.failing_since.as_ref().map_or(now, |(since, _)| *since);
+        state.failing_since = Some((failing_since, err.to_string()));
+    }
+
+    pub fn health_status(&self, now: Instant) -> HealthStatus {
+        let state = self.state.lock().expect("Lock should not be poisoned.");
+        if let Some((failing_since, err)) = &state.failing_since {
+            let failing_duration = now.saturating_duration_since(*failing_since);
+            if failing_duration > MAX_RETRY_INTERVAL {
+                return HealthStatus::Unhealthy {
+                    reason: format!(
+                        "L1 scraping fails for {} seconds: {err}",
+                        failing_duration.as_secs()
+                    ),
+                };
+            }
+        }
+        match state.next_block_number_to_scrape {
+            Some(block_number) => HealthStatus::Healthy {
+                details: format!("Next L1 block to scrape: {block_number}."),
+            },
+            None => HealthStatus::Healthy { details: "L1 scraping hasn't started yet.".to_owned() },
+        }
+    }
+}
+
+#[async_trait]
+impl ComponentHealth for L1ScraperHealth {
+    async fn check_health(&self) -> HealthStatus {
+        self.health_status(Instant::now())
+    }
+}
+
+async fn rewound_l1_block_number<B>(
+    base_layer: &B,
+    config: &L1ScraperConfig,
+) -> L1ScraperResult<L1BlockNumber>
+where
+    B: BaseLayerContract<Error = EthereumBaseLayerError> + Send + Sync,
+{
+    let latest_l1_block_number =
+        base_layer.latest_l1_block_number(config.finality).await?.unwrap_or_default();
+    let rewind_blocks = config.startup_rewind_time.as_secs() / L1_BLOCK_TIME.as_secs();
+    Ok(latest_l1_block_number.saturating_sub(rewind_blocks))
+}
+
+#[derive(Error, Debug)]
+pub enum L1ScraperError {
+    #[error(transparent)]
+    BaseLayer(#[from] EthereumBaseLayerError),
+    #[error(transparent)]
+    HashCalculation(#[from] StarknetApiError),
+    #[error(transparent)]
+    L1ProviderClient(#[from] L1ProviderClientError),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct L1ScraperConfig {
+    pub chain_id: ChainId,
+    pub finality: u64,
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub polling_interval: Duration,
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub startup_rewind_time: Duration,
+}
+
+impl Default for L1ScraperConfig {
+    fn default() -> Self {
+        Self {
+            chain_id: ChainId::Mainnet,
+            finality: 0,
+            polling_interval: Duration::from_secs(1),
+            startup_rewind_time: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+impl SerializeConfig for L1ScraperConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "chain_id",
+                &self.chain_id,
+                "The chain to follow. For more details see https://docs.starknet.io/documentation/architecture_and_concepts/Blocks/transactions/#chain-id.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "finality",
+                &self.finality,
+                "Number of blocks to wait for finality on L1.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "polling_interval",
+                &self.polling_interval.as_secs(),
+                "Interval in seconds between each scraping attempt of L1.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "startup_rewind_time",
+                &self.startup_rewind_time.as_secs(),
+                "Duration in seconds to rewind from the latest L1 block when starting scraping.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
@@ -0,0 +1,216 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use mockall::predicate::{always, eq};
+use mockall::Sequence;
+use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerError;
+use papyrus_base_layer::{EventData, L1BlockHeader, L1Event, MockBaseLayerContract};
+use pretty_assertions::assert_eq;
+use starknet_api::core::Nonce;
+use starknet_api::executable_transaction::L1HandlerTransaction;
+use starknet_api::transaction::fields::Fee;
+use starknet_api::{contract_address, felt};
+use starknet_l1_provider_types::communication::MockL1ProviderClient;
+use starknet_l1_provider_types::l1_provider_types::Event;
+use starknet_sequencer_infra::component_definitions::HealthStatus;
+
+use crate::l1_scraper::{
+    L1Scraper,
+    L1ScraperConfig,
+    L1ScraperError,
+    L1ScraperHealth,
+    MAX_RETRY_INTERVAL,
+};
+
+const LATEST_L1_BLOCK_NUMBER: u64 = 1000;
+// An hour of 12 second L1 blocks.
+const REWIND_BLOCKS: u64 = 300;
+
+fn event_data(nonce: Nonce) -> EventData {
+    EventData {
+        to_address: contract_address!("0x1"),
+        entry_point_selector: Default::default(),
+        nonce,
+        ..Default::default()
+    }
+}
+
+fn base_layer_with_latest_block(latest_l1_block_number: u64) -> MockBaseLayerContract {
+    let mut base_layer = MockBaseLayerContract::new();
+    base_layer.expect_latest_l1_block_number().returning(move |_| Ok(Some(latest_l1_block_number)));
+    base_layer
+        .expect_get_block_header()
+        .returning(|number| Ok(Some(L1BlockHeader { number, ..Default::default() })));
+    base_layer
+}
+
+async fn scraper(
+    base_layer: MockBaseLayerContract,
+    l1_provider_client: MockL1ProviderClient,
+) -> L1Scraper<MockBaseLayerContract> {
+    let mut scraper =
+        L1Scraper::new(L1ScraperConfig::default(), base_layer, Arc::new(l1_provider_client));
+    scraper.rewind().await.unwrap();
+    scraper
+}
+
+#[tokio::test]
+async fn starts_scraping_from_rewound_block() {
+    let scraper =
+        scraper(base_layer_with_latest_block(LATEST_L1_BLOCK_NUMBER), MockL1ProviderClient::new())
+            .await;
+
+    assert_eq!(scraper.next_block_number_to_scrape, LATEST_L1_BLOCK_NUMBER - REWIND_BLOCKS);
+}
+
+#[tokio::test]
+async fn rewind_saturates_on_young_l1() {
+    let scraper =
+        scraper(base_layer_with_latest_block(REWIND_BLOCKS / 2), MockL1ProviderClient::new()).await;
+
+    assert_eq!(scraper.next_block_number_to_scrape, 0);
+}
+
+#[tokio::test]
+async fn fetch_events_sends_scraped_events_to_provider() {
+    // Setup.
+    let chain_id = L1ScraperConfig::default().chain_id;
+    let mut base_layer = base_layer_with_latest_block(LATEST_L1_BLOCK_NUMBER);
+    let (first_msg, second_msg) = (event_data(Nonce(felt!(0_u8))), event_data(Nonce(felt!(1_u8))));
+    let events = vec![
+        L1Event::LogMessageToL2 { tx: first_msg.clone().into(), fee: Fee(1) },
+        L1Event::LogMessageToL2 { tx: second_msg.clone().into(), fee: Fee(2) },
+        L1Event::MessageToL2CancellationStarted(first_msg.clone()),
+        L1Event::MessageToL2Canceled(first_msg.clone()),
+        L1Event::ConsumedMessageToL2(second_msg.clone()),
+    ];
+    base_layer
+        .expect_events()
+        .with(eq(LATEST_L1_BLOCK_NUMBER - REWIND_BLOCKS), eq(LATEST_L1_BLOCK_NUMBER), always())
+        .times(1)
+        .return_once(move |_, _, _| Ok(events));
+
+    let first_tx = L1HandlerTransaction::create(first_msg.into(), &chain_id, Fee(1)).unwrap();
+    let second_tx = L1HandlerTransaction::create(second_msg.into(), &chain_id, Fee(2)).unwrap();
+    let expected_events = vec![
+        Event::L1HandlerTransaction(first_tx.clone()),
+        Event::L1HandlerTransaction(second_tx.clone()),
+        Event::TransactionCancellationStarted(first_tx.tx_hash),
+        Event::TransactionCanceled(first_tx.tx_hash),
+        Event::TransactionConsumed(second_tx.tx_hash),
+    ];
+    let mut l1_provider_client = MockL1ProviderClient::new();
+    l1_provider_client.expect_add_events().with(eq(expected_events)).times(1).returning(|_| Ok(()));
+    let mut scraper = scraper(base_layer, l1_provider_client).await;
+
+    // Test.
+    scraper.fetch_events().await.unwrap();
+
+    assert_eq!(scraper.next_block_number_to_scrape, LATEST_L1_BLOCK_NUMBER + 1);
+}
+
+#[tokio::test]
+async fn fetch_events_skips_scraped_blocks() {
+    // Setup.
+    let mut base_layer = base_layer_with_latest_block(LATEST_L1_BLOCK_NUMBER);
+    base_layer.expect_events().never();
+    let mut l1_provider_client = MockL1ProviderClient::new();
+    l1_provider_client.expect_add_events().never();
+    let mut scraper = scraper(base_layer, l1_provider_client).await;
+    scraper.next_block_number_to_scrape = LATEST_L1_BLOCK_NUMBER + 1;
+
+    // Test.
+    scraper.fetch_events().await.unwrap();
+
+    assert_eq!(scraper.next_block_number_to_scrape, LATEST_L1_BLOCK_NUMBER + 1);
+}
+
+#[tokio::test]
+async fn fetch_events_resets_provider_on_l1_reorg() {
+    // Setup.
+    let mut base_layer = MockBaseLayerContract::new();
+    base_layer.expect_latest_l1_block_number().returning(|_| Ok(Some(LATEST_L1_BLOCK_NUMBER)));
+    base_layer.expect_events().times(1).returning(|_, _, _| Ok(vec![]));
+    // The last scraped block is replaced by a block with a different hash.
+    let mut seq = Sequence::new();
+    for hash in [[1; 32], [2; 32]] {
+        base_layer
+            .expect_get_block_header()
+            .with(eq(LATEST_L1_BLOCK_NUMBER))
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(move |number| {
+                Ok(Some(L1BlockHeader { number, hash, ..Default::default() }))
+            });
+    }
+    let mut l1_provider_client = MockL1ProviderClient::new();
+    l1_provider_client.expect_add_events().times(1).returning(|_| Ok(()));
+    l1_provider_client.expect_handle_reorg().times(1).returning(|| Ok(()));
+    let mut scraper = scraper(base_layer, l1_provider_client).await;
+    scraper.fetch_events().await.unwrap();
+
+    // Test.
+    scraper.fetch_events().await.unwrap();
+
+    assert_eq!(scraper.next_block_number_to_scrape, LATEST_L1_BLOCK_NUMBER - REWIND_BLOCKS);
+}
+
+#[tokio::test]
+async fn reset_rewinds() {
+    // Setup.
+    let mut l1_provider_client = MockL1ProviderClient::new();
+    l1_provider_client.expect_handle_reorg().times(1).returning(|| Ok(()));
+    let mut scraper =
+        scraper(base_layer_with_latest_block(LATEST_L1_BLOCK_NUMBER), l1_provider_client).await;
+    scraper.next_block_number_to_scrape = LATEST_L1_BLOCK_NUMBER + 1;
+
+    // Test.
+    scraper.reset().await.unwrap();
+
+    assert_eq!(scraper.next_block_number_to_scrape, LATEST_L1_BLOCK_NUMBER - REWIND_BLOCKS);
+}
+
+#[tokio::test(start_paused = true)]
+async fn run_retries_failed_scrapes() {
+    // Setup.
+    let mut base_layer = base_layer_with_latest_block(LATEST_L1_BLOCK_NUMBER);
+    let mut seq = Sequence::new();
+    base_layer
+        .expect_events()
+        .times(1)
+        .in_sequence(&mut seq)
+        .returning(|_, _, _| Err(EthereumBaseLayerError::UnsupportedEvent(vec![])));
+    base_layer.expect_events().times(1).in_sequence(&mut seq).returning(|_, _, _| Ok(vec![]));
+    let mut l1_provider_client = MockL1ProviderClient::new();
+    l1_provider_client.expect_add_events().times(1).returning(|_| Ok(()));
+    let mut scraper = scraper(base_layer, l1_provider_client).await;
+
+    // Test.
+    let run_result = tokio::time::timeout(Duration::from_secs(60), scraper.run()).await;
+
+    assert!(run_result.is_err(), "The scraper should keep running after a failed scrape.");
+    assert_eq!(scraper.next_block_number_to_scrape, LATEST_L1_BLOCK_NUMBER + 1);
+}
+
+#[test]
+fn health_reports_persistent_scrape_failures() {
+    // Setup.
+    let health = L1ScraperHealth::default();
+    let err = L1ScraperError::BaseLayer(EthereumBaseLayerError::UnsupportedEvent(vec![]));
+    let start = Instant::now();
+    assert!(health.health_status(start).is_healthy());
+
+    // Test.
+    health.record_failure(&err, start);
+    health.record_failure(&err, start + MAX_RETRY_INTERVAL / 2);
+    // Failures are tolerated while they are retried.
+    assert!(health.health_status(start + MAX_RETRY_INTERVAL).is_healthy());
+    let unhealthy_time = start + MAX_RETRY_INTERVAL + Duration::from_secs(1);
+    assert!(!health.health_status(unhealthy_time).is_healthy());
+
+    health.record_success(5);
+    assert_eq!(
+        health.health_status(unhealthy_time),
+        HealthStatus::Healthy { details: "Next L1 block to scrape: 5.".to_owned() }
+    );
+}
@@ -1,15 +1,31 @@
-pub mod errors;
+pub mod communication;
+pub mod l1_scraper;
 
 #[cfg(test)]
 pub mod test_utils;
 
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
 use indexmap::{IndexMap, IndexSet};
+use papyrus_config::dumping::{append_sub_config_name, SerializeConfig};
+use papyrus_config::{ParamPath, SerializedParam};
+use serde::{Deserialize, Serialize};
 use starknet_api::executable_transaction::L1HandlerTransaction;
 use starknet_api::transaction::TransactionHash;
+use starknet_l1_provider_types::communication::SharedL1ProviderClient;
+use starknet_l1_provider_types::errors::L1ProviderError;
+use starknet_l1_provider_types::l1_provider_types::{
+    Event,
+    L1HandlerTransactionStatus,
+    L1ProviderResult,
+    ValidationStatus,
+};
+use starknet_sequencer_infra::component_definitions::{ComponentHealth, HealthStatus};
+use tracing::debug;
 
-use crate::errors::L1ProviderError;
-
-type L1ProviderResult<T> = Result<T, L1ProviderError>;
+use crate::l1_scraper::L1ScraperConfig;
 
 #[cfg(test)]
 #[path = "l1_provider_tests.rs"]
@@ -19,6 +35,7 @@ pub mod l1_provider_tests;
 // is compatible with it.
 #[derive(Debug, Default)]
 pub struct L1Provider {
+    config: L1ProviderConfig,
     tx_manager: TransactionManager,
     // TODO(Gilad): consider transitioning to a generic phantom state once the infra is stabilized
     // and we see how well it handles consuming the L1Provider when moving between states.
@@ -26,13 +43,6 @@ pub struct L1Provider {
 }
 
 impl L1Provider {
-    pub async fn new(_config: L1ProviderConfig) -> L1ProviderResult<Self> {
-        todo!(
-            "init crawler to start next crawl from ~1 hour ago, this can have l1 errors when \
-             finding the latest block on L1 to 'subtract' 1 hour from."
-        );
-    }
-
     /// Retrieves up to `n_txs` transactions that have yet to be proposed or accepted on L2.
     pub fn get_txs(&mut self, n_txs: usize) -> L1ProviderResult<Vec<L1HandlerTransaction>> {
         match self.state {
@@ -42,8 +52,8 @@ impl L1Provider {
         }
     }
 
-    /// Returns true if and only if the given transaction is both not included in an L2 block, and
-    /// unconsumed on L1.
+    /// Returns true if and only if the given transaction is not included in an L2 block, and is
+    /// neither consumed nor canceled on L1.
     pub fn validate(&self, tx_hash: TransactionHash) -> L1ProviderResult<ValidationStatus> {
         match self.state {
             ProviderState::Validate => Ok(self.tx_manager.tx_status(tx_hash)),
@@ -52,13 +62,42 @@ impl L1Provider {
         }
     }
 
+    /// Applies events scraped from L1 to the internal buffers.
+    pub fn add_events(&mut self, events: Vec<Event>) {
+        for event in events {
+            match event {
+                Event::L1HandlerTransaction(tx) => {
+                    self.tx_manager.add_unconsumed_l1_not_in_l2_block_tx(tx)
+                }
+                Event::TransactionCancellationStarted(tx_hash) => {
+                    self.tx_manager.start_tx_cancellation(tx_hash)
+                }
+                Event::TransactionCanceled(tx_hash) => self.tx_manager.cancel_tx(tx_hash),
+                Event::TransactionConsumed(tx_hash) => self.tx_manager.consume_tx(tx_hash),
+            }
+        }
+    }
+
+    /// Returns the lifecycle status of the given transaction, regardless of the provider's state.
+    pub fn get_tx_status(&self, tx_hash: TransactionHash) -> L1HandlerTransactionStatus {
+        self.tx_manager.lifecycle_status(tx_hash)
+    }
+
     // TODO: when deciding on consensus, if possible, have commit_block also tell the node if it's
     // about to [optimistically-]propose or validate the next block.
-    pub fn commit_block(&mut self, _commited_txs: &[TransactionHash]) {
-        todo!(
-            "Purges txs from internal buffers, if was proposer clear staging buffer, 
-            reset state to Pending until we get proposing/validating notice from consensus."
-        )
+    /// Purges the committed transactions from the internal buffers, and returns the uncommitted
+    /// proposed transactions (if any) to the pool of transactions to propose, unless they were
+    /// canceled in the meantime. Hashes of transactions that weren't scraped yet are recorded as
+    /// well, so that they aren't proposed again once scraped. Since these may also be hashes of
+    /// account transactions, they are forgotten if not scraped within the rewind window.
+    pub fn commit_block(&mut self, committed_txs: &[TransactionHash]) {
+        for tx_hash in committed_txs {
+            self.tx_manager.mark_tx_included_on_l2(tx_hash);
+        }
+        self.tx_manager.proposed_txs.clear();
+        self.tx_manager.drop_canceled_txs();
+        self.tx_manager.prune_l1_history(self.config.l1_scraper_config.startup_rewind_time);
+        self.state = self.state.transition_to_pending();
     }
 
     // TODO: pending formal consensus API, guessing the API here to keep things moving.
@@ -73,27 +112,38 @@ impl L1Provider {
         Ok(())
     }
 
+    /// Abandons the current proposal or validation, if any, e.g., when consensus moves to a new
+    /// round of the same height. Its proposed transactions can be proposed again.
+    pub fn abort_cycle(&mut self) -> L1ProviderResult<()> {
+        self.tx_manager.proposed_txs.clear();
+        self.state = self.state.transition_to_pending();
+        Ok(())
+    }
+
     /// Simple recovery from L1 and L2 reorgs by reseting the service, which rewinds L1 and L2
     /// information.
     pub fn handle_reorg(&mut self) -> L1ProviderResult<()> {
         self.reset()
     }
 
-    // TODO: this will likely change during integration with infra team.
-    pub async fn start(&self) {
-        todo!(
-            "Create a process that wakes up every config.poll_interval seconds and updates
-        internal L1 and L2 buffers according to collected L1 events and recent blocks created on
-        L2."
-        )
-    }
-
+    /// Clears the internal buffers, which are then refilled by the scraper after it rewinds its
+    /// L1 start block. Transactions which are already included on L2 are kept, since an L1 reorg
+    /// doesn't revert them, so that they aren't proposed again once rescraped.
     fn reset(&mut self) -> L1ProviderResult<()> {
-        todo!(
-            "resets internal buffers and rewinds the internal crawler _pointer_ back for ~1 \
-             hour,so that the main loop will start collecting from that time gracefully. May hit \
-             base layer errors."
-        );
+        let TransactionManager {
+            on_l2_awaiting_l1_consumption,
+            committed_unscraped_txs,
+            consumed_txs,
+            ..
+        } = std::mem::take(&mut self.tx_manager);
+        self.tx_manager = TransactionManager {
+            on_l2_awaiting_l1_consumption,
+            committed_unscraped_txs,
+            consumed_txs,
+            ..Default::default()
+        };
+        self.state = self.state.transition_to_pending();
+        Ok(())
     }
 }
 
@@ -101,7 +151,17 @@ impl L1Provider {
 struct TransactionManager {
     txs: IndexMap<TransactionHash, L1HandlerTransaction>,
     proposed_txs: IndexSet<TransactionHash>,
+    // Transactions whose cancellation started on L1, they can still be included on L2 until the
+    // cancellation completes.
+    cancellation_started_txs: IndexSet<TransactionHash>,
     on_l2_awaiting_l1_consumption: IndexSet<TransactionHash>,
+    // Transactions committed on L2 before they were scraped, by the time they were committed. They
+    // are also awaiting L1 consumption, unless they turn out to be account transactions.
+    committed_unscraped_txs: IndexMap<TransactionHash, Instant>,
+    // Consumed and canceled transactions, by the time the provider learned of them. They are only
+    // kept while a scraper rewind may rescrape their messages.
+    consumed_txs: IndexMap<TransactionHash, Instant>,
+    canceled_txs: IndexMap<TransactionHash, Instant>,
 }
 
 impl TransactionManager {
@@ -109,7 +169,8 @@ impl TransactionManager {
         let (tx_hashes, txs): (Vec<_>, Vec<_>) = self
             .txs
             .iter()
-            .skip(self.proposed_txs.len()) // Transactions are proposed FIFO.
+            // Transactions are proposed FIFO.
+            .filter(|(tx_hash, _)| !self.proposed_txs.contains(*tx_hash))
             .take(n_txs)
             .map(|(&hash, tx)| (hash, tx.clone()))
             .unzip();
@@ -119,7 +180,9 @@ impl TransactionManager {
     }
 
     pub fn tx_status(&self, tx_hash: TransactionHash) -> ValidationStatus {
-        if self.txs.contains_key(&tx_hash) {
+        if self.canceled_txs.contains_key(&tx_hash) {
+            ValidationStatus::CanceledOnL1
+        } else if self.txs.contains_key(&tx_hash) {
             ValidationStatus::Validated
         } else if self.on_l2_awaiting_l1_consumption.contains(&tx_hash) {
             ValidationStatus::AlreadyIncludedOnL2
@@ -128,23 +191,127 @@ impl TransactionManager {
         }
     }
 
-    pub fn _add_unconsumed_l1_not_in_l2_block_tx(&mut self, _tx: L1HandlerTransaction) {
-        todo!(
-            "Check if tx is in L2, if it isn't on L2 add it to the txs buffer, otherwise print
-             debug and do nothing."
-        )
+    pub fn lifecycle_status(&self, tx_hash: TransactionHash) -> L1HandlerTransactionStatus {
+        if self.consumed_txs.contains_key(&tx_hash) {
+            L1HandlerTransactionStatus::Consumed
+        } else if self.on_l2_awaiting_l1_consumption.contains(&tx_hash) {
+            L1HandlerTransactionStatus::OnL2
+        } else if self.canceled_txs.contains_key(&tx_hash) {
+            L1HandlerTransactionStatus::Canceled
+        } else if self.cancellation_started_txs.contains(&tx_hash) {
+            L1HandlerTransactionStatus::CancellationStarted
+        } else if self.proposed_txs.contains(&tx_hash) {
+            L1HandlerTransactionStatus::Proposed
+        } else if self.txs.contains_key(&tx_hash) {
+            L1HandlerTransactionStatus::Pending
+        } else {
+            L1HandlerTransactionStatus::Unknown
+        }
     }
 
-    pub fn _mark_tx_included_on_l2(&mut self, _tx_hash: &TransactionHash) {
-        todo!("Adds the tx hash to l2 buffer; remove tx from the txs storage if it's there.")
+    pub fn add_unconsumed_l1_not_in_l2_block_tx(&mut self, tx: L1HandlerTransaction) {
+        let tx_hash = tx.tx_hash;
+        if self.on_l2_awaiting_l1_consumption.contains(&tx_hash)
+            || self.consumed_txs.contains_key(&tx_hash)
+        {
+            debug!("Transaction {tx_hash} is already included on L2, ignoring it.");
+            // It's an L1 handler transaction after all, so it awaits its consumption.
+            self.committed_unscraped_txs.shift_remove(&tx_hash);
+            return;
+        }
+        if self.canceled_txs.contains_key(&tx_hash) {
+            debug!("Transaction {tx_hash} was canceled on L1, ignoring it.");
+            return;
+        }
+
+        if self.txs.insert(tx_hash, tx).is_some() {
+            debug!("Transaction {tx_hash} was already scraped, ignoring it.");
+        }
+    }
+
+    pub fn mark_tx_included_on_l2(&mut self, tx_hash: &TransactionHash) {
+        if self.consumed_txs.contains_key(tx_hash) {
+            debug!("Transaction {tx_hash} is already consumed on L1.");
+            return;
+        }
+        // Preserve insertion order, since transactions are proposed FIFO.
+        if self.txs.shift_remove(tx_hash).is_none()
+            && !self.on_l2_awaiting_l1_consumption.contains(tx_hash)
+        {
+            // The block was committed before the scraper caught up with the transaction.
+            self.committed_unscraped_txs.entry(*tx_hash).or_insert_with(Instant::now);
+        }
+        // The cancellation didn't complete in time.
+        self.cancellation_started_txs.shift_remove(tx_hash);
+        self.canceled_txs.shift_remove(tx_hash);
+        self.on_l2_awaiting_l1_consumption.insert(*tx_hash);
+    }
+
+    pub fn start_tx_cancellation(&mut self, tx_hash: TransactionHash) {
+        // Otherwise, the transaction is either unknown, or already done with on L1 or L2.
+        if self.txs.contains_key(&tx_hash) {
+            self.cancellation_started_txs.insert(tx_hash);
+        }
+    }
+
+    pub fn cancel_tx(&mut self, tx_hash: TransactionHash) {
+        if self.on_l2_awaiting_l1_consumption.contains(&tx_hash)
+            || self.consumed_txs.contains_key(&tx_hash)
+        {
+            debug!("Transaction {tx_hash} is already included on L2, ignoring its cancellation.");
+            return;
+        }
+
+        self.cancellation_started_txs.shift_remove(&tx_hash);
+        self.canceled_txs.entry(tx_hash).or_insert_with(Instant::now);
+        // A proposed transaction is left in place, it is either committed or dropped when the
+        // block is committed.
+        if self.proposed_txs.contains(&tx_hash) {
+            debug!("Transaction {tx_hash} was canceled on L1 after it was proposed.");
+            return;
+        }
+
+        self.txs.shift_remove(&tx_hash);
     }
-}
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum ValidationStatus {
-    Validated,
-    AlreadyIncludedOnL2,
-    ConsumedOnL1OrUnknown,
+    pub fn consume_tx(&mut self, tx_hash: TransactionHash) {
+        if !self.on_l2_awaiting_l1_consumption.shift_remove(&tx_hash) {
+            // Either scraped before the provider learned of the L2 block, or a rewind rescraped it.
+            debug!("Transaction {tx_hash} was consumed on L1 without being committed on L2 first.");
+            if !self.proposed_txs.contains(&tx_hash) {
+                self.txs.shift_remove(&tx_hash);
+            }
+        }
+        self.committed_unscraped_txs.shift_remove(&tx_hash);
+        self.cancellation_started_txs.shift_remove(&tx_hash);
+        self.canceled_txs.shift_remove(&tx_hash);
+        self.consumed_txs.entry(tx_hash).or_insert_with(Instant::now);
+    }
+
+    pub fn drop_canceled_txs(&mut self) {
+        let canceled_txs = &self.canceled_txs;
+        self.txs.retain(|tx_hash, _| !canceled_txs.contains_key(tx_hash));
+    }
+
+    /// Forgets consumed and canceled transactions that were learned of more than `rewind_time`
+    /// ago, since a scraper rewind no longer rescrapes their messages. Likewise, transactions
+    /// committed on L2 more than `rewind_time` ago without being scraped are assumed to be account
+    /// transactions.
+    pub fn prune_l1_history(&mut self, rewind_time: Duration) {
+        let is_in_rewind_window =
+            |_: &TransactionHash, learned_at: &mut Instant| learned_at.elapsed() < rewind_time;
+        self.consumed_txs.retain(is_in_rewind_window);
+        self.canceled_txs.retain(is_in_rewind_window);
+
+        let on_l2_awaiting_l1_consumption = &mut self.on_l2_awaiting_l1_consumption;
+        self.committed_unscraped_txs.retain(|tx_hash, committed_at| {
+            let is_in_rewind_window = committed_at.elapsed() < rewind_time;
+            if !is_in_rewind_window {
+                on_l2_awaiting_l1_consumption.shift_remove(tx_hash);
+            }
+            is_in_rewind_window
+        });
+    }
 }
 
 /// Current state of the provider, where pending means: idle, between proposal/validation cycles.
@@ -161,8 +328,8 @@ impl ProviderState {
         match self {
             ProviderState::Pending => Ok(ProviderState::Propose),
             _ => Err(L1ProviderError::UnexpectedProviderStateTransition {
-                from: self,
-                to: ProviderState::Propose,
+                from: self.to_string(),
+                to: ProviderState::Propose.to_string(),
             }),
         }
     }
@@ -171,14 +338,14 @@ impl ProviderState {
         match self {
             ProviderState::Pending => Ok(ProviderState::Validate),
             _ => Err(L1ProviderError::UnexpectedProviderStateTransition {
-                from: self,
-                to: ProviderState::Validate,
+                from: self.to_string(),
+                to: ProviderState::Validate.to_string(),
             }),
         }
     }
 
-    fn _transition_to_pending(self) -> L1ProviderResult<Self> {
-        todo!()
+    fn transition_to_pending(self) -> Self {
+        ProviderState::Pending
     }
 
     pub fn as_str(&self) -> &str {
@@ -196,5 +363,52 @@ impl std::fmt::Display for ProviderState {
     }
 }
 
-#[derive(Debug)]
-pub struct L1ProviderConfig;
+pub fn create_l1_provider(config: L1ProviderConfig) -> L1Provider {
+    L1Provider { config, ..Default::default() }
+}
+
+// The time the L1 provider has to answer the health probe before it is considered unresponsive.
+const HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Probes the responsiveness of the L1 provider by sending it a request through the given client.
+/// The L1 provider is unhealthy if it doesn't answer in time, e.g., when its server is stuck.
+#[derive(Clone)]
+pub struct L1ProviderHealth {
+    client: SharedL1ProviderClient,
+}
+
+impl L1ProviderHealth {
+    pub fn new(client: SharedL1ProviderClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl ComponentHealth for L1ProviderHealth {
+    async fn check_health(&self) -> HealthStatus {
+        let probe = self.client.get_tx_status(TransactionHash::default());
+        match tokio::time::timeout(HEALTH_PROBE_TIMEOUT, probe).await {
+            Ok(Ok(_)) => HealthStatus::Healthy { details: "Answers requests.".to_owned() },
+            Ok(Err(err)) => HealthStatus::Unhealthy {
+                reason: format!("L1 provider failed to answer a request: {err}"),
+            },
+            Err(_) => HealthStatus::Unhealthy {
+                reason: format!(
+                    "L1 provider didn't answer a request within {} seconds.",
+                    HEALTH_PROBE_TIMEOUT.as_secs()
+                ),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct L1ProviderConfig {
+    pub l1_scraper_config: L1ScraperConfig,
+}
+
+impl SerializeConfig for L1ProviderConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        append_sub_config_name(self.l1_scraper_config.dump(), "l1_scraper_config")
+    }
+}
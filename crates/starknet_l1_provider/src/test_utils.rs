@@ -2,12 +2,13 @@ use indexmap::{IndexMap, IndexSet};
 use starknet_api::executable_transaction::L1HandlerTransaction;
 use starknet_api::transaction::TransactionHash;
 
-use crate::{L1Provider, ProviderState, TransactionManager};
+use crate::{L1Provider, L1ProviderConfig, ProviderState, TransactionManager};
 
 // Represents the internal content of the L1 provider for testing.
 // Enables customized (and potentially inconsistent) creation for unit testing.
 #[derive(Debug, Default)]
 pub struct L1ProviderContent {
+    config: Option<L1ProviderConfig>,
     tx_manager_content: Option<TransactionManagerContent>,
     state: Option<ProviderState>,
 }
@@ -15,6 +16,7 @@ pub struct L1ProviderContent {
 impl From<L1ProviderContent> for L1Provider {
     fn from(content: L1ProviderContent) -> L1Provider {
         L1Provider {
+            config: content.config.unwrap_or_default(),
             tx_manager: content
                 .tx_manager_content
                 .map(|tm_content| tm_content.complete_to_tx_manager())
@@ -26,6 +28,7 @@ impl From<L1ProviderContent> for L1Provider {
 
 #[derive(Debug, Default)]
 pub struct L1ProviderContentBuilder {
+    config: Option<L1ProviderConfig>,
     tx_manager_content_builder: TransactionManagerContentBuilder,
     state: Option<ProviderState>,
 }
@@ -35,6 +38,11 @@ impl L1ProviderContentBuilder {
         Self::default()
     }
 
+    pub fn with_config(mut self, config: L1ProviderConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
     pub fn with_state(mut self, state: ProviderState) -> Self {
         self.state = Some(state);
         self
@@ -56,6 +64,7 @@ impl L1ProviderContentBuilder {
 
     pub fn build(self) -> L1ProviderContent {
         L1ProviderContent {
+            config: self.config,
             tx_manager_content: self.tx_manager_content_builder.build(),
             state: self.state,
         }
@@ -0,0 +1,216 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+#[cfg(any(feature = "testing", test))]
+use mockall::automock;
+use papyrus_proc_macros::handle_response_variants;
+use serde::{Deserialize, Serialize};
+use starknet_api::executable_transaction::L1HandlerTransaction;
+use starknet_api::transaction::TransactionHash;
+use starknet_sequencer_infra::component_client::{
+    ClientError,
+    LocalComponentClient,
+    RemoteComponentClient,
+};
+use starknet_sequencer_infra::component_definitions::{
+    ComponentClient,
+    ComponentRequest,
+    ComponentRequestAndResponseSender,
+};
+use thiserror::Error;
+
+use crate::errors::L1ProviderError;
+use crate::l1_provider_types::{
+    Event,
+    L1HandlerTransactionStatus,
+    L1ProviderResult,
+    ValidationStatus,
+};
+
+#[cfg(test)]
+#[path = "communication_test.rs"]
+mod communication_test;
+
+pub type LocalL1ProviderClient = LocalComponentClient<L1ProviderRequest, L1ProviderResponse>;
+pub type RemoteL1ProviderClient = RemoteComponentClient<L1ProviderRequest, L1ProviderResponse>;
+pub type L1ProviderClientResult<T> = Result<T, L1ProviderClientError>;
+pub type L1ProviderRequestAndResponseSender =
+    ComponentRequestAndResponseSender<L1ProviderRequest, L1ProviderResponse>;
+pub type SharedL1ProviderClient = Arc<dyn L1ProviderClient>;
+
+/// Serves as the L1 provider's shared interface. Requires `Send + Sync` to allow transferring and
+/// sharing resources (inputs, futures) across threads.
+#[cfg_attr(any(feature = "testing", test), automock)]
+#[async_trait]
+pub trait L1ProviderClient: Send + Sync {
+    async fn abort_cycle(&self) -> L1ProviderClientResult<()>;
+    async fn add_events(&self, events: Vec<Event>) -> L1ProviderClientResult<()>;
+    async fn commit_block(&self, tx_hashes: Vec<TransactionHash>) -> L1ProviderClientResult<()>;
+    async fn get_txs(&self, n_txs: usize) -> L1ProviderClientResult<Vec<L1HandlerTransaction>>;
+    async fn get_tx_status(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> L1ProviderClientResult<L1HandlerTransactionStatus>;
+    async fn handle_reorg(&self) -> L1ProviderClientResult<()>;
+    async fn proposal_start(&self) -> L1ProviderClientResult<()>;
+    async fn validate(&self, tx_hash: TransactionHash) -> L1ProviderClientResult<ValidationStatus>;
+    async fn validation_start(&self) -> L1ProviderClientResult<()>;
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum L1ProviderRequest {
+    AbortCycle,
+    AddEvents(Vec<Event>),
+    CommitBlock(Vec<TransactionHash>),
+    GetTransactions(usize),
+    GetTransactionStatus(TransactionHash),
+    HandleReorg,
+    ProposalStart,
+    Validate(TransactionHash),
+    ValidationStart,
+}
+
+impl ComponentRequest for L1ProviderRequest {
+    fn is_idempotent(&self) -> bool {
+        match self {
+            L1ProviderRequest::GetTransactionStatus(_) => true,
+            L1ProviderRequest::AbortCycle
+            | L1ProviderRequest::AddEvents(_)
+            | L1ProviderRequest::CommitBlock(_)
+            | L1ProviderRequest::GetTransactions(_)
+            | L1ProviderRequest::HandleReorg
+            | L1ProviderRequest::ProposalStart
+            | L1ProviderRequest::Validate(_)
+            | L1ProviderRequest::ValidationStart => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum L1ProviderResponse {
+    AbortCycle(L1ProviderResult<()>),
+    AddEvents(L1ProviderResult<()>),
+    CommitBlock(L1ProviderResult<()>),
+    GetTransactions(L1ProviderResult<Vec<L1HandlerTransaction>>),
+    GetTransactionStatus(L1ProviderResult<L1HandlerTransactionStatus>),
+    HandleReorg(L1ProviderResult<()>),
+    ProposalStart(L1ProviderResult<()>),
+    Validate(L1ProviderResult<ValidationStatus>),
+    ValidationStart(L1ProviderResult<()>),
+}
+
+#[derive(Clone, Debug, Error)]
+pub enum L1ProviderClientError {
+    #[error(transparent)]
+    ClientError(#[from] ClientError),
+    #[error(transparent)]
+    L1ProviderError(#[from] L1ProviderError),
+}
+
+#[async_trait]
+impl<ComponentClientType> L1ProviderClient for ComponentClientType
+where
+    ComponentClientType: Send + Sync + ComponentClient<L1ProviderRequest, L1ProviderResponse>,
+{
+    async fn abort_cycle(&self) -> L1ProviderClientResult<()> {
+        let request = L1ProviderRequest::AbortCycle;
+        let response = self.send(request).await;
+        handle_response_variants!(
+            L1ProviderResponse,
+            AbortCycle,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
+
+    async fn add_events(&self, events: Vec<Event>) -> L1ProviderClientResult<()> {
+        let request = L1ProviderRequest::AddEvents(events);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            L1ProviderResponse,
+            AddEvents,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
+
+    async fn commit_block(&self, tx_hashes: Vec<TransactionHash>) -> L1ProviderClientResult<()> {
+        let request = L1ProviderRequest::CommitBlock(tx_hashes);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            L1ProviderResponse,
+            CommitBlock,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
+
+    async fn get_txs(&self, n_txs: usize) -> L1ProviderClientResult<Vec<L1HandlerTransaction>> {
+        let request = L1ProviderRequest::GetTransactions(n_txs);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            L1ProviderResponse,
+            GetTransactions,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
+
+    async fn get_tx_status(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> L1ProviderClientResult<L1HandlerTransactionStatus> {
+        let request = L1ProviderRequest::GetTransactionStatus(tx_hash);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            L1ProviderResponse,
+            GetTransactionStatus,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
+
+    async fn handle_reorg(&self) -> L1ProviderClientResult<()> {
+        let request = L1ProviderRequest::HandleReorg;
+        let response = self.send(request).await;
+        handle_response_variants!(
+            L1ProviderResponse,
+            HandleReorg,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
+
+    async fn proposal_start(&self) -> L1ProviderClientResult<()> {
+        let request = L1ProviderRequest::ProposalStart;
+        let response = self.send(request).await;
+        handle_response_variants!(
+            L1ProviderResponse,
+            ProposalStart,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
+
+    async fn validate(&self, tx_hash: TransactionHash) -> L1ProviderClientResult<ValidationStatus> {
+        let request = L1ProviderRequest::Validate(tx_hash);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            L1ProviderResponse,
+            Validate,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
+
+    async fn validation_start(&self) -> L1ProviderClientResult<()> {
+        let request = L1ProviderRequest::ValidationStart;
+        let response = self.send(request).await;
+        handle_response_variants!(
+            L1ProviderResponse,
+            ValidationStart,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
+}
@@ -0,0 +1,55 @@
+use rstest::rstest;
+use starknet_api::executable_transaction::L1HandlerTransaction;
+use starknet_api::test_utils::l1_handler::{executable_l1_handler_tx, L1HandlerTxArgs};
+use starknet_api::tx_hash;
+use starknet_sequencer_infra::test_utils::assert_codec_round_trip;
+
+use crate::communication::{L1ProviderRequest, L1ProviderResponse};
+use crate::errors::L1ProviderError;
+use crate::l1_provider_types::{Event, L1HandlerTransactionStatus, ValidationStatus};
+
+fn tx() -> L1HandlerTransaction {
+    executable_l1_handler_tx(L1HandlerTxArgs { tx_hash: tx_hash!(1), ..Default::default() })
+}
+
+#[rstest]
+#[case::abort_cycle(L1ProviderRequest::AbortCycle)]
+#[case::add_events(L1ProviderRequest::AddEvents(vec![
+    Event::L1HandlerTransaction(tx()),
+    Event::TransactionCancellationStarted(tx_hash!(2)),
+    Event::TransactionCanceled(tx_hash!(3)),
+    Event::TransactionConsumed(tx_hash!(4)),
+]))]
+#[case::commit_block(L1ProviderRequest::CommitBlock(vec![tx_hash!(1)]))]
+#[case::get_transactions(L1ProviderRequest::GetTransactions(1))]
+#[case::get_transaction_status(L1ProviderRequest::GetTransactionStatus(tx_hash!(1)))]
+#[case::handle_reorg(L1ProviderRequest::HandleReorg)]
+#[case::proposal_start(L1ProviderRequest::ProposalStart)]
+#[case::validate(L1ProviderRequest::Validate(tx_hash!(1)))]
+#[case::validation_start(L1ProviderRequest::ValidationStart)]
+fn test_request_round_trip(#[case] request: L1ProviderRequest) {
+    assert_codec_round_trip(&request);
+}
+
+#[rstest]
+#[case::abort_cycle(L1ProviderResponse::AbortCycle(Ok(())))]
+#[case::add_events(L1ProviderResponse::AddEvents(Ok(())))]
+#[case::commit_block(L1ProviderResponse::CommitBlock(Err(
+    L1ProviderError::UnexpectedProviderStateTransition {
+        from: "Pending".to_owned(),
+        to: "Validate".to_owned(),
+    }
+)))]
+#[case::get_transactions(L1ProviderResponse::GetTransactions(Ok(vec![tx()])))]
+#[case::get_transaction_status(L1ProviderResponse::GetTransactionStatus(Ok(
+    L1HandlerTransactionStatus::OnL2
+)))]
+#[case::handle_reorg(L1ProviderResponse::HandleReorg(Ok(())))]
+#[case::proposal_start(L1ProviderResponse::ProposalStart(Err(
+    L1ProviderError::GetTransactionsInPendingState
+)))]
+#[case::validate(L1ProviderResponse::Validate(Ok(ValidationStatus::CanceledOnL1)))]
+#[case::validation_start(L1ProviderResponse::ValidationStart(Ok(())))]
+fn test_response_round_trip(#[case] response: L1ProviderResponse) {
+    assert_codec_round_trip(&response);
+}
@@ -1,12 +1,8 @@
-use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerError;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::ProviderState;
-
-#[derive(Error, Debug)]
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
 pub enum L1ProviderError {
-    #[error(transparent)]
-    BaseLayer(#[from] EthereumBaseLayerError),
     #[error(
         "`get_txs` called while in `Pending` state, likely due to a crash; restart block proposal"
     )]
@@ -14,7 +10,7 @@ pub enum L1ProviderError {
     #[error("`get_txs` while in validate state")]
     GetTransactionConsensusBug,
     #[error("Cannot transition from {from} to {to}")]
-    UnexpectedProviderStateTransition { from: ProviderState, to: ProviderState },
+    UnexpectedProviderStateTransition { from: String, to: String },
     #[error(
         "`validate` called while in `Pending` state, likely due to a crash; restart block proposal"
     )]
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use starknet_api::executable_transaction::L1HandlerTransaction;
+use starknet_api::transaction::TransactionHash;
+
+use crate::errors::L1ProviderError;
+
+pub type L1ProviderResult<T> = Result<T, L1ProviderError>;
+
+/// An L1 event, as consumed by the provider.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Event {
+    L1HandlerTransaction(L1HandlerTransaction),
+    TransactionCancellationStarted(TransactionHash),
+    TransactionCanceled(TransactionHash),
+    TransactionConsumed(TransactionHash),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValidationStatus {
+    Validated,
+    AlreadyIncludedOnL2,
+    CanceledOnL1,
+    ConsumedOnL1OrUnknown,
+}
+
+/// The lifecycle of an L1 handler transaction, as seen by the provider.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum L1HandlerTransactionStatus {
+    /// Scraped from L1, waiting to be proposed.
+    Pending,
+    /// Proposed in the block currently being built.
+    Proposed,
+    /// Included in a committed L2 block, awaiting consumption on L1.
+    OnL2,
+    /// Consumed on L1, after the L2 block that included it was proven.
+    Consumed,
+    /// Cancellation was started on L1, the message can still be included on L2 until it completes.
+    CancellationStarted,
+    /// Canceled on L1 before the message was included on L2.
+    Canceled,
+    /// Never scraped, or dropped by a reset.
+    Unknown,
+}
@@ -0,0 +1,3 @@
+pub mod communication;
+pub mod errors;
+pub mod l1_provider_types;
@@ -1,11 +1,17 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
+use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::executable_transaction::AccountTransaction;
 use starknet_api::rpc_transaction::{
     RpcDeployAccountTransaction,
     RpcInvokeTransaction,
     RpcTransaction,
 };
+use starknet_api::transaction::TransactionHash;
+use starknet_batcher_types::batcher_types::GetAccountNoncesInput;
+use starknet_batcher_types::communication::SharedBatcherClient;
 use starknet_mempool_p2p_types::communication::SharedMempoolP2pPropagatorClient;
 use starknet_mempool_types::communication::{
     AddTransactionArgsWrapper,
@@ -13,34 +19,136 @@ use starknet_mempool_types::communication::{
     MempoolResponse,
 };
 use starknet_mempool_types::errors::MempoolError;
-use starknet_mempool_types::mempool_types::{CommitBlockArgs, MempoolResult};
+use starknet_mempool_types::mempool_types::{
+    AccountTransactionsPage,
+    CommitBlockArgs,
+    GetAccountTransactionsArgs,
+    MempoolResult,
+    MempoolTransactionStatus,
+};
 use starknet_sequencer_infra::component_definitions::{ComponentRequestHandler, ComponentStarter};
 use starknet_sequencer_infra::component_server::{LocalComponentServer, RemoteComponentServer};
+use starknet_sequencer_infra::errors::ComponentError;
+use tracing::{error, info};
 
-use crate::mempool::Mempool;
+use crate::journal::{MempoolJournal, MempoolJournalConfig, MempoolJournalEvent};
+use crate::mempool::{Mempool, MempoolConfig, MempoolHealth};
+use crate::utils::InstantClock;
 
 pub type LocalMempoolServer =
     LocalComponentServer<MempoolCommunicationWrapper, MempoolRequest, MempoolResponse>;
 pub type RemoteMempoolServer = RemoteComponentServer<MempoolRequest, MempoolResponse>;
 
+/// Creates the mempool. If a journal is configured, the mempool content is restored from it, and
+/// once the mempool starts, it is checked against the nonces committed by the batcher.
 pub fn create_mempool(
+    config: MempoolConfig,
+    journal_config: Option<MempoolJournalConfig>,
     mempool_p2p_propagator_client: SharedMempoolP2pPropagatorClient,
+    batcher_client: Option<SharedBatcherClient>,
 ) -> MempoolCommunicationWrapper {
-    MempoolCommunicationWrapper::new(Mempool::default(), mempool_p2p_propagator_client)
+    let mut mempool = Mempool::new(config, Arc::new(InstantClock));
+    let journal = journal_config.and_then(|journal_config| {
+        let (mut journal, events) = match MempoolJournal::open(journal_config) {
+            Ok(journal_and_events) => journal_and_events,
+            Err(err) => {
+                error!("Failed to open the mempool journal, starting without one: {}", err);
+                return None;
+            }
+        };
+        info!("Restoring the mempool from {} journaled events.", events.len());
+        mempool.replay(events);
+        // Until the next successful compaction, the journal keeps growing from its current content.
+        if let Err(err) = journal.compact(&mempool.snapshot()) {
+            error!("Failed to compact the mempool journal: {}", err);
+        }
+        Some(journal)
+    });
+
+    MempoolCommunicationWrapper::new(
+        mempool,
+        mempool_p2p_propagator_client,
+        journal,
+        batcher_client,
+    )
 }
 
 /// Wraps the mempool to enable inbound async communication from other components.
 pub struct MempoolCommunicationWrapper {
     mempool: Mempool,
     mempool_p2p_propagator_client: SharedMempoolP2pPropagatorClient,
+    journal: Option<MempoolJournal>,
+    batcher_client: Option<SharedBatcherClient>,
 }
 
 impl MempoolCommunicationWrapper {
     pub fn new(
         mempool: Mempool,
         mempool_p2p_propagator_client: SharedMempoolP2pPropagatorClient,
+        journal: Option<MempoolJournal>,
+        batcher_client: Option<SharedBatcherClient>,
     ) -> Self {
-        MempoolCommunicationWrapper { mempool, mempool_p2p_propagator_client }
+        MempoolCommunicationWrapper {
+            mempool,
+            mempool_p2p_propagator_client,
+            journal,
+            batcher_client,
+        }
+    }
+
+    pub fn health(&self) -> MempoolHealth {
+        self.mempool.health()
+    }
+
+    // Records an event that was applied to the mempool. Failures are only logged: the mempool keeps
+    // serving requests, at the risk of losing the event on restart.
+    fn journal_event(&mut self, event: MempoolJournalEvent) {
+        let Some(journal) = &mut self.journal else {
+            return;
+        };
+
+        let result = journal.append(&event).and_then(|()| {
+            if journal.should_compact() {
+                journal.compact(&self.mempool.snapshot())
+            } else {
+                Ok(())
+            }
+        });
+        if let Err(err) = result {
+            error!("Failed to write to the mempool journal: {}", err);
+        }
+    }
+
+    // Blocks may have been committed after the restored content was journaled, e.g., if the
+    // mempool was down while the rest of the node was not. Transactions with a nonce that was used
+    // in those blocks are removed. Failures are only logged, as they leave the mempool as valid as
+    // its journal.
+    async fn remove_stale_txs(&mut self) {
+        let Some(batcher_client) = &self.batcher_client else {
+            return;
+        };
+        let addresses = self.mempool.accounts();
+        if addresses.is_empty() {
+            return;
+        }
+
+        let address_to_nonce =
+            match batcher_client.get_account_nonces(GetAccountNoncesInput { addresses }).await {
+                Ok(response) => response.address_to_nonce,
+                Err(err) => {
+                    error!("Failed to get the committed account nonces from the batcher: {}", err);
+                    return;
+                }
+            };
+        if let Err(err) = self.mempool.remove_stale_txs(address_to_nonce) {
+            error!("Failed to remove stale transactions from the mempool: {}", err);
+            return;
+        }
+        if let Some(journal) = &mut self.journal {
+            if let Err(err) = journal.compact(&self.mempool.snapshot()) {
+                error!("Failed to compact the mempool journal: {}", err);
+            }
+        }
     }
 
     async fn send_tx_to_p2p(
@@ -79,7 +187,13 @@ impl MempoolCommunicationWrapper {
     }
 
     async fn add_tx(&mut self, args_wrapper: AddTransactionArgsWrapper) -> MempoolResult<()> {
+        let tx_hash = args_wrapper.args.tx.tx_hash();
         self.mempool.add_tx(args_wrapper.args.clone())?;
+        let submission_time = self.mempool.submission_time(tx_hash)?;
+        self.journal_event(MempoolJournalEvent::AddTransaction {
+            args: args_wrapper.args.clone(),
+            submission_time,
+        });
         // TODO: Verify that only transactions that were added to the mempool are sent.
         // TODO: handle declare correctly and remove this match.
         match args_wrapper.args.tx {
@@ -89,12 +203,33 @@ impl MempoolCommunicationWrapper {
     }
 
     fn commit_block(&mut self, args: CommitBlockArgs) -> MempoolResult<()> {
-        self.mempool.commit_block(args)
+        self.mempool.commit_block(args.clone())?;
+        self.journal_event(MempoolJournalEvent::CommitBlock(args));
+        Ok(())
+    }
+
+    fn rewind(&mut self) -> MempoolResult<()> {
+        self.mempool.rewind()
     }
 
     fn get_txs(&mut self, n_txs: usize) -> MempoolResult<Vec<AccountTransaction>> {
         self.mempool.get_txs(n_txs)
     }
+
+    fn account_nonce(&self, address: ContractAddress) -> MempoolResult<Option<Nonce>> {
+        self.mempool.account_nonce(address)
+    }
+
+    fn get_account_txs(
+        &self,
+        args: GetAccountTransactionsArgs,
+    ) -> MempoolResult<AccountTransactionsPage> {
+        self.mempool.get_account_txs(args)
+    }
+
+    fn get_tx_status(&self, tx_hash: TransactionHash) -> MempoolResult<MempoolTransactionStatus> {
+        self.mempool.get_tx_status(tx_hash)
+    }
 }
 
 #[async_trait]
@@ -107,11 +242,27 @@ impl ComponentRequestHandler<MempoolRequest, MempoolResponse> for MempoolCommuni
             MempoolRequest::CommitBlock(args) => {
                 MempoolResponse::CommitBlock(self.commit_block(args))
             }
+            MempoolRequest::GetAccountNonce(address) => {
+                MempoolResponse::GetAccountNonce(self.account_nonce(address))
+            }
+            MempoolRequest::GetAccountTransactions(args) => {
+                MempoolResponse::GetAccountTransactions(self.get_account_txs(args))
+            }
+            MempoolRequest::GetTransactionStatus(tx_hash) => {
+                MempoolResponse::GetTransactionStatus(self.get_tx_status(tx_hash))
+            }
             MempoolRequest::GetTransactions(n_txs) => {
                 MempoolResponse::GetTransactions(self.get_txs(n_txs))
             }
+            MempoolRequest::Rewind => MempoolResponse::Rewind(self.rewind()),
         }
     }
 }
 
-impl ComponentStarter for MempoolCommunicationWrapper {}
+#[async_trait]
+impl ComponentStarter for MempoolCommunicationWrapper {
+    async fn start(&mut self) -> Result<(), ComponentError> {
+        self.remove_stale_txs().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_mempool_types::mempool_types::{AddTransactionArgs, CommitBlockArgs};
+use thiserror::Error;
+use tracing::warn;
+use validator::Validate;
+
+#[cfg(test)]
+#[path = "journal_test.rs"]
+pub mod journal_test;
+
+/// An event that changed the content of the mempool.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MempoolJournalEvent {
+    // The submission time is kept so that restored transactions expire on time.
+    AddTransaction { args: AddTransactionArgs, submission_time: SystemTime },
+    CommitBlock(CommitBlockArgs),
+}
+
+#[derive(Debug, Error)]
+pub enum MempoolJournalError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error(transparent)]
+    SerializationError(#[from] serde_json::Error),
+}
+
+pub type MempoolJournalResult<T> = Result<T, MempoolJournalError>;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Validate)]
+pub struct MempoolJournalConfig {
+    pub path: PathBuf,
+    #[validate(range(min = 1))]
+    pub compaction_threshold: usize,
+}
+
+impl Default for MempoolJournalConfig {
+    fn default() -> Self {
+        Self { path: PathBuf::from("./mempool_journal"), compaction_threshold: 10_000 }
+    }
+}
+
+impl SerializeConfig for MempoolJournalConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "path",
+                &self.path,
+                "Path of the mempool journal file. It is created if it does not exist.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "compaction_threshold",
+                &self.compaction_threshold,
+                "Number of events appended to the journal after which it is replaced by a \
+                 snapshot of the mempool content.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// An append-only file of the events that changed the mempool, from which its content is restored
+/// after a restart.
+/// To keep the file bounded, it is periodically replaced by a snapshot: the minimal events that
+/// rebuild the current content of the mempool.
+#[derive(Debug)]
+pub struct MempoolJournal {
+    config: MempoolJournalConfig,
+    writer: BufWriter<File>,
+    n_events_since_compaction: usize,
+}
+
+impl MempoolJournal {
+    /// Opens the journal, creating it if it does not exist, and returns the events recorded in it.
+    /// A partially written last event, e.g., due to a crash, is discarded.
+    pub fn open(
+        config: MempoolJournalConfig,
+    ) -> MempoolJournalResult<(Self, Vec<MempoolJournalEvent>)> {
+        let events = match File::open(&config.path) {
+            Ok(file) => {
+                let (events, valid_len) = read_events(file)?;
+                // Drop the corrupted end, so that new events are appended after valid ones.
+                OpenOptions::new().write(true).open(&config.path)?.set_len(valid_len)?;
+                events
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err.into()),
+        };
+
+        let n_events_since_compaction = events.len();
+        let writer = open_for_append(&config.path)?;
+        Ok((Self { config, writer, n_events_since_compaction }, events))
+    }
+
+    pub fn append(&mut self, event: &MempoolJournalEvent) -> MempoolJournalResult<()> {
+        write_event(&mut self.writer, event)?;
+        self.writer.flush()?;
+        self.n_events_since_compaction += 1;
+
+        Ok(())
+    }
+
+    pub fn should_compact(&self) -> bool {
+        self.n_events_since_compaction >= self.config.compaction_threshold
+    }
+
+    /// Replaces the content of the journal with the given snapshot.
+    /// The snapshot is written to a temporary file that replaces the journal once complete, so
+    /// that a crash in the middle leaves one of them intact.
+    pub fn compact(&mut self, snapshot: &[MempoolJournalEvent]) -> MempoolJournalResult<()> {
+        let snapshot_path = self.config.path.with_extension("snapshot");
+        let mut snapshot_writer = BufWriter::new(File::create(&snapshot_path)?);
+        for event in snapshot {
+            write_event(&mut snapshot_writer, event)?;
+        }
+        snapshot_writer.into_inner().map_err(|err| err.into_error())?.sync_all()?;
+        fs::rename(&snapshot_path, &self.config.path)?;
+
+        self.writer = open_for_append(&self.config.path)?;
+        self.n_events_since_compaction = 0;
+
+        Ok(())
+    }
+}
+
+fn open_for_append(path: &Path) -> MempoolJournalResult<BufWriter<File>> {
+    Ok(BufWriter::new(OpenOptions::new().create(true).append(true).open(path)?))
+}
+
+// Events are written one per line.
+fn write_event(writer: &mut impl Write, event: &MempoolJournalEvent) -> MempoolJournalResult<()> {
+    serde_json::to_writer(&mut *writer, event)?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+// Returns the events in the file, and the length of the part of the file they occupy.
+fn read_events(file: File) -> MempoolJournalResult<(Vec<MempoolJournalEvent>, u64)> {
+    let mut reader = BufReader::new(file);
+    let mut events = Vec::new();
+    let mut valid_len = 0;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let n_read_bytes = reader.read_line(&mut line)?;
+        if n_read_bytes == 0 {
+            break;
+        }
+        // An event is complete only once its line is terminated.
+        let parsed_event = line
+            .strip_suffix('\n')
+            .ok_or_else(|| "unterminated line".to_string())
+            .and_then(|line| serde_json::from_str(line).map_err(|err| err.to_string()));
+        match parsed_event {
+            Ok(event) => events.push(event),
+            Err(err) => {
+                warn!("Discarding the end of the mempool journal, which is corrupted: {}", err);
+                break;
+            }
+        }
+        valid_len += u64::try_from(n_read_bytes).expect("Line length should fit in u64.");
+    }
+
+    Ok((events, valid_len))
+}
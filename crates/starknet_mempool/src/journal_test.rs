@@ -0,0 +1,219 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use pretty_assertions::assert_eq;
+use rstest::{fixture, rstest};
+use starknet_api::{contract_address, nonce, tx_hash};
+use starknet_mempool_types::mempool_types::{AddTransactionArgs, CommitBlockArgs};
+use tempfile::TempDir;
+
+use crate::add_tx_input;
+use crate::journal::{MempoolJournal, MempoolJournalConfig, MempoolJournalEvent};
+use crate::mempool::{Mempool, MempoolConfig};
+use crate::test_utils::{add_tx, commit_block, get_txs_and_assert_expected, FakeClock};
+
+#[fixture]
+fn journal_dir() -> TempDir {
+    tempfile::tempdir().unwrap()
+}
+
+fn journal_config(journal_dir: &TempDir) -> MempoolJournalConfig {
+    MempoolJournalConfig { path: journal_dir.path().join("mempool_journal"), ..Default::default() }
+}
+
+fn add_tx_event(tx_hash: u64, tx_nonce: u64) -> MempoolJournalEvent {
+    add_tx_input_event(add_tx_input!(tx_hash: tx_hash, tx_nonce: tx_nonce))
+}
+
+// Transactions are submitted at the start time of the fake clock.
+fn add_tx_input_event(args: AddTransactionArgs) -> MempoolJournalEvent {
+    MempoolJournalEvent::AddTransaction { args, submission_time: SystemTime::UNIX_EPOCH }
+}
+
+fn fake_clock_mempool(clock: Arc<FakeClock>) -> Mempool {
+    Mempool::new(MempoolConfig::default(), clock)
+}
+
+#[rstest]
+fn test_reopen_returns_appended_events(journal_dir: TempDir) {
+    // Setup.
+    let (mut journal, events) = MempoolJournal::open(journal_config(&journal_dir)).unwrap();
+    assert_eq!(events, []);
+    let appended_events = [
+        add_tx_event(1, 0),
+        add_tx_event(2, 1),
+        MempoolJournalEvent::CommitBlock(CommitBlockArgs {
+            address_to_nonce: HashMap::from([(contract_address!("0x0"), nonce!(2))]),
+            tx_hashes: HashSet::from([tx_hash!(1), tx_hash!(2)]),
+        }),
+    ];
+    for event in &appended_events {
+        journal.append(event).unwrap();
+    }
+    drop(journal);
+
+    // Test.
+    let (_journal, events) = MempoolJournal::open(journal_config(&journal_dir)).unwrap();
+
+    // Assert.
+    assert_eq!(events, appended_events);
+}
+
+#[rstest]
+fn test_corrupted_end_is_discarded(journal_dir: TempDir) {
+    // Setup.
+    let config = journal_config(&journal_dir);
+    let (mut journal, _) = MempoolJournal::open(config.clone()).unwrap();
+    journal.append(&add_tx_event(1, 0)).unwrap();
+    drop(journal);
+    // A partially written event.
+    OpenOptions::new().append(true).open(&config.path).unwrap().write_all(b"{\"AddTrans").unwrap();
+
+    // Test.
+    let (mut journal, events) = MempoolJournal::open(config.clone()).unwrap();
+    journal.append(&add_tx_event(2, 1)).unwrap();
+    drop(journal);
+
+    // Assert: the corrupted event does not hide the ones appended after it.
+    assert_eq!(events, [add_tx_event(1, 0)]);
+    let (_journal, events) = MempoolJournal::open(config).unwrap();
+    assert_eq!(events, [add_tx_event(1, 0), add_tx_event(2, 1)]);
+}
+
+#[rstest]
+fn test_compaction_triggered_by_threshold(journal_dir: TempDir) {
+    // Setup.
+    let config = MempoolJournalConfig { compaction_threshold: 2, ..journal_config(&journal_dir) };
+    let (mut journal, _) = MempoolJournal::open(config).unwrap();
+
+    // Test and assert.
+    journal.append(&add_tx_event(1, 0)).unwrap();
+    assert!(!journal.should_compact());
+    journal.append(&add_tx_event(2, 1)).unwrap();
+    assert!(journal.should_compact());
+    journal.compact(&[]).unwrap();
+    assert!(!journal.should_compact());
+}
+
+#[rstest]
+fn test_snapshot_restores_mempool(journal_dir: TempDir) {
+    // Setup.
+    let mut mempool = Mempool::default();
+    let input_address_0_nonce_3 =
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 3, account_nonce: 3, tip: 10);
+    let input_address_0_nonce_4 =
+        add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 4, account_nonce: 3, tip: 10);
+    let input_address_1_nonce_0 =
+        add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 0, account_nonce: 0, tip: 20);
+    let input_address_1_nonce_2 =
+        add_tx_input!(tx_hash: 4, address: "0x1", tx_nonce: 2, account_nonce: 0, tip: 20);
+    for input in [
+        &input_address_0_nonce_3,
+        &input_address_0_nonce_4,
+        &input_address_1_nonce_0,
+        &input_address_1_nonce_2,
+    ] {
+        add_tx(&mut mempool, input);
+    }
+    commit_block(&mut mempool, [("0x0", 4)], [1]);
+
+    let config = journal_config(&journal_dir);
+    let (mut journal, _) = MempoolJournal::open(config.clone()).unwrap();
+    journal.compact(&mempool.snapshot()).unwrap();
+    drop(journal);
+
+    // Test.
+    let (_journal, events) = MempoolJournal::open(config).unwrap();
+    let mut restored_mempool = Mempool::default();
+    restored_mempool.replay(events);
+
+    // Assert: the committed transaction is gone, and the nonce gap is kept.
+    get_txs_and_assert_expected(
+        &mut restored_mempool,
+        3,
+        &[input_address_1_nonce_0.tx, input_address_0_nonce_4.tx],
+    );
+}
+
+#[rstest]
+fn test_replay_skips_outdated_events() {
+    // Setup.
+    let input_nonce_0 = add_tx_input!(tx_hash: 1, tx_nonce: 0);
+    let input_nonce_1 = add_tx_input!(tx_hash: 3, tx_nonce: 1);
+    let events = [
+        add_tx_input_event(input_nonce_0),
+        MempoolJournalEvent::CommitBlock(CommitBlockArgs {
+            address_to_nonce: HashMap::from([(contract_address!("0x0"), nonce!(1))]),
+            tx_hashes: HashSet::from([tx_hash!(1)]),
+        }),
+        // A transaction with a nonce that was committed before it was added.
+        add_tx_event(2, 0),
+        add_tx_input_event(input_nonce_1.clone()),
+    ];
+
+    // Test.
+    let mut mempool = fake_clock_mempool(Arc::new(FakeClock::default()));
+    mempool.replay(events);
+
+    // Assert.
+    get_txs_and_assert_expected(&mut mempool, 2, &[input_nonce_1.tx]);
+}
+
+#[rstest]
+fn test_replay_keeps_submission_times() {
+    // Setup.
+    let clock = Arc::new(FakeClock::default());
+    let mut mempool = fake_clock_mempool(clock.clone());
+    let input = add_tx_input!(tx_hash: 1, tx_nonce: 0);
+    add_tx(&mut mempool, &input);
+    let snapshot = mempool.snapshot();
+
+    // Test: restart just before the transaction expires.
+    clock.advance(MempoolConfig::default().transaction_ttl - Duration::from_secs(1));
+    let mut restored_mempool = fake_clock_mempool(clock.clone());
+    restored_mempool.replay(snapshot);
+
+    // Assert: the transaction expires as if the mempool was not restarted.
+    clock.advance(Duration::from_secs(2));
+    get_txs_and_assert_expected(&mut restored_mempool, 1, &[]);
+}
+
+#[rstest]
+fn test_remove_stale_txs_after_replay() {
+    // Setup: the transactions of account 0x0 up to nonce 2 were committed while the node was
+    // down, and the mempool already knows that the nonce of account 0x1 is 5.
+    let input_address_0_nonce_1 =
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 1, account_nonce: 1);
+    let input_address_0_nonce_2 =
+        add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 2, account_nonce: 1);
+    let input_address_1_nonce_5 =
+        add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 5, account_nonce: 5);
+    let mut mempool = fake_clock_mempool(Arc::new(FakeClock::default()));
+    mempool.replay([
+        MempoolJournalEvent::CommitBlock(CommitBlockArgs {
+            address_to_nonce: HashMap::from([(contract_address!("0x1"), nonce!(5))]),
+            tx_hashes: HashSet::new(),
+        }),
+        add_tx_input_event(input_address_0_nonce_1),
+        add_tx_input_event(input_address_0_nonce_2.clone()),
+        add_tx_input_event(input_address_1_nonce_5.clone()),
+    ]);
+
+    // Test: the committed state lags behind the mempool for account 0x1.
+    mempool
+        .remove_stale_txs(HashMap::from([
+            (contract_address!("0x0"), nonce!(2)),
+            (contract_address!("0x1"), nonce!(4)),
+        ]))
+        .unwrap();
+
+    // Assert.
+    get_txs_and_assert_expected(
+        &mut mempool,
+        3,
+        &[input_address_1_nonce_5.tx, input_address_0_nonce_2.tx],
+    );
+}
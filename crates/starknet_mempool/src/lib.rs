@@ -1,9 +1,11 @@
 pub mod communication;
+pub mod journal;
 pub mod mempool;
+pub mod metrics;
 pub(crate) mod suspended_transaction_pool;
 pub(crate) mod transaction_pool;
 pub(crate) mod transaction_queue;
-pub(crate) mod utils;
+pub mod utils;
 
 #[cfg(any(feature = "testing", test))]
 pub mod test_utils;
@@ -1,41 +1,113 @@
-use std::collections::HashMap;
-
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use papyrus_config::converters::deserialize_seconds_to_duration;
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
 use starknet_api::block::GasPrice;
 use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::executable_transaction::AccountTransaction;
 use starknet_api::transaction::fields::Tip;
 use starknet_api::transaction::TransactionHash;
+use starknet_mempool_types::communication::SharedMempoolClient;
 use starknet_mempool_types::errors::MempoolError;
 use starknet_mempool_types::mempool_types::{
     AccountState,
+    AccountTransactionsPage,
     AddTransactionArgs,
     CommitBlockArgs,
+    GetAccountTransactionsArgs,
     MempoolResult,
+    MempoolTransactionStatus,
 };
+use starknet_sequencer_infra::component_definitions::{ComponentHealth, HealthStatus};
+use validator::Validate;
 
+use crate::journal::MempoolJournalEvent;
+use crate::metrics::{MEMPOOL_READY_TXS, MEMPOOL_SUSPENDED_TXS};
+use crate::suspended_transaction_pool::SuspendedTransactionPool;
 use crate::transaction_pool::TransactionPool;
 use crate::transaction_queue::TransactionQueue;
-use crate::utils::try_increment_nonce;
+use crate::utils::{try_increment_nonce, Clock, InstantClock};
 
 #[cfg(test)]
 #[path = "mempool_test.rs"]
 pub mod mempool_test;
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Validate)]
 pub struct MempoolConfig {
-    enable_fee_escalation: bool,
+    pub enable_fee_escalation: bool,
     // TODO: consider adding validations; should be bounded?
     // Percentage increase for tip and max gas price to enable transaction replacement.
-    fee_escalation_percentage: u8, // E.g., 10 for a 10% increase.
+    pub fee_escalation_percentage: u8, // E.g., 10 for a 10% increase.
+    // Maximal number of transactions in the pool; when full, lower priority transactions are
+    // evicted to make room for new ones.
+    #[validate(range(min = 1))]
+    pub capacity_in_txs: usize,
+    // Maximal number of transactions of a single account in the pool.
+    #[validate(range(min = 1))]
+    pub max_txs_per_account: usize,
+    // Time after which a transaction that was not sequenced is removed from the pool.
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub transaction_ttl: Duration,
 }
 
 impl Default for MempoolConfig {
     fn default() -> Self {
-        MempoolConfig { enable_fee_escalation: true, fee_escalation_percentage: 10 }
+        MempoolConfig {
+            enable_fee_escalation: true,
+            fee_escalation_percentage: 10,
+            capacity_in_txs: 100_000,
+            max_txs_per_account: 64,
+            transaction_ttl: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+impl SerializeConfig for MempoolConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable_fee_escalation",
+                &self.enable_fee_escalation,
+                "If true, transactions can be replaced with higher fee transactions.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "fee_escalation_percentage",
+                &self.fee_escalation_percentage,
+                "Percentage increase for tip and max gas price to enable transaction replacement.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "capacity_in_txs",
+                &self.capacity_in_txs,
+                "Maximal number of transactions in the mempool; when full, lower priority \
+                 transactions are evicted to make room for new ones.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_txs_per_account",
+                &self.max_txs_per_account,
+                "Maximal number of transactions of a single account in the mempool.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "transaction_ttl",
+                &self.transaction_ttl.as_secs(),
+                "Time in seconds after which a transaction that was not sequenced is removed from \
+                 the mempool, along with the following transactions of its account.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
     }
 }
 
-type AddressToNonce = HashMap<ContractAddress, Nonce>;
+pub(crate) type AddressToNonce = HashMap<ContractAddress, Nonce>;
 
 /// Represents the state tracked by the mempool.
 /// It is partitioned into categories, each serving a distinct role in the lifecycle of transaction
@@ -107,6 +179,10 @@ impl MempoolState {
         addresses_to_rewind
     }
 
+    fn rewind(&mut self) -> Vec<ContractAddress> {
+        self.staged.drain().map(|(address, _)| address).collect()
+    }
+
     fn validate_incoming_tx(&self, tx_reference: TransactionReference) -> MempoolResult<()> {
         let TransactionReference { address, nonce: tx_nonce, .. } = tx_reference;
         if self.get(address).is_some_and(|existing_nonce| tx_nonce < existing_nonce) {
@@ -132,7 +208,7 @@ impl MempoolState {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Mempool {
     config: MempoolConfig,
     // TODO: add docstring explaining visibility and coupling of the fields.
@@ -140,10 +216,35 @@ pub struct Mempool {
     tx_pool: TransactionPool,
     // Transactions eligible for sequencing.
     tx_queue: TransactionQueue,
+    // Pool transactions that follow a nonce gap of their account.
+    suspended_tx_pool: SuspendedTransactionPool,
     state: MempoolState,
+    health: MempoolHealth,
+}
+
+impl Default for Mempool {
+    fn default() -> Self {
+        Self::new(MempoolConfig::default(), Arc::new(InstantClock))
+    }
 }
 
 impl Mempool {
+    pub fn new(config: MempoolConfig, clock: Arc<dyn Clock>) -> Self {
+        Mempool {
+            tx_pool: TransactionPool::new(clock),
+            tx_queue: TransactionQueue::default(),
+            suspended_tx_pool: SuspendedTransactionPool::default(),
+            state: MempoolState::default(),
+            health: MempoolHealth::new(config.capacity_in_txs),
+            config,
+        }
+    }
+
+    /// Returns a handle that reports the size of the mempool to the monitoring endpoint.
+    pub fn health(&self) -> MempoolHealth {
+        self.health.clone()
+    }
+
     /// Returns an iterator of the current eligible transactions for sequencing, ordered by their
     /// priority.
     pub fn iter(&self) -> impl Iterator<Item = &TransactionReference> {
@@ -156,6 +257,8 @@ impl Mempool {
     // TODO: Consider renaming to `pop_txs` to be more consistent with the standard library.
     #[tracing::instrument(skip(self), err)]
     pub fn get_txs(&mut self, n_txs: usize) -> MempoolResult<Vec<AccountTransaction>> {
+        self.remove_expired_txs();
+
         let mut eligible_tx_references: Vec<TransactionReference> = Vec::with_capacity(n_txs);
         let mut n_remaining_txs = n_txs;
 
@@ -175,6 +278,7 @@ impl Mempool {
             "Returned {} out of {n_txs} transactions, ready for sequencing.",
             eligible_tx_references.len()
         );
+        self.update_state_metrics();
 
         Ok(eligible_tx_references
             .iter()
@@ -200,11 +304,16 @@ impl Mempool {
         err
     )]
     pub fn add_tx(&mut self, args: AddTransactionArgs) -> MempoolResult<()> {
+        self.remove_expired_txs();
+
         let AddTransactionArgs { tx, account_state } = args;
         let tx_reference = TransactionReference::new(&tx);
         self.validate_incoming_tx(tx_reference)?;
 
+        // Note: a replaced transaction frees its slot, so replacements always pass the limits.
         self.handle_fee_escalation(&tx)?;
+        self.validate_account_limit(tx_reference.address)?;
+        self.make_room_for(&tx_reference)?;
         self.tx_pool.insert(tx)?;
 
         // Align to account nonce, only if it is at least the one stored.
@@ -215,6 +324,14 @@ impl Mempool {
             self.tx_queue.insert(tx_reference);
         }
 
+        // Hold the transaction back if it follows a nonce gap, otherwise it may fill one.
+        if tx_reference.nonce == stored_account_nonce || self.has_ready_predecessor(&tx_reference) {
+            self.promote_suspended_txs(tx_reference);
+        } else {
+            self.suspended_tx_pool.insert(tx_reference);
+        }
+        self.update_state_metrics();
+
         Ok(())
     }
 
@@ -239,47 +356,186 @@ impl Mempool {
             }
 
             // Remove from pool.
-            self.tx_pool.remove_up_to_nonce(address, next_nonce);
+            for tx_reference in self.tx_pool.remove_up_to_nonce(address, next_nonce) {
+                self.suspended_tx_pool.remove(&tx_reference);
+            }
 
             // Maybe close nonce gap.
-            if self.tx_queue.get_nonce(address).is_none() {
-                if let Some(tx_reference) =
-                    self.tx_pool.get_by_address_and_nonce(address, next_nonce)
-                {
+            if let Some(tx_reference) = self.tx_pool.get_by_address_and_nonce(address, next_nonce) {
+                if self.tx_queue.get_nonce(address).is_none() {
                     self.tx_queue.insert(tx_reference);
                 }
+                self.promote_suspended_txs(tx_reference);
             }
         }
 
         // Commit block and rewind nonces of addresses that were not included in block.
         let addresses_to_rewind = self.state.commit(address_to_nonce);
-        for address in addresses_to_rewind {
-            // Account nonce is the minimal nonce of this address: it was proposed but not included.
-            let tx_reference = self
-                .tx_pool
-                .account_txs_sorted_by_nonce(address)
-                .next()
-                .expect("Address {address} should appear in transaction pool.");
-            self.tx_queue.remove(address);
-            self.tx_queue.insert(*tx_reference);
-        }
+        self.rewind_addresses(addresses_to_rewind);
 
         tracing::debug!("Aligned mempool to committed nonces.");
 
         // Hard-delete: finally, remove committed transactions from the mempool.
         for tx_hash in tx_hashes {
-            let Ok(_tx) = self.tx_pool.remove(tx_hash) else {
+            let Ok(tx) = self.tx_pool.remove(tx_hash) else {
                 continue; // Transaction hash unknown to mempool, from a different node.
             };
+            self.suspended_tx_pool.remove(&TransactionReference::new(&tx));
 
             // TODO(clean_accounts): remove address with no transactions left after a block cycle /
             // TTL.
         }
         tracing::debug!("Removed committed transactions known to mempool.");
+        self.update_state_metrics();
 
         Ok(())
     }
 
+    /// Returns the transactions staged for the block in-progress to the queue, in nonce order, and
+    /// restores the nonces of their accounts. Used when the block in-progress is abandoned, e.g.,
+    /// its proposal was aborted.
+    #[tracing::instrument(skip(self), err)]
+    pub fn rewind(&mut self) -> MempoolResult<()> {
+        let addresses_to_rewind = self.state.rewind();
+        tracing::debug!("Rewinding staged transactions of {} accounts.", addresses_to_rewind.len());
+        self.rewind_addresses(addresses_to_rewind);
+        self.update_state_metrics();
+
+        Ok(())
+    }
+
+    /// Applies journaled events to the mempool, e.g., to restore its content after a restart.
+    /// Events that are no longer valid are skipped, e.g., transactions whose nonce was committed in
+    /// a later event.
+    pub fn replay(&mut self, events: impl IntoIterator<Item = MempoolJournalEvent>) {
+        for event in events {
+            let result = match event {
+                MempoolJournalEvent::AddTransaction { args, submission_time } => {
+                    let tx_hash = args.tx.tx_hash();
+                    self.add_tx(args)
+                        .and_then(|()| self.tx_pool.set_submission_time(tx_hash, submission_time))
+                }
+                MempoolJournalEvent::CommitBlock(args) => self.commit_block(args),
+            };
+            if let Err(err) = result {
+                tracing::debug!("Skipping a journaled event that is no longer valid: {}", err);
+            }
+        }
+    }
+
+    /// Returns the accounts that have transactions in the mempool.
+    pub fn accounts(&self) -> Vec<ContractAddress> {
+        self.tx_pool.accounts().collect()
+    }
+
+    /// Aligns the mempool to account nonces read from the committed state, e.g., after restoring it
+    /// from the journal, as blocks may have been committed while the node was down: transactions
+    /// with a nonce that was already used are removed. Nonces that are not ahead of the committed
+    /// ones known to the mempool are ignored.
+    pub fn remove_stale_txs(&mut self, address_to_nonce: AddressToNonce) -> MempoolResult<()> {
+        let address_to_nonce = address_to_nonce
+            .into_iter()
+            .filter(|(address, nonce)| {
+                !self.state.committed.get(address).is_some_and(|committed| nonce <= committed)
+            })
+            .collect();
+        self.commit_block(CommitBlockArgs { address_to_nonce, tx_hashes: HashSet::new() })
+    }
+
+    /// Returns the events that rebuild the current content of the mempool, when replayed on an
+    /// empty one: the committed nonces, followed by the transactions in the order they were
+    /// added. Transactions staged for the block in-progress are returned to the pool.
+    pub fn snapshot(&self) -> Vec<MempoolJournalEvent> {
+        let commit_event = MempoolJournalEvent::CommitBlock(CommitBlockArgs {
+            address_to_nonce: self.state.committed.clone(),
+            tx_hashes: HashSet::new(),
+        });
+        let add_tx_events = self.tx_pool.iter_by_submission_time().map(|(tx, submission_time)| {
+            let address = tx.contract_address();
+            let nonce = self
+                .state
+                .committed
+                .get(&address)
+                .or_else(|| self.state.tentative.get(&address))
+                .copied()
+                .unwrap_or(tx.nonce());
+            MempoolJournalEvent::AddTransaction {
+                args: AddTransactionArgs {
+                    tx: tx.clone(),
+                    account_state: AccountState { address, nonce },
+                },
+                submission_time,
+            }
+        });
+
+        std::iter::once(commit_event).chain(add_tx_events).collect()
+    }
+
+    /// Returns the time a transaction held in the mempool was submitted.
+    pub fn submission_time(&self, tx_hash: TransactionHash) -> MempoolResult<SystemTime> {
+        self.tx_pool.submission_time(tx_hash)
+    }
+
+    /// Returns the status of a transaction held in the mempool.
+    pub fn get_tx_status(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> MempoolResult<MempoolTransactionStatus> {
+        let tx = self.tx_pool.get_by_tx_hash(tx_hash)?;
+        let TransactionReference { address, nonce, .. } = TransactionReference::new(tx);
+
+        let status = if self.state.staged.get(&address).is_some_and(|&staged| nonce < staged) {
+            MempoolTransactionStatus::Staged
+        } else if self.tx_queue.get_nonce(address) == Some(nonce) {
+            MempoolTransactionStatus::Queued
+        } else if self.suspended_tx_pool.contains(address, nonce) {
+            MempoolTransactionStatus::Suspended
+        } else {
+            MempoolTransactionStatus::Pending
+        };
+
+        Ok(status)
+    }
+
+    /// Returns the nonce that the next transaction of the account should have: the account nonce,
+    /// advanced past the consecutive transactions of the account held in the mempool.
+    /// Returns `None` if the account is unknown to the mempool.
+    pub fn account_nonce(&self, address: ContractAddress) -> MempoolResult<Option<Nonce>> {
+        let Some(mut next_nonce) = self.state.get(address) else {
+            return Ok(None);
+        };
+
+        while self.tx_pool.get_by_address_and_nonce(address, next_nonce).is_some() {
+            next_nonce = try_increment_nonce(next_nonce)?;
+        }
+
+        Ok(Some(next_nonce))
+    }
+
+    /// Returns up to `max_txs` transactions of the account, starting from `start_nonce` and
+    /// ordered by nonce.
+    pub fn get_account_txs(
+        &self,
+        args: GetAccountTransactionsArgs,
+    ) -> MempoolResult<AccountTransactionsPage> {
+        let GetAccountTransactionsArgs { address, start_nonce, max_txs } = args;
+        let mut tx_references = self.tx_pool.account_txs_from_nonce(address, start_nonce);
+
+        let txs = tx_references
+            .by_ref()
+            .take(max_txs)
+            .map(|tx_reference| {
+                self.tx_pool
+                    .get_by_tx_hash(tx_reference.tx_hash)
+                    .expect("Transaction hash from account mapping must appear in pool.")
+                    .clone()
+            })
+            .collect();
+        let next_start_nonce = tx_references.next().map(|tx_reference| tx_reference.nonce);
+
+        Ok(AccountTransactionsPage { txs, next_start_nonce })
+    }
+
     fn validate_incoming_tx(&self, tx_reference: TransactionReference) -> MempoolResult<()> {
         self.state.validate_incoming_tx(tx_reference)
     }
@@ -288,6 +544,130 @@ impl Mempool {
         self.state.validate_commitment(address, next_nonce);
     }
 
+    fn validate_account_limit(&self, address: ContractAddress) -> MempoolResult<()> {
+        if self.tx_pool.n_account_txs(address) >= self.config.max_txs_per_account {
+            return Err(MempoolError::TooManyAccountTransactions { address });
+        }
+
+        Ok(())
+    }
+
+    /// Evicts the lowest priority transaction if the pool is full, provided it has a lower
+    /// priority than the incoming one. Staged transactions are never evicted.
+    fn make_room_for(&mut self, incoming_tx: &TransactionReference) -> MempoolResult<()> {
+        if self.tx_pool.n_txs() < self.config.capacity_in_txs {
+            return Ok(());
+        }
+
+        let Some(evicted_tx) =
+            self.tx_pool.eviction_candidate(incoming_tx.address, &self.state.staged)
+        else {
+            return Err(MempoolError::MempoolFull);
+        };
+        let priority = |tx: &TransactionReference| (tx.tip, tx.max_l2_gas_price);
+        if priority(&evicted_tx) >= priority(incoming_tx) {
+            return Err(MempoolError::MempoolFull);
+        }
+
+        tracing::debug!("{evicted_tx} will be evicted to make room for {incoming_tx}.");
+        self.remove_tx_references(&evicted_tx);
+        self.tx_pool.remove(evicted_tx.tx_hash).expect("Transaction hash from pool must exist.");
+
+        Ok(())
+    }
+
+    /// Removes transactions that have been in the pool for longer than the configured TTL, along
+    /// with the transactions of their accounts that follow them, as these cannot be sequenced
+    /// anymore; as with eviction, no nonce gaps are left behind.
+    /// Staged transactions are kept, as they may still be included in the block in-progress.
+    fn remove_expired_txs(&mut self) {
+        let expired_txs =
+            self.tx_pool.remove_txs_older_than(self.config.transaction_ttl, &self.state.staged);
+        let mut n_removed_txs = expired_txs.len();
+        for tx in &expired_txs {
+            self.remove_tx_references(tx);
+            n_removed_txs += self.remove_txs_after(tx).len();
+        }
+
+        if n_removed_txs > 0 {
+            tracing::debug!("Removed {n_removed_txs} expired transactions.");
+        }
+    }
+
+    /// Removes the transactions of the account that follow the given, removed, transaction.
+    fn remove_txs_after(&mut self, removed_tx: &TransactionReference) -> Vec<TransactionReference> {
+        let Ok(next_nonce) = try_increment_nonce(removed_tx.nonce) else {
+            return Vec::new();
+        };
+        let txs_to_remove: Vec<TransactionReference> =
+            self.tx_pool.account_txs_from_nonce(removed_tx.address, next_nonce).copied().collect();
+        for tx in &txs_to_remove {
+            self.remove_tx_references(tx);
+            self.tx_pool.remove(tx.tx_hash).expect("Transaction hash from pool must exist.");
+        }
+
+        txs_to_remove
+    }
+
+    fn rewind_addresses(&mut self, addresses: Vec<ContractAddress>) {
+        for address in addresses {
+            // Account nonce is the minimal nonce of this address: it was proposed but not included.
+            let tx_reference = self
+                .tx_pool
+                .account_txs_sorted_by_nonce(address)
+                .next()
+                .expect("Address {address} should appear in transaction pool.");
+            let tx_reference = *tx_reference;
+            self.tx_queue.remove(address);
+            self.tx_queue.insert(tx_reference);
+            // The rewound transactions are eligible again, as are the ones that follow them.
+            self.promote_suspended_txs(tx_reference);
+        }
+    }
+
+    // A transaction removed from the pool may be the next eligible transaction of its account,
+    // or a suspended one.
+    fn remove_tx_references(&mut self, tx: &TransactionReference) {
+        if self.tx_queue.get_nonce(tx.address) == Some(tx.nonce) {
+            self.tx_queue.remove(tx.address);
+        }
+        self.suspended_tx_pool.remove(tx);
+    }
+
+    fn has_ready_predecessor(&self, tx: &TransactionReference) -> bool {
+        let Ok(previous_nonce) = tx.nonce.try_decrement() else {
+            return false;
+        };
+
+        self.tx_pool.get_by_address_and_nonce(tx.address, previous_nonce).is_some()
+            && !self.suspended_tx_pool.contains(tx.address, previous_nonce)
+    }
+
+    /// Marks the given transaction as ready, along with the suspended transactions of its account
+    /// that directly follow it.
+    fn promote_suspended_txs(&mut self, tx: TransactionReference) {
+        self.suspended_tx_pool.remove(&tx);
+
+        let mut current_account_state = AccountState { address: tx.address, nonce: tx.nonce };
+        while let Ok(Some(next_tx)) = self.tx_pool.get_next_eligible_tx(current_account_state) {
+            if !self.suspended_tx_pool.remove(&next_tx) {
+                // The following transactions are already ready.
+                break;
+            }
+            current_account_state.nonce = next_tx.nonce;
+        }
+    }
+
+    #[allow(clippy::as_conversions)] // FIXME: use int metrics so `as f64` may be removed.
+    fn update_state_metrics(&self) {
+        let n_suspended_txs = self.suspended_tx_pool.len();
+        let n_ready_txs = self.tx_pool.n_txs() - n_suspended_txs;
+        metrics::gauge!(MEMPOOL_READY_TXS, n_ready_txs as f64);
+        metrics::gauge!(MEMPOOL_SUSPENDED_TXS, n_suspended_txs as f64);
+        self.health.n_ready_txs.store(n_ready_txs, Ordering::Relaxed);
+        self.health.n_suspended_txs.store(n_suspended_txs, Ordering::Relaxed);
+    }
+
     // TODO(Mohammad): Rename this method once consensus API is added.
     pub fn update_gas_price_threshold(&mut self, threshold: GasPrice) {
         self.tx_queue.update_gas_price_threshold(threshold);
@@ -338,6 +718,7 @@ impl Mempool {
         tracing::debug!("{existing_tx_reference} will be replaced by {incoming_tx_reference}.");
 
         self.tx_queue.remove(address);
+        self.suspended_tx_pool.remove(&existing_tx_reference);
         self.tx_pool
             .remove(existing_tx_reference.tx_hash)
             .expect("Transaction hash from pool must exist.");
@@ -384,6 +765,85 @@ fn max_l2_gas_price(tx: &AccountTransaction) -> GasPrice {
     tx.resource_bounds().get_l2_bounds().max_price_per_unit
 }
 
+// The time the mempool has to answer the health probe before it is considered unresponsive.
+const HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The size of the mempool, shared with the monitoring endpoint. The mempool is unhealthy when it
+/// is full, as it then rejects transactions that can't evict lower priority ones, or, if given a
+/// client to probe it with, when it doesn't answer requests in time.
+#[derive(Clone, Default)]
+pub struct MempoolHealth {
+    capacity_in_txs: usize,
+    n_ready_txs: Arc<AtomicUsize>,
+    n_suspended_txs: Arc<AtomicUsize>,
+    client: Option<SharedMempoolClient>,
+}
+
+impl MempoolHealth {
+    pub fn new(capacity_in_txs: usize) -> Self {
+        Self { capacity_in_txs, ..Default::default() }
+    }
+
+    /// Probes the responsiveness of the mempool by sending it a request through the given client.
+    pub fn with_client(self, client: SharedMempoolClient) -> Self {
+        Self { client: Some(client), ..self }
+    }
+
+    async fn probe(&self) -> Result<(), String> {
+        let Some(client) = &self.client else {
+            return Ok(());
+        };
+
+        match tokio::time::timeout(
+            HEALTH_PROBE_TIMEOUT,
+            client.account_nonce(ContractAddress::default()),
+        )
+        .await
+        {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(err)) => Err(format!("Mempool failed to answer a request: {err}")),
+            Err(_) => Err(format!(
+                "Mempool didn't answer a request within {} seconds.",
+                HEALTH_PROBE_TIMEOUT.as_secs()
+            )),
+        }
+    }
+}
+
+impl std::fmt::Debug for MempoolHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MempoolHealth")
+            .field("capacity_in_txs", &self.capacity_in_txs)
+            .field("n_ready_txs", &self.n_ready_txs)
+            .field("n_suspended_txs", &self.n_suspended_txs)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl ComponentHealth for MempoolHealth {
+    async fn check_health(&self) -> HealthStatus {
+        if let Err(reason) = self.probe().await {
+            return HealthStatus::Unhealthy { reason };
+        }
+
+        let n_ready_txs = self.n_ready_txs.load(Ordering::Relaxed);
+        let n_suspended_txs = self.n_suspended_txs.load(Ordering::Relaxed);
+        let n_txs = n_ready_txs + n_suspended_txs;
+        if n_txs >= self.capacity_in_txs {
+            return HealthStatus::Unhealthy {
+                reason: format!("Mempool is full: {n_txs}/{} transactions.", self.capacity_in_txs),
+            };
+        }
+        HealthStatus::Healthy {
+            details: format!(
+                "{n_ready_txs} ready and {n_suspended_txs} suspended transactions, out of {}.",
+                self.capacity_in_txs
+            ),
+        }
+    }
+}
+
 /// Provides a lightweight representation of a transaction for mempool usage (e.g., excluding
 /// execution fields).
 /// TODO(Mohammad): rename this struct to `ThinTransaction` once that name
@@ -1,13 +1,31 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use assert_matches::assert_matches;
 use pretty_assertions::assert_eq;
 use rstest::{fixture, rstest};
 use starknet_api::block::GasPrice;
 use starknet_api::executable_transaction::AccountTransaction;
-use starknet_api::{contract_address, nonce};
+use starknet_api::{contract_address, nonce, tx_hash};
+use starknet_mempool_types::communication::{MempoolClientError, MockMempoolClient};
 use starknet_mempool_types::errors::MempoolError;
-use starknet_mempool_types::mempool_types::AddTransactionArgs;
-
-use crate::mempool::{Mempool, MempoolConfig, TransactionReference};
-use crate::test_utils::{add_tx, add_tx_expect_error, commit_block, get_txs_and_assert_expected};
+use starknet_mempool_types::mempool_types::{
+    AccountTransactionsPage,
+    AddTransactionArgs,
+    GetAccountTransactionsArgs,
+    MempoolTransactionStatus,
+};
+use starknet_sequencer_infra::component_client::ClientError;
+use starknet_sequencer_infra::component_definitions::{ComponentHealth, HealthStatus};
+
+use crate::mempool::{Mempool, MempoolConfig, MempoolHealth, TransactionReference};
+use crate::test_utils::{
+    add_tx,
+    add_tx_expect_error,
+    commit_block,
+    get_txs_and_assert_expected,
+    FakeClock,
+};
 use crate::transaction_pool::TransactionPool;
 use crate::transaction_queue::transaction_queue_test_utils::{
     TransactionQueueContent,
@@ -42,6 +60,7 @@ impl MempoolContent {
 impl From<MempoolContent> for Mempool {
     fn from(mempool_content: MempoolContent) -> Mempool {
         let MempoolContent { tx_pool, tx_queue_content, config } = mempool_content;
+        let health = MempoolHealth::new(config.capacity_in_txs);
         Mempool {
             config,
             tx_pool: tx_pool.unwrap_or_default(),
@@ -49,7 +68,9 @@ impl From<MempoolContent> for Mempool {
                 .map(|content| content.complete_to_tx_queue())
                 .unwrap_or_default(),
             // TODO: Add implementation when needed.
+            suspended_tx_pool: Default::default(),
             state: Default::default(),
+            health,
         }
     }
 }
@@ -101,7 +122,8 @@ impl MempoolContentBuilder {
     }
 
     fn with_fee_escalation_percentage(mut self, fee_escalation_percentage: u8) -> Self {
-        self.config = MempoolConfig { enable_fee_escalation: true, fee_escalation_percentage };
+        self.config =
+            MempoolConfig { enable_fee_escalation: true, fee_escalation_percentage, ..self.config };
         self
     }
 
@@ -128,6 +150,14 @@ impl FromIterator<AccountTransaction> for TransactionPool {
     }
 }
 
+#[track_caller]
+fn assert_suspended_txs(mempool: &Mempool, expected_txs: &[&AccountTransaction]) {
+    assert_eq!(mempool.suspended_tx_pool.len(), expected_txs.len());
+    for tx in expected_txs {
+        assert!(mempool.suspended_tx_pool.contains(tx.contract_address(), tx.nonce()));
+    }
+}
+
 #[track_caller]
 fn add_tx_and_verify_replacement(
     mut mempool: Mempool,
@@ -682,6 +712,342 @@ fn test_fee_escalation_invalid_replacement_overflow_gracefully_handled() {
     add_txs_and_verify_no_replacement(mempool, existing_tx, [invalid_replacement_input]);
 }
 
+// Pool limits tests.
+
+#[rstest]
+fn test_add_tx_evicts_lowest_priority_tx_when_full() {
+    // Setup.
+    let config = MempoolConfig { capacity_in_txs: 2, ..Default::default() };
+    let mut mempool = Mempool::new(config, Arc::new(FakeClock::default()));
+    let input_tip_10 = add_tx_input!(tx_hash: 1, address: "0x0", tip: 10);
+    let input_tip_20 = add_tx_input!(tx_hash: 2, address: "0x1", tip: 20);
+    let input_tip_30 = add_tx_input!(tx_hash: 3, address: "0x2", tip: 30);
+    for input in [&input_tip_10, &input_tip_20, &input_tip_30] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Assert: the transaction with the lowest tip was evicted.
+    let expected_queue_txs = [&input_tip_30.tx, &input_tip_20.tx].map(TransactionReference::new);
+    let expected_mempool_content = MempoolContentBuilder::new()
+        .with_pool([input_tip_20.tx, input_tip_30.tx])
+        .with_priority_queue(expected_queue_txs)
+        .build();
+    expected_mempool_content.assert_eq(&mempool);
+}
+
+#[rstest]
+fn test_add_tx_fails_when_full_and_tip_too_low() {
+    // Setup.
+    let config = MempoolConfig { capacity_in_txs: 1, ..Default::default() };
+    let mut mempool = Mempool::new(config, Arc::new(FakeClock::default()));
+    let input_tip_20 = add_tx_input!(tx_hash: 1, address: "0x0", tip: 20);
+    add_tx(&mut mempool, &input_tip_20);
+
+    // Test and assert: an equal tip does not suffice for eviction.
+    for input in [
+        add_tx_input!(tx_hash: 2, address: "0x1", tip: 10),
+        add_tx_input!(tx_hash: 3, address: "0x1", tip: 20),
+    ] {
+        add_tx_expect_error(&mut mempool, &input, MempoolError::MempoolFull);
+    }
+    let expected_mempool_content =
+        MempoolContentBuilder::new().with_pool([input_tip_20.tx]).build();
+    expected_mempool_content.assert_eq(&mempool);
+}
+
+#[rstest]
+fn test_add_tx_eviction_does_not_create_nonce_gaps() {
+    // Setup: the low tip transaction of account 0x0 is followed by a high tip one.
+    let config = MempoolConfig { capacity_in_txs: 2, ..Default::default() };
+    let mut mempool = Mempool::new(config, Arc::new(FakeClock::default()));
+    let input_nonce_0 =
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0, tip: 10);
+    let input_nonce_1 =
+        add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0, tip: 50);
+    for input in [&input_nonce_0, &input_nonce_1] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test and assert: only the last transaction of the account may be evicted.
+    let input_tip_30 = add_tx_input!(tx_hash: 3, address: "0x1", tip: 30);
+    add_tx_expect_error(&mut mempool, &input_tip_30, MempoolError::MempoolFull);
+    let input_tip_60 = add_tx_input!(tx_hash: 4, address: "0x1", tip: 60);
+    add_tx(&mut mempool, &input_tip_60);
+
+    let expected_mempool_content =
+        MempoolContentBuilder::new().with_pool([input_nonce_0.tx, input_tip_60.tx]).build();
+    expected_mempool_content.assert_eq(&mempool);
+}
+
+#[rstest]
+fn test_add_tx_exceeds_account_limit() {
+    // Setup.
+    let config = MempoolConfig { max_txs_per_account: 2, ..Default::default() };
+    let mut mempool = Mempool::new(config, Arc::new(FakeClock::default()));
+    for input in [
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0),
+        add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 5, account_nonce: 0),
+    ] {
+        add_tx(&mut mempool, &input);
+    }
+
+    // Test and assert.
+    let input = add_tx_input!(tx_hash: 3, address: "0x0", tx_nonce: 6, account_nonce: 0);
+    add_tx_expect_error(
+        &mut mempool,
+        &input,
+        MempoolError::TooManyAccountTransactions { address: contract_address!("0x0") },
+    );
+    // Other accounts are not affected.
+    add_tx(&mut mempool, &add_tx_input!(tx_hash: 4, address: "0x1", tx_nonce: 0, account_nonce: 0));
+}
+
+#[rstest]
+fn test_expired_txs_are_removed() {
+    // Setup.
+    let clock = Arc::new(FakeClock::default());
+    let config = MempoolConfig { transaction_ttl: Duration::from_secs(60), ..Default::default() };
+    let mut mempool = Mempool::new(config, clock.clone());
+    let old_input = add_tx_input!(tx_hash: 1, address: "0x0", tip: 20);
+    add_tx(&mut mempool, &old_input);
+    clock.advance(Duration::from_secs(30));
+    let new_input = add_tx_input!(tx_hash: 2, address: "0x1", tip: 10);
+    add_tx(&mut mempool, &new_input);
+
+    // Test and assert: only the transaction that outlived its TTL is removed.
+    clock.advance(Duration::from_secs(31));
+    get_txs_and_assert_expected(&mut mempool, 2, &[new_input.tx]);
+}
+
+#[rstest]
+fn test_staged_txs_do_not_expire() {
+    // Setup.
+    let clock = Arc::new(FakeClock::default());
+    let config = MempoolConfig { transaction_ttl: Duration::from_secs(60), ..Default::default() };
+    let mut mempool = Mempool::new(config, clock.clone());
+    let input = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    add_tx(&mut mempool, &input);
+    get_txs_and_assert_expected(&mut mempool, 1, &[input.tx]);
+
+    // Test: the transaction expires while its block is in progress.
+    clock.advance(Duration::from_secs(61));
+    get_txs_and_assert_expected(&mut mempool, 1, &[]);
+
+    // Assert: the block did not include it, so it is rewound, and then expires.
+    commit_block(&mut mempool, [], []);
+    get_txs_and_assert_expected(&mut mempool, 1, &[]);
+    let expected_mempool_content =
+        MempoolContentBuilder::new().with_pool([]).with_priority_queue([]).build();
+    expected_mempool_content.assert_eq(&mempool);
+}
+
+// Suspended transactions tests.
+
+#[rstest]
+fn test_add_tx_suspends_txs_until_nonce_gap_is_filled(mut mempool: Mempool) {
+    // Setup.
+    let [input_nonce_0, input_nonce_1, input_nonce_2] = [0, 1, 2].map(
+        |nonce| add_tx_input!(tx_hash: nonce, address: "0x0", tx_nonce: nonce, account_nonce: 0),
+    );
+
+    // Test and assert: transactions are held back while the nonce gap exists.
+    add_tx(&mut mempool, &input_nonce_2);
+    add_tx(&mut mempool, &input_nonce_0);
+    assert_suspended_txs(&mempool, &[&input_nonce_2.tx]);
+
+    add_tx(&mut mempool, &input_nonce_1);
+    assert_suspended_txs(&mempool, &[]);
+    get_txs_and_assert_expected(
+        &mut mempool,
+        3,
+        &[input_nonce_0.tx, input_nonce_1.tx, input_nonce_2.tx],
+    );
+}
+
+#[rstest]
+fn test_commit_block_promotes_suspended_txs(mut mempool: Mempool) {
+    // Setup.
+    let [input_nonce_1, input_nonce_2] = [1, 2].map(
+        |nonce| add_tx_input!(tx_hash: nonce, address: "0x0", tx_nonce: nonce, account_nonce: 0),
+    );
+    for input in [&input_nonce_1, &input_nonce_2] {
+        add_tx(&mut mempool, input);
+    }
+    assert_suspended_txs(&mempool, &[&input_nonce_1.tx, &input_nonce_2.tx]);
+
+    // Test: the missing nonce was sequenced by another node.
+    commit_block(&mut mempool, [("0x0", 1)], []);
+
+    // Assert.
+    assert_suspended_txs(&mempool, &[]);
+    get_txs_and_assert_expected(&mut mempool, 2, &[input_nonce_1.tx, input_nonce_2.tx]);
+}
+
+#[rstest]
+fn test_expired_tx_removes_following_txs() {
+    // Setup.
+    let clock = Arc::new(FakeClock::default());
+    let config = MempoolConfig { transaction_ttl: Duration::from_secs(60), ..Default::default() };
+    let mut mempool = Mempool::new(config, clock.clone());
+    let input_nonce_0 = add_tx_input!(tx_hash: 0, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let input_nonce_1 = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    let input_other_account = add_tx_input!(tx_hash: 2, address: "0x1", tip: 10);
+    add_tx(&mut mempool, &input_nonce_0);
+    clock.advance(Duration::from_secs(30));
+    add_tx(&mut mempool, &input_nonce_1);
+    add_tx(&mut mempool, &input_other_account);
+
+    // Test.
+    clock.advance(Duration::from_secs(31));
+    get_txs_and_assert_expected(&mut mempool, 3, std::slice::from_ref(&input_other_account.tx));
+
+    // Assert: the transaction that followed the expired one is removed as well.
+    assert_suspended_txs(&mempool, &[]);
+    let expected_mempool_content = MempoolContentBuilder::new()
+        .with_pool([input_other_account.tx])
+        .with_priority_queue([])
+        .build();
+    expected_mempool_content.assert_eq(&mempool);
+}
+
+#[rstest]
+fn test_commit_block_rewinds_unincluded_txs(mut mempool: Mempool) {
+    // Setup.
+    let input_nonce_0 = add_tx_input!(tx_hash: 0, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let input_nonce_1 = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    let input_nonce_3 = add_tx_input!(tx_hash: 3, address: "0x0", tx_nonce: 3, account_nonce: 0);
+    for input in [&input_nonce_0, &input_nonce_1, &input_nonce_3] {
+        add_tx(&mut mempool, input);
+    }
+    get_txs_and_assert_expected(&mut mempool, 1, std::slice::from_ref(&input_nonce_0.tx));
+
+    // Test: the proposal was not accepted.
+    commit_block(&mut mempool, [], []);
+
+    // Assert: the proposed transactions are returned to the mempool.
+    assert_suspended_txs(&mempool, &[&input_nonce_3.tx]);
+    get_txs_and_assert_expected(&mut mempool, 3, &[input_nonce_0.tx, input_nonce_1.tx]);
+}
+
+#[rstest]
+fn test_rewind_requeues_staged_txs(mut mempool: Mempool) {
+    // Setup.
+    let input_address_0_nonce_0 =
+        add_tx_input!(tx_hash: 0, address: "0x0", tx_nonce: 0, account_nonce: 0, tip: 20);
+    let input_address_0_nonce_1 =
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 1, account_nonce: 0, tip: 20);
+    let input_address_1_nonce_0 =
+        add_tx_input!(tx_hash: 2, address: "0x1", tx_nonce: 0, account_nonce: 0, tip: 10);
+    for input in [&input_address_0_nonce_0, &input_address_0_nonce_1, &input_address_1_nonce_0] {
+        add_tx(&mut mempool, input);
+    }
+    for input in [&input_address_0_nonce_0, &input_address_0_nonce_1] {
+        get_txs_and_assert_expected(&mut mempool, 1, std::slice::from_ref(&input.tx));
+    }
+
+    // Test: the proposal was aborted.
+    assert_eq!(mempool.rewind(), Ok(()));
+
+    // Assert: the staged transactions are eligible again, in nonce order.
+    assert_eq!(
+        mempool.get_tx_status(input_address_0_nonce_0.tx.tx_hash()),
+        Ok(MempoolTransactionStatus::Queued)
+    );
+    assert_eq!(mempool.state.get(contract_address!("0x0")), Some(nonce!(0)));
+    get_txs_and_assert_expected(
+        &mut mempool,
+        3,
+        &[input_address_0_nonce_0.tx, input_address_1_nonce_0.tx, input_address_0_nonce_1.tx],
+    );
+}
+
+#[rstest]
+fn test_rewind_without_staged_txs(mut mempool: Mempool) {
+    // Setup.
+    let input = add_tx_input!(tx_hash: 0, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    add_tx(&mut mempool, &input);
+
+    // Test.
+    assert_eq!(mempool.rewind(), Ok(()));
+
+    // Assert.
+    get_txs_and_assert_expected(&mut mempool, 1, &[input.tx]);
+}
+
+// Query tests.
+
+#[rstest]
+fn test_get_tx_status(mut mempool: Mempool) {
+    // Setup.
+    let [input_nonce_0, input_nonce_1, input_nonce_2, input_nonce_4] = [0, 1, 2, 4].map(
+        |nonce| add_tx_input!(tx_hash: nonce, address: "0x0", tx_nonce: nonce, account_nonce: 0),
+    );
+    for input in [&input_nonce_0, &input_nonce_1, &input_nonce_2, &input_nonce_4] {
+        add_tx(&mut mempool, input);
+    }
+    get_txs_and_assert_expected(&mut mempool, 1, std::slice::from_ref(&input_nonce_0.tx));
+
+    // Test and assert.
+    for (input, expected_status) in [
+        (&input_nonce_0, MempoolTransactionStatus::Staged),
+        (&input_nonce_1, MempoolTransactionStatus::Queued),
+        (&input_nonce_2, MempoolTransactionStatus::Pending),
+        (&input_nonce_4, MempoolTransactionStatus::Suspended),
+    ] {
+        assert_eq!(mempool.get_tx_status(input.tx.tx_hash()), Ok(expected_status));
+    }
+    let unknown_tx_hash = tx_hash!(5);
+    assert_eq!(
+        mempool.get_tx_status(unknown_tx_hash),
+        Err(MempoolError::TransactionNotFound { tx_hash: unknown_tx_hash })
+    );
+}
+
+#[rstest]
+fn test_account_nonce(mut mempool: Mempool) {
+    // Setup.
+    let address = contract_address!("0x0");
+    assert_eq!(mempool.account_nonce(address), Ok(None));
+    for nonce in [1, 2, 4] {
+        add_tx(
+            &mut mempool,
+            &add_tx_input!(tx_hash: nonce, address: "0x0", tx_nonce: nonce, account_nonce: 1),
+        );
+    }
+
+    // Test and assert: the next nonce follows the consecutive transactions in the mempool.
+    assert_eq!(mempool.account_nonce(address), Ok(Some(nonce!(3))));
+}
+
+#[rstest]
+fn test_get_account_txs_paginates_by_nonce(mut mempool: Mempool) {
+    // Setup.
+    let inputs = [0, 1, 2, 3, 4].map(
+        |nonce| add_tx_input!(tx_hash: nonce, address: "0x0", tx_nonce: nonce, account_nonce: 0),
+    );
+    for input in &inputs {
+        add_tx(&mut mempool, input);
+    }
+    add_tx(&mut mempool, &add_tx_input!(tx_hash: 5, address: "0x1", tx_nonce: 0, account_nonce: 0));
+    let txs = inputs.map(|input| input.tx);
+
+    // Test and assert.
+    let address = contract_address!("0x0");
+    for (start_nonce, expected_txs, expected_next_start_nonce) in
+        [(0, &txs[0..2], Some(nonce!(2))), (2, &txs[2..4], Some(nonce!(4))), (4, &txs[4..], None)]
+    {
+        let args =
+            GetAccountTransactionsArgs { address, start_nonce: nonce!(start_nonce), max_txs: 2 };
+        assert_eq!(
+            mempool.get_account_txs(args),
+            Ok(AccountTransactionsPage {
+                txs: expected_txs.to_vec(),
+                next_start_nonce: expected_next_start_nonce
+            })
+        );
+    }
+}
+
 // `update_gas_price_threshold` tests.
 
 #[rstest]
@@ -735,3 +1101,57 @@ fn test_update_gas_price_threshold_decreases_threshold() {
         .build();
     expected_mempool_content.assert_eq(&mempool);
 }
+
+// Health tests.
+
+#[rstest]
+#[tokio::test]
+async fn test_health_reports_size(mut mempool: Mempool) {
+    // Setup.
+    let health = mempool.health();
+    for input in [
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0),
+        add_tx_input!(tx_hash: 2, address: "0x1", tx_nonce: 1, account_nonce: 0),
+    ] {
+        add_tx(&mut mempool, &input);
+    }
+
+    // Test and assert.
+    assert_eq!(
+        health.check_health().await,
+        HealthStatus::Healthy {
+            details: "1 ready and 1 suspended transactions, out of 100000.".to_owned()
+        }
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_health_reports_full_mempool() {
+    // Setup.
+    let mut mempool = Mempool::new(
+        MempoolConfig { capacity_in_txs: 1, ..Default::default() },
+        Arc::new(FakeClock::default()),
+    );
+    let health = mempool.health();
+    add_tx(&mut mempool, &add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0));
+
+    // Test and assert.
+    assert_matches!(health.check_health().await, HealthStatus::Unhealthy { .. });
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_health_reports_unresponsive_mempool(mempool: Mempool) {
+    // Setup.
+    let mut client = MockMempoolClient::new();
+    client.expect_account_nonce().returning(|_| {
+        Err(MempoolClientError::ClientError(ClientError::ComponentUnavailable(
+            "Mempool".to_owned(),
+        )))
+    });
+    let health = mempool.health().with_client(Arc::new(client));
+
+    // Test and assert.
+    assert_matches!(health.check_health().await, HealthStatus::Unhealthy { .. });
+}
@@ -0,0 +1,6 @@
+/// The number of transactions in the mempool that are not held back by a nonce gap.
+pub const MEMPOOL_READY_TXS: &str = "mempool_ready_txs";
+
+/// The number of transactions in the mempool that are held back by a nonce gap, waiting for the
+/// transactions with the missing nonces.
+pub const MEMPOOL_SUSPENDED_TXS: &str = "mempool_suspended_txs";
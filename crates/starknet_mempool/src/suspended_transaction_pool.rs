@@ -4,19 +4,21 @@ use starknet_api::core::{ContractAddress, Nonce};
 
 use crate::mempool::TransactionReference;
 
-type _AddressNonceToTransaction = HashMap<(ContractAddress, Nonce), TransactionReference>;
+type AddressNonceToTransaction = HashMap<(ContractAddress, Nonce), TransactionReference>;
 
-#[derive(Debug)]
-pub struct _SuspendedTransactionPool {
-    suspended_tx_pool: _AddressNonceToTransaction,
+/// Holds references to pool transactions that cannot be sequenced yet, since a transaction with a
+/// lower nonce of the same account is missing.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct SuspendedTransactionPool {
+    suspended_tx_pool: AddressNonceToTransaction,
 }
 
-impl _SuspendedTransactionPool {
-    pub fn _contains(&self, address: ContractAddress, nonce: Nonce) -> bool {
+impl SuspendedTransactionPool {
+    pub fn contains(&self, address: ContractAddress, nonce: Nonce) -> bool {
         self.suspended_tx_pool.contains_key(&(address, nonce))
     }
 
-    pub fn _insert(&mut self, tx: TransactionReference) {
+    pub fn insert(&mut self, tx: TransactionReference) {
         assert_eq!(
             self.suspended_tx_pool.insert((tx.address, tx.nonce), tx),
             None,
@@ -24,7 +26,11 @@ impl _SuspendedTransactionPool {
         );
     }
 
-    pub fn _remove(&mut self, tx: &TransactionReference) -> bool {
+    pub fn remove(&mut self, tx: &TransactionReference) -> bool {
         self.suspended_tx_pool.remove(&(tx.address, tx.nonce)).is_some()
     }
+
+    pub fn len(&self) -> usize {
+        self.suspended_tx_pool.len()
+    }
 }
@@ -1,4 +1,6 @@
 use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
 
 use pretty_assertions::assert_eq;
 use starknet_api::executable_transaction::AccountTransaction;
@@ -7,6 +9,7 @@ use starknet_mempool_types::errors::MempoolError;
 use starknet_mempool_types::mempool_types::{AddTransactionArgs, CommitBlockArgs};
 
 use crate::mempool::Mempool;
+use crate::utils::Clock;
 
 /// Creates an executable invoke transaction with the given field subset (the rest receive default
 /// values).
@@ -254,3 +257,27 @@ pub fn get_txs_and_assert_expected(
     let txs = mempool.get_txs(n_txs).unwrap();
     assert_eq!(txs, expected_txs);
 }
+
+/// A clock whose time only advances when requested, starting at the Unix epoch.
+#[derive(Debug)]
+pub struct FakeClock {
+    now: Mutex<SystemTime>,
+}
+
+impl Default for FakeClock {
+    fn default() -> Self {
+        FakeClock { now: Mutex::new(SystemTime::UNIX_EPOCH) }
+    }
+}
+
+impl FakeClock {
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock().unwrap()
+    }
+}
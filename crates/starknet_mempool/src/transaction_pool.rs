@@ -1,4 +1,6 @@
 use std::collections::{hash_map, BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
 use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::executable_transaction::AccountTransaction;
@@ -6,26 +8,56 @@ use starknet_api::transaction::TransactionHash;
 use starknet_mempool_types::errors::MempoolError;
 use starknet_mempool_types::mempool_types::{AccountState, MempoolResult};
 
-use crate::mempool::TransactionReference;
-use crate::utils::try_increment_nonce;
+use crate::mempool::{AddressToNonce, TransactionReference};
+use crate::utils::{try_increment_nonce, Clock, InstantClock};
 
 type HashToTransaction = HashMap<TransactionHash, AccountTransaction>;
 
 /// Contains all transactions currently held in the mempool.
-/// Invariant: both data structures are consistent regarding the existence of transactions:
-/// A transaction appears in one if and only if it appears in the other.
+/// Invariant: all data structures are consistent regarding the existence of transactions:
+/// A transaction appears in one if and only if it appears in the others.
 /// No duplicate transactions appear in the pool.
-#[derive(Debug, Default, Eq, PartialEq)]
+#[derive(Debug)]
 pub struct TransactionPool {
     // Holds the complete transaction objects; it should be the sole entity that does so.
     tx_pool: HashToTransaction,
     // Transactions organized by account address, sorted by ascending nonce values.
     txs_by_account: AccountTransactionIndex,
+    // Transactions sorted by their time of insertion to the pool.
+    txs_by_submission_time: TimedTransactionMap,
     // Tracks the capacity of the pool.
     capacity: PoolCapacity,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for TransactionPool {
+    fn default() -> Self {
+        Self::new(Arc::new(InstantClock))
+    }
 }
 
+// Submission times are not compared, as they depend on the clock.
+impl PartialEq for TransactionPool {
+    fn eq(&self, other: &Self) -> bool {
+        self.tx_pool == other.tx_pool
+            && self.txs_by_account == other.txs_by_account
+            && self.capacity == other.capacity
+    }
+}
+
+impl Eq for TransactionPool {}
+
 impl TransactionPool {
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            tx_pool: HashToTransaction::default(),
+            txs_by_account: AccountTransactionIndex::default(),
+            txs_by_submission_time: TimedTransactionMap::default(),
+            capacity: PoolCapacity::default(),
+            clock,
+        }
+    }
+
     pub fn insert(&mut self, tx: AccountTransaction) -> MempoolResult<()> {
         let tx_reference = TransactionReference::new(&tx);
         let tx_hash = tx_reference.tx_hash;
@@ -47,6 +79,9 @@ impl TransactionPool {
             )
         };
 
+        // Insert to timed mapping.
+        self.txs_by_submission_time.insert(tx_reference, self.clock.now());
+
         self.capacity.add();
 
         Ok(())
@@ -65,24 +100,46 @@ impl TransactionPool {
             )
         });
 
+        // Remove from timed mapping.
+        self.txs_by_submission_time.remove(tx_hash).unwrap_or_else(|| {
+            panic!(
+                "Transaction pool consistency error: transaction with hash {tx_hash} appears in
+                main mapping, but does not appear in the timed mapping"
+            )
+        });
+
         self.capacity.remove();
 
         Ok(tx)
     }
 
-    pub fn remove_up_to_nonce(&mut self, address: ContractAddress, nonce: Nonce) {
+    /// Removes all transactions of the given account with a nonce lower than the given one, and
+    /// returns them.
+    pub fn remove_up_to_nonce(
+        &mut self,
+        address: ContractAddress,
+        nonce: Nonce,
+    ) -> Vec<TransactionReference> {
         let removed_txs = self.txs_by_account.remove_up_to_nonce(address, nonce);
 
-        for TransactionReference { tx_hash, .. } in removed_txs {
+        for &TransactionReference { tx_hash, .. } in &removed_txs {
             self.tx_pool.remove(&tx_hash).unwrap_or_else(|| {
                 panic!(
                     "Transaction pool consistency error: transaction with hash {tx_hash} appears
                     in account mapping, but does not appear in the main mapping"
                 );
             });
+            self.txs_by_submission_time.remove(tx_hash).unwrap_or_else(|| {
+                panic!(
+                    "Transaction pool consistency error: transaction with hash {tx_hash} appears
+                    in account mapping, but does not appear in the timed mapping"
+                );
+            });
 
             self.capacity.remove();
         }
+
+        removed_txs
     }
 
     pub fn account_txs_sorted_by_nonce(
@@ -92,6 +149,16 @@ impl TransactionPool {
         self.txs_by_account.account_txs_sorted_by_nonce(address)
     }
 
+    /// Returns the transactions of the given account with a nonce of at least the given one,
+    /// sorted by nonce.
+    pub fn account_txs_from_nonce(
+        &self,
+        address: ContractAddress,
+        nonce: Nonce,
+    ) -> impl Iterator<Item = &TransactionReference> {
+        self.txs_by_account.account_txs_from_nonce(address, nonce)
+    }
+
     pub fn get_by_tx_hash(&self, tx_hash: TransactionHash) -> MempoolResult<&AccountTransaction> {
         self.tx_pool.get(&tx_hash).ok_or(MempoolError::TransactionNotFound { tx_hash })
     }
@@ -116,6 +183,104 @@ impl TransactionPool {
     pub fn _contains_account(&self, address: ContractAddress) -> bool {
         self.txs_by_account._contains(address)
     }
+
+    pub fn n_txs(&self) -> usize {
+        self.capacity.n_txs
+    }
+
+    pub fn n_account_txs(&self, address: ContractAddress) -> usize {
+        self.txs_by_account.n_account_txs(address)
+    }
+
+    /// Returns the accounts that have transactions in the pool.
+    pub fn accounts(&self) -> impl Iterator<Item = ContractAddress> + '_ {
+        self.txs_by_account.0.keys().copied()
+    }
+
+    /// Returns the transactions in the pool, along with their submission time, in the order they
+    /// were inserted.
+    pub fn iter_by_submission_time(
+        &self,
+    ) -> impl Iterator<Item = (&AccountTransaction, SystemTime)> {
+        self.txs_by_submission_time.txs_by_submission_time.iter().map(
+            |(&(submission_time, _), tx_reference)| {
+                let tx = self
+                    .tx_pool
+                    .get(&tx_reference.tx_hash)
+                    .expect("Transaction hash from submission time mapping must appear in pool.");
+                (tx, submission_time)
+            },
+        )
+    }
+
+    pub fn submission_time(&self, tx_hash: TransactionHash) -> MempoolResult<SystemTime> {
+        self.txs_by_submission_time
+            .hash_to_submission_time
+            .get(&tx_hash)
+            .copied()
+            .ok_or(MempoolError::TransactionNotFound { tx_hash })
+    }
+
+    /// Overrides the submission time of a transaction in the pool, e.g., when restoring it after a
+    /// restart, so that it expires as if it was never removed.
+    pub fn set_submission_time(
+        &mut self,
+        tx_hash: TransactionHash,
+        submission_time: SystemTime,
+    ) -> MempoolResult<()> {
+        let tx_reference = self
+            .txs_by_submission_time
+            .remove(tx_hash)
+            .ok_or(MempoolError::TransactionNotFound { tx_hash })?;
+        self.txs_by_submission_time.insert(tx_reference, submission_time);
+
+        Ok(())
+    }
+
+    /// Removes and returns all transactions that were inserted more than `duration` ago, except
+    /// those with a nonce lower than the one given for their account in `exclude_txs`.
+    pub fn remove_txs_older_than(
+        &mut self,
+        duration: Duration,
+        exclude_txs: &AddressToNonce,
+    ) -> Vec<TransactionReference> {
+        let Some(cutoff_time) = self.clock.now().checked_sub(duration) else {
+            return Vec::default();
+        };
+
+        let expired_txs: Vec<TransactionReference> = self
+            .txs_by_submission_time
+            .txs_older_than(cutoff_time)
+            .filter(|tx| !exclude_txs.get(&tx.address).is_some_and(|&nonce| tx.nonce < nonce))
+            .copied()
+            .collect();
+
+        for tx in &expired_txs {
+            self.remove(tx.tx_hash).expect("Transaction hash from timed mapping must exist.");
+        }
+
+        expired_txs
+    }
+
+    /// Returns the transaction to evict in order to make room for a new one: the lowest priority
+    /// transaction among the last transactions of each account, so that no nonce gaps are
+    /// created.
+    /// Accounts whose transactions all have a nonce lower than the one given in `exclude_txs`
+    /// are skipped, as are the transactions of `exclude_address`.
+    // TODO(Ayelet): Consider indexing the last transaction of each account, if this becomes a
+    // bottleneck.
+    pub fn eviction_candidate(
+        &self,
+        exclude_address: ContractAddress,
+        exclude_txs: &AddressToNonce,
+    ) -> Option<TransactionReference> {
+        self.txs_by_account
+            .last_txs()
+            .filter(|tx| tx.address != exclude_address)
+            .filter(|tx| !exclude_txs.get(&tx.address).is_some_and(|&nonce| tx.nonce < nonce))
+            .min_by_key(|tx| (tx.tip, tx.max_l2_gas_price, tx.tx_hash))
+            .copied()
+    }
 }
 
 #[derive(Debug, Default, Eq, PartialEq)]
@@ -151,6 +316,16 @@ impl AccountTransactionIndex {
         self.0.get(&address).into_iter().flat_map(|nonce_to_tx_ref| nonce_to_tx_ref.values())
     }
 
+    fn account_txs_from_nonce(
+        &self,
+        address: ContractAddress,
+        nonce: Nonce,
+    ) -> impl Iterator<Item = &TransactionReference> {
+        self.0.get(&address).into_iter().flat_map(move |nonce_to_tx_ref| {
+            nonce_to_tx_ref.range(nonce..).map(|(_, tx_reference)| tx_reference)
+        })
+    }
+
     fn remove_up_to_nonce(
         &mut self,
         address: ContractAddress,
@@ -175,6 +350,43 @@ impl AccountTransactionIndex {
     fn _contains(&self, address: ContractAddress) -> bool {
         self.0.contains_key(&address)
     }
+
+    fn n_account_txs(&self, address: ContractAddress) -> usize {
+        self.0.get(&address).map_or(0, BTreeMap::len)
+    }
+
+    /// Returns the transaction with the highest nonce of each account.
+    fn last_txs(&self) -> impl Iterator<Item = &TransactionReference> {
+        self.0.values().filter_map(|nonce_to_tx_ref| nonce_to_tx_ref.values().next_back())
+    }
+}
+
+#[derive(Debug, Default, Eq, PartialEq)]
+struct TimedTransactionMap {
+    txs_by_submission_time: BTreeMap<(SystemTime, TransactionHash), TransactionReference>,
+    hash_to_submission_time: HashMap<TransactionHash, SystemTime>,
+}
+
+impl TimedTransactionMap {
+    fn insert(&mut self, tx: TransactionReference, submission_time: SystemTime) {
+        self.hash_to_submission_time.insert(tx.tx_hash, submission_time);
+        self.txs_by_submission_time.insert((submission_time, tx.tx_hash), tx);
+    }
+
+    fn remove(&mut self, tx_hash: TransactionHash) -> Option<TransactionReference> {
+        let submission_time = self.hash_to_submission_time.remove(&tx_hash)?;
+        self.txs_by_submission_time.remove(&(submission_time, tx_hash))
+    }
+
+    /// Returns the transactions submitted strictly before `cutoff_time`, oldest first.
+    fn txs_older_than(
+        &self,
+        cutoff_time: SystemTime,
+    ) -> impl Iterator<Item = &TransactionReference> {
+        self.txs_by_submission_time
+            .range(..(cutoff_time, TransactionHash::default()))
+            .map(|(_, tx)| tx)
+    }
 }
 
 #[derive(Debug, Default, Eq, PartialEq)]
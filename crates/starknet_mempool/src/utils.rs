@@ -1,3 +1,6 @@
+use std::fmt::Debug;
+use std::time::SystemTime;
+
 use starknet_api::core::Nonce;
 use starknet_mempool_types::communication::MempoolResult;
 use starknet_mempool_types::errors::MempoolError;
@@ -5,3 +8,17 @@ use starknet_mempool_types::errors::MempoolError;
 pub fn try_increment_nonce(nonce: Nonce) -> MempoolResult<Nonce> {
     nonce.try_increment().map_err(|_| MempoolError::NonceTooLarge(nonce))
 }
+
+/// Provides the current time; abstracted to allow controlling time in tests.
+pub trait Clock: Debug + Send + Sync {
+    fn now(&self) -> SystemTime;
+}
+
+#[derive(Debug, Default)]
+pub struct InstantClock;
+
+impl Clock for InstantClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
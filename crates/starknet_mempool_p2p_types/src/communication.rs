@@ -12,6 +12,7 @@ use starknet_sequencer_infra::component_client::{
 };
 use starknet_sequencer_infra::component_definitions::{
     ComponentClient,
+    ComponentRequest,
     ComponentRequestAndResponseSender,
 };
 use thiserror::Error;
@@ -19,6 +20,10 @@ use thiserror::Error;
 use crate::errors::MempoolP2pPropagatorError;
 use crate::mempool_p2p_types::MempoolP2pPropagatorResult;
 
+#[cfg(test)]
+#[path = "communication_test.rs"]
+mod communication_test;
+
 #[async_trait]
 pub trait MempoolP2pPropagatorClient: Send + Sync {
     /// Adds a transaction to be propagated to other peers. This should only be called on a new
@@ -51,6 +56,12 @@ pub enum MempoolP2pPropagatorRequest {
     ContinuePropagation(BroadcastedMessageMetadata),
 }
 
+impl ComponentRequest for MempoolP2pPropagatorRequest {
+    fn is_idempotent(&self) -> bool {
+        false
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum MempoolP2pPropagatorResponse {
     AddTransaction(MempoolP2pPropagatorResult<()>),
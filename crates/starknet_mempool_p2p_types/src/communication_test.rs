@@ -0,0 +1,37 @@
+use papyrus_network_types::network_types::BroadcastedMessageMetadata;
+use papyrus_test_utils::{get_rng, GetTestInstance};
+use rstest::rstest;
+use starknet_api::test_utils::invoke::{rpc_invoke_tx, InvokeTxArgs};
+use starknet_sequencer_infra::codec::Codec;
+use starknet_sequencer_infra::test_utils::{
+    assert_codec_round_trip,
+    assert_round_trip_with_codecs,
+};
+
+use crate::communication::{MempoolP2pPropagatorRequest, MempoolP2pPropagatorResponse};
+use crate::errors::MempoolP2pPropagatorError;
+
+#[test]
+fn test_add_transaction_request_round_trip() {
+    // `RpcTransaction` is internally tagged, which only JSON decodes.
+    let request =
+        MempoolP2pPropagatorRequest::AddTransaction(rpc_invoke_tx(InvokeTxArgs::default()));
+    assert_round_trip_with_codecs(&request, &[Codec::Json]);
+}
+
+#[test]
+fn test_continue_propagation_request_round_trip() {
+    let request = MempoolP2pPropagatorRequest::ContinuePropagation(
+        BroadcastedMessageMetadata::get_test_instance(&mut get_rng()),
+    );
+    assert_codec_round_trip(&request);
+}
+
+#[rstest]
+#[case::add_transaction(MempoolP2pPropagatorResponse::AddTransaction(Ok(())))]
+#[case::continue_propagation(MempoolP2pPropagatorResponse::ContinuePropagation(Err(
+    MempoolP2pPropagatorError::NetworkSendError
+)))]
+fn test_response_round_trip(#[case] response: MempoolP2pPropagatorResponse) {
+    assert_codec_round_trip(&response);
+}
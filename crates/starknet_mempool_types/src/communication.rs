@@ -6,7 +6,9 @@ use mockall::automock;
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
 use papyrus_proc_macros::handle_response_variants;
 use serde::{Deserialize, Serialize};
+use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::executable_transaction::AccountTransaction;
+use starknet_api::transaction::TransactionHash;
 use starknet_sequencer_infra::component_client::{
     ClientError,
     LocalComponentClient,
@@ -14,12 +16,23 @@ use starknet_sequencer_infra::component_client::{
 };
 use starknet_sequencer_infra::component_definitions::{
     ComponentClient,
+    ComponentRequest,
     ComponentRequestAndResponseSender,
 };
 use thiserror::Error;
 
 use crate::errors::MempoolError;
-use crate::mempool_types::{AddTransactionArgs, CommitBlockArgs};
+use crate::mempool_types::{
+    AccountTransactionsPage,
+    AddTransactionArgs,
+    CommitBlockArgs,
+    GetAccountTransactionsArgs,
+    MempoolTransactionStatus,
+};
+
+#[cfg(test)]
+#[path = "communication_test.rs"]
+mod communication_test;
 
 pub type LocalMempoolClient = LocalComponentClient<MempoolRequest, MempoolResponse>;
 pub type RemoteMempoolClient = RemoteComponentClient<MempoolRequest, MempoolResponse>;
@@ -45,20 +58,56 @@ pub trait MempoolClient: Send + Sync {
     async fn add_tx(&self, args: AddTransactionArgsWrapper) -> MempoolClientResult<()>;
     async fn commit_block(&self, args: CommitBlockArgs) -> MempoolClientResult<()>;
     async fn get_txs(&self, n_txs: usize) -> MempoolClientResult<Vec<AccountTransaction>>;
+    /// Returns the transactions taken for a block that will not be committed, e.g., an aborted
+    /// proposal, to the mempool, so they can be sequenced again.
+    async fn rewind(&self) -> MempoolClientResult<()>;
+    /// Returns the next nonce of the account, accounting for its transactions in the mempool, or
+    /// `None` if the mempool holds no information on the account.
+    async fn account_nonce(&self, address: ContractAddress) -> MempoolClientResult<Option<Nonce>>;
+    async fn get_account_txs(
+        &self,
+        args: GetAccountTransactionsArgs,
+    ) -> MempoolClientResult<AccountTransactionsPage>;
+    async fn get_tx_status(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> MempoolClientResult<MempoolTransactionStatus>;
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum MempoolRequest {
     AddTransaction(AddTransactionArgsWrapper),
     CommitBlock(CommitBlockArgs),
+    GetAccountNonce(ContractAddress),
+    GetAccountTransactions(GetAccountTransactionsArgs),
+    GetTransactionStatus(TransactionHash),
     GetTransactions(usize),
+    Rewind,
+}
+
+impl ComponentRequest for MempoolRequest {
+    fn is_idempotent(&self) -> bool {
+        match self {
+            MempoolRequest::GetAccountNonce(_)
+            | MempoolRequest::GetAccountTransactions(_)
+            | MempoolRequest::GetTransactionStatus(_) => true,
+            MempoolRequest::AddTransaction(_)
+            | MempoolRequest::CommitBlock(_)
+            | MempoolRequest::GetTransactions(_)
+            | MempoolRequest::Rewind => false,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum MempoolResponse {
     AddTransaction(MempoolResult<()>),
     CommitBlock(MempoolResult<()>),
+    GetAccountNonce(MempoolResult<Option<Nonce>>),
+    GetAccountTransactions(MempoolResult<AccountTransactionsPage>),
+    GetTransactionStatus(MempoolResult<MempoolTransactionStatus>),
     GetTransactions(MempoolResult<Vec<AccountTransaction>>),
+    Rewind(MempoolResult<()>),
 }
 
 #[derive(Clone, Debug, Error)]
@@ -96,4 +145,49 @@ where
             MempoolError
         )
     }
+
+    async fn rewind(&self) -> MempoolClientResult<()> {
+        let request = MempoolRequest::Rewind;
+        let response = self.send(request).await;
+        handle_response_variants!(MempoolResponse, Rewind, MempoolClientError, MempoolError)
+    }
+
+    async fn account_nonce(&self, address: ContractAddress) -> MempoolClientResult<Option<Nonce>> {
+        let request = MempoolRequest::GetAccountNonce(address);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            MempoolResponse,
+            GetAccountNonce,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn get_account_txs(
+        &self,
+        args: GetAccountTransactionsArgs,
+    ) -> MempoolClientResult<AccountTransactionsPage> {
+        let request = MempoolRequest::GetAccountTransactions(args);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            MempoolResponse,
+            GetAccountTransactions,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn get_tx_status(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> MempoolClientResult<MempoolTransactionStatus> {
+        let request = MempoolRequest::GetTransactionStatus(tx_hash);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            MempoolResponse,
+            GetTransactionStatus,
+            MempoolClientError,
+            MempoolError
+        )
+    }
 }
@@ -0,0 +1,69 @@
+use std::collections::{HashMap, HashSet};
+
+use papyrus_network_types::network_types::BroadcastedMessageMetadata;
+use papyrus_test_utils::{get_rng, GetTestInstance};
+use rstest::rstest;
+use starknet_api::executable_transaction::AccountTransaction;
+use starknet_api::test_utils::invoke::{executable_invoke_tx, InvokeTxArgs};
+use starknet_api::{contract_address, nonce, tx_hash};
+use starknet_sequencer_infra::codec::Codec;
+use starknet_sequencer_infra::test_utils::assert_round_trip_with_codecs;
+
+use crate::communication::{AddTransactionArgsWrapper, MempoolRequest, MempoolResponse};
+use crate::errors::MempoolError;
+use crate::mempool_types::{
+    AccountState,
+    AccountTransactionsPage,
+    AddTransactionArgs,
+    CommitBlockArgs,
+    GetAccountTransactionsArgs,
+    MempoolTransactionStatus,
+};
+
+fn tx() -> AccountTransaction {
+    AccountTransaction::Invoke(executable_invoke_tx(InvokeTxArgs::default()))
+}
+
+#[rstest]
+#[case::add_transaction(MempoolRequest::AddTransaction(AddTransactionArgsWrapper {
+    args: AddTransactionArgs { tx: tx(), account_state: AccountState::default() },
+    p2p_message_metadata: Some(BroadcastedMessageMetadata::get_test_instance(&mut get_rng())),
+}))]
+#[case::commit_block(MempoolRequest::CommitBlock(CommitBlockArgs {
+    address_to_nonce: HashMap::from([(contract_address!("0x1"), nonce!(2))]),
+    tx_hashes: HashSet::from([tx_hash!(3)]),
+}))]
+#[case::get_account_nonce(MempoolRequest::GetAccountNonce(contract_address!("0x1")))]
+#[case::get_account_transactions(MempoolRequest::GetAccountTransactions(
+    GetAccountTransactionsArgs {
+        address: contract_address!("0x1"),
+        start_nonce: nonce!(2),
+        max_txs: 3,
+    }
+))]
+#[case::get_transaction_status(MempoolRequest::GetTransactionStatus(tx_hash!(1)))]
+#[case::get_transactions(MempoolRequest::GetTransactions(1))]
+#[case::rewind(MempoolRequest::Rewind)]
+fn test_request_round_trip(#[case] request: MempoolRequest) {
+    // Bincode doesn't decode the data availability modes of transactions.
+    assert_round_trip_with_codecs(&request, &Codec::SELF_DESCRIBING);
+}
+
+#[rstest]
+#[case::add_transaction(MempoolResponse::AddTransaction(Ok(())))]
+#[case::commit_block(MempoolResponse::CommitBlock(Err(MempoolError::DuplicateNonce {
+    address: contract_address!("0x1"),
+    nonce: nonce!(2),
+})))]
+#[case::get_account_nonce(MempoolResponse::GetAccountNonce(Ok(Some(nonce!(1)))))]
+#[case::get_account_transactions(MempoolResponse::GetAccountTransactions(Ok(
+    AccountTransactionsPage { txs: vec![tx()], next_start_nonce: Some(nonce!(1)) }
+)))]
+#[case::get_transaction_status(MempoolResponse::GetTransactionStatus(Ok(
+    MempoolTransactionStatus::Queued
+)))]
+#[case::get_transactions(MempoolResponse::GetTransactions(Ok(vec![tx()])))]
+#[case::rewind(MempoolResponse::Rewind(Err(MempoolError::MempoolFull)))]
+fn test_response_round_trip(#[case] response: MempoolResponse) {
+    assert_round_trip_with_codecs(&response, &Codec::SELF_DESCRIBING);
+}
@@ -9,12 +9,16 @@ pub enum MempoolError {
     DuplicateNonce { address: ContractAddress, nonce: Nonce },
     #[error("Duplicate transaction, with hash: {tx_hash}")]
     DuplicateTransaction { tx_hash: TransactionHash },
+    #[error("Mempool is full, and the transaction's fee is too low to evict other transactions.")]
+    MempoolFull,
     #[error("{0}")]
     NonceTooLarge(Nonce),
     #[error("Nonce: {nonce} for account address {address} has already been processed.")]
     NonceTooOld { address: ContractAddress, nonce: Nonce },
     #[error("Transaction with hash: {tx_hash} could not be sent using p2p client.")]
     P2pPropagatorClientError { tx_hash: TransactionHash },
+    #[error("Account address {address} reached the limit of transactions in the mempool.")]
+    TooManyAccountTransactions { address: ContractAddress },
     #[error("Transaction with hash: {tx_hash} not found")]
     TransactionNotFound { tx_hash: TransactionHash },
 }
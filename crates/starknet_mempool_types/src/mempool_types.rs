@@ -33,4 +33,33 @@ pub struct CommitBlockArgs {
     pub tx_hashes: HashSet<TransactionHash>,
 }
 
+/// The state of a transaction held in the mempool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MempoolTransactionStatus {
+    /// Returned for sequencing in the block in progress, but not committed yet.
+    Staged,
+    /// The next transaction of its account to be sequenced.
+    Queued,
+    /// Waiting for transactions of its account with lower nonces to be sequenced.
+    Pending,
+    /// Held back until the transactions with the missing nonces of its account arrive.
+    Suspended,
+}
+
+/// Requests a page of an account's transactions in the mempool, ordered by nonce.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GetAccountTransactionsArgs {
+    pub address: ContractAddress,
+    /// The lowest nonce to return.
+    pub start_nonce: Nonce,
+    pub max_txs: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AccountTransactionsPage {
+    pub txs: Vec<AccountTransaction>,
+    /// The start nonce of the next page, if there are more transactions.
+    pub next_start_nonce: Option<Nonce>,
+}
+
 pub type MempoolResult<T> = Result<T, MempoolError>;
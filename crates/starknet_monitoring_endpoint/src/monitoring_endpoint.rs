@@ -1,11 +1,19 @@
 use std::any::type_name;
+use std::collections::BTreeMap;
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
 use axum::routing::get;
-use axum::{async_trait, Router, Server};
+use axum::{async_trait, Json, Router, Server};
 use hyper::Error;
-use starknet_sequencer_infra::component_definitions::ComponentStarter;
+use metrics_exporter_prometheus::{BuildError, PrometheusBuilder, PrometheusHandle};
+use starknet_sequencer_infra::component_definitions::{
+    ComponentStarter,
+    HealthStatus,
+    SharedComponentHealth,
+};
 use starknet_sequencer_infra::errors::ComponentError;
 use tracing::{info, instrument};
 
@@ -19,15 +27,37 @@ pub(crate) const MONITORING_PREFIX: &str = "monitoring";
 pub(crate) const ALIVE: &str = "alive";
 pub(crate) const READY: &str = "ready";
 pub(crate) const VERSION: &str = "nodeVersion";
+pub(crate) const METRICS: &str = "metrics";
+
+/// The health checks of the components that run in the node, by component name.
+pub type ComponentHealthChecks = BTreeMap<&'static str, SharedComponentHealth>;
 
 pub struct MonitoringEndpoint {
     config: MonitoringEndpointConfig,
     version: &'static str,
+    prometheus_handle: Option<PrometheusHandle>,
+    health_checks: Arc<ComponentHealthChecks>,
 }
 
 impl MonitoringEndpoint {
-    pub fn new(config: MonitoringEndpointConfig, version: &'static str) -> Self {
-        MonitoringEndpoint { config, version }
+    /// Installs the global metrics recorder if metrics are collected, so it must be called at most
+    /// once in that case.
+    pub fn new(
+        config: MonitoringEndpointConfig,
+        version: &'static str,
+        health_checks: ComponentHealthChecks,
+    ) -> Result<Self, BuildError> {
+        let prometheus_handle = if config.collect_metrics {
+            Some(PrometheusBuilder::new().install_recorder()?)
+        } else {
+            None
+        };
+        Ok(MonitoringEndpoint {
+            config,
+            version,
+            prometheus_handle,
+            health_checks: Arc::new(health_checks),
+        })
     }
 
     #[instrument(
@@ -38,7 +68,7 @@ impl MonitoringEndpoint {
         ),
         level = "debug")]
     pub async fn run(&self) -> Result<(), Error> {
-        let MonitoringEndpointConfig { ip, port } = self.config;
+        let MonitoringEndpointConfig { ip, port, .. } = self.config;
         let endpoint_addr = SocketAddr::new(ip, port);
 
         let app = self.app();
@@ -49,6 +79,8 @@ impl MonitoringEndpoint {
 
     fn app(&self) -> Router {
         let version = self.version.to_string();
+        let health_checks = self.health_checks.clone();
+        let prometheus_handle = self.prometheus_handle.clone();
 
         Router::new()
             .route(
@@ -57,20 +89,54 @@ impl MonitoringEndpoint {
             )
             .route(
                 format!("/{MONITORING_PREFIX}/{READY}").as_str(),
-                get(move || async { StatusCode::OK.to_string() }),
+                get(move || ready(health_checks)),
             )
             .route(
                 format!("/{MONITORING_PREFIX}/{VERSION}").as_str(),
                 get(move || async { version }),
             )
+            .route(
+                format!("/{MONITORING_PREFIX}/{METRICS}").as_str(),
+                get(move || metrics(prometheus_handle)),
+            )
+    }
+}
+
+/// Returns the health of each component. The status code is 503: service unavailable, if any of
+/// them is unhealthy.
+#[instrument(level = "debug", skip(health_checks))]
+async fn ready(
+    health_checks: Arc<ComponentHealthChecks>,
+) -> (StatusCode, Json<BTreeMap<&'static str, HealthStatus>>) {
+    let mut statuses = BTreeMap::new();
+    for (&component, health) in health_checks.iter() {
+        statuses.insert(component, health.check_health().await);
+    }
+
+    let status_code = if statuses.values().all(HealthStatus::is_healthy) {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status_code, Json(statuses))
+}
+
+/// Returns the metrics in the Prometheus format. In case the node doesn't collect metrics, returns
+/// an empty response with status code 405: method not allowed.
+#[instrument(level = "debug", skip(prometheus_handle))]
+async fn metrics(prometheus_handle: Option<PrometheusHandle>) -> Response {
+    match prometheus_handle {
+        Some(handle) => handle.render().into_response(),
+        None => StatusCode::METHOD_NOT_ALLOWED.into_response(),
     }
 }
 
 pub fn create_monitoring_endpoint(
     config: MonitoringEndpointConfig,
     version: &'static str,
-) -> MonitoringEndpoint {
-    MonitoringEndpoint::new(config, version)
+    health_checks: ComponentHealthChecks,
+) -> Result<MonitoringEndpoint, BuildError> {
+    MonitoringEndpoint::new(config, version, health_checks)
 }
 
 #[async_trait]
@@ -1,11 +1,15 @@
 use std::net::IpAddr;
+use std::sync::Arc;
 
 use axum::http::StatusCode;
 use axum::response::Response;
-use axum::Router;
+use axum::{async_trait, Router};
 use hyper::body::to_bytes;
 use hyper::Client;
 use pretty_assertions::assert_eq;
+use rstest::rstest;
+use serde_json::{json, Value};
+use starknet_sequencer_infra::component_definitions::{ComponentHealth, HealthStatus};
 use tokio::spawn;
 use tokio::task::yield_now;
 use tower::ServiceExt;
@@ -13,8 +17,10 @@ use tower::ServiceExt;
 use super::MonitoringEndpointConfig;
 use crate::monitoring_endpoint::{
     create_monitoring_endpoint,
+    ComponentHealthChecks,
     MonitoringEndpoint,
     ALIVE,
+    METRICS,
     READY,
     VERSION,
 };
@@ -22,8 +28,24 @@ use crate::test_utils::build_request;
 
 const TEST_VERSION: &str = "1.2.3-dev";
 
+struct FixedHealth(HealthStatus);
+
+#[async_trait]
+impl ComponentHealth for FixedHealth {
+    async fn check_health(&self) -> HealthStatus {
+        self.0.clone()
+    }
+}
+
 fn setup_monitoring_endpoint() -> MonitoringEndpoint {
-    create_monitoring_endpoint(MonitoringEndpointConfig::default(), TEST_VERSION)
+    setup_monitoring_endpoint_with_health_checks(ComponentHealthChecks::new())
+}
+
+fn setup_monitoring_endpoint_with_health_checks(
+    health_checks: ComponentHealthChecks,
+) -> MonitoringEndpoint {
+    create_monitoring_endpoint(MonitoringEndpointConfig::default(), TEST_VERSION, health_checks)
+        .unwrap()
 }
 
 async fn request_app(app: Router, method: &str) -> Response {
@@ -51,12 +73,79 @@ async fn test_ready() {
     assert_eq!(response.status(), StatusCode::OK);
 }
 
+#[rstest]
+#[case::healthy(
+    HealthStatus::Healthy { details: "height 2".to_owned() },
+    StatusCode::OK,
+    json!({"status": "healthy", "details": "height 2"})
+)]
+#[case::unhealthy(
+    HealthStatus::Unhealthy { reason: "storage error".to_owned() },
+    StatusCode::SERVICE_UNAVAILABLE,
+    json!({"status": "unhealthy", "reason": "storage error"})
+)]
+#[tokio::test]
+async fn test_ready_aggregates_health_checks(
+    #[case] batcher_health: HealthStatus,
+    #[case] expected_status: StatusCode,
+    #[case] expected_batcher_health: Value,
+) {
+    let mut health_checks = ComponentHealthChecks::new();
+    health_checks.insert("batcher", Arc::new(FixedHealth(batcher_health)));
+    health_checks.insert(
+        "mempool",
+        Arc::new(FixedHealth(HealthStatus::Healthy { details: "size 1".to_owned() })),
+    );
+    let app = setup_monitoring_endpoint_with_health_checks(health_checks).app();
+
+    let response = request_app(app, READY).await;
+    assert_eq!(response.status(), expected_status);
+
+    let body: Value =
+        serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap();
+    assert_eq!(
+        body,
+        json!({
+            "batcher": expected_batcher_health,
+            "mempool": {"status": "healthy", "details": "size 1"},
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_without_metrics() {
+    let response = request_app(setup_monitoring_endpoint().app(), METRICS).await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+    let body = to_bytes(response.into_body()).await.unwrap();
+    assert!(body.is_empty());
+}
+
+#[tokio::test]
+async fn test_with_metrics() {
+    // Installs the global recorder, so no other test may collect metrics.
+    let config = MonitoringEndpointConfig { collect_metrics: true, ..Default::default() };
+    let app = create_monitoring_endpoint(config, TEST_VERSION, ComponentHealthChecks::new())
+        .unwrap()
+        .app();
+
+    let metric_name = "metric_name";
+    let metric_value = 8224;
+    metrics::absolute_counter!(metric_name, metric_value);
+
+    let response = request_app(app, METRICS).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = String::from_utf8(to_bytes(response.into_body()).await.unwrap().to_vec()).unwrap();
+    assert!(body.contains(&format!("{metric_name} {metric_value}")), "Unexpected metrics: {body}");
+}
+
 #[tokio::test]
 async fn test_endpoint_as_server() {
     spawn(async move { setup_monitoring_endpoint().run().await });
     yield_now().await;
 
-    let MonitoringEndpointConfig { ip, port } = MonitoringEndpointConfig::default();
+    let MonitoringEndpointConfig { ip, port, .. } = MonitoringEndpointConfig::default();
 
     let client = Client::new();
 
@@ -0,0 +1,28 @@
+use std::process::Command;
+
+const FINGERPRINT_ENV_VAR: &str = "REMOTE_PROTOCOL_BUILD_FINGERPRINT";
+
+// Fingerprints the build, so that remote component clients and servers built from different
+// sources, which may encode their messages differently, refuse to talk to each other. The
+// fingerprint is the git commit of the sources, unless given explicitly, e.g., when building
+// outside of the repository.
+fn main() {
+    println!("cargo:rerun-if-env-changed={FINGERPRINT_ENV_VAR}");
+    println!("cargo:rerun-if-changed=../../.git/HEAD");
+    println!("cargo:rerun-if-changed=../../.git/refs/heads");
+    println!("cargo:rerun-if-changed=../../.git/packed-refs");
+
+    let fingerprint = std::env::var(FINGERPRINT_ENV_VAR)
+        .ok()
+        .or_else(git_commit)
+        .unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_owned());
+    println!("cargo:rustc-env={FINGERPRINT_ENV_VAR}={fingerprint}");
+}
+
+fn git_commit() -> Option<String> {
+    let output = Command::new("git").args(["rev-parse", "HEAD"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8(output.stdout).ok()?.trim().to_owned())
+}
@@ -0,0 +1,34 @@
+#!/bin/bash
+# Generates the certificates used by the remote component tests: a CA, a certificate signed by it
+# for both servers and clients of local components, and a certificate signed by an untrusted CA.
+set -e
+cd "$(dirname "$0")"
+
+ext_file=$(mktemp)
+trap 'rm -f "${ext_file}" ./*.csr ./*.srl' EXIT
+cat > "${ext_file}" <<EXT
+basicConstraints = critical, CA:FALSE
+keyUsage = critical, digitalSignature
+extendedKeyUsage = serverAuth, clientAuth
+subjectAltName = DNS:localhost, IP:127.0.0.1, IP:::1
+EXT
+
+generate_ca() {
+    openssl genpkey -algorithm EC -pkeyopt ec_paramgen_curve:P-256 -out "$1_key.pem"
+    openssl req -x509 -new -key "$1_key.pem" -days 36500 -subj "/CN=$1" -out "$1_cert.pem" \
+        -addext "basicConstraints = critical, CA:TRUE" \
+        -addext "keyUsage = critical, keyCertSign, cRLSign"
+}
+
+generate_cert() {
+    openssl genpkey -algorithm EC -pkeyopt ec_paramgen_curve:P-256 -out "$1_key.pem"
+    openssl req -new -key "$1_key.pem" -subj "/CN=$1" -out "$1.csr"
+    openssl x509 -req -in "$1.csr" -CA "$2_cert.pem" -CAkey "$2_key.pem" -CAcreateserial \
+        -days 36500 -extfile "${ext_file}" -out "$1_cert.pem"
+}
+
+generate_ca ca
+generate_cert component ca
+generate_ca untrusted_ca
+generate_cert untrusted_component untrusted_ca
+rm ca_key.pem untrusted_ca_key.pem untrusted_ca_cert.pem
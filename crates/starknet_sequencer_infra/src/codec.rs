@@ -0,0 +1,85 @@
+//! The encodings of the messages between remote component clients and servers.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::component_definitions::APPLICATION_OCTET_STREAM;
+
+#[cfg(test)]
+#[path = "codec_test.rs"]
+pub mod codec_test;
+
+pub const APPLICATION_JSON: &str = "application/json";
+pub const APPLICATION_CBOR: &str = "application/cbor";
+
+#[derive(Debug, Error)]
+pub enum CodecError {
+    #[error(transparent)]
+    Bincode(#[from] bincode::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    CborSerialization(#[from] ciborium::ser::Error<std::io::Error>),
+    #[error(transparent)]
+    CborDeserialization(#[from] ciborium::de::Error<std::io::Error>),
+}
+
+/// The encoding of the messages of a remote component, identified on the wire by the content type
+/// of the HTTP messages.
+///
+/// Only `Json` decodes internally tagged enums that contain compactly serialized types, e.g.,
+/// `RpcTransaction`, so components whose messages contain them must use it. `Bincode` doesn't
+/// decode types that accept several representations, e.g., the data availability modes of
+/// transactions.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Codec {
+    /// Compact, but tied to the Rust definitions of the messages.
+    Bincode,
+    /// Human readable, e.g., for sending requests with curl while debugging.
+    #[default]
+    Json,
+    /// Self-describing, so that fields can be added to the messages and they can be read by
+    /// non-Rust tools.
+    Cbor,
+}
+
+impl Codec {
+    pub const ALL: [Codec; 3] = [Codec::Bincode, Codec::Json, Codec::Cbor];
+    /// The codecs that decode any message, given that it isn't internally tagged.
+    pub const SELF_DESCRIBING: [Codec; 2] = [Codec::Json, Codec::Cbor];
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Codec::Bincode => APPLICATION_OCTET_STREAM,
+            Codec::Json => APPLICATION_JSON,
+            Codec::Cbor => APPLICATION_CBOR,
+        }
+    }
+
+    /// The codec of a content type, ignoring its parameters, e.g., `charset`.
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime_type = content_type.split(';').next().unwrap_or_default().trim();
+        Codec::ALL.into_iter().find(|codec| mime_type.eq_ignore_ascii_case(codec.content_type()))
+    }
+
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, CodecError> {
+        match self {
+            Codec::Bincode => Ok(bincode::serialize(value)?),
+            Codec::Json => Ok(serde_json::to_vec(value)?),
+            Codec::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes)?;
+                Ok(bytes)
+            }
+        }
+    }
+
+    pub fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, CodecError> {
+        match self {
+            Codec::Bincode => Ok(bincode::deserialize(bytes)?),
+            Codec::Json => Ok(serde_json::from_slice(bytes)?),
+            Codec::Cbor => Ok(ciborium::from_reader(bytes)?),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use serde::{Deserialize, Serialize};
+use starknet_types_core::felt::Felt;
+
+use crate::codec::{Codec, APPLICATION_JSON};
+use crate::component_definitions::ServerError;
+use crate::serde_utils::BincodeSerdeWrapper;
+use crate::test_utils::assert_codec_round_trip;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum TestMessage {
+    Unit,
+    Value(Felt),
+    Struct { a: u32, b: Option<String> },
+}
+
+#[rstest]
+#[case::unit(TestMessage::Unit)]
+#[case::value(TestMessage::Value(Felt::ONE))]
+#[case::named_fields(TestMessage::Struct { a: 17, b: Some("b".to_owned()) })]
+fn test_round_trip(
+    #[case] message: TestMessage,
+    #[values(Codec::Bincode, Codec::Json, Codec::Cbor)] codec: Codec,
+) {
+    let encoded = codec.encode(&message).unwrap();
+    assert_eq!(codec.decode::<TestMessage>(&encoded).unwrap(), message);
+}
+
+#[test]
+fn test_server_error_round_trip() {
+    assert_codec_round_trip(&ServerError::InternalError("error".to_owned()));
+}
+
+#[test]
+fn test_bincode_matches_previous_encoding() {
+    let message = TestMessage::Value(Felt::ONE);
+    assert_eq!(
+        Codec::Bincode.encode(&message).unwrap(),
+        BincodeSerdeWrapper::new(message).to_bincode().unwrap()
+    );
+}
+
+#[test]
+fn test_json_is_human_readable() {
+    let encoded = Codec::Json.encode(&TestMessage::Struct { a: 17, b: None }).unwrap();
+    assert_eq!(String::from_utf8(encoded).unwrap(), r#"{"Struct":{"a":17,"b":null}}"#);
+}
+
+#[test]
+fn test_from_content_type() {
+    for codec in Codec::ALL {
+        assert_eq!(Codec::from_content_type(codec.content_type()), Some(codec));
+    }
+    assert_eq!(
+        Codec::from_content_type(&format!("{APPLICATION_JSON}; charset=utf-8")),
+        Some(Codec::Json)
+    );
+    assert_eq!(Codec::from_content_type("text/plain"), None);
+}
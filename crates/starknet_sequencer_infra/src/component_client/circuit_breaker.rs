@@ -0,0 +1,55 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+#[path = "circuit_breaker_test.rs"]
+mod circuit_breaker_test;
+
+#[derive(Debug, Default)]
+struct CircuitBreakerState {
+    consecutive_failures: usize,
+    open_until: Option<Instant>,
+}
+
+/// Fails requests fast after `failure_threshold` consecutive failed attempts, instead of waiting on
+/// a component that is down. Once open, the circuit rejects requests for `reset_timeout`; then it
+/// lets requests through again, and reopens on the first failure until a request succeeds.
+///
+/// The state is shared between the clones of a client.
+#[derive(Clone, Debug)]
+pub(crate) struct CircuitBreaker {
+    failure_threshold: usize,
+    reset_timeout: Duration,
+    state: Arc<Mutex<CircuitBreakerState>>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(failure_threshold: usize, reset_timeout: Duration) -> Self {
+        Self { failure_threshold, reset_timeout, state: Default::default() }
+    }
+
+    /// Whether requests are currently rejected.
+    pub(crate) fn is_open(&self, now: Instant) -> bool {
+        self.lock_state().open_until.is_some_and(|open_until| now < open_until)
+    }
+
+    pub(crate) fn record_success(&self) {
+        *self.lock_state() = CircuitBreakerState::default();
+    }
+
+    /// Records a failed attempt, and returns whether it opened the circuit.
+    pub(crate) fn record_failure(&self, now: Instant) -> bool {
+        let mut state = self.lock_state();
+        state.consecutive_failures += 1;
+        if state.consecutive_failures < self.failure_threshold {
+            return false;
+        }
+
+        state.open_until = Some(now + self.reset_timeout);
+        true
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, CircuitBreakerState> {
+        self.state.lock().expect("Lock should not be poisoned.")
+    }
+}
@@ -0,0 +1,40 @@
+use std::time::{Duration, Instant};
+
+use crate::component_client::circuit_breaker::CircuitBreaker;
+
+const RESET_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[test]
+fn test_opens_after_consecutive_failures() {
+    let now = Instant::now();
+    let circuit_breaker = CircuitBreaker::new(2, RESET_TIMEOUT);
+
+    assert!(!circuit_breaker.record_failure(now));
+    circuit_breaker.record_success();
+    assert!(!circuit_breaker.record_failure(now));
+    assert!(!circuit_breaker.is_open(now));
+
+    assert!(circuit_breaker.record_failure(now));
+    assert!(circuit_breaker.is_open(now));
+    // Shared between clones.
+    assert!(circuit_breaker.clone().is_open(now));
+}
+
+#[test]
+fn test_half_open_after_reset_timeout() {
+    let now = Instant::now();
+    let circuit_breaker = CircuitBreaker::new(2, RESET_TIMEOUT);
+    circuit_breaker.record_failure(now);
+    circuit_breaker.record_failure(now);
+
+    // Requests are let through after the reset timeout, and a single failure reopens the circuit.
+    let later = now + RESET_TIMEOUT;
+    assert!(!circuit_breaker.is_open(later));
+    assert!(circuit_breaker.record_failure(later));
+    assert!(circuit_breaker.is_open(later));
+
+    // A success closes it.
+    circuit_breaker.record_success();
+    assert!(!circuit_breaker.is_open(later));
+    assert!(!circuit_breaker.record_failure(later));
+}
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use hyper::StatusCode;
 use serde::de::DeserializeOwned;
@@ -6,6 +7,7 @@ use serde::Serialize;
 use thiserror::Error;
 
 use super::{LocalComponentClient, RemoteComponentClient};
+use crate::codec::CodecError;
 use crate::component_definitions::ServerError;
 
 #[derive(Clone, Debug, Error)]
@@ -13,13 +15,23 @@ pub enum ClientError {
     #[error("Communication error: {0}")]
     CommunicationFailure(Arc<hyper::Error>),
     #[error("Could not deserialize server response: {0}")]
-    ResponseDeserializationFailure(Arc<bincode::Error>),
+    ResponseDeserializationFailure(Arc<CodecError>),
     #[error("Could not parse the response: {0}")]
     ResponseParsingFailure(Arc<hyper::Error>),
     #[error("Got status code: {0}, with server error: {1}")]
     ResponseError(StatusCode, ServerError),
     #[error("Got an unexpected response type: {0}")]
     UnexpectedResponse(String),
+    #[error("Protocol mismatch: {0}")]
+    ProtocolMismatch(String),
+    #[error("Could not serialize the request: {0}")]
+    RequestSerializationFailure(Arc<CodecError>),
+    #[error("The component is unavailable: {0}")]
+    ComponentUnavailable(String),
+    #[error("The request did not complete within its deadline of {0:?}.")]
+    DeadlineExceeded(Duration),
+    #[error("The request was not sent: the circuit breaker of the client is open.")]
+    CircuitOpen,
 }
 
 pub type ClientResult<T> = Result<T, ClientError>;
@@ -1,13 +1,16 @@
 use std::any::type_name;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use tokio::sync::mpsc::{channel, Sender};
+use tokio::time::timeout;
 use tracing::info;
 
-use crate::component_client::ClientResult;
+use crate::component_client::{ClientError, ClientResult};
 use crate::component_definitions::{ComponentClient, ComponentRequestAndResponseSender};
+use crate::metrics::{component_label, COMPONENT_CLIENT_DEADLINE_EXCEEDED, COMPONENT_LABEL};
 
 /// The `LocalComponentClient` struct is a generic client for sending component requests and
 /// receiving responses asynchronously.
@@ -19,10 +22,13 @@ use crate::component_definitions::{ComponentClient, ComponentRequestAndResponseS
 /// # Fields
 /// - `tx`: An asynchronous sender channel for transmitting
 ///   `ComponentRequestAndResponseSender<Request, Response>` messages.
+/// - `request_timeout`: The deadline for the component to respond to a request.
 ///
 /// # Example
 /// ```rust
 /// // Example usage of the LocalComponentClient
+/// use std::time::Duration;
+///
 /// use serde::{Deserialize, Serialize};
 /// use tokio::sync::mpsc::Sender;
 ///
@@ -51,7 +57,7 @@ use crate::component_definitions::{ComponentClient, ComponentRequestAndResponseS
 ///     >(100);
 ///
 ///     // Instantiate the client.
-///     let client = LocalComponentClient::new(tx);
+///     let client = LocalComponentClient::new(tx, Duration::from_secs(60));
 ///
 ///     // Instantiate a request.
 ///     let request = MyRequest { content: "Hello, world!".to_string() };
@@ -70,6 +76,7 @@ where
     Response: Send + Sync,
 {
     tx: Sender<ComponentRequestAndResponseSender<Request, Response>>,
+    request_timeout: Duration,
 }
 
 impl<Request, Response> LocalComponentClient<Request, Response>
@@ -77,8 +84,11 @@ where
     Request: Send + Sync,
     Response: Send + Sync,
 {
-    pub fn new(tx: Sender<ComponentRequestAndResponseSender<Request, Response>>) -> Self {
-        Self { tx }
+    pub fn new(
+        tx: Sender<ComponentRequestAndResponseSender<Request, Response>>,
+        request_timeout: Duration,
+    ) -> Self {
+        Self { tx, request_timeout }
     }
 }
 
@@ -92,8 +102,24 @@ where
     async fn send(&self, request: Request) -> ClientResult<Response> {
         let (res_tx, mut res_rx) = channel::<Response>(1);
         let request_and_res_tx = ComponentRequestAndResponseSender { request, tx: res_tx };
-        self.tx.send(request_and_res_tx).await.expect("Outbound connection should be open.");
-        Ok(res_rx.recv().await.expect("Inbound connection should be open."))
+        let send_and_receive = async {
+            self.tx.send(request_and_res_tx).await.map_err(|_| {
+                ClientError::ComponentUnavailable("its request channel is closed.".to_owned())
+            })?;
+            res_rx.recv().await.ok_or_else(|| {
+                ClientError::ComponentUnavailable(
+                    "it dropped the request without responding.".to_owned(),
+                )
+            })
+        };
+
+        timeout(self.request_timeout, send_and_receive).await.unwrap_or_else(|_| {
+            metrics::increment_counter!(
+                COMPONENT_CLIENT_DEADLINE_EXCEEDED,
+                COMPONENT_LABEL => component_label::<Request>()
+            );
+            Err(ClientError::DeadlineExceeded(self.request_timeout))
+        })
     }
 }
 
@@ -115,6 +141,6 @@ where
     Response: Send + Sync,
 {
     fn clone(&self) -> Self {
-        Self { tx: self.tx.clone() }
+        Self { tx: self.tx.clone(), request_timeout: self.request_timeout }
     }
 }
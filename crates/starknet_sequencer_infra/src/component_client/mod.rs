@@ -1,3 +1,4 @@
+mod circuit_breaker;
 mod definitions;
 mod local_component_client;
 mod remote_component_client;
@@ -2,24 +2,56 @@ use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::net::IpAddr;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use hyper::body::to_bytes;
-use hyper::header::CONTENT_TYPE;
-use hyper::{Body, Client, Request as HyperRequest, Response as HyperResponse, StatusCode, Uri};
+use hyper::client::HttpConnector;
+use hyper::header::{AUTHORIZATION, CONTENT_TYPE};
+use hyper::{
+    Body,
+    Client,
+    HeaderMap,
+    Request as HyperRequest,
+    Response as HyperResponse,
+    StatusCode,
+    Uri,
+};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use rand::Rng;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use tokio::time::{sleep, timeout_at};
+use tracing::warn;
 
+use super::circuit_breaker::CircuitBreaker;
 use super::definitions::{ClientError, ClientResult};
-use crate::component_definitions::{ComponentClient, RemoteClientConfig, APPLICATION_OCTET_STREAM};
-use crate::serde_utils::BincodeSerdeWrapper;
+use crate::codec::Codec;
+use crate::component_definitions::{
+    protocol_fingerprint,
+    ComponentClient,
+    ComponentRequest,
+    RemoteClientConfig,
+    ServerError,
+    PROTOCOL_FINGERPRINT_HEADER,
+};
+use crate::metrics::{
+    component_label,
+    COMPONENT_CLIENT_CIRCUIT_OPEN,
+    COMPONENT_CLIENT_CIRCUIT_OPEN_REJECTIONS,
+    COMPONENT_CLIENT_DEADLINE_EXCEEDED,
+    COMPONENT_CLIENT_FAILURES,
+    COMPONENT_CLIENT_RETRIES,
+    COMPONENT_LABEL,
+};
+use crate::tls::{client_tls_config, plaintext_client_config};
 
 /// The `RemoteComponentClient` struct is a generic client for sending component requests and
 /// receiving responses asynchronously through HTTP connection.
 ///
 /// # Type Parameters
-/// - `Request`: The type of the request. This type must implement the `serde::Serialize` trait.
+/// - `Request`: The type of the request. This type must implement the `serde::Serialize` trait, and
+///   the `ComponentRequest` trait to be sent.
 /// - `Response`: The type of the response. This type must implement the
 ///   `serde::de::DeserializeOwned` (e.g. by using #[derive(Deserialize)]) trait.
 ///
@@ -27,6 +59,17 @@ use crate::serde_utils::BincodeSerdeWrapper;
 /// - `uri`: URI address of the server.
 /// - `client`: The inner HTTP client that initiates the connection to the server and manages it.
 /// - `config`: Client configuration.
+/// - `circuit_breaker`: Rejects requests without sending them while the server keeps failing.
+///
+/// A request fails if it does not complete within the configured deadline. Within it, idempotent
+/// requests are retried with an exponential backoff; other requests are sent once, since a failed
+/// attempt may have reached the component. After repeated failed attempts, the circuit breaker of
+/// the client opens, and requests fail fast until it is reset.
+///
+/// Each request carries the protocol version and the request type, which the server checks before
+/// deserializing the request. Requests are encoded with the configured codec, and responses with
+/// the codec given by their content type. If configured, the client also authenticates with a
+/// shared secret and connects over mutual TLS.
 ///
 /// # Example
 /// ```rust
@@ -34,9 +77,11 @@ use crate::serde_utils::BincodeSerdeWrapper;
 ///
 /// use serde::{Deserialize, Serialize};
 ///
+/// use crate::starknet_sequencer_infra::codec::Codec;
 /// use crate::starknet_sequencer_infra::component_client::RemoteComponentClient;
 /// use crate::starknet_sequencer_infra::component_definitions::{
 ///     ComponentClient,
+///     ComponentRequest,
 ///     RemoteClientConfig,
 /// };
 ///
@@ -46,6 +91,12 @@ use crate::serde_utils::BincodeSerdeWrapper;
 ///     pub content: String,
 /// }
 ///
+/// impl ComponentRequest for MyRequest {
+///     fn is_idempotent(&self) -> bool {
+///         true
+///     }
+/// }
+///
 /// #[derive(Serialize, Deserialize, Debug)]
 /// struct MyResponse {
 ///     content: String,
@@ -63,6 +114,14 @@ use crate::serde_utils::BincodeSerdeWrapper;
 ///         retries: 3,
 ///         idle_connections: usize::MAX,
 ///         idle_timeout: 90,
+///         request_timeout_ms: 60_000,
+///         retry_base_delay_ms: 100,
+///         retry_max_delay_ms: 5_000,
+///         circuit_breaker_failure_threshold: 5,
+///         circuit_breaker_reset_timeout_ms: 5_000,
+///         codec: Codec::Bincode,
+///         shared_secret: None,
+///         tls: None,
 ///     };
 ///     let client = RemoteComponentClient::<MyRequest, MyResponse>::new(config);
 ///
@@ -84,8 +143,9 @@ where
     Response: DeserializeOwned,
 {
     uri: Uri,
-    client: Client<hyper::client::HttpConnector>,
+    client: Client<HttpsConnector<HttpConnector>>,
     config: RemoteClientConfig,
+    circuit_breaker: CircuitBreaker,
     _req: PhantomData<Request>,
     _res: PhantomData<Response>,
 }
@@ -98,23 +158,46 @@ where
     pub fn new(config: RemoteClientConfig) -> Self {
         let ip_address = config.socket.ip();
         let port = config.socket.port();
+        let (scheme, tls_config) = match &config.tls {
+            Some(tls_config) => (
+                "https",
+                client_tls_config(tls_config).expect("Failed to load the client TLS configuration"),
+            ),
+            None => ("http", plaintext_client_config()),
+        };
         let uri = match ip_address {
-            IpAddr::V4(ip_address) => format!("http://{}:{}/", ip_address, port).parse().unwrap(),
-            IpAddr::V6(ip_address) => format!("http://[{}]:{}/", ip_address, port).parse().unwrap(),
+            IpAddr::V4(ip_address) => {
+                format!("{}://{}:{}/", scheme, ip_address, port).parse().unwrap()
+            }
+            IpAddr::V6(ip_address) => {
+                format!("{}://[{}]:{}/", scheme, ip_address, port).parse().unwrap()
+            }
         };
+        let connector = HttpsConnectorBuilder::new()
+            .with_tls_config(tls_config)
+            .https_or_http()
+            .enable_http2()
+            .build();
         let client = Client::builder()
             .http2_only(true)
             .pool_max_idle_per_host(config.idle_connections)
             .pool_idle_timeout(Duration::from_secs(config.idle_timeout))
-            .build_http();
-        Self { uri, client, config, _req: PhantomData, _res: PhantomData }
+            .build(connector);
+        let circuit_breaker = CircuitBreaker::new(
+            config.circuit_breaker_failure_threshold,
+            Duration::from_millis(config.circuit_breaker_reset_timeout_ms),
+        );
+        Self { uri, client, config, circuit_breaker, _req: PhantomData, _res: PhantomData }
     }
 
     fn construct_http_request(&self, serialized_request: Vec<u8>) -> HyperRequest<Body> {
-        HyperRequest::post(self.uri.clone())
-            .header(CONTENT_TYPE, APPLICATION_OCTET_STREAM)
-            .body(Body::from(serialized_request))
-            .expect("Request building should succeed")
+        let mut http_request = HyperRequest::post(self.uri.clone())
+            .header(CONTENT_TYPE, self.config.codec.content_type())
+            .header(PROTOCOL_FINGERPRINT_HEADER, protocol_fingerprint::<Request, Response>());
+        if let Some(shared_secret) = &self.config.shared_secret {
+            http_request = http_request.header(AUTHORIZATION, format!("Bearer {shared_secret}"));
+        }
+        http_request.body(Body::from(serialized_request)).expect("Request building should succeed")
     }
 
     async fn try_send(&self, http_request: HyperRequest<Body>) -> ClientResult<Response> {
@@ -123,13 +206,64 @@ where
             .request(http_request)
             .await
             .map_err(|e| ClientError::CommunicationFailure(Arc::new(e)))?;
+        // The body is encoded by the protocol of the server, so it is decoded only if it matches.
+        check_protocol_fingerprint::<Request, Response>(http_response.headers())?;
 
+        let codec = http_response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .and_then(Codec::from_content_type)
+            .unwrap_or(self.config.codec);
         match http_response.status() {
-            StatusCode::OK => get_response_body(http_response).await,
-            status_code => Err(ClientError::ResponseError(
-                status_code,
-                get_response_body(http_response).await?,
-            )),
+            StatusCode::OK => get_response_body(http_response, codec).await,
+            status_code => {
+                let server_error = get_response_body(http_response, codec).await?;
+                Err(server_error_to_client_error(status_code, server_error))
+            }
+        }
+    }
+
+    // The delay before the given retry, drawn uniformly from the upper half of the exponential
+    // backoff, so that clients that failed together do not retry together.
+    fn retry_delay(&self, retry: u32) -> Duration {
+        let backoff_ms = self
+            .config
+            .retry_base_delay_ms
+            .saturating_mul(2_u64.saturating_pow(retry))
+            .min(self.config.retry_max_delay_ms);
+        Duration::from_millis(rand::thread_rng().gen_range(backoff_ms / 2..=backoff_ms))
+    }
+
+    fn record_attempt_result(&self, result: &ClientResult<Response>) {
+        match result {
+            Err(error) if is_server_failure(error) => {
+                metrics::increment_counter!(
+                    COMPONENT_CLIENT_FAILURES,
+                    COMPONENT_LABEL => component_label::<Request>()
+                );
+                if self.circuit_breaker.record_failure(Instant::now()) {
+                    warn!(
+                        "Circuit breaker of the client of {} opened: {}",
+                        component_label::<Request>(),
+                        error
+                    );
+                    metrics::gauge!(
+                        COMPONENT_CLIENT_CIRCUIT_OPEN,
+                        1.0,
+                        COMPONENT_LABEL => component_label::<Request>()
+                    );
+                }
+            }
+            // The server handled the request, and is therefore up.
+            _ => {
+                self.circuit_breaker.record_success();
+                metrics::gauge!(
+                    COMPONENT_CLIENT_CIRCUIT_OPEN,
+                    0.0,
+                    COMPONENT_LABEL => component_label::<Request>()
+                );
+            }
         }
     }
 }
@@ -138,33 +272,106 @@ where
 impl<Request, Response> ComponentClient<Request, Response>
     for RemoteComponentClient<Request, Response>
 where
-    Request: Send + Sync + Serialize + DeserializeOwned + Debug,
+    Request: Send + Sync + Serialize + DeserializeOwned + Debug + ComponentRequest,
     Response: Send + Sync + Serialize + DeserializeOwned + Debug,
 {
     async fn send(&self, component_request: Request) -> ClientResult<Response> {
+        let request_timeout = self.config.request_timeout();
+        let deadline = tokio::time::Instant::now() + request_timeout;
+        let max_attempts =
+            if component_request.is_idempotent() { self.config.retries + 1 } else { 1 };
+
         // Serialize the request.
-        let serialized_request = BincodeSerdeWrapper::new(component_request)
-            .to_bincode()
-            .expect("Request serialization should succeed");
+        let serialized_request = self
+            .config
+            .codec
+            .encode(&component_request)
+            .map_err(|e| ClientError::RequestSerializationFailure(Arc::new(e)))?;
 
-        // Construct the request, and send it up to 'max_retries + 1' times. Return if received a
-        // successful response, or the last response if all attempts failed.
-        let max_attempts = self.config.retries + 1;
+        // Send the request up to 'max_attempts' times, until the deadline or until the circuit
+        // breaker opens, as long as the server fails. Return the response or the last error.
+        let mut result = Err(ClientError::CircuitOpen);
         for attempt in 0..max_attempts {
-            let http_request = self.construct_http_request(serialized_request.clone());
-            let res = self.try_send(http_request).await;
-            if res.is_ok() {
-                return res;
+            if attempt > 0 {
+                let retry_delay = self.retry_delay(u32::try_from(attempt - 1).unwrap_or(u32::MAX));
+                if tokio::time::Instant::now() + retry_delay >= deadline {
+                    break;
+                }
+                sleep(retry_delay).await;
+                metrics::increment_counter!(
+                    COMPONENT_CLIENT_RETRIES,
+                    COMPONENT_LABEL => component_label::<Request>()
+                );
             }
-            if attempt == max_attempts - 1 {
-                return res;
+            if self.circuit_breaker.is_open(Instant::now()) {
+                metrics::increment_counter!(
+                    COMPONENT_CLIENT_CIRCUIT_OPEN_REJECTIONS,
+                    COMPONENT_LABEL => component_label::<Request>()
+                );
+                break;
+            }
+
+            let http_request = self.construct_http_request(serialized_request.clone());
+            result = timeout_at(deadline, self.try_send(http_request)).await.unwrap_or_else(|_| {
+                metrics::increment_counter!(
+                    COMPONENT_CLIENT_DEADLINE_EXCEEDED,
+                    COMPONENT_LABEL => component_label::<Request>()
+                );
+                Err(ClientError::DeadlineExceeded(request_timeout))
+            });
+            self.record_attempt_result(&result);
+            // Other errors, e.g., a rejected request, would recur on a retry.
+            if !matches!(&result, Err(error) if is_server_failure(error)) {
+                break;
             }
         }
-        unreachable!("Guaranteed to return a response before reaching this point.");
+        result
+    }
+}
+
+// Failures of the server or of the connection to it, as opposed to the server rejecting the
+// request.
+fn is_server_failure(error: &ClientError) -> bool {
+    match error {
+        ClientError::CommunicationFailure(_)
+        | ClientError::ResponseParsingFailure(_)
+        | ClientError::ComponentUnavailable(_)
+        | ClientError::DeadlineExceeded(_) => true,
+        ClientError::ResponseError(status_code, _) => status_code.is_server_error(),
+        _ => false,
+    }
+}
+
+// Errors that the local client of the server would return are returned as is, so that callers
+// handle them the same whether the component is local or remote.
+fn server_error_to_client_error(status_code: StatusCode, server_error: ServerError) -> ClientError {
+    match server_error {
+        ServerError::ComponentUnavailable(details) => ClientError::ComponentUnavailable(details),
+        ServerError::DeadlineExceeded(request_timeout) => {
+            ClientError::DeadlineExceeded(request_timeout)
+        }
+        server_error => ClientError::ResponseError(status_code, server_error),
+    }
+}
+
+fn check_protocol_fingerprint<Request, Response>(headers: &HeaderMap) -> ClientResult<()> {
+    let client_fingerprint = protocol_fingerprint::<Request, Response>();
+    let server_fingerprint = headers
+        .get(PROTOCOL_FINGERPRINT_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("missing");
+    if server_fingerprint != client_fingerprint {
+        return Err(ClientError::ProtocolMismatch(format!(
+            "client protocol is {client_fingerprint}, server protocol is {server_fingerprint}."
+        )));
     }
+    Ok(())
 }
 
-async fn get_response_body<Response>(response: HyperResponse<Body>) -> Result<Response, ClientError>
+async fn get_response_body<Response>(
+    response: HyperResponse<Body>,
+    codec: Codec,
+) -> Result<Response, ClientError>
 where
     Response: Serialize + DeserializeOwned + Debug,
 {
@@ -172,8 +379,7 @@ where
         .await
         .map_err(|e| ClientError::ResponseParsingFailure(Arc::new(e)))?;
 
-    BincodeSerdeWrapper::<Response>::from_bincode(&body_bytes)
-        .map_err(|e| ClientError::ResponseDeserializationFailure(Arc::new(e)))
+    codec.decode(&body_bytes).map_err(|e| ClientError::ResponseDeserializationFailure(Arc::new(e)))
 }
 
 // Can't derive because derive forces the generics to also be `Clone`, which we prefer not to do
@@ -188,6 +394,7 @@ where
             uri: self.uri.clone(),
             client: self.client.clone(),
             config: self.config.clone(),
+            circuit_breaker: self.circuit_breaker.clone(),
             _req: PhantomData,
             _res: PhantomData,
         }
@@ -2,9 +2,17 @@ use std::any::type_name;
 use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
-use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::dumping::{
+    ser_optional_param,
+    ser_optional_sub_config,
+    ser_param,
+    SerializeConfig,
+};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -13,20 +21,43 @@ use tokio::sync::mpsc::{Receiver, Sender};
 use tracing::{error, info};
 use validator::Validate;
 
+use crate::codec::Codec;
 use crate::component_client::ClientResult;
 use crate::errors::ComponentError;
 
 pub const APPLICATION_OCTET_STREAM: &str = "application/octet-stream";
+/// Identifies the sources remote component clients and servers were built from, see `build.rs`.
+/// Builds from different sources may encode messages differently, so they don't talk to each other.
+pub const BUILD_FINGERPRINT: &str = env!("REMOTE_PROTOCOL_BUILD_FINGERPRINT");
+pub(crate) const PROTOCOL_FINGERPRINT_HEADER: &str = "x-protocol-fingerprint";
 const DEFAULT_CHANNEL_BUFFER_SIZE: usize = 32;
 const DEFAULT_RETRIES: usize = 3;
 const DEFAULT_IDLE_CONNECTIONS: usize = usize::MAX;
 const DEFAULT_IDLE_TIMEOUT: u64 = 90;
+const DEFAULT_REQUEST_TIMEOUT_MS: u64 = 60_000;
+const DEFAULT_RETRY_BASE_DELAY_MS: u64 = 100;
+const DEFAULT_RETRY_MAX_DELAY_MS: u64 = 5_000;
+const DEFAULT_CIRCUIT_BREAKER_FAILURE_THRESHOLD: usize = 5;
+const DEFAULT_CIRCUIT_BREAKER_RESET_TIMEOUT_MS: u64 = 5_000;
+
+/// The fingerprint of the protocol between remote clients and servers of a component, which must
+/// match for them to communicate.
+pub fn protocol_fingerprint<Request, Response>() -> String {
+    format!("{BUILD_FINGERPRINT}/{}/{}", type_name::<Request>(), type_name::<Response>())
+}
 
 #[async_trait]
 pub trait ComponentRequestHandler<Request, Response> {
     async fn handle_request(&mut self, request: Request) -> Response;
 }
 
+/// A request that a remote component client may send.
+pub trait ComponentRequest {
+    /// Whether handling the request more than once has the same effect as handling it once. Only
+    /// such requests are retried, since a failed attempt may have reached the component.
+    fn is_idempotent(&self) -> bool;
+}
+
 #[async_trait]
 pub trait ComponentClient<Request, Response>
 where
@@ -44,6 +75,35 @@ pub trait ComponentStarter {
     }
 }
 
+/// The result of a health check of a component, as reported by the monitoring endpoint.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum HealthStatus {
+    /// The component can serve requests. The details describe its state, e.g., its height.
+    Healthy {
+        details: String,
+    },
+    Unhealthy {
+        reason: String,
+    },
+}
+
+impl HealthStatus {
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, HealthStatus::Healthy { .. })
+    }
+}
+
+/// Reports the health of a component, from state that it shares with the component once the
+/// component is moved to its server. The node is ready when all of its components are healthy.
+#[async_trait]
+pub trait ComponentHealth: Send + Sync {
+    /// Called on every readiness probe, so must be cheap.
+    async fn check_health(&self) -> HealthStatus;
+}
+
+pub type SharedComponentHealth = Arc<dyn ComponentHealth>;
+
 pub struct ComponentCommunication<T: Send + Sync> {
     tx: Option<Sender<T>>,
     rx: Option<Receiver<T>>,
@@ -76,31 +136,132 @@ where
 pub enum ServerError {
     #[error("Could not deserialize client request: {0}")]
     RequestDeserializationFailure(String),
+    #[error("Request is not authorized.")]
+    Unauthorized,
+    #[error("Protocol mismatch: {0}")]
+    ProtocolMismatch(String),
+    #[error("Could not read client request: {0}")]
+    RequestReadFailure(String),
+    #[error("Could not serialize server response: {0}")]
+    ResponseSerializationFailure(String),
+    #[error("The component is unavailable: {0}")]
+    ComponentUnavailable(String),
+    #[error("Internal server error: {0}")]
+    InternalError(String),
+    #[error("The component did not respond within the deadline of {0:?}.")]
+    DeadlineExceeded(Duration),
 }
 
 // The communication configuration of the local component.
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
 pub struct LocalServerConfig {
     pub channel_buffer_size: usize,
+    pub request_timeout_ms: u64,
+}
+
+impl LocalServerConfig {
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout_ms)
+    }
 }
 
 impl SerializeConfig for LocalServerConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([ser_param(
-            "channel_buffer_size",
-            &self.channel_buffer_size,
-            "The communication channel buffer size.",
-            ParamPrivacyInput::Public,
-        )])
+        BTreeMap::from_iter([
+            ser_param(
+                "channel_buffer_size",
+                &self.channel_buffer_size,
+                "The communication channel buffer size.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "request_timeout_ms",
+                &self.request_timeout_ms,
+                "The deadline in milliseconds for the component to respond to a request of a \
+                 local client.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
     }
 }
 
 impl Default for LocalServerConfig {
     fn default() -> Self {
-        Self { channel_buffer_size: DEFAULT_CHANNEL_BUFFER_SIZE }
+        Self {
+            channel_buffer_size: DEFAULT_CHANNEL_BUFFER_SIZE,
+            request_timeout_ms: DEFAULT_REQUEST_TIMEOUT_MS,
+        }
+    }
+}
+
+/// The TLS configuration of a remote component client or server. Both sides authenticate each
+/// other: each presents its own certificate, and accepts only a peer whose certificate is signed by
+/// the given CA.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct RemoteTlsConfig {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+    pub ca_cert_path: PathBuf,
+}
+
+impl Default for RemoteTlsConfig {
+    fn default() -> Self {
+        Self {
+            cert_path: PathBuf::from("cert.pem"),
+            key_path: PathBuf::from("key.pem"),
+            ca_cert_path: PathBuf::from("ca_cert.pem"),
+        }
     }
 }
 
+impl SerializeConfig for RemoteTlsConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "cert_path",
+                &self.cert_path,
+                "Path of the PEM certificate chain presented to the peer.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "key_path",
+                &self.key_path,
+                "Path of the PEM private key of the certificate.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "ca_cert_path",
+                &self.ca_cert_path,
+                "Path of the PEM certificate of the CA that signs the certificates of peers.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+fn ser_remote_codec_and_security_params(
+    codec: &Codec,
+    shared_secret: &Option<String>,
+    tls: &Option<RemoteTlsConfig>,
+) -> BTreeMap<ParamPath, SerializedParam> {
+    let mut dump = BTreeMap::from_iter([ser_param(
+        "codec",
+        codec,
+        "The encoding of the messages: Bincode, Json or Cbor. Must match between the client and \
+         the server.",
+        ParamPrivacyInput::Public,
+    )]);
+    dump.append(&mut ser_optional_param(
+        shared_secret,
+        String::new(),
+        "shared_secret",
+        "A secret that the client sends with each request, and the server requires.",
+        ParamPrivacyInput::Private,
+    ));
+    dump.append(&mut ser_optional_sub_config(tls, "tls"));
+    dump
+}
+
 // TODO(Nadin): Move the RemoteClientConfig and RemoteServerConfig to relevant modules.
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
 pub struct RemoteClientConfig {
@@ -108,6 +269,21 @@ pub struct RemoteClientConfig {
     pub retries: usize,
     pub idle_connections: usize,
     pub idle_timeout: u64,
+    pub request_timeout_ms: u64,
+    pub retry_base_delay_ms: u64,
+    pub retry_max_delay_ms: u64,
+    #[validate(range(min = 1))]
+    pub circuit_breaker_failure_threshold: usize,
+    pub circuit_breaker_reset_timeout_ms: u64,
+    pub codec: Codec,
+    pub shared_secret: Option<String>,
+    pub tls: Option<RemoteTlsConfig>,
+}
+
+impl RemoteClientConfig {
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout_ms)
+    }
 }
 
 impl Default for RemoteClientConfig {
@@ -118,13 +294,21 @@ impl Default for RemoteClientConfig {
             retries: DEFAULT_RETRIES,
             idle_connections: DEFAULT_IDLE_CONNECTIONS,
             idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            request_timeout_ms: DEFAULT_REQUEST_TIMEOUT_MS,
+            retry_base_delay_ms: DEFAULT_RETRY_BASE_DELAY_MS,
+            retry_max_delay_ms: DEFAULT_RETRY_MAX_DELAY_MS,
+            circuit_breaker_failure_threshold: DEFAULT_CIRCUIT_BREAKER_FAILURE_THRESHOLD,
+            circuit_breaker_reset_timeout_ms: DEFAULT_CIRCUIT_BREAKER_RESET_TIMEOUT_MS,
+            codec: Codec::default(),
+            shared_secret: None,
+            tls: None,
         }
     }
 }
 
 impl SerializeConfig for RemoteClientConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([
+        let mut dump = BTreeMap::from_iter([
             ser_param(
                 "socket",
                 &self.socket.to_string(),
@@ -134,7 +318,7 @@ impl SerializeConfig for RemoteClientConfig {
             ser_param(
                 "retries",
                 &self.retries,
-                "The max number of retries for sending a message.",
+                "The max number of retries for sending an idempotent request.",
                 ParamPrivacyInput::Public,
             ),
             ser_param(
@@ -149,29 +333,76 @@ impl SerializeConfig for RemoteClientConfig {
                 "The duration in seconds to keep an idle connection open before closing.",
                 ParamPrivacyInput::Public,
             ),
-        ])
+            ser_param(
+                "request_timeout_ms",
+                &self.request_timeout_ms,
+                "The deadline in milliseconds for a request to complete, including its retries.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "retry_base_delay_ms",
+                &self.retry_base_delay_ms,
+                "The delay in milliseconds before the first retry, doubled on each further retry.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "retry_max_delay_ms",
+                &self.retry_max_delay_ms,
+                "The maximum delay in milliseconds between retries.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "circuit_breaker_failure_threshold",
+                &self.circuit_breaker_failure_threshold,
+                "The number of consecutive failed attempts after which requests are rejected \
+                 without being sent.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "circuit_breaker_reset_timeout_ms",
+                &self.circuit_breaker_reset_timeout_ms,
+                "The duration in milliseconds requests are rejected for once the circuit breaker \
+                 opens.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        dump.append(&mut ser_remote_codec_and_security_params(
+            &self.codec,
+            &self.shared_secret,
+            &self.tls,
+        ));
+        dump
     }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
 pub struct RemoteServerConfig {
     pub socket: SocketAddr,
+    pub codec: Codec,
+    pub shared_secret: Option<String>,
+    pub tls: Option<RemoteTlsConfig>,
 }
 
 impl Default for RemoteServerConfig {
     fn default() -> Self {
         let socket = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 8080);
-        Self { socket }
+        Self { socket, codec: Codec::default(), shared_secret: None, tls: None }
     }
 }
 
 impl SerializeConfig for RemoteServerConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([ser_param(
+        let mut dump = BTreeMap::from_iter([ser_param(
             "socket",
             &self.socket.to_string(),
             "The remote component server socket.",
             ParamPrivacyInput::Public,
-        )])
+        )]);
+        dump.append(&mut ser_remote_codec_and_security_params(
+            &self.codec,
+            &self.shared_secret,
+            &self.tls,
+        ));
+        dump
     }
 }
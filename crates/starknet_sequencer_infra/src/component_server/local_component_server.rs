@@ -4,7 +4,7 @@ use std::marker::PhantomData;
 
 use async_trait::async_trait;
 use tokio::sync::mpsc::Receiver;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 use crate::component_definitions::{
     ComponentRequestAndResponseSender,
@@ -221,9 +221,14 @@ async fn request_response_loop<Request, Response, Component>(
         let response = component.handle_request(request).await;
         debug!("Component {} is sending response {:?}", type_name::<Component>(), response);
 
-        // Send the response to the client. This might result in a panic if the client has closed
-        // the response channel, which is considered a bug.
-        tx.send(response).await.expect("Response connection should be open.");
+        // Send the response to the client. The client might have stopped waiting for it, e.g., a
+        // remote client that disconnected, in which case the response is dropped.
+        if tx.send(response).await.is_err() {
+            warn!(
+                "Component {} could not send a response: the client is gone.",
+                type_name::<Component>()
+            );
+        }
     }
 
     info!("Stopping server for component {}", type_name::<Component>());
@@ -1,25 +1,45 @@
+use std::convert::Infallible;
 use std::fmt::Debug;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use hyper::body::to_bytes;
-use hyper::header::CONTENT_TYPE;
+use hyper::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use hyper::server::conn::Http;
 use hyper::service::{make_service_fn, service_fn};
-use hyper::{Body, Request as HyperRequest, Response as HyperResponse, Server, StatusCode};
+use hyper::{
+    Body,
+    HeaderMap,
+    Request as HyperRequest,
+    Response as HyperResponse,
+    Server,
+    StatusCode,
+};
+use rustls::ServerConfig;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use tokio::net::TcpListener;
+use tokio::time::timeout;
+use tokio_rustls::TlsAcceptor;
+use tracing::{error, warn};
 
+use crate::codec::Codec;
 use crate::component_client::{ClientError, LocalComponentClient};
 use crate::component_definitions::{
+    protocol_fingerprint,
     ComponentClient,
     RemoteServerConfig,
     ServerError,
-    APPLICATION_OCTET_STREAM,
+    PROTOCOL_FINGERPRINT_HEADER,
 };
 use crate::component_server::ComponentServerStarter;
 use crate::errors::ComponentServerError;
-use crate::serde_utils::BincodeSerdeWrapper;
+use crate::tls::server_tls_config;
+
+// Bounds the time a connection may hold resources before it is authenticated.
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// The `RemoteComponentServer` struct is a generic server that handles requests and responses for a
 /// specified component. It receives requests, processes them using the provided component, and
@@ -40,14 +60,22 @@ use crate::serde_utils::BincodeSerdeWrapper;
 ///
 /// - `component`: The component responsible for handling the requests and generating responses.
 /// - `socket`: A socket address for the server to listen on.
+/// - `config`: Server configuration.
+///
+/// Requests of a different protocol version, request type or codec than the server's are rejected
+/// before being deserialized. If configured, the server also requires a shared secret from clients
+/// and accepts only mutual TLS connections.
 ///
 /// # Example
 /// ```rust
 /// // Example usage of the RemoteComponentServer
+/// use std::time::Duration;
+///
 /// use async_trait::async_trait;
 /// use serde::{Deserialize, Serialize};
 /// use tokio::task;
 ///
+/// use crate::starknet_sequencer_infra::codec::Codec;
 /// use crate::starknet_sequencer_infra::component_client::LocalComponentClient;
 /// use crate::starknet_sequencer_infra::component_definitions::{
 ///     ComponentRequestHandler,
@@ -88,12 +116,15 @@ use crate::serde_utils::BincodeSerdeWrapper;
 /// async fn main() {
 ///     // Instantiate a local client to communicate with component.
 ///     let (tx, _rx) = tokio::sync::mpsc::channel(32);
-///     let local_client = LocalComponentClient::<MyRequest, MyResponse>::new(tx);
+///     let local_client =
+///         LocalComponentClient::<MyRequest, MyResponse>::new(tx, Duration::from_secs(60));
 ///
 ///     // Set the ip address and port of the server's socket.
 ///     let ip_address = std::net::IpAddr::V6(std::net::Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1));
 ///     let port: u16 = 8080;
-///     let config = RemoteServerConfig { socket: std::net::SocketAddr::new(ip_address, port) };
+///     let socket = std::net::SocketAddr::new(ip_address, port);
+///     let config =
+///         RemoteServerConfig { socket, codec: Codec::Bincode, shared_secret: None, tls: None };
 ///
 ///     // Instantiate the server.
 ///     let mut server = RemoteComponentServer::<MyRequest, MyResponse>::new(local_client, config);
@@ -111,6 +142,7 @@ where
 {
     socket: SocketAddr,
     local_client: LocalComponentClient<Request, Response>,
+    config: RemoteServerConfig,
 }
 
 impl<Request, Response> RemoteComponentServer<Request, Response>
@@ -122,52 +154,207 @@ where
         local_client: LocalComponentClient<Request, Response>,
         config: RemoteServerConfig,
     ) -> Self {
-        Self { local_client, socket: config.socket }
+        Self { local_client, socket: config.socket, config }
     }
 
+    // Never fails: errors are returned to the client as a `ServerError` with a matching status
+    // code, so that a single request cannot bring down the server.
     async fn remote_component_server_handler(
         http_request: HyperRequest<Body>,
         local_client: LocalComponentClient<Request, Response>,
-    ) -> Result<HyperResponse<Body>, hyper::Error> {
-        let body_bytes = to_bytes(http_request.into_body()).await?;
-
-        let http_response = match BincodeSerdeWrapper::<Request>::from_bincode(&body_bytes)
-            .map_err(|e| ClientError::ResponseDeserializationFailure(Arc::new(e)))
+        codec: Codec,
+        shared_secret: Option<Arc<str>>,
+    ) -> Result<HyperResponse<Body>, Infallible> {
+        if let Err((status_code, server_error)) =
+            Self::check_request_headers(http_request.headers(), codec, shared_secret.as_deref())
         {
-            Ok(request) => {
-                let response = local_client.send(request).await;
-                match response {
-                    Ok(response) => HyperResponse::builder()
-                        .status(StatusCode::OK)
-                        .header(CONTENT_TYPE, APPLICATION_OCTET_STREAM)
-                        .body(Body::from(
-                            BincodeSerdeWrapper::new(response)
-                                .to_bincode()
-                                .expect("Response serialization should succeed"),
-                        )),
-                    Err(error) => {
-                        panic!(
-                            "Remote server failed sending with its local client. Error: {:?}",
-                            error
-                        );
-                    }
-                }
+            return Ok(Self::server_error_response(codec, status_code, server_error));
+        }
+
+        let body_bytes = match to_bytes(http_request.into_body()).await {
+            Ok(body_bytes) => body_bytes,
+            Err(error) => {
+                let server_error = ServerError::RequestReadFailure(error.to_string());
+                return Ok(Self::server_error_response(
+                    codec,
+                    StatusCode::BAD_REQUEST,
+                    server_error,
+                ));
             }
+        };
+
+        let request = match codec.decode::<Request>(&body_bytes) {
+            Ok(request) => request,
             Err(error) => {
                 let server_error = ServerError::RequestDeserializationFailure(error.to_string());
-                HyperResponse::builder().status(StatusCode::BAD_REQUEST).body(Body::from(
-                    BincodeSerdeWrapper::new(server_error)
-                        .to_bincode()
-                        .expect("Server error serialization should succeed"),
+                return Ok(Self::server_error_response(
+                    codec,
+                    StatusCode::BAD_REQUEST,
+                    server_error,
+                ));
+            }
+        };
+
+        let response = match local_client.send(request).await {
+            Ok(response) => response,
+            Err(error) => {
+                error!("Remote server failed sending with its local client. Error: {:?}", error);
+                let (status_code, server_error) = match error {
+                    ClientError::ComponentUnavailable(details) => (
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        ServerError::ComponentUnavailable(details),
+                    ),
+                    ClientError::DeadlineExceeded(request_timeout) => (
+                        StatusCode::GATEWAY_TIMEOUT,
+                        ServerError::DeadlineExceeded(request_timeout),
+                    ),
+                    error => (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        ServerError::InternalError(error.to_string()),
+                    ),
+                };
+                return Ok(Self::server_error_response(codec, status_code, server_error));
+            }
+        };
+
+        match codec.encode(&response) {
+            Ok(serialized_response) => {
+                Ok(Self::http_response(codec, StatusCode::OK, serialized_response))
+            }
+            Err(error) => {
+                error!("Remote server failed serializing a response. Error: {:?}", error);
+                let server_error = ServerError::ResponseSerializationFailure(error.to_string());
+                Ok(Self::server_error_response(
+                    codec,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    server_error,
                 ))
             }
         }
-        .expect("Response building should succeed");
+    }
 
-        Ok(http_response)
+    fn check_request_headers(
+        headers: &HeaderMap,
+        codec: Codec,
+        shared_secret: Option<&str>,
+    ) -> Result<(), (StatusCode, ServerError)> {
+        if let Some(shared_secret) = shared_secret {
+            let expected_authorization = format!("Bearer {shared_secret}");
+            let is_authorized = headers.get(AUTHORIZATION).is_some_and(|authorization| {
+                constant_time_eq(authorization.as_bytes(), expected_authorization.as_bytes())
+            });
+            if !is_authorized {
+                return Err((StatusCode::UNAUTHORIZED, ServerError::Unauthorized));
+            }
+        }
+
+        let header_value = |name| headers.get(name).and_then(|value| value.to_str().ok());
+        let server_fingerprint = protocol_fingerprint::<Request, Response>();
+        let client_fingerprint = header_value(PROTOCOL_FINGERPRINT_HEADER).unwrap_or("missing");
+        if client_fingerprint != server_fingerprint {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                ServerError::ProtocolMismatch(format!(
+                    "server protocol is {server_fingerprint}, client protocol is \
+                     {client_fingerprint}."
+                )),
+            ));
+        }
+        let client_content_type = header_value(CONTENT_TYPE.as_str()).unwrap_or("missing");
+        if Codec::from_content_type(client_content_type) != Some(codec) {
+            return Err((
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                ServerError::ProtocolMismatch(format!(
+                    "server content type is {}, client content type is {client_content_type}.",
+                    codec.content_type()
+                )),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn http_response(codec: Codec, status_code: StatusCode, body: Vec<u8>) -> HyperResponse<Body> {
+        let mut http_response = HyperResponse::new(Body::from(body));
+        *http_response.status_mut() = status_code;
+        let headers = http_response.headers_mut();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static(codec.content_type()));
+        let fingerprint = protocol_fingerprint::<Request, Response>();
+        headers.insert(
+            PROTOCOL_FINGERPRINT_HEADER,
+            HeaderValue::from_str(&fingerprint)
+                .expect("The protocol fingerprint should be a valid header value."),
+        );
+        http_response
+    }
+
+    fn server_error_response(
+        codec: Codec,
+        status_code: StatusCode,
+        server_error: ServerError,
+    ) -> HyperResponse<Body> {
+        // Serializing the error cannot fail in practice, and if it does the client fails to
+        // deserialize the empty body instead.
+        let serialized_error = codec.encode(&server_error).unwrap_or_default();
+        Self::http_response(codec, status_code, serialized_error)
+    }
+
+    // Serves mutual TLS connections. Connections that fail the handshake, e.g., clients without a
+    // valid certificate, or don't complete it in time are dropped without affecting the others.
+    async fn serve_tls(&self, tls_config: ServerConfig) -> Result<(), ComponentServerError> {
+        let acceptor = TlsAcceptor::from(Arc::new(tls_config));
+        let listener = TcpListener::bind(self.socket)
+            .await
+            .map_err(|err| ComponentServerError::HttpServerStartError(err.to_string()))?;
+        let codec = self.config.codec;
+        let shared_secret: Option<Arc<str>> = self.config.shared_secret.as_deref().map(Arc::from);
+
+        loop {
+            let (stream, peer_address) = match listener.accept().await {
+                Ok(connection) => connection,
+                Err(err) => {
+                    warn!("Failed to accept a connection: {}", err);
+                    continue;
+                }
+            };
+            let acceptor = acceptor.clone();
+            let local_client = self.local_client.clone();
+            let shared_secret = shared_secret.clone();
+            tokio::spawn(async move {
+                let stream = match timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                    Ok(Ok(stream)) => stream,
+                    Ok(Err(err)) => {
+                        warn!("TLS handshake with {} failed: {}", peer_address, err);
+                        return;
+                    }
+                    Err(_) => {
+                        warn!("TLS handshake with {} timed out.", peer_address);
+                        return;
+                    }
+                };
+                let service = service_fn(move |req| {
+                    Self::remote_component_server_handler(
+                        req,
+                        local_client.clone(),
+                        codec,
+                        shared_secret.clone(),
+                    )
+                });
+                if let Err(err) =
+                    Http::new().http2_only(true).serve_connection(stream, service).await
+                {
+                    warn!("Connection with {} failed: {}", peer_address, err);
+                }
+            });
+        }
     }
 }
 
+// Compares secrets in a time that does not depend on the length of their common prefix.
+fn constant_time_eq(lhs: &[u8], rhs: &[u8]) -> bool {
+    lhs.len() == rhs.len() && lhs.iter().zip(rhs).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
 #[async_trait]
 impl<Request, Response> ComponentServerStarter for RemoteComponentServer<Request, Response>
 where
@@ -175,11 +362,25 @@ where
     Response: Serialize + DeserializeOwned + Send + Sync + Debug + 'static,
 {
     async fn start(&mut self) -> Result<(), ComponentServerError> {
+        if let Some(tls_config) = &self.config.tls {
+            let tls_config = server_tls_config(tls_config)
+                .map_err(|err| ComponentServerError::HttpServerStartError(err.to_string()))?;
+            return self.serve_tls(tls_config).await;
+        }
+
+        let codec = self.config.codec;
+        let shared_secret: Option<Arc<str>> = self.config.shared_secret.as_deref().map(Arc::from);
         let make_svc = make_service_fn(|_conn| {
             let local_client = self.local_client.clone();
-            async {
+            let shared_secret = shared_secret.clone();
+            async move {
                 Ok::<_, hyper::Error>(service_fn(move |req| {
-                    Self::remote_component_server_handler(req, local_client.clone())
+                    Self::remote_component_server_handler(
+                        req,
+                        local_client.clone(),
+                        codec,
+                        shared_secret.clone(),
+                    )
                 }))
             }
         });
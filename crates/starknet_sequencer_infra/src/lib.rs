@@ -1,10 +1,13 @@
+pub mod codec;
 pub mod component_client;
 pub mod component_definitions;
 pub mod component_server;
 pub mod errors;
+pub mod metrics;
 pub mod serde_utils;
 #[cfg(any(feature = "testing", test))]
 pub mod test_utils;
 #[cfg(test)]
 pub mod tests;
+pub(crate) mod tls;
 pub mod trace_util;
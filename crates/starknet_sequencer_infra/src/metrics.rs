@@ -0,0 +1,29 @@
+//! Metrics of the component clients, labelled by the component they send requests to.
+
+use std::any::type_name;
+
+/// The label of the component a client sends requests to.
+pub const COMPONENT_LABEL: &str = "component";
+
+/// The number of requests resent after a failed attempt.
+pub const COMPONENT_CLIENT_RETRIES: &str = "component_client_retries";
+
+/// The number of requests that did not complete within their deadline.
+pub const COMPONENT_CLIENT_DEADLINE_EXCEEDED: &str = "component_client_deadline_exceeded";
+
+/// The number of failed attempts counted by the circuit breaker of the client.
+pub const COMPONENT_CLIENT_FAILURES: &str = "component_client_failures";
+
+/// The number of requests rejected without being sent because the circuit breaker was open.
+pub const COMPONENT_CLIENT_CIRCUIT_OPEN_REJECTIONS: &str =
+    "component_client_circuit_open_rejections";
+
+/// Whether the circuit breaker of the client is open: set when it opens, and cleared when a request
+/// succeeds.
+pub const COMPONENT_CLIENT_CIRCUIT_OPEN: &str = "component_client_circuit_open";
+
+/// The value of the component label of a client of `Request`, e.g., `MempoolRequest`.
+pub(crate) fn component_label<Request>() -> &'static str {
+    let request_type = type_name::<Request>();
+    request_type.rsplit("::").next().unwrap_or(request_type)
+}
@@ -1,7 +1,12 @@
+use std::any::type_name;
 use std::net::SocketAddr;
 
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use tokio::net::TcpListener;
 
+use crate::codec::Codec;
+
 /// Returns a unique IP address and port for testing purposes.
 /// Tests run in parallel, so servers (like RPC or web) running on separate tests must have
 /// different ports, otherwise the server will fail with "address already in use".
@@ -15,3 +20,27 @@ pub async fn get_available_socket() -> SocketAddr {
         .local_addr()
         .expect("Failed to get local address")
 }
+
+/// Asserts that every codec decodes the encoding of the value to a value with the same encoding.
+pub fn assert_codec_round_trip<T: Serialize + DeserializeOwned>(value: &T) {
+    assert_round_trip_with_codecs(value, &Codec::ALL);
+}
+
+/// Like [`assert_codec_round_trip`], for values that only some codecs support, e.g., values that
+/// contain an `RpcTransaction`.
+pub fn assert_round_trip_with_codecs<T: Serialize + DeserializeOwned>(value: &T, codecs: &[Codec]) {
+    for &codec in codecs {
+        let encoded = codec.encode(value).unwrap_or_else(|error| {
+            panic!("{codec:?} failed to encode a {}: {error}", type_name::<T>())
+        });
+        let decoded: T = codec.decode(&encoded).unwrap_or_else(|error| {
+            panic!("{codec:?} failed to decode a {}: {error}", type_name::<T>())
+        });
+        assert_eq!(
+            codec.encode(&decoded).unwrap(),
+            encoded,
+            "{codec:?} changed a {} in a round trip.",
+            type_name::<T>()
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::future::pending;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -19,6 +20,8 @@ use tokio::sync::mpsc::{channel, Sender};
 use tokio::sync::{Barrier, Mutex};
 use tokio::task;
 
+const LOCAL_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
 #[derive(Debug, Clone)]
 struct ComponentC {
     counter: Arc<Mutex<usize>>,
@@ -153,7 +156,7 @@ async fn wait_and_verify_response(
     expected_counter_value: usize,
     barrier: Arc<Barrier>,
 ) {
-    let c_client = LocalComponentClient::new(tx_c);
+    let c_client = LocalComponentClient::new(tx_c, LOCAL_REQUEST_TIMEOUT);
 
     barrier.wait().await;
     assert_eq!(c_client.c_get_counter().await.unwrap(), expected_counter_value);
@@ -168,7 +171,7 @@ async fn test_setup_c_d() {
     let (tx_c, rx_c) =
         channel::<ComponentRequestAndResponseSender<ComponentCRequest, ComponentCResponse>>(32);
 
-    let c_client = LocalComponentClient::new(tx_c.clone());
+    let c_client = LocalComponentClient::new(tx_c.clone(), LOCAL_REQUEST_TIMEOUT);
 
     let barrier = Arc::new(Barrier::new(3));
     let component_c = ComponentC::new(init_counter_value, max_iterations, barrier.clone());
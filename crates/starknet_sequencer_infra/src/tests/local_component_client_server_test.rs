@@ -1,3 +1,6 @@
+use std::time::Duration;
+
+use assert_matches::assert_matches;
 use async_trait::async_trait;
 use starknet_types_core::felt::Felt;
 use tokio::sync::mpsc::channel;
@@ -20,6 +23,7 @@ use crate::tests::{
     ResultB,
     ValueA,
     ValueB,
+    LOCAL_REQUEST_TIMEOUT,
 };
 
 type ComponentAClient = LocalComponentClient<ComponentARequest, ComponentAResponse>;
@@ -68,8 +72,8 @@ async fn test_setup() {
     let (tx_b, rx_b) =
         channel::<ComponentRequestAndResponseSender<ComponentBRequest, ComponentBResponse>>(32);
 
-    let a_client = ComponentAClient::new(tx_a.clone());
-    let b_client = ComponentBClient::new(tx_b.clone());
+    let a_client = ComponentAClient::new(tx_a.clone(), LOCAL_REQUEST_TIMEOUT);
+    let b_client = ComponentBClient::new(tx_b.clone(), LOCAL_REQUEST_TIMEOUT);
 
     let component_a = ComponentA::new(Box::new(b_client.clone()));
     let component_b = ComponentB::new(setup_value, Box::new(a_client.clone()));
@@ -87,3 +91,27 @@ async fn test_setup() {
 
     test_a_b_functionality(a_client, b_client, expected_value).await;
 }
+
+#[tokio::test]
+async fn test_stopped_component() {
+    let (tx_a, rx_a) =
+        channel::<ComponentRequestAndResponseSender<ComponentARequest, ComponentAResponse>>(32);
+    let a_client = ComponentAClient::new(tx_a, LOCAL_REQUEST_TIMEOUT);
+    drop(rx_a);
+
+    assert_matches!(a_client.a_get_value().await, Err(ClientError::ComponentUnavailable(_)));
+}
+
+#[tokio::test]
+async fn test_deadline_exceeded() {
+    let request_timeout = Duration::from_millis(10);
+    // The component receives the request, but never responds.
+    let (tx_a, _rx_a) =
+        channel::<ComponentRequestAndResponseSender<ComponentARequest, ComponentAResponse>>(32);
+    let a_client = ComponentAClient::new(tx_a, request_timeout);
+
+    assert_matches!(
+        a_client.a_get_value().await,
+        Err(ClientError::DeadlineExceeded(timeout)) if timeout == request_timeout
+    );
+}
@@ -1,11 +1,16 @@
 mod local_component_client_server_test;
 mod remote_component_client_server_test;
+use std::time::Duration;
+
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use starknet_types_core::felt::Felt;
 
 use crate::component_client::ClientResult;
-use crate::component_definitions::{ComponentRequestHandler, ComponentStarter};
+use crate::component_definitions::{ComponentRequest, ComponentRequestHandler, ComponentStarter};
+
+pub(crate) const LOCAL_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
 pub(crate) type ValueA = Felt;
 pub(crate) type ValueB = Felt;
 pub(crate) type ResultA = ClientResult<ValueA>;
@@ -16,6 +21,12 @@ pub enum ComponentARequest {
     AGetValue,
 }
 
+impl ComponentRequest for ComponentARequest {
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub enum ComponentAResponse {
     AGetValue(ValueA),
@@ -27,6 +38,12 @@ pub enum ComponentBRequest {
     BSetValue(ValueB),
 }
 
+impl ComponentRequest for ComponentBRequest {
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub enum ComponentBResponse {
     BGetValue(ValueB),
@@ -1,7 +1,11 @@
 use std::fmt::Debug;
 use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
+use assert_matches::assert_matches;
 use async_trait::async_trait;
 use hyper::body::to_bytes;
 use hyper::header::CONTENT_TYPE;
@@ -9,12 +13,13 @@ use hyper::service::{make_service_fn, service_fn};
 use hyper::{Body, Client, Request, Response, Server, StatusCode, Uri};
 use rstest::rstest;
 use serde::de::DeserializeOwned;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use starknet_types_core::felt::Felt;
 use tokio::sync::mpsc::channel;
 use tokio::sync::Mutex;
 use tokio::task;
 
+use crate::codec::Codec;
 use crate::component_client::{
     ClientError,
     ClientResult,
@@ -22,19 +27,21 @@ use crate::component_client::{
     RemoteComponentClient,
 };
 use crate::component_definitions::{
+    protocol_fingerprint,
     ComponentClient,
+    ComponentRequest,
     ComponentRequestAndResponseSender,
     RemoteClientConfig,
     RemoteServerConfig,
+    RemoteTlsConfig,
     ServerError,
-    APPLICATION_OCTET_STREAM,
+    PROTOCOL_FINGERPRINT_HEADER,
 };
 use crate::component_server::{
     ComponentServerStarter,
     LocalComponentServer,
     RemoteComponentServer,
 };
-use crate::serde_utils::BincodeSerdeWrapper;
 use crate::test_utils::get_available_socket;
 use crate::tests::{
     test_a_b_functionality,
@@ -50,6 +57,7 @@ use crate::tests::{
     ResultB,
     ValueA,
     ValueB,
+    LOCAL_REQUEST_TIMEOUT,
 };
 
 type ComponentAClient = RemoteComponentClient<ComponentARequest, ComponentAResponse>;
@@ -64,6 +72,22 @@ const DESERIALIZE_REQ_ERROR_MESSAGE: &str = "Could not deserialize client reques
 // ClientError::ResponseDeserializationFailure error message.
 const DESERIALIZE_RES_ERROR_MESSAGE: &str = "Could not deserialize server response";
 const VALID_VALUE_A: ValueA = Felt::ONE;
+// ServerError::Unauthorized error message.
+const UNAUTHORIZED_ERROR_MESSAGE: &str = "Request is not authorized";
+// ServerError::ProtocolMismatch error message.
+const PROTOCOL_MISMATCH_ERROR_MESSAGE: &str = "Protocol mismatch";
+const SHARED_SECRET: &str = "shared secret";
+const FAST_RETRY_DELAY_MS: u64 = 1;
+
+// A request of component A that is not retried.
+#[derive(Serialize, Deserialize, Debug)]
+struct NonIdempotentARequest(ComponentARequest);
+
+impl ComponentRequest for NonIdempotentARequest {
+    fn is_idempotent(&self) -> bool {
+        false
+    }
+}
 
 #[async_trait]
 impl ComponentAClientTrait for RemoteComponentClient<ComponentARequest, ComponentAResponse> {
@@ -105,6 +129,10 @@ async fn verify_error(
     assert_error_contains_keywords(error.to_string(), expected_error_contained_keywords)
 }
 
+fn a_protocol_fingerprint() -> String {
+    protocol_fingerprint::<ComponentARequest, ComponentAResponse>()
+}
+
 fn assert_error_contains_keywords(error: String, expected_error_contained_keywords: &[&str]) {
     for expected_keyword in expected_error_contained_keywords {
         if !error.contains(expected_keyword) {
@@ -128,7 +156,8 @@ where
         {
             Ok(Response::builder()
                 .status(StatusCode::BAD_REQUEST)
-                .body(Body::from(BincodeSerdeWrapper::new(body).to_bincode().unwrap()))
+                .header(PROTOCOL_FINGERPRINT_HEADER, a_protocol_fingerprint())
+                .body(Body::from(Codec::default().encode(&body).unwrap()))
                 .unwrap())
         }
 
@@ -148,11 +177,29 @@ where
 }
 
 async fn setup_for_tests(setup_value: ValueB, a_socket: SocketAddr, b_socket: SocketAddr) {
-    let a_config = RemoteClientConfig { socket: a_socket, ..Default::default() };
-    let b_config = RemoteClientConfig { socket: b_socket, ..Default::default() };
+    setup_configured_for_tests(setup_value, a_socket, b_socket, RemoteServerConfig::default())
+        .await;
+}
 
-    let a_remote_client = ComponentAClient::new(a_config);
-    let b_remote_client = ComponentBClient::new(b_config);
+// Sets up the components, communicating with the codec, shared secret and TLS configuration of the
+// given server configuration.
+async fn setup_configured_for_tests(
+    setup_value: ValueB,
+    a_socket: SocketAddr,
+    b_socket: SocketAddr,
+    config: RemoteServerConfig,
+) {
+    let client_config = |socket| RemoteClientConfig {
+        socket,
+        codec: config.codec,
+        shared_secret: config.shared_secret.clone(),
+        tls: config.tls.clone(),
+        ..Default::default()
+    };
+    let server_config = |socket| RemoteServerConfig { socket, ..config.clone() };
+
+    let a_remote_client = ComponentAClient::new(client_config(a_socket));
+    let b_remote_client = ComponentBClient::new(client_config(b_socket));
 
     let component_a = ComponentA::new(Box::new(b_remote_client));
     let component_b = ComponentB::new(setup_value, Box::new(a_remote_client.clone()));
@@ -162,16 +209,22 @@ async fn setup_for_tests(setup_value: ValueB, a_socket: SocketAddr, b_socket: So
     let (tx_b, rx_b) =
         channel::<ComponentRequestAndResponseSender<ComponentBRequest, ComponentBResponse>>(32);
 
-    let a_local_client = LocalComponentClient::<ComponentARequest, ComponentAResponse>::new(tx_a);
-    let b_local_client = LocalComponentClient::<ComponentBRequest, ComponentBResponse>::new(tx_b);
+    let a_local_client = LocalComponentClient::<ComponentARequest, ComponentAResponse>::new(
+        tx_a,
+        LOCAL_REQUEST_TIMEOUT,
+    );
+    let b_local_client = LocalComponentClient::<ComponentBRequest, ComponentBResponse>::new(
+        tx_b,
+        LOCAL_REQUEST_TIMEOUT,
+    );
 
     let mut component_a_local_server = LocalComponentServer::new(component_a, rx_a);
     let mut component_b_local_server = LocalComponentServer::new(component_b, rx_b);
 
     let mut component_a_remote_server =
-        RemoteComponentServer::new(a_local_client, RemoteServerConfig { socket: a_socket });
+        RemoteComponentServer::new(a_local_client, server_config(a_socket));
     let mut component_b_remote_server =
-        RemoteComponentServer::new(b_local_client, RemoteServerConfig { socket: b_socket });
+        RemoteComponentServer::new(b_local_client, server_config(b_socket));
 
     task::spawn(async move {
         let _ = component_a_local_server.start().await;
@@ -226,13 +279,14 @@ async fn test_faulty_client_setup() {
             let uri: Uri =
                 format!("http://[{}]:{}/", self.socket.ip(), self.socket.port()).parse().unwrap();
             let http_request = Request::post(uri)
-                .header(CONTENT_TYPE, APPLICATION_OCTET_STREAM)
-                .body(Body::from(BincodeSerdeWrapper::new(component_request).to_bincode().unwrap()))
+                .header(CONTENT_TYPE, Codec::default().content_type())
+                .header(PROTOCOL_FINGERPRINT_HEADER, a_protocol_fingerprint())
+                .body(Body::from(Codec::default().encode(&component_request).unwrap()))
                 .unwrap();
             let http_response = Client::new().request(http_request).await.unwrap();
             let status_code = http_response.status();
             let body_bytes = to_bytes(http_response.into_body()).await.unwrap();
-            let response = BincodeSerdeWrapper::<ServerError>::from_bincode(&body_bytes).unwrap();
+            let response = Codec::default().decode::<ServerError>(&body_bytes).unwrap();
             Err(ClientError::ResponseError(status_code, response))
         }
     }
@@ -281,16 +335,19 @@ async fn test_retry_request() {
             should_send_ok: Arc<Mutex<bool>>,
         ) -> Result<Response<Body>, hyper::Error> {
             let mut should_send_ok = should_send_ok.lock().await;
-            let body = ComponentAResponse::AGetValue(VALID_VALUE_A);
             let ret = if *should_send_ok {
+                let body = ComponentAResponse::AGetValue(VALID_VALUE_A);
                 Response::builder()
                     .status(StatusCode::OK)
-                    .body(Body::from(BincodeSerdeWrapper::new(body).to_bincode().unwrap()))
+                    .header(PROTOCOL_FINGERPRINT_HEADER, a_protocol_fingerprint())
+                    .body(Body::from(Codec::default().encode(&body).unwrap()))
                     .unwrap()
             } else {
+                let body = ServerError::InternalError(MOCK_SERVER_ERROR.to_string());
                 Response::builder()
-                    .status(StatusCode::IM_A_TEAPOT)
-                    .body(Body::from(BincodeSerdeWrapper::new(body).to_bincode().unwrap()))
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .header(PROTOCOL_FINGERPRINT_HEADER, a_protocol_fingerprint())
+                    .body(Body::from(Codec::default().encode(&body).unwrap()))
                     .unwrap()
             };
             *should_send_ok = !*should_send_ok;
@@ -319,6 +376,7 @@ async fn test_retry_request() {
         retries: 1,
         idle_connections: MAX_IDLE_CONNECTION,
         idle_timeout: IDLE_TIMEOUT,
+        ..Default::default()
     };
     let a_client_retry = ComponentAClient::new(retry_config);
     assert_eq!(a_client_retry.a_get_value().await.unwrap(), VALID_VALUE_A);
@@ -329,8 +387,291 @@ async fn test_retry_request() {
         retries: 0,
         idle_connections: MAX_IDLE_CONNECTION,
         idle_timeout: IDLE_TIMEOUT,
+        ..Default::default()
     };
     let a_client_no_retry = ComponentAClient::new(no_retry_config);
-    let expected_error_contained_keywords = [StatusCode::IM_A_TEAPOT.as_str()];
+    let expected_error_contained_keywords = [StatusCode::INTERNAL_SERVER_ERROR.as_str()];
     verify_error(a_client_no_retry.clone(), &expected_error_contained_keywords).await;
 }
+
+#[tokio::test]
+async fn test_stopped_component() {
+    let socket = get_available_socket().await;
+    let (tx_a, rx_a) =
+        channel::<ComponentRequestAndResponseSender<ComponentARequest, ComponentAResponse>>(32);
+    let a_local_client = LocalComponentClient::<ComponentARequest, ComponentAResponse>::new(
+        tx_a,
+        LOCAL_REQUEST_TIMEOUT,
+    );
+    let mut component_a_remote_server = RemoteComponentServer::new(
+        a_local_client,
+        RemoteServerConfig { socket, ..Default::default() },
+    );
+    task::spawn(async move {
+        let _ = component_a_remote_server.start().await;
+    });
+    // Todo(uriel): Get rid of this
+    task::yield_now().await;
+    // The local server of the component stopped.
+    drop(rx_a);
+
+    let client = ComponentAClient::new(RemoteClientConfig { socket, ..Default::default() });
+
+    // The server keeps serving, and the error is the one a local client would have returned.
+    for _ in 0..2 {
+        assert_matches!(client.a_get_value().await, Err(ClientError::ComponentUnavailable(_)));
+    }
+}
+
+#[tokio::test]
+async fn test_shared_secret() {
+    let setup_value: ValueB = Felt::from(90);
+    let a_socket = get_available_socket().await;
+    let b_socket = get_available_socket().await;
+    let shared_secret = Some(SHARED_SECRET.to_string());
+    let server_config =
+        RemoteServerConfig { shared_secret: shared_secret.clone(), ..Default::default() };
+    setup_configured_for_tests(setup_value, a_socket, b_socket, server_config).await;
+
+    let a_client_config = RemoteClientConfig {
+        socket: a_socket,
+        shared_secret: shared_secret.clone(),
+        ..Default::default()
+    };
+    let b_client_config =
+        RemoteClientConfig { socket: b_socket, shared_secret, ..Default::default() };
+    test_a_b_functionality(
+        ComponentAClient::new(a_client_config),
+        ComponentBClient::new(b_client_config),
+        setup_value,
+    )
+    .await;
+
+    let expected_error_contained_keywords =
+        [StatusCode::UNAUTHORIZED.as_str(), UNAUTHORIZED_ERROR_MESSAGE];
+    for shared_secret in [None, Some("wrong secret".to_string())] {
+        let client_config =
+            RemoteClientConfig { socket: a_socket, shared_secret, ..Default::default() };
+        verify_error(ComponentAClient::new(client_config), &expected_error_contained_keywords)
+            .await;
+    }
+}
+
+#[tokio::test]
+async fn test_request_type_mismatch() {
+    let a_socket = get_available_socket().await;
+    let b_socket = get_available_socket().await;
+    setup_for_tests(Felt::from(123), a_socket, b_socket).await;
+
+    // A client of component B, sending to the server of component A.
+    let client =
+        ComponentBClient::new(RemoteClientConfig { socket: a_socket, ..Default::default() });
+
+    let Err(error) = client.b_get_value().await else {
+        panic!("Expected an error.");
+    };
+    assert_error_contains_keywords(
+        error.to_string(),
+        &[PROTOCOL_MISMATCH_ERROR_MESSAGE, "ComponentARequest", "ComponentBRequest"],
+    );
+}
+
+#[rstest]
+#[case::bincode(Codec::Bincode)]
+#[case::cbor(Codec::Cbor)]
+#[tokio::test]
+async fn test_codec(#[case] codec: Codec) {
+    let setup_value: ValueB = Felt::from(90);
+    let a_socket = get_available_socket().await;
+    let b_socket = get_available_socket().await;
+    let server_config = RemoteServerConfig { codec, ..Default::default() };
+    setup_configured_for_tests(setup_value, a_socket, b_socket, server_config).await;
+
+    let a_client_config = RemoteClientConfig { socket: a_socket, codec, ..Default::default() };
+    let b_client_config = RemoteClientConfig { socket: b_socket, codec, ..Default::default() };
+    test_a_b_functionality(
+        ComponentAClient::new(a_client_config),
+        ComponentBClient::new(b_client_config),
+        setup_value,
+    )
+    .await;
+
+    // A client with another codec is rejected, and can read the error.
+    let client =
+        ComponentAClient::new(RemoteClientConfig { socket: a_socket, ..Default::default() });
+    let Err(error) = client.a_get_value().await else {
+        panic!("Expected an error.");
+    };
+    assert_error_contains_keywords(
+        error.to_string(),
+        &[
+            StatusCode::UNSUPPORTED_MEDIA_TYPE.as_str(),
+            PROTOCOL_MISMATCH_ERROR_MESSAGE,
+            codec.content_type(),
+        ],
+    );
+}
+
+#[tokio::test]
+async fn test_mutual_tls() {
+    let setup_value: ValueB = Felt::from(90);
+    let a_socket = get_available_socket().await;
+    let b_socket = get_available_socket().await;
+    let tls = test_tls_config("component");
+    let server_config = RemoteServerConfig { tls: Some(tls.clone()), ..Default::default() };
+    setup_configured_for_tests(setup_value, a_socket, b_socket, server_config).await;
+
+    let a_client_config =
+        RemoteClientConfig { socket: a_socket, tls: Some(tls.clone()), ..Default::default() };
+    let b_client_config =
+        RemoteClientConfig { socket: b_socket, tls: Some(tls), ..Default::default() };
+    test_a_b_functionality(
+        ComponentAClient::new(a_client_config),
+        ComponentBClient::new(b_client_config),
+        setup_value,
+    )
+    .await;
+
+    // Clients without TLS, or with a certificate that is not signed by the CA, are rejected.
+    for tls in [None, Some(test_tls_config("untrusted_component"))] {
+        let client_config = RemoteClientConfig { socket: a_socket, tls, ..Default::default() };
+        assert!(ComponentAClient::new(client_config).a_get_value().await.is_err());
+    }
+}
+
+fn test_tls_config(cert_name: &str) -> RemoteTlsConfig {
+    let tls_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resources/tls");
+    RemoteTlsConfig {
+        cert_path: tls_dir.join(format!("{cert_name}_cert.pem")),
+        key_path: tls_dir.join(format!("{cert_name}_key.pem")),
+        ca_cert_path: tls_dir.join("ca_cert.pem"),
+    }
+}
+
+// Spawns a server that fails all requests with the given status code, and returns the number of
+// requests it received.
+async fn create_failing_server(socket: SocketAddr, status_code: StatusCode) -> Arc<AtomicUsize> {
+    let n_requests = Arc::new(AtomicUsize::new(0));
+    let server_n_requests = n_requests.clone();
+    task::spawn(async move {
+        let make_svc = make_service_fn(move |_conn| {
+            let n_requests = server_n_requests.clone();
+            async move {
+                Ok::<_, hyper::Error>(service_fn(move |_req| {
+                    n_requests.fetch_add(1, Ordering::SeqCst);
+                    let body = ServerError::InternalError(MOCK_SERVER_ERROR.to_string());
+                    async move {
+                        Response::builder()
+                            .status(status_code)
+                            .header(PROTOCOL_FINGERPRINT_HEADER, a_protocol_fingerprint())
+                            .body(Body::from(Codec::default().encode(&body).unwrap()))
+                    }
+                }))
+            }
+        });
+        Server::bind(&socket).serve(make_svc).await.unwrap();
+    });
+    // Todo(uriel): Get rid of this
+    // Ensure the server starts running.
+    task::yield_now().await;
+
+    n_requests
+}
+
+#[tokio::test]
+async fn test_only_idempotent_requests_are_retried() {
+    let socket = get_available_socket().await;
+    let n_requests = create_failing_server(socket, StatusCode::INTERNAL_SERVER_ERROR).await;
+    let config = RemoteClientConfig {
+        socket,
+        retries: 2,
+        retry_base_delay_ms: FAST_RETRY_DELAY_MS,
+        ..Default::default()
+    };
+
+    let idempotent_client = ComponentAClient::new(config.clone());
+    assert!(idempotent_client.send(ComponentARequest::AGetValue).await.is_err());
+    assert_eq!(n_requests.swap(0, Ordering::SeqCst), 3);
+
+    let non_idempotent_client =
+        RemoteComponentClient::<NonIdempotentARequest, ComponentAResponse>::new(config);
+    let request = NonIdempotentARequest(ComponentARequest::AGetValue);
+    assert!(non_idempotent_client.send(request).await.is_err());
+    assert_eq!(n_requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_rejected_requests_are_not_retried() {
+    let socket = get_available_socket().await;
+    let n_requests = create_failing_server(socket, StatusCode::IM_A_TEAPOT).await;
+    let config = RemoteClientConfig {
+        socket,
+        retries: 2,
+        retry_base_delay_ms: FAST_RETRY_DELAY_MS,
+        ..Default::default()
+    };
+    let client = ComponentAClient::new(config);
+
+    assert_matches!(
+        client.a_get_value().await,
+        Err(ClientError::ResponseError(StatusCode::IM_A_TEAPOT, _))
+    );
+    assert_eq!(n_requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_deadline_exceeded() {
+    let socket = get_available_socket().await;
+    // A server that never responds.
+    task::spawn(async move {
+        let make_svc = make_service_fn(|_conn| async {
+            Ok::<_, hyper::Error>(service_fn(|_req| async {
+                std::future::pending::<Result<Response<Body>, hyper::Error>>().await
+            }))
+        });
+        Server::bind(&socket).serve(make_svc).await.unwrap();
+    });
+    // Todo(uriel): Get rid of this
+    // Ensure the server starts running.
+    task::yield_now().await;
+
+    let request_timeout = Duration::from_millis(100);
+    let config = RemoteClientConfig {
+        socket,
+        request_timeout_ms: 100,
+        retry_base_delay_ms: FAST_RETRY_DELAY_MS,
+        ..Default::default()
+    };
+    let client = ComponentAClient::new(config);
+
+    assert_matches!(
+        client.a_get_value().await,
+        Err(ClientError::DeadlineExceeded(timeout)) if timeout == request_timeout
+    );
+}
+
+#[tokio::test]
+async fn test_circuit_breaker() {
+    let socket = get_available_socket().await;
+    let n_requests = create_failing_server(socket, StatusCode::INTERNAL_SERVER_ERROR).await;
+    let config = RemoteClientConfig {
+        socket,
+        retries: 0,
+        circuit_breaker_failure_threshold: 2,
+        circuit_breaker_reset_timeout_ms: 100,
+        ..Default::default()
+    };
+    let client = ComponentAClient::new(config);
+
+    for _ in 0..2 {
+        assert_matches!(client.a_get_value().await, Err(ClientError::ResponseError(..)));
+    }
+    // The circuit is open, and shared between the clones of the client.
+    assert_matches!(client.clone().a_get_value().await, Err(ClientError::CircuitOpen));
+    assert_eq!(n_requests.load(Ordering::SeqCst), 2);
+
+    // After the reset timeout, requests are sent again.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_matches!(client.a_get_value().await, Err(ClientError::ResponseError(..)));
+    assert_eq!(n_requests.load(Ordering::SeqCst), 3);
+}
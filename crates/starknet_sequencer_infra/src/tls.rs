@@ -0,0 +1,91 @@
+//! Mutual TLS between remote component clients and servers.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use rustls::server::AllowAnyAuthenticatedClient;
+use rustls::{Certificate, ClientConfig, PrivateKey, RootCertStore, ServerConfig};
+use rustls_pemfile::Item;
+use thiserror::Error;
+
+use crate::component_definitions::RemoteTlsConfig;
+
+const HTTP2_ALPN: &[u8] = b"h2";
+
+#[derive(Debug, Error)]
+pub(crate) enum TlsConfigError {
+    #[error("Failed to read {path:?}: {source}")]
+    Io { path: PathBuf, source: std::io::Error },
+    #[error("No certificate found in {0:?}.")]
+    MissingCertificate(PathBuf),
+    #[error("No private key found in {0:?}.")]
+    MissingPrivateKey(PathBuf),
+    #[error(transparent)]
+    Rustls(#[from] rustls::Error),
+}
+
+/// A server configuration that requires clients to present a certificate signed by the CA.
+pub(crate) fn server_tls_config(config: &RemoteTlsConfig) -> Result<ServerConfig, TlsConfigError> {
+    let client_verifier = AllowAnyAuthenticatedClient::new(read_ca(&config.ca_cert_path)?).boxed();
+    let mut server_config = ServerConfig::builder()
+        .with_safe_defaults()
+        .with_client_cert_verifier(client_verifier)
+        .with_single_cert(read_certs(&config.cert_path)?, read_key(&config.key_path)?)?;
+    server_config.alpn_protocols = vec![HTTP2_ALPN.to_vec()];
+    Ok(server_config)
+}
+
+/// A client configuration that accepts only servers with a certificate signed by the CA.
+pub(crate) fn client_tls_config(config: &RemoteTlsConfig) -> Result<ClientConfig, TlsConfigError> {
+    Ok(ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(read_ca(&config.ca_cert_path)?)
+        .with_client_auth_cert(read_certs(&config.cert_path)?, read_key(&config.key_path)?)?)
+}
+
+/// A client configuration for plain HTTP connections, which never uses it.
+pub(crate) fn plaintext_client_config() -> ClientConfig {
+    ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(RootCertStore::empty())
+        .with_no_client_auth()
+}
+
+fn read_pem_items(path: &Path) -> Result<Vec<Item>, TlsConfigError> {
+    let to_error = |source| TlsConfigError::Io { path: path.to_owned(), source };
+    let mut reader = BufReader::new(File::open(path).map_err(to_error)?);
+    rustls_pemfile::read_all(&mut reader).map_err(to_error)
+}
+
+fn read_certs(path: &Path) -> Result<Vec<Certificate>, TlsConfigError> {
+    let certs: Vec<_> = read_pem_items(path)?
+        .into_iter()
+        .filter_map(|item| match item {
+            Item::X509Certificate(cert) => Some(Certificate(cert)),
+            _ => None,
+        })
+        .collect();
+    if certs.is_empty() {
+        return Err(TlsConfigError::MissingCertificate(path.to_owned()));
+    }
+    Ok(certs)
+}
+
+fn read_key(path: &Path) -> Result<PrivateKey, TlsConfigError> {
+    read_pem_items(path)?
+        .into_iter()
+        .find_map(|item| match item {
+            Item::PKCS8Key(key) | Item::ECKey(key) | Item::RSAKey(key) => Some(PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| TlsConfigError::MissingPrivateKey(path.to_owned()))
+}
+
+fn read_ca(path: &Path) -> Result<RootCertStore, TlsConfigError> {
+    let mut roots = RootCertStore::empty();
+    for cert in read_certs(path)? {
+        roots.add(&cert)?;
+    }
+    Ok(roots)
+}
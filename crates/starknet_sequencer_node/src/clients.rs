@@ -14,6 +14,20 @@ use starknet_gateway_types::communication::{
     RemoteGatewayClient,
     SharedGatewayClient,
 };
+use starknet_l1_gas_price_types::communication::{
+    L1GasPriceRequest,
+    L1GasPriceResponse,
+    LocalL1GasPriceProviderClient,
+    RemoteL1GasPriceProviderClient,
+    SharedL1GasPriceProviderClient,
+};
+use starknet_l1_provider_types::communication::{
+    L1ProviderRequest,
+    L1ProviderResponse,
+    LocalL1ProviderClient,
+    RemoteL1ProviderClient,
+    SharedL1ProviderClient,
+};
 use starknet_mempool_p2p_types::communication::{
     LocalMempoolP2pPropagatorClient,
     MempoolP2pPropagatorRequest,
@@ -45,6 +59,8 @@ pub struct SequencerNodeClients {
     batcher_client: Option<Client<BatcherRequest, BatcherResponse>>,
     mempool_client: Option<Client<MempoolRequest, MempoolResponse>>,
     gateway_client: Option<Client<GatewayRequest, GatewayResponse>>,
+    l1_gas_price_provider_client: Option<Client<L1GasPriceRequest, L1GasPriceResponse>>,
+    l1_provider_client: Option<Client<L1ProviderRequest, L1ProviderResponse>>,
     // TODO (Lev): Change to Option<Box<dyn MemPoolClient>>.
     mempool_p2p_propagator_client:
         Option<Client<MempoolP2pPropagatorRequest, MempoolP2pPropagatorResponse>>,
@@ -141,6 +157,34 @@ impl SequencerNodeClients {
         }
     }
 
+    pub fn get_l1_gas_price_provider_shared_client(
+        &self,
+    ) -> Option<SharedL1GasPriceProviderClient> {
+        get_shared_client!(self, l1_gas_price_provider_client)
+    }
+
+    pub fn get_l1_gas_price_provider_local_client(
+        &self,
+    ) -> Option<LocalComponentClient<L1GasPriceRequest, L1GasPriceResponse>> {
+        match &self.l1_gas_price_provider_client {
+            Some(client) => client.get_local_client(),
+            None => None,
+        }
+    }
+
+    pub fn get_l1_provider_shared_client(&self) -> Option<SharedL1ProviderClient> {
+        get_shared_client!(self, l1_provider_client)
+    }
+
+    pub fn get_l1_provider_local_client(
+        &self,
+    ) -> Option<LocalComponentClient<L1ProviderRequest, L1ProviderResponse>> {
+        match &self.l1_provider_client {
+            Some(client) => client.get_local_client(),
+            None => None,
+        }
+    }
+
     pub fn get_mempool_p2p_propagator_shared_client(
         &self,
     ) -> Option<SharedMempoolP2pPropagatorClient> {
@@ -180,10 +224,13 @@ impl SequencerNodeClients {
 /// * $execution_mode - A reference to the component's execution mode, i.e., type
 ///   &ComponentExecutionMode.
 /// * $local_client_type - The type for the local client to create, e.g., LocalBatcherClient. The
-///   client type should have a function $local_client_type::new(tx: $channel_expr).
+///   client type should have a function $local_client_type::new(tx: $channel_expr,
+///   request_timeout).
 /// * $remote_client_type - The type for the remote client to create, e.g., RemoteBatcherClient. The
 ///   client type should have a function $remote_client_type::new(config).
 /// * $channel_expr - Sender side for the local client.
+/// * $local_server_config - Configuration of the local server, from which the local client takes
+///   its request deadline.
 /// * $remote_client_config - Configuration for the remote client, passed as Some(config) when
 ///   available.
 ///
@@ -204,6 +251,7 @@ impl SequencerNodeClients {
 ///     LocalBatcherClient,
 ///     RemoteBatcherClient,
 ///     channels.take_batcher_tx(),
+///     config.components.batcher.local_server_config,
 ///     config.components.batcher.remote_client_config
 /// );
 ///
@@ -218,12 +266,15 @@ macro_rules! create_client {
         $local_client_type:ty,
         $remote_client_type:ty,
         $channel_expr:expr,
+        $local_server_config:expr,
         $remote_client_config:expr
     ) => {
         match *$execution_mode {
             ComponentExecutionMode::LocalExecutionWithRemoteDisabled
             | ComponentExecutionMode::LocalExecutionWithRemoteEnabled => {
-                let local_client = Some(<$local_client_type>::new($channel_expr));
+                let request_timeout =
+                    $local_server_config.clone().unwrap_or_default().request_timeout();
+                let local_client = Some(<$local_client_type>::new($channel_expr, request_timeout));
                 Some(Client::new(local_client, None))
             }
             ComponentExecutionMode::Remote => match $remote_client_config {
@@ -247,6 +298,7 @@ pub fn create_node_clients(
         LocalBatcherClient,
         RemoteBatcherClient,
         channels.take_batcher_tx(),
+        config.components.batcher.local_server_config,
         config.components.batcher.remote_client_config
     );
     let mempool_client = create_client!(
@@ -254,6 +306,7 @@ pub fn create_node_clients(
         LocalMempoolClient,
         RemoteMempoolClient,
         channels.take_mempool_tx(),
+        config.components.mempool.local_server_config,
         config.components.mempool.remote_client_config
     );
     let gateway_client = create_client!(
@@ -261,14 +314,34 @@ pub fn create_node_clients(
         LocalGatewayClient,
         RemoteGatewayClient,
         channels.take_gateway_tx(),
+        config.components.gateway.local_server_config,
         config.components.gateway.remote_client_config
     );
 
+    let l1_gas_price_provider_client = create_client!(
+        &config.components.l1_gas_price_provider.execution_mode,
+        LocalL1GasPriceProviderClient,
+        RemoteL1GasPriceProviderClient,
+        channels.take_l1_gas_price_provider_tx(),
+        config.components.l1_gas_price_provider.local_server_config,
+        config.components.l1_gas_price_provider.remote_client_config
+    );
+
+    let l1_provider_client = create_client!(
+        &config.components.l1_provider.execution_mode,
+        LocalL1ProviderClient,
+        RemoteL1ProviderClient,
+        channels.take_l1_provider_tx(),
+        config.components.l1_provider.local_server_config,
+        config.components.l1_provider.remote_client_config
+    );
+
     let mempool_p2p_propagator_client = create_client!(
         &config.components.mempool_p2p.execution_mode,
         LocalMempoolP2pPropagatorClient,
         RemoteMempoolP2pPropagatorClient,
         channels.take_mempool_p2p_propagator_tx(),
+        config.components.mempool_p2p.local_server_config,
         config.components.mempool_p2p.remote_client_config
     );
 
@@ -277,6 +350,7 @@ pub fn create_node_clients(
         LocalStateSyncClient,
         RemoteStateSyncClient,
         channels.take_state_sync_tx(),
+        config.components.state_sync.local_server_config,
         config.components.state_sync.remote_client_config
     );
 
@@ -284,6 +358,8 @@ pub fn create_node_clients(
         batcher_client,
         mempool_client,
         gateway_client,
+        l1_gas_price_provider_client,
+        l1_provider_client,
         mempool_p2p_propagator_client,
         state_sync_client,
     }
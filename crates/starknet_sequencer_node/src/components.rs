@@ -1,13 +1,24 @@
+use std::sync::Arc;
+
+use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerContract;
 use starknet_batcher::batcher::{create_batcher, Batcher};
 use starknet_consensus_manager::consensus_manager::ConsensusManager;
 use starknet_gateway::gateway::{create_gateway, Gateway};
 use starknet_http_server::http_server::{create_http_server, HttpServer};
+use starknet_l1_gas_price::l1_gas_price_provider::{
+    create_l1_gas_price_provider,
+    L1GasPriceProvider,
+};
+use starknet_l1_gas_price::l1_gas_price_scraper::L1GasPriceScraper;
+use starknet_l1_provider::l1_scraper::L1Scraper;
+use starknet_l1_provider::{create_l1_provider, L1Provider, L1ProviderHealth};
 use starknet_mempool::communication::{create_mempool, MempoolCommunicationWrapper};
 use starknet_mempool_p2p::create_p2p_propagator_and_runner;
 use starknet_mempool_p2p::propagator::MempoolP2pPropagator;
 use starknet_mempool_p2p::runner::MempoolP2pRunner;
 use starknet_monitoring_endpoint::monitoring_endpoint::{
     create_monitoring_endpoint,
+    ComponentHealthChecks,
     MonitoringEndpoint,
 };
 
@@ -21,6 +32,10 @@ pub struct SequencerNodeComponents {
     pub consensus_manager: Option<ConsensusManager>,
     pub gateway: Option<Gateway>,
     pub http_server: Option<HttpServer>,
+    pub l1_gas_price_provider: Option<L1GasPriceProvider>,
+    pub l1_gas_price_scraper: Option<L1GasPriceScraper<EthereumBaseLayerContract>>,
+    pub l1_provider: Option<L1Provider>,
+    pub l1_scraper: Option<L1Scraper<EthereumBaseLayerContract>>,
     pub mempool: Option<MempoolCommunicationWrapper>,
     pub monitoring_endpoint: Option<MonitoringEndpoint>,
     pub mempool_p2p_propagator: Option<MempoolP2pPropagator>,
@@ -31,21 +46,43 @@ pub fn create_node_components(
     config: &SequencerNodeConfig,
     clients: &SequencerNodeClients,
 ) -> SequencerNodeComponents {
-    let batcher = match config.components.batcher.execution_mode {
+    // The storage of the batcher is the state storage of the node; it is opened once, as the
+    // gateway may read the state from it too.
+    let (batcher, storage_reader) = match config.components.batcher.execution_mode {
         ComponentExecutionMode::LocalExecutionWithRemoteDisabled
         | ComponentExecutionMode::LocalExecutionWithRemoteEnabled => {
             let mempool_client =
                 clients.get_mempool_shared_client().expect("Mempool Client should be available");
-            Some(create_batcher(config.batcher_config.clone(), mempool_client))
+            let l1_provider_client = clients
+                .get_l1_provider_shared_client()
+                .expect("L1 Provider Client should be available");
+            let (storage_reader, storage_writer) =
+                papyrus_storage::open_storage(config.batcher_config.storage.clone())
+                    .expect("Failed to open batcher's storage");
+            let batcher = create_batcher(
+                config.batcher_config.clone(),
+                storage_reader.clone(),
+                storage_writer,
+                mempool_client,
+                l1_provider_client,
+            );
+            (Some(batcher), Some(storage_reader))
         }
-        ComponentExecutionMode::Disabled | ComponentExecutionMode::Remote => None,
+        ComponentExecutionMode::Disabled | ComponentExecutionMode::Remote => (None, None),
     };
     let consensus_manager = match config.components.consensus_manager.execution_mode {
         ComponentExecutionMode::LocalExecutionWithRemoteDisabled
         | ComponentExecutionMode::LocalExecutionWithRemoteEnabled => {
             let batcher_client =
                 clients.get_batcher_shared_client().expect("Batcher Client should be available");
-            Some(ConsensusManager::new(config.consensus_manager_config.clone(), batcher_client))
+            let l1_gas_price_provider_client = clients
+                .get_l1_gas_price_provider_shared_client()
+                .expect("L1 Gas Price Provider Client should be available");
+            Some(ConsensusManager::new(
+                config.consensus_manager_config.clone(),
+                batcher_client,
+                l1_gas_price_provider_client,
+            ))
         }
         ComponentExecutionMode::Disabled | ComponentExecutionMode::Remote => None,
     };
@@ -54,12 +91,19 @@ pub fn create_node_components(
         | ComponentExecutionMode::LocalExecutionWithRemoteEnabled => {
             let mempool_client =
                 clients.get_mempool_shared_client().expect("Mempool Client should be available");
+            let storage_reader = config.gateway_config.read_state_from_storage.then(|| {
+                storage_reader.clone().expect(
+                    "The gateway reads the state from the storage of the batcher, which should \
+                     run locally.",
+                )
+            });
 
             Some(create_gateway(
                 config.gateway_config.clone(),
                 config.rpc_state_reader_config.clone(),
                 config.compiler_config.clone(),
                 mempool_client,
+                storage_reader,
             ))
         }
         ComponentExecutionMode::Disabled | ComponentExecutionMode::Remote => None,
@@ -75,6 +119,47 @@ pub fn create_node_components(
         ComponentExecutionMode::Disabled | ComponentExecutionMode::Remote => None,
     };
 
+    // The L1 scrapers run alongside the providers they feed.
+    let (l1_gas_price_provider, l1_gas_price_scraper) =
+        match config.components.l1_gas_price_provider.execution_mode {
+            ComponentExecutionMode::LocalExecutionWithRemoteDisabled
+            | ComponentExecutionMode::LocalExecutionWithRemoteEnabled => {
+                let l1_gas_price_provider_client = clients
+                    .get_l1_gas_price_provider_shared_client()
+                    .expect("L1 Gas Price Provider Client should be available");
+                let base_layer = EthereumBaseLayerContract::new(config.base_layer_config.clone())
+                    .expect("Failed to create the base layer contract.");
+                let l1_gas_price_scraper = L1GasPriceScraper::new(
+                    config.l1_gas_price_scraper_config.clone(),
+                    base_layer,
+                    l1_gas_price_provider_client,
+                );
+                (
+                    Some(create_l1_gas_price_provider(config.l1_gas_price_provider_config.clone())),
+                    Some(l1_gas_price_scraper),
+                )
+            }
+            ComponentExecutionMode::Disabled | ComponentExecutionMode::Remote => (None, None),
+        };
+
+    let (l1_provider, l1_scraper) = match config.components.l1_provider.execution_mode {
+        ComponentExecutionMode::LocalExecutionWithRemoteDisabled
+        | ComponentExecutionMode::LocalExecutionWithRemoteEnabled => {
+            let l1_provider_client = clients
+                .get_l1_provider_shared_client()
+                .expect("L1 Provider Client should be available");
+            let base_layer = EthereumBaseLayerContract::new(config.base_layer_config.clone())
+                .expect("Failed to create the base layer contract.");
+            let l1_scraper = L1Scraper::new(
+                config.l1_provider_config.l1_scraper_config.clone(),
+                base_layer,
+                l1_provider_client,
+            );
+            (Some(create_l1_provider(config.l1_provider_config.clone())), Some(l1_scraper))
+        }
+        ComponentExecutionMode::Disabled | ComponentExecutionMode::Remote => (None, None),
+    };
+
     let (mempool_p2p_propagator, mempool_p2p_runner) = match config
         .components
         .mempool_p2p
@@ -97,15 +182,54 @@ pub fn create_node_components(
             let mempool_p2p_propagator_client = clients
                 .get_mempool_p2p_propagator_shared_client()
                 .expect("Propagator Client should be available");
-            let mempool = create_mempool(mempool_p2p_propagator_client);
+            let mempool = create_mempool(
+                config.mempool_config.clone(),
+                config.mempool_journal_config.clone(),
+                mempool_p2p_propagator_client,
+                clients.get_batcher_shared_client(),
+            );
             Some(mempool)
         }
         ComponentExecutionMode::Disabled | ComponentExecutionMode::Remote => None,
     };
 
+    // The components that run in this node report their health to its monitoring endpoint.
+    let mut health_checks = ComponentHealthChecks::new();
+    if let Some(batcher) = &batcher {
+        health_checks.insert("batcher", Arc::new(batcher.health()));
+    }
+    if let Some(consensus_manager) = &consensus_manager {
+        health_checks.insert("consensus", Arc::new(consensus_manager.health()));
+    }
+    if let Some(gateway) = &gateway {
+        health_checks.insert("gateway", Arc::new(gateway.health()));
+    }
+    if let Some(l1_gas_price_scraper) = &l1_gas_price_scraper {
+        health_checks.insert("l1_gas_price_scraper", Arc::new(l1_gas_price_scraper.health()));
+    }
+    if l1_provider.is_some() {
+        let l1_provider_client = clients
+            .get_l1_provider_shared_client()
+            .expect("L1 Provider Client should be available");
+        health_checks.insert("l1_provider", Arc::new(L1ProviderHealth::new(l1_provider_client)));
+    }
+    if let Some(l1_scraper) = &l1_scraper {
+        health_checks.insert("l1_scraper", Arc::new(l1_scraper.health()));
+    }
+    if let Some(mempool) = &mempool {
+        let mempool_client =
+            clients.get_mempool_shared_client().expect("Mempool Client should be available");
+        health_checks.insert("mempool", Arc::new(mempool.health().with_client(mempool_client)));
+    }
+
     let monitoring_endpoint = match config.components.monitoring_endpoint.execution_mode {
         ComponentExecutionMode::LocalExecutionWithRemoteEnabled => Some(
-            create_monitoring_endpoint(config.monitoring_endpoint_config.clone(), VERSION_FULL),
+            create_monitoring_endpoint(
+                config.monitoring_endpoint_config.clone(),
+                VERSION_FULL,
+                health_checks,
+            )
+            .expect("Failed to install the metrics recorder."),
         ),
         ComponentExecutionMode::LocalExecutionWithRemoteDisabled => None,
         ComponentExecutionMode::Disabled | ComponentExecutionMode::Remote => None,
@@ -116,6 +240,10 @@ pub fn create_node_components(
         consensus_manager,
         gateway,
         http_server,
+        l1_gas_price_provider,
+        l1_gas_price_scraper,
+        l1_provider,
+        l1_scraper,
         mempool,
         monitoring_endpoint,
         mempool_p2p_propagator,
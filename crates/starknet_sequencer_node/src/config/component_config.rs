@@ -3,12 +3,14 @@ use std::collections::BTreeMap;
 use papyrus_config::dumping::{append_sub_config_name, SerializeConfig};
 use papyrus_config::{ParamPath, SerializedParam};
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use starknet_sequencer_infra::codec::Codec;
+use validator::{Validate, ValidationError};
 
 use crate::config::component_execution_config::ComponentExecutionConfig;
 
 /// The components configuration.
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+#[validate(schema(function = "validate_component_codecs"))]
 pub struct ComponentConfig {
     #[validate]
     pub batcher: ComponentExecutionConfig,
@@ -19,6 +21,10 @@ pub struct ComponentConfig {
     #[validate]
     pub http_server: ComponentExecutionConfig,
     #[validate]
+    pub l1_gas_price_provider: ComponentExecutionConfig,
+    #[validate]
+    pub l1_provider: ComponentExecutionConfig,
+    #[validate]
     pub mempool: ComponentExecutionConfig,
     #[validate]
     pub mempool_p2p: ComponentExecutionConfig,
@@ -35,6 +41,8 @@ impl Default for ComponentConfig {
             consensus_manager: ComponentExecutionConfig::consensus_manager_default_config(),
             gateway: ComponentExecutionConfig::gateway_default_config(),
             http_server: ComponentExecutionConfig::http_server_default_config(),
+            l1_gas_price_provider: ComponentExecutionConfig::l1_gas_price_provider_default_config(),
+            l1_provider: ComponentExecutionConfig::l1_provider_default_config(),
             mempool: ComponentExecutionConfig::mempool_default_config(),
             mempool_p2p: ComponentExecutionConfig::mempool_p2p_default_config(),
             monitoring_endpoint: ComponentExecutionConfig::monitoring_endpoint_default_config(),
@@ -50,6 +58,8 @@ impl SerializeConfig for ComponentConfig {
             append_sub_config_name(self.consensus_manager.dump(), "consensus_manager"),
             append_sub_config_name(self.gateway.dump(), "gateway"),
             append_sub_config_name(self.http_server.dump(), "http_server"),
+            append_sub_config_name(self.l1_gas_price_provider.dump(), "l1_gas_price_provider"),
+            append_sub_config_name(self.l1_provider.dump(), "l1_provider"),
             append_sub_config_name(self.mempool.dump(), "mempool"),
             append_sub_config_name(self.mempool_p2p.dump(), "mempool_p2p"),
             append_sub_config_name(self.monitoring_endpoint.dump(), "monitoring_endpoint"),
@@ -59,3 +69,30 @@ impl SerializeConfig for ComponentConfig {
         sub_configs.into_iter().flatten().collect()
     }
 }
+
+// Transactions don't round trip through bincode, and `RpcTransaction`s only through JSON, so the
+// components whose messages contain them are restricted to the codecs that decode them.
+fn validate_component_codecs(component_config: &ComponentConfig) -> Result<(), ValidationError> {
+    let supported_codecs: [(&str, &ComponentExecutionConfig, &[Codec]); 4] = [
+        ("batcher", &component_config.batcher, &Codec::SELF_DESCRIBING),
+        ("gateway", &component_config.gateway, &[Codec::Json]),
+        ("mempool", &component_config.mempool, &Codec::SELF_DESCRIBING),
+        ("mempool_p2p", &component_config.mempool_p2p, &[Codec::Json]),
+    ];
+    for (component_name, execution_config, supported_codecs) in supported_codecs {
+        let unsupported_codec = execution_config
+            .remote_client_config
+            .iter()
+            .map(|config| config.codec)
+            .chain(execution_config.remote_server_config.iter().map(|config| config.codec))
+            .find(|codec| !supported_codecs.contains(codec));
+        if let Some(codec) = unsupported_codec {
+            let mut error = ValidationError::new("Unsupported codec.");
+            error.message = Some(
+                format!("The {component_name} component can't use the {codec:?} codec.").into(),
+            );
+            return Err(error);
+        }
+    }
+    Ok(())
+}
@@ -134,6 +134,24 @@ impl ComponentExecutionConfig {
         }
     }
 
+    pub fn l1_gas_price_provider_default_config() -> Self {
+        Self {
+            execution_mode: ComponentExecutionMode::LocalExecutionWithRemoteDisabled,
+            local_server_config: Some(LocalServerConfig::default()),
+            remote_client_config: None,
+            remote_server_config: None,
+        }
+    }
+
+    pub fn l1_provider_default_config() -> Self {
+        Self {
+            execution_mode: ComponentExecutionMode::LocalExecutionWithRemoteDisabled,
+            local_server_config: Some(LocalServerConfig::default()),
+            remote_client_config: None,
+            remote_server_config: None,
+        }
+    }
+
     pub fn state_sync_default_config() -> Self {
         Self {
             execution_mode: ComponentExecutionMode::LocalExecutionWithRemoteDisabled,
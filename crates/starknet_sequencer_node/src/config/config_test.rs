@@ -10,6 +10,7 @@ use papyrus_config::dumping::SerializeConfig;
 use papyrus_config::validators::config_validate;
 use papyrus_config::SerializedParam;
 use rstest::rstest;
+use starknet_sequencer_infra::codec::Codec;
 use starknet_sequencer_infra::component_definitions::{
     LocalServerConfig,
     RemoteClientConfig,
@@ -17,6 +18,7 @@ use starknet_sequencer_infra::component_definitions::{
 };
 use validator::Validate;
 
+use crate::config::component_config::ComponentConfig;
 use crate::config::component_execution_config::{ComponentExecutionConfig, ComponentExecutionMode};
 use crate::config::node_config::{
     SequencerNodeConfig,
@@ -58,6 +60,58 @@ fn test_valid_component_execution_config(
     assert_eq!(component_exe_config.validate(), Ok(()));
 }
 
+fn remote_client_config(codec: Codec) -> ComponentExecutionConfig {
+    ComponentExecutionConfig {
+        execution_mode: ComponentExecutionMode::Remote,
+        local_server_config: None,
+        remote_client_config: Some(RemoteClientConfig { codec, ..Default::default() }),
+        remote_server_config: None,
+    }
+}
+
+fn remote_server_config(codec: Codec) -> ComponentExecutionConfig {
+    ComponentExecutionConfig {
+        execution_mode: ENABLE_REMOTE_CONNECTION_MODE,
+        local_server_config: Some(LocalServerConfig::default()),
+        remote_client_config: None,
+        remote_server_config: Some(RemoteServerConfig { codec, ..Default::default() }),
+    }
+}
+
+/// Test that components whose messages contain transactions can't use codecs that don't decode
+/// them, on either side of the remote connection.
+#[rstest]
+#[case::batcher_bincode("batcher", Codec::Bincode, false)]
+#[case::batcher_json("batcher", Codec::Json, true)]
+#[case::batcher_cbor("batcher", Codec::Cbor, true)]
+#[case::gateway_bincode("gateway", Codec::Bincode, false)]
+#[case::gateway_json("gateway", Codec::Json, true)]
+#[case::gateway_cbor("gateway", Codec::Cbor, false)]
+#[case::mempool_bincode("mempool", Codec::Bincode, false)]
+#[case::mempool_json("mempool", Codec::Json, true)]
+#[case::mempool_cbor("mempool", Codec::Cbor, true)]
+#[case::mempool_p2p_bincode("mempool_p2p", Codec::Bincode, false)]
+#[case::mempool_p2p_json("mempool_p2p", Codec::Json, true)]
+#[case::mempool_p2p_cbor("mempool_p2p", Codec::Cbor, false)]
+fn test_component_codec_validation(
+    #[case] component: &str,
+    #[case] codec: Codec,
+    #[case] is_valid: bool,
+    #[values(remote_client_config, remote_server_config)]
+    execution_config: fn(Codec) -> ComponentExecutionConfig,
+) {
+    let mut component_config = ComponentConfig::default();
+    let component_execution_config = match component {
+        "batcher" => &mut component_config.batcher,
+        "gateway" => &mut component_config.gateway,
+        "mempool" => &mut component_config.mempool,
+        "mempool_p2p" => &mut component_config.mempool_p2p,
+        _ => panic!("Unexpected component: {component}."),
+    };
+    *component_execution_config = execution_config(codec);
+    assert_eq!(component_config.validate().is_ok(), is_valid);
+}
+
 /// Test the validation of the struct SequencerNodeConfig and that the default config file is up to
 /// date. To update the default config file, run:
 /// cargo run --bin sequencer_dump_config -q
@@ -6,9 +6,11 @@ use std::vec::Vec;
 
 use clap::Command;
 use infra_utils::path::resolve_project_relative_path;
+use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerConfig;
 use papyrus_config::dumping::{
     append_sub_config_name,
     generate_struct_pointer,
+    ser_optional_sub_config,
     ser_pointer_target_required_param,
     set_pointing_param_paths,
     ConfigPointers,
@@ -23,6 +25,11 @@ use starknet_batcher::VersionedConstantsOverrides;
 use starknet_consensus_manager::config::ConsensusManagerConfig;
 use starknet_gateway::config::{GatewayConfig, RpcStateReaderConfig};
 use starknet_http_server::config::HttpServerConfig;
+use starknet_l1_gas_price::l1_gas_price_provider::L1GasPriceProviderConfig;
+use starknet_l1_gas_price::l1_gas_price_scraper::L1GasPriceScraperConfig;
+use starknet_l1_provider::L1ProviderConfig;
+use starknet_mempool::journal::MempoolJournalConfig;
+use starknet_mempool::mempool::MempoolConfig;
 use starknet_mempool_p2p::config::MempoolP2pConfig;
 use starknet_monitoring_endpoint::config::MonitoringEndpointConfig;
 use starknet_sierra_compile::config::SierraToCasmCompilationConfig;
@@ -48,9 +55,18 @@ pub static CONFIG_POINTERS: LazyLock<ConfigPointers> = LazyLock::new(|| {
                 "batcher_config.storage.db_config.chain_id",
                 "consensus_manager_config.consensus_config.network_config.chain_id",
                 "gateway_config.chain_info.chain_id",
+                "l1_provider_config.l1_scraper_config.chain_id",
                 "mempool_p2p_config.network_config.chain_id",
             ]),
         ),
+        (
+            ser_pointer_target_required_param(
+                "eth_node_url",
+                SerializationType::String,
+                "URL of the Ethereum node the sequencer reads L1 from.",
+            ),
+            set_pointing_param_paths(&["base_layer_config.node_url"]),
+        ),
         (
             ser_pointer_target_required_param(
                 "eth_fee_token_address",
@@ -106,6 +122,7 @@ pub static CONFIG_NON_POINTERS_WHITELIST: LazyLock<Pointers> =
 pub struct SequencerNodeConfig {
     #[validate]
     pub components: ComponentConfig,
+    pub base_layer_config: EthereumBaseLayerConfig,
     #[validate]
     pub batcher_config: BatcherConfig,
     #[validate]
@@ -115,10 +132,18 @@ pub struct SequencerNodeConfig {
     #[validate]
     pub http_server_config: HttpServerConfig,
     #[validate]
+    pub l1_gas_price_provider_config: L1GasPriceProviderConfig,
+    pub l1_gas_price_scraper_config: L1GasPriceScraperConfig,
+    pub l1_provider_config: L1ProviderConfig,
+    #[validate]
     pub rpc_state_reader_config: RpcStateReaderConfig,
     #[validate]
     pub compiler_config: SierraToCasmCompilationConfig,
     #[validate]
+    pub mempool_config: MempoolConfig,
+    #[validate]
+    pub mempool_journal_config: Option<MempoolJournalConfig>,
+    #[validate]
     pub mempool_p2p_config: MempoolP2pConfig,
     #[validate]
     pub monitoring_endpoint_config: MonitoringEndpointConfig,
@@ -128,6 +153,7 @@ impl SerializeConfig for SequencerNodeConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
         let sub_configs = vec![
             append_sub_config_name(self.components.dump(), "components"),
+            append_sub_config_name(self.base_layer_config.dump(), "base_layer_config"),
             append_sub_config_name(self.batcher_config.dump(), "batcher_config"),
             append_sub_config_name(
                 self.consensus_manager_config.dump(),
@@ -135,8 +161,19 @@ impl SerializeConfig for SequencerNodeConfig {
             ),
             append_sub_config_name(self.gateway_config.dump(), "gateway_config"),
             append_sub_config_name(self.http_server_config.dump(), "http_server_config"),
+            append_sub_config_name(
+                self.l1_gas_price_provider_config.dump(),
+                "l1_gas_price_provider_config",
+            ),
+            append_sub_config_name(
+                self.l1_gas_price_scraper_config.dump(),
+                "l1_gas_price_scraper_config",
+            ),
+            append_sub_config_name(self.l1_provider_config.dump(), "l1_provider_config"),
             append_sub_config_name(self.rpc_state_reader_config.dump(), "rpc_state_reader_config"),
             append_sub_config_name(self.compiler_config.dump(), "compiler_config"),
+            append_sub_config_name(self.mempool_config.dump(), "mempool_config"),
+            ser_optional_sub_config(&self.mempool_journal_config, "mempool_journal_config"),
             append_sub_config_name(self.mempool_p2p_config.dump(), "mempool_p2p_config"),
             append_sub_config_name(
                 self.monitoring_endpoint_config.dump(),
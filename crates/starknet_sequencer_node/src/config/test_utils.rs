@@ -2,6 +2,7 @@ use std::vec::Vec; // Used by #[gen_field_names_fn].
 
 use papyrus_proc_macros::gen_field_names_and_cli_args_fn;
 use starknet_api::core::{ChainId, ContractAddress};
+use url::Url;
 
 use crate::config::node_config::node_command;
 
@@ -12,6 +13,7 @@ pub struct RequiredParams {
     pub eth_fee_token_address: ContractAddress,
     pub strk_fee_token_address: ContractAddress,
     pub sequencer_address: ContractAddress,
+    pub eth_node_url: Url,
 }
 
 impl RequiredParams {
@@ -21,6 +23,7 @@ impl RequiredParams {
             eth_fee_token_address: ContractAddress::from(2_u128),
             strk_fee_token_address: ContractAddress::from(3_u128),
             sequencer_address: ContractAddress::from(17_u128),
+            eth_node_url: Url::parse("http://localhost:8545").unwrap(),
         }
     }
 }
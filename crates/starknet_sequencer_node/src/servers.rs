@@ -6,6 +6,16 @@ use starknet_batcher::communication::{LocalBatcherServer, RemoteBatcherServer};
 use starknet_consensus_manager::communication::ConsensusManagerServer;
 use starknet_gateway::communication::{LocalGatewayServer, RemoteGatewayServer};
 use starknet_http_server::communication::HttpServer;
+use starknet_l1_gas_price::communication::{
+    L1GasPriceScraperServer,
+    LocalL1GasPriceProviderServer,
+    RemoteL1GasPriceProviderServer,
+};
+use starknet_l1_provider::communication::{
+    L1ScraperServer,
+    LocalL1ProviderServer,
+    RemoteL1ProviderServer,
+};
 use starknet_mempool::communication::{LocalMempoolServer, RemoteMempoolServer};
 use starknet_mempool_p2p::propagator::{
     LocalMempoolP2pPropagatorServer,
@@ -32,6 +42,8 @@ use crate::config::node_config::SequencerNodeConfig;
 struct LocalServers {
     pub(crate) batcher: Option<Box<LocalBatcherServer>>,
     pub(crate) gateway: Option<Box<LocalGatewayServer>>,
+    pub(crate) l1_gas_price_provider: Option<Box<LocalL1GasPriceProviderServer>>,
+    pub(crate) l1_provider: Option<Box<LocalL1ProviderServer>>,
     pub(crate) mempool: Option<Box<LocalMempoolServer>>,
     pub(crate) mempool_p2p_propagator: Option<Box<LocalMempoolP2pPropagatorServer>>,
 }
@@ -40,6 +52,8 @@ struct LocalServers {
 struct WrapperServers {
     pub(crate) consensus_manager: Option<Box<ConsensusManagerServer>>,
     pub(crate) http_server: Option<Box<HttpServer>>,
+    pub(crate) l1_gas_price_scraper: Option<Box<L1GasPriceScraperServer>>,
+    pub(crate) l1_scraper: Option<Box<L1ScraperServer>>,
     pub(crate) monitoring_endpoint: Option<Box<MonitoringEndpointServer>>,
     pub(crate) mempool_p2p_runner: Option<Box<MempoolP2pRunnerServer>>,
 }
@@ -49,6 +63,8 @@ struct WrapperServers {
 pub struct RemoteServers {
     pub batcher: Option<Box<RemoteBatcherServer>>,
     pub gateway: Option<Box<RemoteGatewayServer>>,
+    pub l1_gas_price_provider: Option<Box<RemoteL1GasPriceProviderServer>>,
+    pub l1_provider: Option<Box<RemoteL1ProviderServer>>,
     pub mempool: Option<Box<RemoteMempoolServer>>,
     pub mempool_p2p_propagator: Option<Box<RemoteMempoolP2pPropagatorServer>>,
 }
@@ -222,6 +238,16 @@ fn create_local_servers(
         components.gateway,
         communication.take_gateway_rx()
     );
+    let l1_gas_price_provider_server = create_local_server!(
+        &config.components.l1_gas_price_provider.execution_mode,
+        components.l1_gas_price_provider,
+        communication.take_l1_gas_price_provider_rx()
+    );
+    let l1_provider_server = create_local_server!(
+        &config.components.l1_provider.execution_mode,
+        components.l1_provider,
+        communication.take_l1_provider_rx()
+    );
     let mempool_server = create_local_server!(
         &config.components.mempool.execution_mode,
         components.mempool,
@@ -235,6 +261,8 @@ fn create_local_servers(
     LocalServers {
         batcher: batcher_server,
         gateway: gateway_server,
+        l1_gas_price_provider: l1_gas_price_provider_server,
+        l1_provider: l1_provider_server,
         mempool: mempool_server,
         mempool_p2p_propagator: mempool_p2p_propagator_server,
     }
@@ -258,6 +286,20 @@ pub fn create_remote_servers(
         config.components.gateway.remote_server_config
     );
 
+    let l1_gas_price_provider_client = clients.get_l1_gas_price_provider_local_client();
+    let l1_gas_price_provider_server = create_remote_server!(
+        &config.components.l1_gas_price_provider.execution_mode,
+        l1_gas_price_provider_client,
+        config.components.l1_gas_price_provider.remote_server_config
+    );
+
+    let l1_provider_client = clients.get_l1_provider_local_client();
+    let l1_provider_server = create_remote_server!(
+        &config.components.l1_provider.execution_mode,
+        l1_provider_client,
+        config.components.l1_provider.remote_server_config
+    );
+
     let mempool_client = clients.get_mempool_local_client();
     let mempool_server = create_remote_server!(
         &config.components.mempool.execution_mode,
@@ -274,6 +316,8 @@ pub fn create_remote_servers(
     RemoteServers {
         batcher: batcher_server,
         gateway: gateway_server,
+        l1_gas_price_provider: l1_gas_price_provider_server,
+        l1_provider: l1_provider_server,
         mempool: mempool_server,
         mempool_p2p_propagator: mempool_p2p_propagator_server,
     }
@@ -292,6 +336,16 @@ fn create_wrapper_servers(
         components.http_server
     );
 
+    let l1_gas_price_scraper_server = create_wrapper_server!(
+        &config.components.l1_gas_price_provider.execution_mode,
+        components.l1_gas_price_scraper
+    );
+
+    let l1_scraper_server = create_wrapper_server!(
+        &config.components.l1_provider.execution_mode,
+        components.l1_scraper
+    );
+
     let monitoring_endpoint_server = create_wrapper_server!(
         &config.components.monitoring_endpoint.execution_mode,
         components.monitoring_endpoint
@@ -304,6 +358,8 @@ fn create_wrapper_servers(
     WrapperServers {
         consensus_manager: consensus_manager_server,
         http_server,
+        l1_gas_price_scraper: l1_gas_price_scraper_server,
+        l1_scraper: l1_scraper_server,
         monitoring_endpoint: monitoring_endpoint_server,
         mempool_p2p_runner: mempool_p2p_runner_server,
     }
@@ -339,6 +395,23 @@ pub async fn run_component_servers(servers: SequencerNodeServers) -> anyhow::Res
     // HttpServer server.
     let http_server_future = get_server_future(servers.wrapper_servers.http_server);
 
+    // L1GasPriceProvider servers.
+    let local_l1_gas_price_provider_future =
+        get_server_future(servers.local_servers.l1_gas_price_provider);
+    let remote_l1_gas_price_provider_future =
+        get_server_future(servers.remote_servers.l1_gas_price_provider);
+
+    // L1GasPriceScraper server.
+    let l1_gas_price_scraper_future =
+        get_server_future(servers.wrapper_servers.l1_gas_price_scraper);
+
+    // L1Provider servers.
+    let local_l1_provider_future = get_server_future(servers.local_servers.l1_provider);
+    let remote_l1_provider_future = get_server_future(servers.remote_servers.l1_provider);
+
+    // L1Scraper server.
+    let l1_scraper_future = get_server_future(servers.wrapper_servers.l1_scraper);
+
     // Mempool servers.
     let local_mempool_future = get_server_future(servers.local_servers.mempool);
     let remote_mempool_future = get_server_future(servers.remote_servers.mempool);
@@ -362,6 +435,12 @@ pub async fn run_component_servers(servers: SequencerNodeServers) -> anyhow::Res
     let local_gateway_handle = tokio::spawn(local_gateway_future);
     let remote_gateway_handle = tokio::spawn(remote_gateway_future);
     let http_server_handle = tokio::spawn(http_server_future);
+    let local_l1_gas_price_provider_handle = tokio::spawn(local_l1_gas_price_provider_future);
+    let remote_l1_gas_price_provider_handle = tokio::spawn(remote_l1_gas_price_provider_future);
+    let l1_gas_price_scraper_handle = tokio::spawn(l1_gas_price_scraper_future);
+    let local_l1_provider_handle = tokio::spawn(local_l1_provider_future);
+    let remote_l1_provider_handle = tokio::spawn(remote_l1_provider_future);
+    let l1_scraper_handle = tokio::spawn(l1_scraper_future);
     let local_mempool_handle = tokio::spawn(local_mempool_future);
     let remote_mempool_handle = tokio::spawn(remote_mempool_future);
     let monitoring_endpoint_handle = tokio::spawn(monitoring_endpoint_future);
@@ -394,6 +473,30 @@ pub async fn run_component_servers(servers: SequencerNodeServers) -> anyhow::Res
             error!("Http Server stopped.");
             res?
         }
+        res = local_l1_gas_price_provider_handle => {
+            error!("Local L1 Gas Price Provider Server stopped.");
+            res?
+        }
+        res = remote_l1_gas_price_provider_handle => {
+            error!("Remote L1 Gas Price Provider Server stopped.");
+            res?
+        }
+        res = l1_gas_price_scraper_handle => {
+            error!("L1 Gas Price Scraper Server stopped.");
+            res?
+        }
+        res = local_l1_provider_handle => {
+            error!("Local L1 Provider Server stopped.");
+            res?
+        }
+        res = remote_l1_provider_handle => {
+            error!("Remote L1 Provider Server stopped.");
+            res?
+        }
+        res = l1_scraper_handle => {
+            error!("L1 Scraper Server stopped.");
+            res?
+        }
         res = local_mempool_handle => {
             error!("Local Mempool Server stopped.");
             res?
@@ -11,6 +11,7 @@ use starknet_sequencer_infra::component_client::{
 };
 use starknet_sequencer_infra::component_definitions::{
     ComponentClient,
+    ComponentRequest,
     ComponentRequestAndResponseSender,
 };
 use thiserror::Error;
@@ -18,6 +19,10 @@ use thiserror::Error;
 use crate::errors::StateSyncError;
 use crate::state_sync_types::SyncBlock;
 
+#[cfg(test)]
+#[path = "communication_test.rs"]
+mod communication_test;
+
 #[async_trait]
 pub trait StateSyncClient: Send + Sync {
     /// Request for a block at a specific height.
@@ -52,6 +57,14 @@ pub enum StateSyncRequest {
     GetBlock(BlockNumber),
 }
 
+impl ComponentRequest for StateSyncRequest {
+    fn is_idempotent(&self) -> bool {
+        match self {
+            StateSyncRequest::GetBlock(_) => true,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum StateSyncResponse {
     GetBlock(StateSyncResult<Option<SyncBlock>>),
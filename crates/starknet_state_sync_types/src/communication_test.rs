@@ -0,0 +1,30 @@
+use indexmap::indexmap;
+use rstest::rstest;
+use starknet_api::block::BlockNumber;
+use starknet_api::state::ThinStateDiff;
+use starknet_api::{class_hash, contract_address, nonce, tx_hash};
+use starknet_sequencer_infra::test_utils::assert_codec_round_trip;
+
+use crate::communication::{StateSyncRequest, StateSyncResponse};
+use crate::errors::StateSyncError;
+use crate::state_sync_types::SyncBlock;
+
+#[test]
+fn test_request_round_trip() {
+    assert_codec_round_trip(&StateSyncRequest::GetBlock(BlockNumber(1)));
+}
+
+#[rstest]
+#[case::block(StateSyncResponse::GetBlock(Ok(Some(SyncBlock {
+    state_diff: ThinStateDiff {
+        deployed_contracts: indexmap! { contract_address!("0x1") => class_hash!("0x2") },
+        nonces: indexmap! { contract_address!("0x1") => nonce!(3) },
+        ..Default::default()
+    },
+    transaction_hashes: vec![tx_hash!(4)],
+}))))]
+#[case::no_block(StateSyncResponse::GetBlock(Ok(None)))]
+#[case::err(StateSyncResponse::GetBlock(Err(StateSyncError::StorageError("error".to_owned()))))]
+fn test_response_round_trip(#[case] response: StateSyncResponse) {
+    assert_codec_round_trip(&response);
+}